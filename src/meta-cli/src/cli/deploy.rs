@@ -1,7 +1,7 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
-use self::actors::task::deploy::{DeployAction, DeployActionGenerator};
+use self::actors::task::deploy::{BreakingChangesPolicy, DeployAction, DeployActionGenerator};
 use self::actors::task_manager::{self, StopReason};
 use super::{Action, ConfigArgs, NodeArgs};
 use crate::config::Config;
@@ -91,6 +91,10 @@ pub struct DeployOptions {
     /// initial retry interval in milliseconds
     #[clap(long)]
     pub retry_interval_ms: Option<u64>,
+
+    /// Check the changes against the currently deployed version of the typegraphs
+    #[clap(long, value_enum, default_value_t)]
+    pub breaking_changes: BreakingChangesPolicy,
 }
 
 #[derive(Debug)]
@@ -225,7 +229,9 @@ mod default_mode {
                 .prisma_migrations_base_dir(PathOption::Absolute)
                 .into(),
             default_migration_action,
-        );
+            deploy.options.breaking_changes,
+        )
+        .naming_strategy(deploy.config.typegraphs.naming_strategy.clone());
 
         let init = TaskManagerInit::<DeployAction>::new(
             deploy.config.clone(),
@@ -338,7 +344,9 @@ mod watch_mode {
                 .prisma_migrations_base_dir(PathOption::Absolute)
                 .into(),
             default_migration_action,
-        );
+            deploy.options.breaking_changes,
+        )
+        .naming_strategy(deploy.config.typegraphs.naming_strategy.clone());
 
         let init = TaskManagerInit::<DeployAction>::new(
             deploy.config.clone(),
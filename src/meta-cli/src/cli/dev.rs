@@ -68,6 +68,7 @@ impl Action for Dev {
             threads: self.threads,
             retry: self.retry,
             retry_interval_ms: self.retry_interval_ms,
+            breaking_changes: Default::default(),
         };
 
         let deploy = DeploySubcommand::new(
@@ -0,0 +1,79 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use super::serialize::serialize_files;
+use super::{Action, ConfigArgs};
+use crate::interlude::*;
+use clap::{Parser, ValueEnum};
+use std::io::{self, Write};
+
+#[derive(ValueEnum, Debug, Clone)]
+enum ExportFormat {
+    /// JSON Schema of the input and output of each exposed function
+    JsonSchema,
+    /// OpenAPI 3.1 document for the REST endpoints
+    Openapi,
+}
+
+#[derive(Parser, Debug)]
+pub struct Export {
+    /// The source file that defines the typegraph(s).
+    #[clap(short, long = "file", value_parser)]
+    files: Vec<PathBuf>,
+
+    /// Name of the typegraph to export. Required if more than one are defined.
+    #[clap(short, long, value_parser)]
+    typegraph: Option<String>,
+
+    /// Output format
+    #[clap(long, value_enum, default_value_t = ExportFormat::Openapi)]
+    format: ExportFormat,
+
+    /// URL of the typegate, for the `servers` section of the OpenAPI document
+    #[clap(long)]
+    base_url: Option<String>,
+
+    /// The output file. Default: stdout
+    #[clap(short, long, value_parser)]
+    out: Option<PathBuf>,
+}
+
+#[async_trait]
+impl Action for Export {
+    #[cfg_attr(feature = "tracing-instrument", tracing::instrument)]
+    async fn run(&self, args: ConfigArgs) -> Result<()> {
+        let tgs = serialize_files(&args, &self.files, None).await?;
+
+        let tg = match self.typegraph.as_ref() {
+            Some(tg_name) => tgs
+                .iter()
+                .find(|tg| &tg.name().unwrap() == tg_name)
+                .ok_or_else(|| ferr!("typegraph {tg_name:?} not found"))?,
+            None => {
+                if tgs.len() != 1 {
+                    bail!(
+                        "expected only one typegraph, got {}; use --typegraph to select one",
+                        tgs.len()
+                    );
+                }
+                &tgs[0]
+            }
+        };
+
+        let doc = match self.format {
+            ExportFormat::JsonSchema => tg.to_json_schema(),
+            ExportFormat::Openapi => tg.to_openapi(self.base_url.as_deref()),
+        }
+        .map_err(|e| ferr!("export failed: {e:#}"))?;
+        let contents = serde_json::to_string_pretty(&doc)?;
+
+        if let Some(path) = self.out.as_ref() {
+            tokio::fs::write(path, contents)
+                .await
+                .wrap_err_with(|| format!("could not write to {path:?}"))?;
+        } else {
+            io::stdout().write_all(contents.as_bytes())?;
+        }
+        Ok(())
+    }
+}
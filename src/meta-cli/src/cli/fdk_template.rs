@@ -12,6 +12,7 @@ use super::{Action, ConfigArgs};
 enum Template {
     Rust,
     Python,
+    Go,
     // Typescript,
 }
 
@@ -40,6 +41,7 @@ impl Action for CreateFdkTemplate {
         let template = match self.template {
             Template::Rust => metagen::FDK_RS_DEFAULT_TEMPLATE,
             Template::Python => metagen::FDK_PY_DEFAULT_TEMPLATE,
+            Template::Go => metagen::FDK_GO_DEFAULT_TEMPLATE,
             // Template::Typescript => metagen::FDK_TS_DEFAULT_TEMPLATE,
         };
 
@@ -2,13 +2,17 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::Mutex;
 
 use super::serialize::SerializeReportExt;
 use crate::cli::{Action, ConfigArgs, NodeArgs};
 use crate::config::PathOption;
 use crate::deploy::actors::task::serialize::{SerializeAction, SerializeActionGenerator};
 use crate::deploy::actors::task_manager::{TaskManagerInit, TaskSource};
+use crate::deploy::actors::watcher::watch_directory;
 use crate::interlude::*;
+use crate::typegraph::dependency_graph::DependencyGraph;
 use crate::{config::Config, deploy::actors::console::ConsoleActor};
 use actix::Actor;
 use clap::Parser;
@@ -16,6 +20,7 @@ use dashmap::DashMap;
 use futures_concurrency::future::FutureGroup;
 use metagen::*;
 use tg_schema::Typegraph;
+use typegraph_core::sdk::core::Artifact;
 
 #[derive(Parser, Debug, Clone)]
 pub struct Gen {
@@ -33,6 +38,18 @@ pub struct Gen {
     /// Force load a typegraph file
     #[clap(short, long)]
     file: Option<PathBuf>,
+
+    /// Regenerate the affected outputs when a typegraph or an fdk template changes
+    #[clap(long, conflicts_with_all = ["check", "dry_run"])]
+    watch: bool,
+
+    /// Fail with a diff if the outputs on disk are not up to date
+    #[clap(long, conflicts_with = "dry_run")]
+    check: bool,
+
+    /// List the files that would be written without writing them
+    #[clap(long)]
+    dry_run: bool,
 }
 
 #[async_trait]
@@ -55,13 +72,7 @@ impl Action for Gen {
             .build(&dir)
             .await
             .with_context(|| format!("building node from config: {node_config:#?}"))?;
-
-        let resolver = MetagenCtx {
-            config: config.clone(),
-            dir,
-            typegate: Arc::new(node),
-            typegraph_cache: DashMap::new(),
-        };
+        let node = Arc::new(node);
 
         if !mgen_conf.targets.contains_key(&self.gen_target) {
             error!("no metagen target found under key {:?}", self.gen_target);
@@ -77,47 +88,352 @@ impl Action for Gen {
             bail!("no metagen target found under key {:?}", self.gen_target);
         }
 
-        let files = metagen::generate_target(
+        // the artifacts of the typegraphs loaded from their path
+        let dependency_graph = Arc::new(Mutex::new(DependencyGraph::default()));
+
+        if self.watch {
+            self.check_watchable(&config, &node, &dir, &dependency_graph)
+                .await?;
+        }
+
+        let files = self
+            .generate(&config, &node, &dir, &dependency_graph, |_| true)
+            .await?;
+
+        let working_dir = args.dir()?;
+        if self.check {
+            return check_outputs(files, &working_dir).await;
+        }
+        if self.dry_run {
+            for output in pending_outputs(files).await? {
+                let relative_path = diff_display(&output.path, &working_dir);
+                match output.existing {
+                    Some(_) => println!("would overwrite {relative_path}"),
+                    None => println!("would create {relative_path}"),
+                }
+            }
+            return Ok(());
+        }
+
+        let output_paths = files.0.keys().cloned().collect::<Vec<_>>();
+        write_outputs(files, &working_dir, false).await?;
+        info!("all outputs have successfully been written");
+
+        if self.watch {
+            self.run_watch(&config, &node, &dir, &dependency_graph, output_paths)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Gen {
+    async fn generate(
+        &self,
+        config: &Arc<Config>,
+        node: &Arc<typegate_api::Node>,
+        dir: &Path,
+        dependency_graph: &Arc<Mutex<DependencyGraph>>,
+        filter: impl Fn(&indexmap::IndexMap<String, GeneratorInputOrder>) -> bool,
+    ) -> Result<GeneratorOutput> {
+        let mgen_conf = config.metagen.as_ref().unwrap();
+        // a fresh context for each run: the typegraphs might have changed
+        let resolver = MetagenCtx {
+            config: config.clone(),
+            dir: dir.to_path_buf(),
+            typegate: node.clone(),
+            typegraph_cache: DashMap::new(),
+            dependency_graph: dependency_graph.clone(),
+        };
+
+        metagen::generate_target_filtered(
             mgen_conf,
             &self.gen_target,
             config.path.as_ref().unwrap().parent().unwrap().into(),
             resolver,
+            filter,
         )
+        .await
+    }
+
+    /// The typegraphs fetched from the typegate would only be the deployed
+    /// version: the watch mode requires the generators to load them from
+    /// their `typegraph_path`
+    async fn check_watchable(
+        &self,
+        config: &Arc<Config>,
+        node: &Arc<typegate_api::Node>,
+        dir: &Path,
+        dependency_graph: &Arc<Mutex<DependencyGraph>>,
+    ) -> Result<()> {
+        let deployed = Mutex::new(vec![]);
+        self.generate(config, node, dir, dependency_graph, |bill| {
+            deployed
+                .lock()
+                .unwrap()
+                .extend(bill.values().filter_map(|order| match order {
+                    GeneratorInputOrder::TypegraphFromTypegate { name } => Some(name.clone()),
+                    _ => None,
+                }));
+            false
+        })
         .await?;
+        let deployed = deployed.into_inner().unwrap();
+        if !deployed.is_empty() {
+            bail!(
+                "--watch requires the generators to load the typegraphs from `typegraph_path`; \
+                 typegraphs fetched from the typegate: {}",
+                deployed.join(", ")
+            );
+        }
+        Ok(())
+    }
 
-        let working_dir = args.dir()?;
-        let working_dir = &working_dir;
+    async fn run_watch(
+        &self,
+        config: &Arc<Config>,
+        node: &Arc<typegate_api::Node>,
+        dir: &Path,
+        dependency_graph: &Arc<Mutex<DependencyGraph>>,
+        output_paths: Vec<PathBuf>,
+    ) -> Result<()> {
+        let watch_dir = config.dir()?;
 
-        files
-            .0
-            .into_iter()
-            .map(|(path, file)| async move {
-                tokio::fs::create_dir_all(path.parent().unwrap()).await?;
-                if file.overwrite || !tokio::fs::try_exists(&path).await? {
-                    let relative_path = pathdiff::diff_paths(&path, working_dir);
-                    info!(
-                        "writing to {:?}...",
-                        relative_path.as_deref().unwrap_or(path.as_path()),
-                    );
-                    tokio::fs::write(path, file.contents).await?;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let _debouncer = watch_directory(watch_dir, move |paths| {
+            let _ = tx.send(paths);
+        })?;
+        info!("watching {watch_dir:?} for changes...");
+
+        let config_path = config.path.as_ref().map(|path| canonicalized(path));
+        // the files we write trigger events too
+        let mut outputs = output_paths
+            .iter()
+            .map(|path| canonicalized(path))
+            .collect::<HashSet<_>>();
+        while let Some(paths) = rx.recv().await {
+            let changed = paths
+                .into_iter()
+                .map(|path| canonicalized(&path))
+                .filter(|path| !outputs.contains(path))
+                .collect::<Vec<_>>();
+            if changed.is_empty() {
+                continue;
+            }
+            if changed
+                .iter()
+                .any(|path| Some(path) == config_path.as_ref())
+            {
+                warn!("metatype configuration file changed; restart `meta gen` to reload it");
+                continue;
+            }
+            // the graph is updated by the generation, read it before
+            let changed_deps = {
+                let dependency_graph = dependency_graph.lock().unwrap();
+                changed
+                    .iter()
+                    .flat_map(|path| dependency_graph.get_rdeps(path))
+                    .collect::<HashSet<_>>()
+            };
+
+            let files = match self
+                .generate(config, node, dir, dependency_graph, |bill| {
+                    is_affected(bill, &changed, &changed_deps)
+                })
+                .await
+            {
+                Ok(files) => files,
+                Err(err) => {
+                    error!("generation failed: {err:?}");
+                    continue;
                 }
-                Ok::<_, tokio::io::Error>(())
-            })
-            .collect::<Vec<_>>()
-            .try_join()
-            .await?;
-        info!("all outputs have successfully been written");
+            };
+            if files.0.is_empty() {
+                continue;
+            }
+            for rel_path in changed.iter().map(|path| diff_display(path, dir)) {
+                info!("file changed: {rel_path}");
+            }
+            let output_paths = files.0.keys().cloned().collect::<Vec<_>>();
+            if let Err(err) = write_outputs(files, dir, true).await {
+                error!("failed writing outputs: {err:?}");
+            }
+            outputs.extend(output_paths.iter().map(|path| canonicalized(path)));
+        }
 
         Ok(())
     }
 }
 
+/// Whether the generator with the bill of inputs has to be run again
+/// after the change of the paths; `changed_deps` are the typegraphs
+/// having one of the paths as an artifact
+fn is_affected(
+    bill: &indexmap::IndexMap<String, GeneratorInputOrder>,
+    changed: &[PathBuf],
+    changed_deps: &HashSet<PathBuf>,
+) -> bool {
+    bill.values().any(|order| match order {
+        GeneratorInputOrder::TypegraphFromPath { path, .. } => {
+            let path = canonicalized(path);
+            changed.contains(&path) || changed_deps.contains(&path)
+        }
+        GeneratorInputOrder::LoadFdkTemplate {
+            override_path: Some(template_dir),
+            ..
+        } => {
+            let template_dir = canonicalized(template_dir);
+            changed.iter().any(|path| path.starts_with(&template_dir))
+        }
+        GeneratorInputOrder::LoadFdkTemplate {
+            override_path: None,
+            ..
+        } => false,
+        // see `Gen::check_watchable`
+        GeneratorInputOrder::TypegraphFromTypegate { .. } => false,
+        // the database schema does not depend on the sources
+        GeneratorInputOrder::PrismaIntrospection { .. } => false,
+    })
+}
+
+/// Deleted files can't be canonicalized
+fn canonicalized(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn diff_display(path: &Path, working_dir: &Path) -> String {
+    pathdiff::diff_paths(path, working_dir)
+        .as_deref()
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// A generated file to be written along with the current content on disk
+struct PendingOutput {
+    path: PathBuf,
+    existing: Option<String>,
+    file: GeneratedFile,
+}
+
+/// The outputs that would be written, i.e. excluding the existing files
+/// that are not to be overwritten
+async fn pending_outputs(files: GeneratorOutput) -> Result<Vec<PendingOutput>> {
+    let outputs = files
+        .0
+        .into_iter()
+        .map(|(path, file)| async move {
+            let existing = if tokio::fs::try_exists(&path).await? {
+                Some(tokio::fs::read_to_string(&path).await?)
+            } else {
+                None
+            };
+            Ok::<_, tokio::io::Error>(PendingOutput {
+                path,
+                existing,
+                file,
+            })
+        })
+        .collect::<Vec<_>>()
+        .try_join()
+        .await?;
+    Ok(outputs
+        .into_iter()
+        .filter(|output| output.existing.is_none() || output.file.overwrite)
+        .collect())
+}
+
+/// Write the generated files; when `report_changes` is set, only the files
+/// whose content changed are written, with a summary of the line changes
+async fn write_outputs(
+    files: GeneratorOutput,
+    working_dir: &Path,
+    report_changes: bool,
+) -> Result<()> {
+    pending_outputs(files)
+        .await?
+        .into_iter()
+        .map(|output| async move {
+            let relative_path = diff_display(&output.path, working_dir);
+            match (output.existing, report_changes) {
+                (Some(existing), true) if existing == output.file.contents => return Ok(()),
+                (Some(existing), true) => {
+                    let (added, removed) = line_changes(&existing, &output.file.contents);
+                    info!("  ~ {relative_path} (+{added} -{removed})");
+                }
+                (None, true) => {
+                    info!("  + {relative_path}");
+                }
+                (_, false) => {
+                    info!("writing to {relative_path:?}...");
+                }
+            }
+            tokio::fs::create_dir_all(output.path.parent().unwrap()).await?;
+            tokio::fs::write(output.path, output.file.contents).await?;
+            Ok::<_, tokio::io::Error>(())
+        })
+        .collect::<Vec<_>>()
+        .try_join()
+        .await?;
+    Ok(())
+}
+
+/// Print a unified diff for each output that differs from the file on disk
+/// and fail if any does
+async fn check_outputs(files: GeneratorOutput, working_dir: &Path) -> Result<()> {
+    let mut outdated = 0;
+    for output in pending_outputs(files).await? {
+        let existing = output.existing.as_deref().unwrap_or("");
+        if output.existing.is_some() && existing == output.file.contents {
+            continue;
+        }
+        outdated += 1;
+        let relative_path = diff_display(&output.path, working_dir);
+        let old_header = if output.existing.is_some() {
+            format!("a/{relative_path}")
+        } else {
+            "/dev/null".to_string()
+        };
+        let diff = similar::TextDiff::from_lines(existing, output.file.contents.as_str());
+        print!(
+            "{}",
+            diff.unified_diff()
+                .header(&old_header, &format!("b/{relative_path}"))
+        );
+    }
+
+    if outdated > 0 {
+        bail!("{outdated} generated file(s) are not up to date, run `meta gen` to update them");
+    }
+    info!("all outputs are up to date");
+    Ok(())
+}
+
+/// Count of the added and removed lines, ignoring moved lines
+fn line_changes(old: &str, new: &str) -> (usize, usize) {
+    let mut counts = HashMap::<&str, isize>::new();
+    for line in old.lines() {
+        *counts.entry(line).or_default() -= 1;
+    }
+    for line in new.lines() {
+        *counts.entry(line).or_default() += 1;
+    }
+    counts
+        .values()
+        .fold((0, 0), |(added, removed), count| match *count {
+            count if count > 0 => (added + count as usize, removed),
+            count => (added, removed + count.unsigned_abs()),
+        })
+}
+
 #[derive(Debug)]
 struct MetagenCtx {
     config: Arc<Config>,
     typegate: Arc<typegate_api::Node>,
     dir: PathBuf,
     typegraph_cache: DashMap<String, Arc<Typegraph>>,
+    dependency_graph: Arc<Mutex<DependencyGraph>>,
 }
 
 impl MetagenCtx {
@@ -199,9 +515,23 @@ impl InputResolver for MetagenCtx {
                 let raw = self
                     .get_cached_typegraph_or(
                         &key,
-                        load_tg_at(config, path, name.as_deref(), &self.dir),
+                        load_tg_at(config, path.clone(), name.as_deref(), &self.dir),
                     )
                     .await?;
+                let artifacts = raw
+                    .meta
+                    .artifacts
+                    .values()
+                    .map(|artifact| Artifact {
+                        path: artifact.path.to_string_lossy().into_owned(),
+                        hash: artifact.hash.clone(),
+                        size: artifact.size,
+                    })
+                    .collect::<Vec<_>>();
+                self.dependency_graph
+                    .lock()
+                    .unwrap()
+                    .update_typegraph(path, &artifacts);
 
                 GeneratorInputResolved::TypegraphFromTypegate { raw }
             }
@@ -212,6 +542,18 @@ impl InputResolver for MetagenCtx {
                 let template = load_fdk_template(default, override_path.as_deref()).await?;
                 GeneratorInputResolved::FdkTemplate { template }
             }
+            GeneratorInputOrder::PrismaIntrospection { typegraph, runtime } => {
+                let res = self
+                    .typegate
+                    .prisma_introspect(&typegraph, runtime.as_deref())
+                    .await
+                    .wrap_err_with(|| {
+                        format!("failed to introspect the database of {typegraph:?}")
+                    })?;
+                GeneratorInputResolved::PrismaIntrospection {
+                    raw: Arc::new(res.introspection),
+                }
+            }
         })
     }
 }
@@ -236,6 +578,7 @@ async fn load_tg_at(
                 .prisma_migrations_base_dir(PathOption::Absolute)
                 .into(),
             false,
+            config.typegraphs.naming_strategy.clone(),
         ),
         console,
         TaskSource::Static(vec![path.clone()]),
@@ -265,3 +608,96 @@ async fn load_tg_at(
 
     Ok(tg)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bill(orders: Vec<GeneratorInputOrder>) -> indexmap::IndexMap<String, GeneratorInputOrder> {
+        orders
+            .into_iter()
+            .enumerate()
+            .map(|(idx, order)| (format!("input{idx}"), order))
+            .collect()
+    }
+
+    #[test]
+    fn count_line_changes() {
+        assert_eq!(line_changes("a\nb\nc\n", "a\nb\nc\n"), (0, 0));
+        assert_eq!(line_changes("a\nb\nc\n", "a\nc\nd\ne\n"), (2, 1));
+        // moved lines are not counted
+        assert_eq!(line_changes("a\nb\n", "b\na\n"), (0, 0));
+        assert_eq!(line_changes("", "a\na\n"), (2, 0));
+    }
+
+    #[test]
+    fn affected_generators() {
+        let from_path = bill(vec![GeneratorInputOrder::TypegraphFromPath {
+            path: "/project/tg/main.py".into(),
+            name: None,
+        }]);
+        let no_deps = HashSet::new();
+        assert!(is_affected(
+            &from_path,
+            &["/project/tg/main.py".into()],
+            &no_deps
+        ));
+        // an artifact of the typegraph
+        assert!(is_affected(
+            &from_path,
+            &["/project/tg/scripts/hello.ts".into()],
+            &HashSet::from(["/project/tg/main.py".into()])
+        ));
+        // the artifact of another typegraph
+        assert!(!is_affected(
+            &from_path,
+            &["/project/tg/scripts/hello.ts".into()],
+            &HashSet::from(["/project/tg/other.py".into()])
+        ));
+        assert!(!is_affected(
+            &from_path,
+            &["/project/README.md".into()],
+            &no_deps
+        ));
+
+        let with_template = bill(vec![GeneratorInputOrder::LoadFdkTemplate {
+            default: &[],
+            override_path: Some("/project/templates".into()),
+        }]);
+        assert!(is_affected(
+            &with_template,
+            &["/project/templates/fdk.rs.jinja".into()],
+            &no_deps
+        ));
+        assert!(!is_affected(
+            &with_template,
+            &["/project/templates.txt".into()],
+            &no_deps
+        ));
+
+        let default_template = bill(vec![GeneratorInputOrder::LoadFdkTemplate {
+            default: &[],
+            override_path: None,
+        }]);
+        assert!(!is_affected(
+            &default_template,
+            &["/project/tg/main.py".into()],
+            &no_deps
+        ));
+
+        let from_typegate = bill(vec![
+            GeneratorInputOrder::TypegraphFromTypegate {
+                name: "main".into(),
+            },
+            GeneratorInputOrder::PrismaIntrospection {
+                typegraph: "main".into(),
+                runtime: None,
+            },
+        ]);
+        assert!(!is_affected(
+            &from_typegate,
+            &["/project/tg/main.py".into()],
+            &no_deps
+        ));
+    }
+}
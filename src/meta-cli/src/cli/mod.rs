@@ -17,10 +17,12 @@ pub(crate) mod completion;
 pub(crate) mod deploy;
 pub(crate) mod dev;
 pub(crate) mod doctor;
+pub(crate) mod export;
 pub(crate) mod fdk_template;
 pub(crate) mod gen;
 pub(crate) mod list;
 pub(crate) mod new;
+pub(crate) mod prisma;
 pub(crate) mod serialize;
 pub(crate) mod typegate;
 pub(crate) mod undeploy;
@@ -80,6 +82,8 @@ pub(crate) enum Commands {
     List(list::List),
     /// Access metagen generators
     Gen(gen::Gen),
+    /// Export the typegraph as JSON Schema or OpenAPI document
+    Export(export::Export),
     /// Upgrade
     Upgrade(upgrade::Upgrade),
     /// Generate shell completion
@@ -88,6 +92,8 @@ pub(crate) enum Commands {
     Doctor(doctor::Doctor),
     /// Create a new Metatype project
     New(new::New),
+    /// Manage prisma migrations
+    Prisma(prisma::Prisma),
     /// Dump the default fdk template
     FdkTemplate(fdk_template::CreateFdkTemplate),
     /// Access a minimal deno CLI
@@ -0,0 +1,176 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::interlude::*;
+
+use crate::config::Config;
+use clap::{Parser, Subcommand};
+use owo_colors::OwoColorize;
+
+use super::{Action, ConfigArgs, NodeArgs};
+
+#[derive(Parser, Debug)]
+pub struct Prisma {
+    #[clap(subcommand)]
+    command: PrismaCommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum PrismaCommands {
+    /// Squash all the migrations of a prisma runtime into a single one
+    Squash(Squash),
+    /// Create the first migration from an existing database and mark it as applied
+    Baseline(Baseline),
+}
+
+#[derive(Parser, Debug)]
+struct MigrationTarget {
+    #[command(flatten)]
+    node: NodeArgs,
+
+    /// Target typegate (cf config)
+    #[clap(short, long)]
+    target: String,
+
+    /// Name of the deployed typegraph
+    #[clap(long)]
+    typegraph: String,
+
+    /// Name of the prisma runtime
+    #[clap(long)]
+    runtime: String,
+}
+
+#[derive(Parser, Debug)]
+struct Squash {
+    #[command(flatten)]
+    migration_target: MigrationTarget,
+
+    /// Name of the squashed migration
+    #[clap(long, default_value = "squashed")]
+    name: String,
+
+    /// Mark the squashed migration as applied on the target database.
+    /// Use this when all the squashed migrations have already been applied.
+    #[clap(long, default_value_t = false)]
+    mark_applied: bool,
+}
+
+#[derive(Parser, Debug)]
+struct Baseline {
+    #[command(flatten)]
+    migration_target: MigrationTarget,
+
+    /// Name of the baseline migration
+    #[clap(long, default_value = "baseline")]
+    name: String,
+}
+
+#[async_trait]
+impl Action for Prisma {
+    #[cfg_attr(feature = "tracing-instrument", tracing::instrument)]
+    async fn run(&self, args: ConfigArgs) -> Result<()> {
+        let dir = args.dir()?;
+        let config = Config::load_or_find(args.config.as_deref(), &dir)?;
+
+        match &self.command {
+            PrismaCommands::Squash(squash) => squash.run(&config, &dir).await,
+            PrismaCommands::Baseline(baseline) => baseline.run(&config, &dir).await,
+        }
+    }
+}
+
+impl MigrationTarget {
+    fn migrations_dir(&self, config: &Config) -> PathBuf {
+        config
+            .prisma_migration_dir_abs(&self.typegraph)
+            .join(&self.runtime)
+    }
+
+    /// Replace the content of the local migrations folder
+    fn replace_migrations(&self, config: &Config, migrations: &str) -> Result<PathBuf> {
+        let dest = self.migrations_dir(config);
+        if dest.try_exists()? {
+            std::fs::remove_dir_all(&dest)
+                .wrap_err_with(|| format!("failed to clear migrations folder at {dest:?}"))?;
+        }
+        archive_utils::unpack(&dest, Some(migrations)).map_err(anyhow_to_eyre!())?;
+        Ok(dest)
+    }
+}
+
+impl Squash {
+    async fn run(&self, config: &Config, dir: &Path) -> Result<()> {
+        let target = &self.migration_target;
+        let node = config.node(&target.node, &target.target).build(dir).await?;
+
+        let migrations =
+            archive_utils::archive(target.migrations_dir(config)).map_err(anyhow_to_eyre!())?;
+        if migrations.is_none() {
+            info!(
+                "no migrations found for prisma runtime {} in typegraph {}",
+                target.runtime.magenta(),
+                target.typegraph.cyan()
+            );
+            return Ok(());
+        }
+
+        let res = node
+            .prisma_squash(
+                &target.typegraph,
+                Some(&target.runtime),
+                migrations,
+                &self.name,
+                self.mark_applied,
+            )
+            .await
+            .map_err(anyhow_to_eyre!())?;
+
+        let (Some(name), Some(migrations)) = (res.squashed_migration_name, res.migrations) else {
+            info!("nothing to squash");
+            return Ok(());
+        };
+
+        let dest = target.replace_migrations(config, &migrations)?;
+        info!(
+            "squashed {} migration(s) into {} at {}",
+            res.squashed_count,
+            name.green(),
+            dest.display().bold()
+        );
+        if self.mark_applied {
+            info!("migration {} marked as applied", name.green());
+        }
+
+        Ok(())
+    }
+}
+
+impl Baseline {
+    async fn run(&self, config: &Config, dir: &Path) -> Result<()> {
+        let target = &self.migration_target;
+        let node = config.node(&target.node, &target.target).build(dir).await?;
+
+        let migrations =
+            archive_utils::archive(target.migrations_dir(config)).map_err(anyhow_to_eyre!())?;
+
+        let res = node
+            .prisma_baseline(
+                &target.typegraph,
+                Some(&target.runtime),
+                migrations,
+                &self.name,
+            )
+            .await
+            .map_err(anyhow_to_eyre!())?;
+
+        let dest = target.replace_migrations(config, &res.migrations)?;
+        info!(
+            "baseline migration {} created at {} and marked as applied",
+            res.baseline_migration_name.green(),
+            dest.display().bold()
+        );
+
+        Ok(())
+    }
+}
@@ -11,8 +11,10 @@ use crate::interlude::*;
 use clap::Parser;
 use core::fmt::Debug;
 use std::io::{self, Write};
+use tg_schema::stats::TypegraphStats;
 use tg_schema::Typegraph;
 use tokio::io::AsyncWriteExt;
+use typegraph::ExpansionConfig;
 
 #[derive(Parser, Debug)]
 pub struct Serialize {
@@ -43,6 +45,10 @@ pub struct Serialize {
     #[clap(short, long)]
     prefix: Option<String>,
 
+    /// Report the size and complexity of the typegraph(s) instead of the serialized typegraph(s)
+    #[clap(long, default_value_t = false)]
+    stats: bool,
+
     // FIXME: restructure the typegraph core to handle multiple threads
     #[allow(unused)]
     #[clap(skip = None)]
@@ -53,57 +59,26 @@ pub struct Serialize {
 impl Action for Serialize {
     #[cfg_attr(feature = "tracing-instrument", tracing::instrument)]
     async fn run(&self, args: ConfigArgs) -> Result<()> {
-        let dir = args.dir()?;
-        let config_path = args.config.clone();
-
-        let config =
-            Config::load_or_find(config_path.as_deref(), &dir).or_else(|e| match config_path {
-                Some(_) => Err(e),
-                None => Ok(Config::default_in(&dir)),
-            })?;
-
-        let config = Arc::new(config);
-
-        let console = ConsoleActor::new(Arc::clone(&config)).start();
-
-        let action_generator = SerializeActionGenerator::new(
-            self.prefix.clone(),
-            config.dir().unwrap_or_log().into(),
-            dir.into(),
-            config
-                .prisma_migrations_base_dir(PathOption::Absolute)
-                .into(),
-            true,
-        );
-
-        if self.files.is_empty() {
-            bail!("no file provided");
-        }
-
-        // TODO fail_fast
-        let init = TaskManagerInit::<SerializeAction>::new(
-            config.clone(),
-            action_generator,
-            console,
-            TaskSource::Static(self.files.clone()),
-        )
-        .max_parallel_tasks(1); // FIXME: make the server work with multiple threads
-
-        let report = init.run().await;
-
-        match report.stop_reason {
-            StopReason::Error => bail!("failed"),
-            StopReason::Manual | StopReason::ManualForced => {
-                bail!("cancelled")
+        let tgs = serialize_files(&args, &self.files, self.prefix.clone()).await?;
+
+        if self.stats {
+            let tgs = match self.typegraph.as_ref() {
+                Some(tg_name) => tgs
+                    .into_iter()
+                    .filter(|tg| &tg.name().unwrap() == tg_name)
+                    .collect(),
+                None => tgs,
+            };
+            if tgs.is_empty() {
+                bail!("no typegraph found");
             }
-            StopReason::Natural => {}
-            StopReason::Restart => panic!("restart not supported for serialize"),
-        }
-
-        // TODO no need to report errors
-        let tgs = report.into_typegraphs()?;
-
-        if let Some(tg_name) = self.typegraph.as_ref() {
+            let mut report = String::new();
+            for tg in tgs.iter() {
+                report.push_str(&format!("typegraph {}\n", tg.name().unwrap()));
+                report.push_str(&typegraph_stats(tg).to_string());
+            }
+            self.write(&report).await?;
+        } else if let Some(tg_name) = self.typegraph.as_ref() {
             if let Some(tg) = tgs.iter().find(|tg| &tg.name().unwrap() == tg_name) {
                 self.write(&self.to_string(&tg)?).await?;
             } else {
@@ -128,6 +103,73 @@ impl Action for Serialize {
     }
 }
 
+fn typegraph_stats(tg: &Arc<Typegraph>) -> TypegraphStats {
+    let mut stats = TypegraphStats::new(tg);
+    match ExpansionConfig::with_default_engines().expand(tg.clone()) {
+        Ok(expanded) => stats.add_duplications(tg, expanded.duplicate_counts()),
+        Err(e) => tracing::warn!("could not count the duplicated types: {e}"),
+    }
+    stats
+}
+
+/// Serialize all the typegraphs defined in the given files
+pub(crate) async fn serialize_files(
+    args: &ConfigArgs,
+    files: &[PathBuf],
+    prefix: Option<String>,
+) -> Result<Vec<Arc<Typegraph>>> {
+    let dir = args.dir()?;
+    let config_path = args.config.clone();
+
+    let config =
+        Config::load_or_find(config_path.as_deref(), &dir).or_else(|e| match config_path {
+            Some(_) => Err(e),
+            None => Ok(Config::default_in(&dir)),
+        })?;
+
+    let config = Arc::new(config);
+
+    let console = ConsoleActor::new(Arc::clone(&config)).start();
+
+    let action_generator = SerializeActionGenerator::new(
+        prefix,
+        config.dir().unwrap_or_log().into(),
+        dir.into(),
+        config
+            .prisma_migrations_base_dir(PathOption::Absolute)
+            .into(),
+        true,
+        config.typegraphs.naming_strategy.clone(),
+    );
+
+    if files.is_empty() {
+        bail!("no file provided");
+    }
+
+    // TODO fail_fast
+    let init = TaskManagerInit::<SerializeAction>::new(
+        config.clone(),
+        action_generator,
+        console,
+        TaskSource::Static(files.to_vec()),
+    )
+    .max_parallel_tasks(1); // FIXME: make the server work with multiple threads
+
+    let report = init.run().await;
+
+    match report.stop_reason {
+        StopReason::Error => bail!("failed"),
+        StopReason::Manual | StopReason::ManualForced => {
+            bail!("cancelled")
+        }
+        StopReason::Natural => {}
+        StopReason::Restart => panic!("restart not supported for serialize"),
+    }
+
+    // TODO no need to report errors
+    report.into_typegraphs()
+}
+
 pub trait SerializeReportExt {
     #[allow(clippy::vec_box)]
     fn into_typegraphs(self) -> Result<Vec<Arc<Typegraph>>>;
@@ -13,6 +13,7 @@ use std::io;
 use std::slice;
 use std::str::FromStr;
 use typegate_api::Node;
+use typegraph_core::sdk::core::NamingStrategy;
 
 pub const METATYPE_FILES: &[&str] = &["metatype.yml", "metatype.yaml"];
 pub const VENV_FOLDERS: &[&str] = &[".venv"];
@@ -203,6 +204,9 @@ pub struct Typegraphs {
     pub loaders: HashMap<ModuleType, TypegraphLoaderConfig>,
     #[serde(default)]
     pub materializers: Materializers,
+    /// naming strategy for the generated type names
+    #[serde(default)]
+    pub naming_strategy: Option<NamingStrategy>,
 }
 
 #[derive(Deserialize, Debug, Default, Clone)]
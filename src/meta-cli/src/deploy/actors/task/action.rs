@@ -8,6 +8,7 @@ use crate::interlude::*;
 use crate::{config::Config, deploy::actors::console::ConsoleActor};
 use std::sync::Arc;
 use tokio::process::Command;
+use typegraph_core::sdk::core::NamingStrategy;
 
 #[derive(Debug, Clone)]
 pub struct SharedActionConfig {
@@ -18,6 +19,7 @@ pub struct SharedActionConfig {
     pub migrations_dir: Arc<Path>,
     pub default_migration_action: MigrationAction,
     pub artifact_resolution: bool,
+    pub naming_strategy: Option<NamingStrategy>,
 }
 
 pub trait TaskActionGenerator: Clone {
@@ -21,9 +21,12 @@ use reqwest::header::{self, HeaderMap, HeaderValue};
 use reqwest::Client;
 use serde::Deserialize;
 use std::{path::Path, sync::Arc};
+use tg_schema::diff::{ChangeSeverity, TypegraphDiff};
 use tokio::process::Command;
 use typegate_api::Node;
-use typegraph_core::sdk::core::{Artifact, Handler as _, PrismaMigrationConfig, SerializeParams};
+use typegraph_core::sdk::core::{
+    Artifact, Handler as _, NamingStrategy, PrismaMigrationConfig, SerializeParams,
+};
 use typegraph_core::sdk::utils::{Handler as _, QueryDeployParams};
 use typegraph_core::Lib;
 
@@ -37,6 +40,19 @@ pub struct PrismaRuntimeId {
     pub name: String,
 }
 
+/// What to do when the new version of a typegraph has breaking changes
+/// compared to the currently deployed version
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BreakingChangesPolicy {
+    /// Do not check for breaking changes
+    #[default]
+    Ignore,
+    /// Report the breaking and dangerous changes
+    Warn,
+    /// Refuse to deploy a typegraph with breaking changes
+    Deny,
+}
+
 #[derive(Debug)]
 pub struct DeployActionInner {
     task_ref: TaskRef,
@@ -44,6 +60,7 @@ pub struct DeployActionInner {
     shared_config: Arc<SharedActionConfig>,
     deploy_target: Arc<Node>,
     secrets: Arc<Secrets>,
+    breaking_changes: BreakingChangesPolicy,
 }
 
 #[derive(Clone)]
@@ -51,6 +68,7 @@ pub struct DeployActionGenerator {
     node: Arc<Node>,
     secrets: Arc<Secrets>, // TODO secrets_store
     shared_config: Arc<SharedActionConfig>,
+    breaking_changes: BreakingChangesPolicy,
 }
 
 impl DeployActionGenerator {
@@ -61,6 +79,7 @@ impl DeployActionGenerator {
         working_dir: Arc<Path>,
         migrations_dir: Arc<Path>,
         default_migration_action: MigrationAction,
+        breaking_changes: BreakingChangesPolicy,
     ) -> Self {
         Self {
             secrets,
@@ -72,11 +91,18 @@ impl DeployActionGenerator {
                 migrations_dir,
                 default_migration_action,
                 artifact_resolution: true,
+                naming_strategy: None,
             }
             .into(),
             node,
+            breaking_changes,
         }
     }
+
+    pub fn naming_strategy(mut self, naming_strategy: Option<NamingStrategy>) -> Self {
+        Arc::make_mut(&mut self.shared_config).naming_strategy = naming_strategy;
+        self
+    }
 }
 
 impl TaskActionGenerator for DeployActionGenerator {
@@ -89,6 +115,7 @@ impl TaskActionGenerator for DeployActionGenerator {
             shared_config: self.shared_config.clone(),
             deploy_target: self.node.clone(),
             secrets: self.secrets.clone(),
+            breaking_changes: self.breaking_changes,
         }
         .into()
     }
@@ -400,14 +427,66 @@ impl DeployActionInner {
                 default_migration_action: deploy_data.default_migration_action,
             },
             pretty: false,
+            naming_strategy: self.shared_config.naming_strategy.clone(),
         })
         .map_err(|error| DeployError {
             typegraph: params.typegraph_path.clone(),
             errors: error.stack,
         })?;
 
-        self.request_typegate(params, typgraph, artifacts, deploy_data.secrets)
+        let warnings = self
+            .check_breaking_changes(&typgraph)
             .await
+            .map_err(|errors| DeployError {
+                typegraph: params.typegraph_name.clone(),
+                errors,
+            })?;
+
+        let mut res = self
+            .request_typegate(params, typgraph, artifacts, deploy_data.secrets)
+            .await?;
+        res.messages.extend(warnings);
+        Ok(res)
+    }
+
+    /// Compare the serialized typegraph with the version currently deployed
+    /// on the target typegate
+    async fn check_breaking_changes(
+        &self,
+        typegraph: &str,
+    ) -> Result<Vec<MessageEntry>, Vec<String>> {
+        if self.breaking_changes == BreakingChangesPolicy::Ignore {
+            return Ok(vec![]);
+        }
+
+        let new_tg: tg_schema::Typegraph = serde_json::from_str(typegraph)
+            .map_err(|e| vec![format!("could not parse the serialized typegraph: {e}")])?;
+        let tg_name = new_tg.name().map_err(|e| vec![e.to_string()])?;
+        let old_tg = match self.deploy_target.typegraph(&tg_name).await {
+            Ok(Some(tg)) => tg,
+            // first deployment
+            Ok(None) => return Ok(vec![]),
+            Err(e) => {
+                return Err(vec![format!(
+                    "could not fetch the deployed version of {tg_name:?}: {e}"
+                )])
+            }
+        };
+        let diff = TypegraphDiff::new(&old_tg, &new_tg).map_err(|e| vec![format!("{e:#}")])?;
+
+        if self.breaking_changes == BreakingChangesPolicy::Deny && diff.has_breaking_changes() {
+            let mut errors = vec![
+                "breaking changes from the deployed version; use `--breaking-changes warn` to deploy anyway"
+                    .to_string(),
+            ];
+            errors.extend(diff.filter(ChangeSeverity::Breaking).map(|c| c.to_string()));
+            return Err(errors);
+        }
+
+        Ok(diff
+            .filter(ChangeSeverity::Dangerous)
+            .map(|c| MessageEntry::Warning(c.to_string()))
+            .collect())
     }
 
     async fn request_typegate(
@@ -50,6 +50,7 @@ impl ListActionGenerator {
                     reset: false,
                 },
                 artifact_resolution,
+                naming_strategy: None,
             }
             .into(),
         }
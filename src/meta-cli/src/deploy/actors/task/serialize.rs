@@ -16,7 +16,7 @@ use serde::Deserialize;
 use std::sync::Arc;
 use tg_schema::Typegraph;
 use tokio::process::Command;
-use typegraph_core::sdk::core::{Handler, SerializeParams};
+use typegraph_core::sdk::core::{Handler, NamingStrategy, SerializeParams};
 use typegraph_core::Lib;
 
 pub type SerializeAction = Arc<SerializeActionInner>;
@@ -40,6 +40,7 @@ impl SerializeActionGenerator {
         working_dir: Arc<Path>,
         migrations_dir: Arc<Path>,
         artifact_resolution: bool,
+        naming_strategy: Option<NamingStrategy>,
     ) -> Self {
         Self {
             shared_config: SharedActionConfig {
@@ -54,6 +55,7 @@ impl SerializeActionGenerator {
                     reset: false,
                 },
                 artifact_resolution,
+                naming_strategy,
             }
             .into(),
         }
@@ -201,8 +203,13 @@ impl TaskAction for SerializeAction {
 }
 
 impl SerializeActionInner {
-    fn serialize(&self, params: SerializeParams) -> Result<Arc<Typegraph>, SerializeError> {
+    fn serialize(&self, mut params: SerializeParams) -> Result<Arc<Typegraph>, SerializeError> {
         let typegraph_name = params.typegraph_name.clone();
+        if params.naming_strategy.is_none() {
+            params
+                .naming_strategy
+                .clone_from(&self.shared_config.naming_strategy);
+        }
         match Lib::serialize_typegraph(params) {
             Ok((value, _)) => {
                 Ok(serde_json::from_str(&value).expect("Failed to deserialize JSON typegraph"))
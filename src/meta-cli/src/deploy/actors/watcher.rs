@@ -111,25 +111,13 @@ impl<A: TaskAction> WatcherActor<A> {
         ctx: &mut <WatcherActor<A> as actix::Actor>::Context,
     ) -> Result<()> {
         let self_addr = ctx.address();
-        let mut debouncer =
-            new_debouncer(Duration::from_secs(1), move |res: DebounceEventResult| {
-                let events = res.unwrap();
-                for path in events.into_iter().map(|e| e.path) {
-                    self_addr.do_send(File(path));
-                }
-            })?;
-        debouncer.watcher().configure(
-            notify::Config::default()
-                .with_poll_interval(Duration::from_secs(1))
-                .with_compare_contents(false), // TODO configurable?
-        )?;
-
-        let watcher = debouncer.watcher();
         self.console
             .info(format!("Watching {path:?}...", path = self.directory));
-        watcher
-            .watch(&self.directory, RecursiveMode::Recursive)
-            .with_context(|| format!("Watching {path:?}", path = self.directory))?;
+        let debouncer = watch_directory(&self.directory, move |paths| {
+            for path in paths {
+                self_addr.do_send(File(path));
+            }
+        })?;
 
         self.debouncer = Some(debouncer);
 
@@ -137,6 +125,31 @@ impl<A: TaskAction> WatcherActor<A> {
     }
 }
 
+/// Recursively watch `directory`, calling `on_change` with the paths of
+/// each batch of debounced events.
+/// The watcher stops when the returned debouncer is dropped.
+pub fn watch_directory(
+    directory: &Path,
+    mut on_change: impl FnMut(Vec<PathBuf>) + Send + 'static,
+) -> Result<Debouncer<RecommendedWatcher>> {
+    let mut debouncer = new_debouncer(Duration::from_secs(1), move |res: DebounceEventResult| {
+        let events = res.unwrap();
+        on_change(events.into_iter().map(|e| e.path).collect());
+    })?;
+    debouncer.watcher().configure(
+        notify::Config::default()
+            .with_poll_interval(Duration::from_secs(1))
+            .with_compare_contents(false), // TODO configurable?
+    )?;
+
+    debouncer
+        .watcher()
+        .watch(directory, RecursiveMode::Recursive)
+        .with_context(|| format!("Watching {directory:?}"))?;
+
+    Ok(debouncer)
+}
+
 impl<A: TaskAction + 'static> Handler<Stop> for WatcherActor<A> {
     type Result = ();
 
@@ -133,20 +133,10 @@ impl FileFilter {
             return true;
         }
 
-        let rel_path = diff_paths(path, &self.base_dir).unwrap();
-        if rel_path.as_os_str().is_empty() {
-            return true;
-        }
-
-        if let Some(gi) = &self.gitignore {
-            if matches!(gi.matched(&rel_path, false), Match::Ignore(_)) {
-                return true;
-            }
-        }
-
-        if self.exclude_hidden && is_hidden(&rel_path) {
+        if self.is_ignored(path) {
             return true;
         }
+        let rel_path = diff_paths(path, &self.base_dir).unwrap();
 
         match ModuleType::try_from(path) {
             Ok(ModuleType::Python) => self.is_excluded_by_filter(&rel_path, &self.python_filter),
@@ -160,6 +150,23 @@ impl FileFilter {
         }
     }
 
+    /// Whether the path is gitignored or hidden, regardless of the
+    /// typegraph loader filters
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let rel_path = diff_paths(path, &self.base_dir).unwrap();
+        if rel_path.as_os_str().is_empty() {
+            return true;
+        }
+
+        if let Some(gi) = &self.gitignore {
+            if matches!(gi.matched(&rel_path, false), Match::Ignore(_)) {
+                return true;
+            }
+        }
+
+        self.exclude_hidden && is_hidden(&rel_path)
+    }
+
     fn is_excluded_by_filter(&self, rel_path: &Path, filter: &GlobFilter) -> bool {
         if !filter.include_set.is_empty() && !filter.include_set.is_match(rel_path) {
             return true;
@@ -86,6 +86,10 @@ pub enum RpcCall {
         key: String,
         check: ContextCheck,
     },
+    RegisterContextRulePolicy {
+        name: String,
+        rule: String,
+    },
     RenameType {
         tpe: TypeId,
         new_name: String,
@@ -171,6 +175,10 @@ impl super::RpcDispatch for RpcCall {
             }
             Self::RegisterContextPolicy { key, check } => Lib::register_context_policy(key, check)
                 .map(|res| serde_json::to_value(res).unwrap()),
+            Self::RegisterContextRulePolicy { name, rule } => {
+                Lib::register_context_rule_policy(name, rule)
+                    .map(|res| serde_json::to_value(res).unwrap())
+            }
             Self::RenameType { tpe, new_name } => {
                 Lib::rename_type(tpe, new_name).map(|res| serde_json::to_value(res).unwrap())
             }
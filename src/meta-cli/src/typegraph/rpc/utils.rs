@@ -18,6 +18,10 @@ pub enum RpcCall {
     AddGraphqlEndpoint {
         graphql: String,
     },
+    AddStringFormat {
+        name: String,
+        pattern: String,
+    },
     AddAuth {
         data: Auth,
     },
@@ -64,6 +68,9 @@ impl super::RpcDispatch for RpcCall {
             Self::AddGraphqlEndpoint { graphql } => {
                 Lib::add_graphql_endpoint(graphql).map(|res| serde_json::to_value(res).unwrap())
             }
+            Self::AddStringFormat { name, pattern } => {
+                Lib::add_string_format(name, pattern).map(|res| serde_json::to_value(res).unwrap())
+            }
             Self::AddAuth { data } => {
                 Lib::add_auth(data).map(|res| serde_json::to_value(res).unwrap())
             }
@@ -5,7 +5,14 @@ use crate::args::{PlaceholderValue, PreparedArgs};
 use crate::common::*;
 use crate::files::{File, FileExtractor};
 use crate::interlude::*;
-use crate::nodes::{SelectNodeErased, ToMutationDoc, ToQueryDoc, ToSelectDoc};
+use crate::middleware::{Middleware, Middlewares, Next, OperationType, RequestContext};
+#[cfg(not(target_family = "wasm"))]
+use crate::middleware::{NextSync, SyncMiddleware, SyncMiddlewares};
+use crate::nodes::{SelectNodeErased, SubscriptionNode, ToMutationDoc, ToQueryDoc, ToSelectDoc};
+use crate::persisted::{self, DocPayload};
+use crate::sse::{self, EventOutcome, SseParser};
+use futures::{Stream, StreamExt};
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 #[derive(Default, Clone)]
@@ -14,6 +21,15 @@ pub struct GraphQlTransportOptions {
     pub timeout: Option<std::time::Duration>,
 }
 
+/// The document of an operation along with its hash
+/// when persisted queries are enabled.
+#[derive(Clone, Copy)]
+struct OperationDoc<'a> {
+    doc: &'a str,
+    hash: Option<&'a str>,
+    ty: &'static str,
+}
+
 // PlaceholderValue, fieldName -> gql_var_name
 pub(crate) type FoundPlaceholders = Vec<(PlaceholderValue, HashMap<CowStr, CowStr>)>;
 
@@ -36,7 +52,7 @@ use reqwest::blocking::{Client as ClientSync, RequestBuilder as RequestBuilderSy
 pub(crate) fn build_gql_req_sync(
     client: &ClientSync,
     addr: Url,
-    doc: &str,
+    doc: DocPayload<'_>,
     mut variables: JsonObject,
     path_to_files: HashMap<String, Vec<TypePath>>,
     opts: &GraphQlTransportOptions,
@@ -57,10 +73,7 @@ pub(crate) fn build_gql_req_sync(
     );
     headers.extend(opts.headers.clone());
 
-    let operations = serde_json::json!({
-        "query": doc,
-        "variables": variables
-    });
+    let operations = doc.to_operations(&variables);
 
     // TODO rename files
 
@@ -108,7 +121,7 @@ use reqwest::{Client, RequestBuilder, Url};
 async fn build_gql_reqwest(
     client: &Client,
     addr: Url,
-    doc: &str,
+    doc: DocPayload<'_>,
     mut variables: JsonObject,
     path_to_files: HashMap<String, Vec<TypePath>>,
     opts: &GraphQlTransportOptions,
@@ -126,10 +139,7 @@ async fn build_gql_reqwest(
     );
     headers.extend(opts.headers.clone());
 
-    let operations = serde_json::json!({
-        "query": doc,
-        "variables": variables
-    });
+    let operations = doc.to_operations(&variables);
 
     if !files.is_empty() {
         // multipart
@@ -172,12 +182,202 @@ async fn build_gql_reqwest(
     }
 }
 
+fn parse_subscription_response<Out: serde::de::DeserializeOwned>(
+    data: Vec<serde_json::Value>,
+) -> Result<Out, GraphQLRequestError> {
+    SubscriptionNode::<Out>::parse_response(data).map_err(|err| GraphQLRequestError::BodyError {
+        error: Box::from(format!(
+            "error deserializing response into output type: {err}"
+        )),
+    })
+}
+
+fn is_event_stream(headers: &reqwest::header::HeaderMap) -> bool {
+    headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|val| val.to_str().ok())
+        .map(|val| val.starts_with(sse::EVENT_STREAM))
+        .unwrap_or(false)
+}
+
+fn subscription_opts(opts: &GraphQlTransportOptions) -> GraphQlTransportOptions {
+    let mut opts = opts.clone();
+    opts.headers.insert(
+        reqwest::header::ACCEPT,
+        sse::EVENT_STREAM.try_into().unwrap(),
+    );
+    opts
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn send_req_sync(
+    client: &ClientSync,
+    middlewares: &SyncMiddlewares,
+    req: RequestBuilderSync,
+    ty: &'static str,
+    doc: &str,
+) -> Result<reqwest::blocking::Response, GraphQLRequestError> {
+    let req = req
+        .build()
+        .map_err(|error| GraphQLRequestError::NetworkError {
+            error: Box::new(error),
+        })?;
+    let cx = RequestContext {
+        operation: OperationType::from_doc_ty(ty),
+        doc: doc.into(),
+    };
+    NextSync::new(client, middlewares)
+        .run(req, &cx)
+        .map_err(|error| GraphQLRequestError::NetworkError { error })
+}
+
+async fn send_req(
+    client: &Client,
+    middlewares: &Middlewares,
+    req: RequestBuilder,
+    ty: &'static str,
+    doc: &str,
+) -> Result<reqwest::Response, GraphQLRequestError> {
+    let req = req
+        .build()
+        .map_err(|error| GraphQLRequestError::NetworkError {
+            error: Box::new(error),
+        })?;
+    let cx = RequestContext {
+        operation: OperationType::from_doc_ty(ty),
+        doc: doc.into(),
+    };
+    Next::new(client, middlewares)
+        .run(req, &cx)
+        .await
+        .map_err(|error| GraphQLRequestError::NetworkError { error })
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn read_response_sync(
+    res: reqwest::blocking::Response,
+) -> Result<GraphQLResponse, GraphQLRequestError> {
+    let status = res.status();
+    match res.json::<JsonObject>() {
+        Ok(body) => Ok(GraphQLResponse {
+            status_code: status.as_u16(),
+            body,
+        }),
+        Err(error) => Err(GraphQLRequestError::BodyError {
+            error: Box::new(error),
+        }),
+    }
+}
+
+async fn read_response(res: reqwest::Response) -> Result<GraphQLResponse, GraphQLRequestError> {
+    let status = res.status();
+    match res.json::<JsonObject>().await {
+        Ok(body) => Ok(GraphQLResponse {
+            status_code: status.as_u16(),
+            body,
+        }),
+        Err(error) => Err(GraphQLRequestError::BodyError {
+            error: Box::new(error),
+        }),
+    }
+}
+
+/// Send a query or mutation, trying the persisted
+/// document first if a hash is available.
+#[cfg(not(target_family = "wasm"))]
+fn send_operation_sync(
+    client: &ClientSync,
+    middlewares: &SyncMiddlewares,
+    addr: &Url,
+    op: OperationDoc<'_>,
+    variables: JsonObject,
+    path_to_files: HashMap<String, Vec<TypePath>>,
+    opts: &GraphQlTransportOptions,
+) -> Result<GraphQLResponse, GraphQLRequestError> {
+    // multipart bodies aren't worth the round trip
+    let hash = op.hash.filter(|_| path_to_files.is_empty());
+    let payload = match hash {
+        Some(hash) => {
+            let req = build_gql_req_sync(
+                client,
+                addr.clone(),
+                DocPayload::HashOnly(hash),
+                variables.clone(),
+                HashMap::new(),
+                opts,
+            )?;
+            let res = read_response_sync(send_req_sync(client, middlewares, req, op.ty, op.doc)?)?;
+            if !persisted::is_miss(&res) {
+                return Ok(res);
+            }
+            DocPayload::FullWithHash { doc: op.doc, hash }
+        }
+        None => DocPayload::Full(op.doc),
+    };
+    let req = build_gql_req_sync(
+        client,
+        addr.clone(),
+        payload,
+        variables,
+        path_to_files,
+        opts,
+    )?;
+    read_response_sync(send_req_sync(client, middlewares, req, op.ty, op.doc)?)
+}
+
+/// Send a query or mutation, trying the persisted
+/// document first if a hash is available.
+async fn send_operation(
+    client: &Client,
+    middlewares: &Middlewares,
+    addr: &Url,
+    op: OperationDoc<'_>,
+    variables: JsonObject,
+    path_to_files: HashMap<String, Vec<TypePath>>,
+    opts: &GraphQlTransportOptions,
+) -> Result<GraphQLResponse, GraphQLRequestError> {
+    // multipart bodies aren't worth the round trip
+    let hash = op.hash.filter(|_| path_to_files.is_empty());
+    let payload = match hash {
+        Some(hash) => {
+            let req = build_gql_reqwest(
+                client,
+                addr.clone(),
+                DocPayload::HashOnly(hash),
+                variables.clone(),
+                HashMap::new(),
+                opts,
+            )
+            .await?;
+            let res =
+                read_response(send_req(client, middlewares, req, op.ty, op.doc).await?).await?;
+            if !persisted::is_miss(&res) {
+                return Ok(res);
+            }
+            DocPayload::FullWithHash { doc: op.doc, hash }
+        }
+        None => DocPayload::Full(op.doc),
+    };
+    let req = build_gql_reqwest(
+        client,
+        addr.clone(),
+        payload,
+        variables,
+        path_to_files,
+        opts,
+    )
+    .await?;
+    read_response(send_req(client, middlewares, req, op.ty, op.doc).await?).await
+}
+
 #[derive(Clone)]
 #[cfg(not(target_family = "wasm"))]
 pub struct GraphQlTransportReqwestSync {
     addr: Url,
     ty_to_gql_ty_map: TyToGqlTyMap,
     client: reqwest::blocking::Client,
+    middlewares: SyncMiddlewares,
+    persisted_queries: bool,
 }
 
 #[derive(Clone)]
@@ -185,6 +385,8 @@ pub struct GraphQlTransportReqwest {
     addr: Url,
     ty_to_gql_ty_map: TyToGqlTyMap,
     client: reqwest::Client,
+    middlewares: Middlewares,
+    persisted_queries: bool,
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -194,9 +396,26 @@ impl GraphQlTransportReqwestSync {
             addr,
             ty_to_gql_ty_map,
             client: reqwest::blocking::Client::new(),
+            middlewares: Default::default(),
+            persisted_queries: false,
         }
     }
 
+    /// Add a middleware to the chain wrapping each request,
+    /// see [`crate::middleware`].
+    pub fn with_middleware(mut self, middleware: impl SyncMiddleware) -> Self {
+        Arc::make_mut(&mut self.middlewares).push(Arc::new(middleware));
+        self
+    }
+
+    /// Send queries and mutations as automatic persisted queries:
+    /// only the hash of the document is sent unless the server
+    /// asks for the full text.
+    pub fn with_persisted_queries(mut self) -> Self {
+        self.persisted_queries = true;
+        self
+    }
+
     fn fetch(
         &self,
         nodes: Vec<SelectNodeErased>,
@@ -213,34 +432,21 @@ impl GraphQlTransportReqwestSync {
         if !placeholders.is_empty() {
             panic!("placeholders found in non-prepared query")
         }
-        let req = build_gql_req_sync(
+        let hash = self.persisted_queries.then(|| persisted::hash_doc(&doc));
+        let res = send_operation_sync(
             &self.client,
-            self.addr.clone(),
-            &doc,
+            &self.middlewares,
+            &self.addr,
+            OperationDoc {
+                doc: &doc,
+                hash: hash.as_deref(),
+                ty,
+            },
             variables,
             path_to_files,
             opts,
         )?;
-        match req.send() {
-            Ok(res) => {
-                let status = res.status();
-                match res.json::<JsonObject>() {
-                    Ok(body) => handle_response(
-                        GraphQLResponse {
-                            status_code: status.as_u16(),
-                            body,
-                        },
-                        nodes_len,
-                    ),
-                    Err(error) => Err(GraphQLRequestError::BodyError {
-                        error: Box::new(error),
-                    }),
-                }
-            }
-            Err(error) => Err(GraphQLRequestError::NetworkError {
-                error: Box::new(error),
-            }),
-        }
+        handle_response(res, nodes_len)
     }
 
     pub fn query<Doc: ToSelectDoc + ToQueryDoc>(
@@ -284,6 +490,101 @@ impl GraphQlTransportReqwestSync {
         })?;
         Ok(resp)
     }
+    /// Make a subscription request, the results are delivered
+    /// as they arrive through the returned iterator.
+    pub fn subscription<Out: serde::de::DeserializeOwned>(
+        &self,
+        node: SubscriptionNode<Out>,
+    ) -> Result<impl Iterator<Item = Result<Out, GraphQLRequestError>>, GraphQLRequestError> {
+        self.subscription_with_opts(node, &Default::default())
+    }
+
+    pub fn subscription_with_opts<Out: serde::de::DeserializeOwned>(
+        &self,
+        node: SubscriptionNode<Out>,
+        opts: &GraphQlTransportOptions,
+    ) -> Result<impl Iterator<Item = Result<Out, GraphQLRequestError>>, GraphQLRequestError> {
+        use std::io::Read;
+
+        let GqlRequest {
+            doc,
+            variables,
+            placeholders,
+            path_to_files,
+        } = GqlRequestBuilder::new(&self.ty_to_gql_ty_map).build(
+            node.to_select_doc(),
+            "subscription",
+            None,
+        )?;
+        if !placeholders.is_empty() {
+            panic!("placeholders found in non-prepared query")
+        }
+        let req = build_gql_req_sync(
+            &self.client,
+            self.addr.clone(),
+            DocPayload::Full(&doc),
+            variables,
+            path_to_files,
+            &subscription_opts(opts),
+        )?;
+        let res = send_req_sync(&self.client, &self.middlewares, req, "subscription", &doc)?;
+
+        let mut single = None;
+        let mut body = if is_event_stream(res.headers()) {
+            Some(res)
+        } else {
+            // errors and servers without support for streaming
+            // respond with a single json body
+            let status = res.status();
+            let body =
+                res.json::<JsonObject>()
+                    .map_err(|error| GraphQLRequestError::BodyError {
+                        error: Box::new(error),
+                    })?;
+            let data = handle_response(
+                GraphQLResponse {
+                    status_code: status.as_u16(),
+                    body,
+                },
+                1,
+            )?;
+            single = Some(parse_subscription_response(data));
+            None
+        };
+
+        let mut parser = SseParser::default();
+        let mut pending = VecDeque::new();
+        let mut buf = vec![0; 8 * 1024];
+        Ok(std::iter::from_fn(move || {
+            if let Some(out) = single.take() {
+                return Some(out);
+            }
+            while let Some(res) = body.as_mut() {
+                if let Some(event) = pending.pop_front() {
+                    match sse::handle_event(event) {
+                        EventOutcome::Next(res) => {
+                            return Some(res.and_then(parse_subscription_response))
+                        }
+                        EventOutcome::Skip => continue,
+                        EventOutcome::Complete => break,
+                    }
+                }
+                match res.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(len) => pending.extend(parser.feed(&buf[..len])),
+                    Err(error) => {
+                        body = None;
+                        return Some(Err(GraphQLRequestError::NetworkError {
+                            error: Box::new(error),
+                        }));
+                    }
+                }
+            }
+            body = None;
+            None
+        }))
+    }
+
     pub fn prepare_query<Doc: ToSelectDoc + ToQueryDoc>(
         &self,
         fun: impl FnOnce(&mut PreparedArgs) -> Doc,
@@ -302,6 +603,8 @@ impl GraphQlTransportReqwestSync {
             opts,
             "query",
             &self.ty_to_gql_ty_map,
+            self.middlewares.clone(),
+            self.persisted_queries,
         )
     }
 
@@ -323,6 +626,8 @@ impl GraphQlTransportReqwestSync {
             opts,
             "mutation",
             &self.ty_to_gql_ty_map,
+            self.middlewares.clone(),
+            self.persisted_queries,
         )
     }
 }
@@ -333,9 +638,26 @@ impl GraphQlTransportReqwest {
             addr,
             ty_to_gql_ty_map,
             client: reqwest::Client::new(),
+            middlewares: Default::default(),
+            persisted_queries: false,
         }
     }
 
+    /// Add a middleware to the chain wrapping each request,
+    /// see [`crate::middleware`].
+    pub fn with_middleware(mut self, middleware: impl Middleware) -> Self {
+        Arc::make_mut(&mut self.middlewares).push(Arc::new(middleware));
+        self
+    }
+
+    /// Send queries and mutations as automatic persisted queries:
+    /// only the hash of the document is sent unless the server
+    /// asks for the full text.
+    pub fn with_persisted_queries(mut self) -> Self {
+        self.persisted_queries = true;
+        self
+    }
+
     async fn fetch(
         &self,
         nodes: Vec<SelectNodeErased>,
@@ -353,35 +675,22 @@ impl GraphQlTransportReqwest {
             panic!("placeholders found in non-prepared query")
         }
 
-        let req = build_gql_reqwest(
+        let hash = self.persisted_queries.then(|| persisted::hash_doc(&doc));
+        let res = send_operation(
             &self.client,
-            self.addr.clone(),
-            &doc,
+            &self.middlewares,
+            &self.addr,
+            OperationDoc {
+                doc: &doc,
+                hash: hash.as_deref(),
+                ty,
+            },
             variables,
             path_to_files,
             opts,
         )
         .await?;
-        match req.send().await {
-            Ok(res) => {
-                let status = res.status();
-                match res.json::<JsonObject>().await {
-                    Ok(body) => handle_response(
-                        GraphQLResponse {
-                            status_code: status.as_u16(),
-                            body,
-                        },
-                        nodes_len,
-                    ),
-                    Err(error) => Err(GraphQLRequestError::BodyError {
-                        error: Box::new(error),
-                    }),
-                }
-            }
-            Err(error) => Err(GraphQLRequestError::NetworkError {
-                error: Box::new(error),
-            }),
-        }
+        handle_response(res, nodes_len)
     }
 
     pub async fn query<Doc: ToSelectDoc + ToQueryDoc>(
@@ -425,6 +734,98 @@ impl GraphQlTransportReqwest {
         })?;
         Ok(resp)
     }
+    /// Make a subscription request, the results are delivered
+    /// as they arrive through the returned stream.
+    pub async fn subscription<Out: serde::de::DeserializeOwned>(
+        &self,
+        node: SubscriptionNode<Out>,
+    ) -> Result<impl Stream<Item = Result<Out, GraphQLRequestError>>, GraphQLRequestError> {
+        self.subscription_with_opts(node, &Default::default()).await
+    }
+
+    pub async fn subscription_with_opts<Out: serde::de::DeserializeOwned>(
+        &self,
+        node: SubscriptionNode<Out>,
+        opts: &GraphQlTransportOptions,
+    ) -> Result<impl Stream<Item = Result<Out, GraphQLRequestError>>, GraphQLRequestError> {
+        let GqlRequest {
+            doc,
+            variables,
+            placeholders,
+            path_to_files,
+        } = GqlRequestBuilder::new(&self.ty_to_gql_ty_map).build(
+            node.to_select_doc(),
+            "subscription",
+            None,
+        )?;
+        if !placeholders.is_empty() {
+            panic!("placeholders found in non-prepared query")
+        }
+        let req = build_gql_reqwest(
+            &self.client,
+            self.addr.clone(),
+            DocPayload::Full(&doc),
+            variables,
+            path_to_files,
+            &subscription_opts(opts),
+        )
+        .await?;
+        let res = send_req(&self.client, &self.middlewares, req, "subscription", &doc).await?;
+
+        if !is_event_stream(res.headers()) {
+            // errors and servers without support for streaming
+            // respond with a single json body
+            let status = res.status();
+            let body =
+                res.json::<JsonObject>()
+                    .await
+                    .map_err(|error| GraphQLRequestError::BodyError {
+                        error: Box::new(error),
+                    })?;
+            let data = handle_response(
+                GraphQLResponse {
+                    status_code: status.as_u16(),
+                    body,
+                },
+                1,
+            )?;
+            let out = parse_subscription_response(data);
+            return Ok(futures::stream::iter([out]).left_stream());
+        }
+
+        let state = (
+            Box::pin(res.bytes_stream()),
+            SseParser::default(),
+            VecDeque::new(),
+        );
+        let stream =
+            futures::stream::unfold(state, |(mut body, mut parser, mut pending)| async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        match sse::handle_event(event) {
+                            EventOutcome::Next(res) => {
+                                let out = res.and_then(parse_subscription_response);
+                                return Some((out, (body, parser, pending)));
+                            }
+                            EventOutcome::Skip => continue,
+                            EventOutcome::Complete => return None,
+                        }
+                    }
+                    match body.next().await {
+                        Some(Ok(chunk)) => pending.extend(parser.feed(&chunk)),
+                        Some(Err(error)) => {
+                            let err = GraphQLRequestError::NetworkError {
+                                error: Box::new(error),
+                            };
+                            return Some((Err(err), (body, parser, pending)));
+                        }
+                        None => return None,
+                    }
+                }
+            });
+        Ok(stream.right_stream())
+    }
+
     pub fn prepare_query<Doc: ToSelectDoc + ToQueryDoc>(
         &self,
         fun: impl FnOnce(&mut PreparedArgs) -> Doc,
@@ -443,6 +844,8 @@ impl GraphQlTransportReqwest {
             opts,
             "query",
             &self.ty_to_gql_ty_map,
+            self.middlewares.clone(),
+            self.persisted_queries,
         )
     }
 
@@ -464,6 +867,8 @@ impl GraphQlTransportReqwest {
             opts,
             "mutation",
             &self.ty_to_gql_ty_map,
+            self.middlewares.clone(),
+            self.persisted_queries,
         )
     }
 }
@@ -502,8 +907,12 @@ pub(crate) fn resolve_prepared_variables<T>(
 pub struct PreparedRequestReqwest<Out> {
     addr: Url,
     client: reqwest::Client,
+    middlewares: Middlewares,
+    ty: &'static str,
     nodes_len: usize,
     pub doc: String,
+    /// Hash of `doc` if sent as a persisted query
+    pub doc_hash: Option<String>,
     variables: JsonObject,
     path_to_files: HashMap<String, Vec<TypePath>>,
     opts: GraphQlTransportOptions,
@@ -515,8 +924,12 @@ pub struct PreparedRequestReqwest<Out> {
 pub struct PreparedRequestReqwestSync<Doc> {
     addr: Url,
     client: reqwest::blocking::Client,
+    middlewares: SyncMiddlewares,
+    ty: &'static str,
     nodes_len: usize,
     pub doc: String,
+    /// Hash of `doc` if sent as a persisted query
+    pub doc_hash: Option<String>,
     variables: JsonObject,
     path_to_files: HashMap<String, Vec<TypePath>>,
     opts: GraphQlTransportOptions,
@@ -526,12 +939,22 @@ pub struct PreparedRequestReqwestSync<Doc> {
 
 #[cfg(not(target_family = "wasm"))]
 impl<Doc: ToSelectDoc> PreparedRequestReqwestSync<Doc> {
+    fn operation_doc(&self) -> OperationDoc<'_> {
+        OperationDoc {
+            doc: &self.doc,
+            hash: self.doc_hash.as_deref(),
+            ty: self.ty,
+        }
+    }
+
     fn new(
         fun: impl FnOnce(&mut PreparedArgs) -> Doc,
         addr: Url,
         opts: GraphQlTransportOptions,
         ty: &'static str,
         ty_to_gql_ty_map: &TyToGqlTyMap,
+        middlewares: SyncMiddlewares,
+        persisted_queries: bool,
     ) -> Result<Self, PrepareRequestError<GraphQLRequestError>> {
         let nodes = fun(&mut PreparedArgs);
         let nodes = nodes.to_select_doc();
@@ -544,13 +967,19 @@ impl<Doc: ToSelectDoc> PreparedRequestReqwestSync<Doc> {
         } = GqlRequestBuilder::new(ty_to_gql_ty_map)
             .build(nodes, ty, None)
             .map_err(PrepareRequestError::BuildError)?;
+        // the document is rendered and hashed once
+        // and reused by each perform
+        let doc_hash = persisted_queries.then(|| persisted::hash_doc(&doc));
         Ok(Self {
             doc,
+            doc_hash,
             variables,
             path_to_files,
             nodes_len,
             addr,
             client: reqwest::blocking::Client::new(),
+            middlewares,
+            ty,
             opts,
             placeholders: Arc::new(placeholders),
             _phantom: PhantomData,
@@ -573,43 +1002,17 @@ impl<Doc: ToSelectDoc> PreparedRequestReqwestSync<Doc> {
         let variables =
             resolve_prepared_variables(&self.placeholders, self.variables.clone(), args)?;
         // TODO extract files from variables after resolution
-        let req = build_gql_req_sync(
+        let res = send_operation_sync(
             &self.client,
-            self.addr.clone(),
-            &self.doc,
+            &self.middlewares,
+            &self.addr,
+            self.operation_doc(),
             variables,
             self.path_to_files.clone(),
             &self.opts,
-        )?;
-        let res = match req.send() {
-            Ok(res) => {
-                let status = res.status();
-                match res.json::<JsonObject>() {
-                    Ok(body) => handle_response(
-                        GraphQLResponse {
-                            status_code: status.as_u16(),
-                            body,
-                        },
-                        self.nodes_len,
-                    )
-                    .map_err(PrepareRequestError::RequestError)?,
-                    Err(error) => {
-                        return Err(PrepareRequestError::RequestError(
-                            GraphQLRequestError::BodyError {
-                                error: Box::new(error),
-                            },
-                        ))
-                    }
-                }
-            }
-            Err(error) => {
-                return Err(PrepareRequestError::RequestError(
-                    GraphQLRequestError::NetworkError {
-                        error: Box::new(error),
-                    },
-                ))
-            }
-        };
+        )
+        .and_then(|res| handle_response(res, self.nodes_len))
+        .map_err(PrepareRequestError::RequestError)?;
         Doc::parse_response(res).map_err(|err| {
             PrepareRequestError::RequestError(GraphQLRequestError::BodyError {
                 error: Box::from(format!(
@@ -621,12 +1024,22 @@ impl<Doc: ToSelectDoc> PreparedRequestReqwestSync<Doc> {
 }
 
 impl<Doc: ToSelectDoc> PreparedRequestReqwest<Doc> {
+    fn operation_doc(&self) -> OperationDoc<'_> {
+        OperationDoc {
+            doc: &self.doc,
+            hash: self.doc_hash.as_deref(),
+            ty: self.ty,
+        }
+    }
+
     fn new(
         fun: impl FnOnce(&mut PreparedArgs) -> Doc,
         addr: Url,
         opts: GraphQlTransportOptions,
         ty: &'static str,
         ty_to_gql_ty_map: &TyToGqlTyMap,
+        middlewares: Middlewares,
+        persisted_queries: bool,
     ) -> Result<Self, PrepareRequestError<GraphQLRequestError>> {
         let nodes = fun(&mut PreparedArgs);
         let nodes = nodes.to_select_doc();
@@ -640,13 +1053,17 @@ impl<Doc: ToSelectDoc> PreparedRequestReqwest<Doc> {
             .build(nodes, ty, None)
             .map_err(PrepareRequestError::BuildError)?;
         let placeholders = std::sync::Arc::new(placeholders);
+        let doc_hash = persisted_queries.then(|| persisted::hash_doc(&doc));
         Ok(Self {
             doc,
+            doc_hash,
             variables,
             path_to_files,
             nodes_len,
             addr,
             client: reqwest::Client::new(),
+            middlewares,
+            ty,
             opts,
             placeholders,
             _phantom: PhantomData,
@@ -669,46 +1086,18 @@ impl<Doc: ToSelectDoc> PreparedRequestReqwest<Doc> {
         let variables =
             resolve_prepared_variables(&self.placeholders, self.variables.clone(), args)?;
         // TODO extract files from variables
-        let req = build_gql_reqwest(
+        let res = send_operation(
             &self.client,
-            self.addr.clone(),
-            &self.doc,
+            &self.middlewares,
+            &self.addr,
+            self.operation_doc(),
             variables,
             self.path_to_files.clone(),
             &self.opts,
         )
-        .await?;
-        let res = match req.send().await {
-            Ok(res) => {
-                let status = res.status();
-                // let headers = res.headers().clone();
-                match res.json::<JsonObject>().await {
-                    Ok(body) => handle_response(
-                        GraphQLResponse {
-                            status_code: status.as_u16(),
-                            // headers,
-                            body,
-                        },
-                        self.nodes_len,
-                    )
-                    .map_err(PrepareRequestError::RequestError)?,
-                    Err(error) => {
-                        return Err(PrepareRequestError::RequestError(
-                            GraphQLRequestError::BodyError {
-                                error: Box::new(error),
-                            },
-                        ))
-                    }
-                }
-            }
-            Err(error) => {
-                return Err(PrepareRequestError::RequestError(
-                    GraphQLRequestError::NetworkError {
-                        error: Box::new(error),
-                    },
-                ))
-            }
-        };
+        .await
+        .and_then(|res| handle_response(res, self.nodes_len))
+        .map_err(PrepareRequestError::RequestError)?;
         Doc::parse_response(res).map_err(|err| {
             PrepareRequestError::RequestError(GraphQLRequestError::BodyError {
                 error: Box::from(format!(
@@ -727,8 +1116,11 @@ impl<Doc> Clone for PreparedRequestReqwestSync<Doc> {
         Self {
             addr: self.addr.clone(),
             client: self.client.clone(),
+            middlewares: self.middlewares.clone(),
+            ty: self.ty,
             nodes_len: self.nodes_len,
             doc: self.doc.clone(),
+            doc_hash: self.doc_hash.clone(),
             variables: self.variables.clone(),
             path_to_files: self.path_to_files.clone(),
             opts: self.opts.clone(),
@@ -742,8 +1134,11 @@ impl<Doc> Clone for PreparedRequestReqwest<Doc> {
         Self {
             addr: self.addr.clone(),
             client: self.client.clone(),
+            middlewares: self.middlewares.clone(),
+            ty: self.ty,
             nodes_len: self.nodes_len,
             doc: self.doc.clone(),
+            doc_hash: self.doc_hash.clone(),
             variables: self.variables.clone(),
             path_to_files: self.path_to_files.clone(),
             opts: self.opts.clone(),
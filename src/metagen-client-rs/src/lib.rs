@@ -8,8 +8,14 @@ pub mod files;
 #[cfg(feature = "graphql")]
 pub mod graphql;
 pub mod hostcall;
+#[cfg(feature = "graphql")]
+pub mod middleware;
 pub mod nodes;
+#[cfg(feature = "graphql")]
+mod persisted;
 pub mod selection;
+#[cfg(feature = "graphql")]
+mod sse;
 
 mod interlude {
     pub use serde::{Deserialize, Serialize};
@@ -33,6 +39,8 @@ pub mod prelude {
     #[cfg(feature = "graphql")]
     pub use crate::graphql::*;
     pub use crate::interlude::BoxErr;
+    #[cfg(feature = "graphql")]
+    pub use crate::middleware;
     pub use crate::nodes::*;
     pub use crate::selection::*;
     pub use crate::{impl_selection_traits, impl_union_selection_traits};
@@ -0,0 +1,875 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Middlewares wrap the requests made by the GraphQL transports
+//! and can be used to sign requests, refresh credentials or retry
+//! failed requests.
+//!
+//! [`Middleware`]s are used by [`GraphQlTransportReqwest`][crate::graphql::GraphQlTransportReqwest]
+//! and [`SyncMiddleware`]s by [`GraphQlTransportReqwestSync`][crate::graphql::GraphQlTransportReqwestSync].
+//! They run in the order they were added, each one calling the next through [`Next`].
+
+use crate::interlude::*;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationType {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl OperationType {
+    pub(crate) fn from_doc_ty(ty: &str) -> Self {
+        match ty {
+            "query" => Self::Query,
+            "mutation" => Self::Mutation,
+            "subscription" => Self::Subscription,
+            _ => unreachable!("unexpected operation type {ty}"),
+        }
+    }
+
+    /// Mutations might have side effects and are not safe to repeat.
+    pub fn is_idempotent(&self) -> bool {
+        !matches!(self, Self::Mutation)
+    }
+}
+
+/// Information about the request available to the middlewares.
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    pub operation: OperationType,
+    pub doc: Arc<str>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub type MiddlewareFuture<'a> = futures::future::BoxFuture<'a, Result<reqwest::Response, BoxErr>>;
+#[cfg(target_family = "wasm")]
+pub type MiddlewareFuture<'a> =
+    futures::future::LocalBoxFuture<'a, Result<reqwest::Response, BoxErr>>;
+
+pub trait Middleware: Send + Sync + 'static {
+    fn handle<'a>(
+        &'a self,
+        req: reqwest::Request,
+        cx: &'a RequestContext,
+        next: Next<'a>,
+    ) -> MiddlewareFuture<'a>;
+}
+
+pub(crate) type Middlewares = Arc<Vec<Arc<dyn Middleware>>>;
+
+/// The rest of the middleware chain.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    client: &'a reqwest::Client,
+    middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(client: &'a reqwest::Client, middlewares: &'a [Arc<dyn Middleware>]) -> Self {
+        Self {
+            client,
+            middlewares,
+        }
+    }
+
+    pub async fn run(
+        self,
+        req: reqwest::Request,
+        cx: &RequestContext,
+    ) -> Result<reqwest::Response, BoxErr> {
+        match self.middlewares.split_first() {
+            Some((first, rest)) => {
+                first
+                    .handle(
+                        req,
+                        cx,
+                        Next {
+                            client: self.client,
+                            middlewares: rest,
+                        },
+                    )
+                    .await
+            }
+            None => Ok(self.client.execute(req).await?),
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub trait SyncMiddleware: Send + Sync + 'static {
+    fn handle(
+        &self,
+        req: reqwest::blocking::Request,
+        cx: &RequestContext,
+        next: NextSync<'_>,
+    ) -> Result<reqwest::blocking::Response, BoxErr>;
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub(crate) type SyncMiddlewares = Arc<Vec<Arc<dyn SyncMiddleware>>>;
+
+/// The rest of the middleware chain.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Clone, Copy)]
+pub struct NextSync<'a> {
+    client: &'a reqwest::blocking::Client,
+    middlewares: &'a [Arc<dyn SyncMiddleware>],
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl<'a> NextSync<'a> {
+    pub(crate) fn new(
+        client: &'a reqwest::blocking::Client,
+        middlewares: &'a [Arc<dyn SyncMiddleware>],
+    ) -> Self {
+        Self {
+            client,
+            middlewares,
+        }
+    }
+
+    pub fn run(
+        self,
+        req: reqwest::blocking::Request,
+        cx: &RequestContext,
+    ) -> Result<reqwest::blocking::Response, BoxErr> {
+        match self.middlewares.split_first() {
+            Some((first, rest)) => first.handle(
+                req,
+                cx,
+                NextSync {
+                    client: self.client,
+                    middlewares: rest,
+                },
+            ),
+            None => Ok(self.client.execute(req)?),
+        }
+    }
+}
+
+/// Modify the requests before they are sent, e.g. to sign them
+/// or to add tracing propagation headers.
+///
+/// The `Tracing` middleware only records local spans, the trace context
+/// can be propagated to the typegate by adding the W3C `traceparent`
+/// header from your tracing setup:
+///
+/// ```no_run
+/// use metagen_client::prelude::*;
+/// use metagen_client::middleware::{MapRequest, RequestContext};
+///
+/// // e.g. formatted from the current `opentelemetry` span context
+/// fn current_traceparent() -> String {
+///     "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".into()
+/// }
+///
+/// let propagate = MapRequest(|req: &mut reqwest::Request, _cx: &RequestContext| {
+///     req.headers_mut()
+///         .insert("traceparent", current_traceparent().try_into()?);
+///     Ok::<_, BoxErr>(())
+/// });
+/// ```
+pub struct MapRequest<F>(pub F);
+
+impl<F> Middleware for MapRequest<F>
+where
+    F: Fn(&mut reqwest::Request, &RequestContext) -> Result<(), BoxErr> + Send + Sync + 'static,
+{
+    fn handle<'a>(
+        &'a self,
+        mut req: reqwest::Request,
+        cx: &'a RequestContext,
+        next: Next<'a>,
+    ) -> MiddlewareFuture<'a> {
+        Box::pin(async move {
+            (self.0)(&mut req, cx)?;
+            next.run(req, cx).await
+        })
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl<F> SyncMiddleware for MapRequest<F>
+where
+    F: Fn(&mut reqwest::blocking::Request, &RequestContext) -> Result<(), BoxErr>
+        + Send
+        + Sync
+        + 'static,
+{
+    fn handle(
+        &self,
+        mut req: reqwest::blocking::Request,
+        cx: &RequestContext,
+        next: NextSync<'_>,
+    ) -> Result<reqwest::blocking::Response, BoxErr> {
+        (self.0)(&mut req, cx)?;
+        next.run(req, cx)
+    }
+}
+
+/// Retry the idempotent operations that failed on network errors,
+/// `429` or `5xx` responses with an exponential backoff.
+///
+/// Requests whose body can't be cloned, like file uploads, are
+/// not retried.
+#[derive(Debug, Clone)]
+pub struct Retry {
+    pub max_retries: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Retry {
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        self.initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay)
+    }
+
+    fn should_retry(status: reqwest::StatusCode) -> bool {
+        status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl Middleware for Retry {
+    fn handle<'a>(
+        &'a self,
+        req: reqwest::Request,
+        cx: &'a RequestContext,
+        next: Next<'a>,
+    ) -> MiddlewareFuture<'a> {
+        Box::pin(async move {
+            if !cx.operation.is_idempotent() {
+                return next.run(req, cx).await;
+            }
+            let mut attempt = 0;
+            loop {
+                let retry_req = if attempt < self.max_retries {
+                    req.try_clone()
+                } else {
+                    None
+                };
+                let Some(retry_req) = retry_req else {
+                    return next.run(req, cx).await;
+                };
+                match next.run(retry_req, cx).await {
+                    Ok(res) if !Self::should_retry(res.status()) => return Ok(res),
+                    _ => {}
+                }
+                tokio::time::sleep(self.delay(attempt)).await;
+                attempt += 1;
+            }
+        })
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl SyncMiddleware for Retry {
+    fn handle(
+        &self,
+        req: reqwest::blocking::Request,
+        cx: &RequestContext,
+        next: NextSync<'_>,
+    ) -> Result<reqwest::blocking::Response, BoxErr> {
+        if !cx.operation.is_idempotent() {
+            return next.run(req, cx);
+        }
+        let mut attempt = 0;
+        loop {
+            let retry_req = if attempt < self.max_retries {
+                req.try_clone()
+            } else {
+                None
+            };
+            let Some(retry_req) = retry_req else {
+                return next.run(req, cx);
+            };
+            match next.run(retry_req, cx) {
+                Ok(res) if !Self::should_retry(res.status()) => return Ok(res),
+                _ => {}
+            }
+            std::thread::sleep(self.delay(attempt));
+            attempt += 1;
+        }
+    }
+}
+
+pub type TokenFuture = futures::future::BoxFuture<'static, Result<String, BoxErr>>;
+
+/// Add a bearer token to the requests, obtained through the `refresh`
+/// function on the first request and again whenever the typegate
+/// responds with `401`.
+///
+/// Concurrent requests share a single refresh: the ones rejected with
+/// the same token wait for it and reuse its result.
+pub struct BearerAuth<F> {
+    refresh: F,
+    token: RwLock<Option<String>>,
+    refreshing: futures::lock::Mutex<()>,
+}
+
+impl<F> BearerAuth<F> {
+    pub fn new(refresh: F) -> Self {
+        Self {
+            refresh,
+            token: RwLock::new(None),
+            refreshing: futures::lock::Mutex::new(()),
+        }
+    }
+
+    /// Start with a known token.
+    pub fn with_token(self, token: impl Into<String>) -> Self {
+        *self.token.write().expect("poisoned lock") = Some(token.into());
+        self
+    }
+
+    fn cached(&self) -> Option<String> {
+        self.token.read().expect("poisoned lock").clone()
+    }
+
+    fn store(&self, token: String) {
+        *self.token.write().expect("poisoned lock") = Some(token);
+    }
+
+    /// The token refreshed by a concurrent request since `stale` was used.
+    fn refreshed_since(&self, stale: Option<&str>) -> Option<String> {
+        self.cached().filter(|token| Some(token.as_str()) != stale)
+    }
+}
+
+impl<F> BearerAuth<F>
+where
+    F: Fn() -> TokenFuture + Send + Sync + 'static,
+{
+    async fn refresh_token(&self, stale: Option<&str>) -> Result<String, BoxErr> {
+        let _guard = self.refreshing.lock().await;
+        if let Some(token) = self.refreshed_since(stale) {
+            return Ok(token);
+        }
+        let token = (self.refresh)().await?;
+        self.store(token.clone());
+        Ok(token)
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl<F> BearerAuth<F>
+where
+    F: Fn() -> Result<String, BoxErr> + Send + Sync + 'static,
+{
+    fn refresh_token_sync(&self, stale: Option<&str>) -> Result<String, BoxErr> {
+        let _guard = futures::executor::block_on(self.refreshing.lock());
+        if let Some(token) = self.refreshed_since(stale) {
+            return Ok(token);
+        }
+        let token = (self.refresh)()?;
+        self.store(token.clone());
+        Ok(token)
+    }
+}
+
+fn bearer(token: &str) -> Result<reqwest::header::HeaderValue, BoxErr> {
+    let mut value = reqwest::header::HeaderValue::try_from(format!("Bearer {token}"))?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+impl<F> Middleware for BearerAuth<F>
+where
+    F: Fn() -> TokenFuture + Send + Sync + 'static,
+{
+    fn handle<'a>(
+        &'a self,
+        mut req: reqwest::Request,
+        cx: &'a RequestContext,
+        next: Next<'a>,
+    ) -> MiddlewareFuture<'a> {
+        Box::pin(async move {
+            let token = match self.cached() {
+                Some(token) => token,
+                None => self.refresh_token(None).await?,
+            };
+            req.headers_mut()
+                .insert(reqwest::header::AUTHORIZATION, bearer(&token)?);
+            let retry_req = req.try_clone();
+            let res = next.run(req, cx).await?;
+            match retry_req {
+                Some(mut retry_req) if res.status() == reqwest::StatusCode::UNAUTHORIZED => {
+                    let token = self.refresh_token(Some(&token)).await?;
+                    retry_req
+                        .headers_mut()
+                        .insert(reqwest::header::AUTHORIZATION, bearer(&token)?);
+                    next.run(retry_req, cx).await
+                }
+                _ => Ok(res),
+            }
+        })
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl<F> SyncMiddleware for BearerAuth<F>
+where
+    F: Fn() -> Result<String, BoxErr> + Send + Sync + 'static,
+{
+    fn handle(
+        &self,
+        mut req: reqwest::blocking::Request,
+        cx: &RequestContext,
+        next: NextSync<'_>,
+    ) -> Result<reqwest::blocking::Response, BoxErr> {
+        let token = match self.cached() {
+            Some(token) => token,
+            None => self.refresh_token_sync(None)?,
+        };
+        req.headers_mut()
+            .insert(reqwest::header::AUTHORIZATION, bearer(&token)?);
+        let retry_req = req.try_clone();
+        let res = next.run(req, cx)?;
+        match retry_req {
+            Some(mut retry_req) if res.status() == reqwest::StatusCode::UNAUTHORIZED => {
+                let token = self.refresh_token_sync(Some(&token))?;
+                retry_req
+                    .headers_mut()
+                    .insert(reqwest::header::AUTHORIZATION, bearer(&token)?);
+                next.run(retry_req, cx)
+            }
+            _ => Ok(res),
+        }
+    }
+}
+
+/// Wrap each request in a `graphql_request` span recording the
+/// operation type and the response status.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, Default)]
+pub struct Tracing;
+
+#[cfg(feature = "tracing")]
+impl Tracing {
+    fn span(cx: &RequestContext, req_url: &reqwest::Url) -> tracing::Span {
+        tracing::info_span!(
+            "graphql_request",
+            operation = ?cx.operation,
+            url = %req_url,
+            status = tracing::field::Empty,
+        )
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Middleware for Tracing {
+    fn handle<'a>(
+        &'a self,
+        req: reqwest::Request,
+        cx: &'a RequestContext,
+        next: Next<'a>,
+    ) -> MiddlewareFuture<'a> {
+        use tracing::Instrument;
+
+        let span = Self::span(cx, req.url());
+        Box::pin(
+            async move {
+                let res = next.run(req, cx).await;
+                match &res {
+                    Ok(res) => {
+                        tracing::Span::current().record("status", res.status().as_u16());
+                    }
+                    Err(err) => tracing::warn!("graphql request failed: {err}"),
+                }
+                res
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(all(feature = "tracing", not(target_family = "wasm")))]
+impl SyncMiddleware for Tracing {
+    fn handle(
+        &self,
+        req: reqwest::blocking::Request,
+        cx: &RequestContext,
+        next: NextSync<'_>,
+    ) -> Result<reqwest::blocking::Response, BoxErr> {
+        let span = Self::span(cx, req.url());
+        let _guard = span.enter();
+        let res = next.run(req, cx);
+        match &res {
+            Ok(res) => {
+                span.record("status", res.status().as_u16());
+            }
+            Err(err) => tracing::warn!("graphql request failed: {err}"),
+        }
+        res
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// HTTP server responding with the given statuses in order, then
+    /// with `200`, and recording the `authorization` header of the requests.
+    struct MockServer {
+        url: reqwest::Url,
+        requests: Arc<Mutex<Vec<Option<String>>>>,
+    }
+
+    impl MockServer {
+        fn start(statuses: &[u16]) -> Self {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/", listener.local_addr().unwrap())
+                .parse()
+                .unwrap();
+            let requests = Arc::new(Mutex::new(vec![]));
+            let statuses = statuses.to_vec();
+            let recorded = requests.clone();
+            std::thread::spawn(move || {
+                let mut statuses = statuses.into_iter();
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let mut reader = BufReader::new(&mut stream);
+                    let mut auth = None;
+                    let mut content_length = 0;
+                    let mut chunked = false;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        let line = line.trim_end();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(": ") {
+                            match &name.to_lowercase()[..] {
+                                "authorization" => auth = Some(value.to_owned()),
+                                "content-length" => content_length = value.parse().unwrap(),
+                                "transfer-encoding" => chunked = value == "chunked",
+                                _ => {}
+                            }
+                        }
+                    }
+                    if chunked {
+                        loop {
+                            let mut size = String::new();
+                            reader.read_line(&mut size).unwrap();
+                            let size = usize::from_str_radix(size.trim_end(), 16).unwrap();
+                            let mut chunk = vec![0; size + 2];
+                            reader.read_exact(&mut chunk).unwrap();
+                            if size == 0 {
+                                break;
+                            }
+                        }
+                    } else {
+                        reader.read_exact(&mut vec![0; content_length]).unwrap();
+                    }
+                    recorded.lock().unwrap().push(auth);
+                    let status = statuses.next().unwrap_or(200);
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} Mock\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{{}}"
+                    )
+                    .unwrap();
+                }
+            });
+            Self { url, requests }
+        }
+
+        fn requests(&self) -> Vec<Option<String>> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn context(operation: OperationType) -> RequestContext {
+        RequestContext {
+            operation,
+            doc: "{ query }".into(),
+        }
+    }
+
+    fn retry() -> Retry {
+        Retry {
+            max_retries: 2,
+            initial_delay: Duration::from_millis(20),
+            max_delay: Duration::from_millis(30),
+        }
+    }
+
+    async fn send(
+        middleware: impl Middleware,
+        req: reqwest::Request,
+        operation: OperationType,
+    ) -> Result<reqwest::Response, BoxErr> {
+        let client = reqwest::Client::new();
+        let middlewares: Vec<Arc<dyn Middleware>> = vec![Arc::new(middleware)];
+        Next::new(&client, &middlewares)
+            .run(req, &context(operation))
+            .await
+    }
+
+    fn send_sync(
+        middleware: impl SyncMiddleware,
+        req: reqwest::blocking::Request,
+        operation: OperationType,
+    ) -> Result<reqwest::blocking::Response, BoxErr> {
+        let client = reqwest::blocking::Client::new();
+        let middlewares: Vec<Arc<dyn SyncMiddleware>> = vec![Arc::new(middleware)];
+        NextSync::new(&client, &middlewares).run(req, &context(operation))
+    }
+
+    fn post(url: &reqwest::Url) -> reqwest::Request {
+        reqwest::Client::new()
+            .post(url.clone())
+            .body("{}")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn retry_delay_test() {
+        let retry = retry();
+        assert_eq!(retry.delay(0), Duration::from_millis(20));
+        assert_eq!(retry.delay(1), Duration::from_millis(30));
+        assert_eq!(retry.delay(40), Duration::from_millis(30));
+    }
+
+    #[tokio::test]
+    async fn retry_test() -> Result<(), BoxErr> {
+        let server = MockServer::start(&[503, 429]);
+        let start = std::time::Instant::now();
+        let res = send(retry(), post(&server.url), OperationType::Query).await?;
+        assert_eq!(res.status(), 200);
+        assert_eq!(server.requests().len(), 3);
+        // backing off 20ms then 30ms
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        // gives up after `max_retries`
+        let server = MockServer::start(&[500, 502, 503, 504]);
+        let res = send(retry(), post(&server.url), OperationType::Query).await?;
+        assert_eq!(res.status(), 503);
+        assert_eq!(server.requests().len(), 3);
+
+        // client errors are not retried
+        let server = MockServer::start(&[400]);
+        let res = send(retry(), post(&server.url), OperationType::Query).await?;
+        assert_eq!(res.status(), 400);
+        assert_eq!(server.requests().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retry_mutation_test() -> Result<(), BoxErr> {
+        let server = MockServer::start(&[503]);
+        let res = send(retry(), post(&server.url), OperationType::Mutation).await?;
+        assert_eq!(res.status(), 503);
+        assert_eq!(server.requests().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retry_streamed_body_test() -> Result<(), BoxErr> {
+        let server = MockServer::start(&[503]);
+        let body = futures::stream::once(async { Ok::<_, std::io::Error>(b"{}".to_vec()) });
+        let req = reqwest::Client::new()
+            .post(server.url.clone())
+            .body(reqwest::Body::wrap_stream(body))
+            .build()?;
+        let res = send(retry(), req, OperationType::Query).await?;
+        assert_eq!(res.status(), 503);
+        assert_eq!(server.requests().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn retry_sync_test() -> Result<(), BoxErr> {
+        let client = reqwest::blocking::Client::new();
+
+        let server = MockServer::start(&[503, 429]);
+        let req = client.post(server.url.clone()).body("{}").build()?;
+        let res = send_sync(retry(), req, OperationType::Query)?;
+        assert_eq!(res.status(), 200);
+        assert_eq!(server.requests().len(), 3);
+
+        let server = MockServer::start(&[503]);
+        let req = client.post(server.url.clone()).body("{}").build()?;
+        let res = send_sync(retry(), req, OperationType::Mutation)?;
+        assert_eq!(res.status(), 503);
+        assert_eq!(server.requests().len(), 1);
+
+        // readers can't be cloned
+        let server = MockServer::start(&[503]);
+        let body = reqwest::blocking::Body::new(std::io::Cursor::new(b"{}".to_vec()));
+        let req = client.post(server.url.clone()).body(body).build()?;
+        let res = send_sync(retry(), req, OperationType::Query)?;
+        assert_eq!(res.status(), 503);
+        assert_eq!(server.requests().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bearer_auth_test() -> Result<(), BoxErr> {
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let auth = Arc::new(BearerAuth::new({
+            let refreshes = refreshes.clone();
+            move || -> TokenFuture {
+                let n = refreshes.fetch_add(1, Ordering::SeqCst) + 1;
+                Box::pin(async move { Ok(format!("token-{n}")) })
+            }
+        }));
+        let client = reqwest::Client::new();
+        let middlewares: Vec<Arc<dyn Middleware>> = vec![auth];
+        let cx = context(OperationType::Query);
+
+        let server = MockServer::start(&[401]);
+        for _ in 0..2 {
+            let res = Next::new(&client, &middlewares)
+                .run(post(&server.url), &cx)
+                .await?;
+            assert_eq!(res.status(), 200);
+        }
+        // refreshed on the first request and on the `401`,
+        // then the token is reused
+        assert_eq!(refreshes.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            server.requests(),
+            vec![
+                Some("Bearer token-1".to_owned()),
+                Some("Bearer token-2".to_owned()),
+                Some("Bearer token-2".to_owned()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bearer_auth_concurrent_test() -> Result<(), BoxErr> {
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let auth = BearerAuth::new({
+            let refreshes = refreshes.clone();
+            move || -> TokenFuture {
+                let n = refreshes.fetch_add(1, Ordering::SeqCst) + 1;
+                Box::pin(async move {
+                    // leave time for all the requests to be rejected
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    Ok(format!("token-{n}"))
+                })
+            }
+        })
+        .with_token("initial");
+        let client = reqwest::Client::new();
+        let middlewares: Vec<Arc<dyn Middleware>> = vec![Arc::new(auth)];
+        let cx = context(OperationType::Query);
+
+        let server = MockServer::start(&[401, 401, 401]);
+        let results = futures::future::join_all(
+            (0..3).map(|_| Next::new(&client, &middlewares).run(post(&server.url), &cx)),
+        )
+        .await;
+        for res in results {
+            assert_eq!(res?.status(), 200);
+        }
+        // the parallel `401`s share a single refresh
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            server.requests(),
+            [
+                vec![Some("Bearer initial".to_owned()); 3],
+                vec![Some("Bearer token-1".to_owned()); 3]
+            ]
+            .concat()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bearer_auth_sync_test() -> Result<(), BoxErr> {
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let auth = BearerAuth::new({
+            let refreshes = refreshes.clone();
+            move || -> Result<String, BoxErr> {
+                let n = refreshes.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok(format!("token-{n}"))
+            }
+        })
+        .with_token("initial");
+        let client = reqwest::blocking::Client::new();
+        let middlewares: Vec<Arc<dyn SyncMiddleware>> = vec![Arc::new(auth)];
+        let cx = context(OperationType::Query);
+
+        let server = MockServer::start(&[200, 401]);
+        for _ in 0..3 {
+            let req = client.post(server.url.clone()).body("{}").build()?;
+            let res = NextSync::new(&client, &middlewares).run(req, &cx)?;
+            assert_eq!(res.status(), 200);
+        }
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            server.requests(),
+            vec![
+                Some("Bearer initial".to_owned()),
+                Some("Bearer initial".to_owned()),
+                Some("Bearer token-1".to_owned()),
+                Some("Bearer token-1".to_owned()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bearer_auth_concurrent_sync_test() -> Result<(), BoxErr> {
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let auth = BearerAuth::new({
+            let refreshes = refreshes.clone();
+            move || -> Result<String, BoxErr> {
+                let n = refreshes.fetch_add(1, Ordering::SeqCst) + 1;
+                std::thread::sleep(Duration::from_millis(100));
+                Ok(format!("token-{n}"))
+            }
+        })
+        .with_token("initial");
+        let middlewares: Vec<Arc<dyn SyncMiddleware>> = vec![Arc::new(auth)];
+
+        let server = MockServer::start(&[401, 401, 401]);
+        std::thread::scope(|scope| {
+            let handles = (0..3)
+                .map(|_| {
+                    scope.spawn(|| -> Result<_, BoxErr> {
+                        let client = reqwest::blocking::Client::new();
+                        let req = client.post(server.url.clone()).body("{}").build()?;
+                        let res = NextSync::new(&client, &middlewares)
+                            .run(req, &context(OperationType::Query))?;
+                        Ok(res.status())
+                    })
+                })
+                .collect::<Vec<_>>();
+            for handle in handles {
+                assert_eq!(handle.join().unwrap().unwrap(), 200);
+            }
+        });
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            server.requests(),
+            [
+                vec![Some("Bearer initial".to_owned()); 3],
+                vec![Some("Bearer token-1".to_owned()); 3]
+            ]
+            .concat()
+        );
+        Ok(())
+    }
+}
@@ -40,6 +40,17 @@ pub struct SelectNodeErased {
 pub struct QueryNode<Out>(pub SelectNodeErased, pub PhantomData<(Out,)>);
 /// Wrappers around [`SelectNodeErased`] that only holds mutation nodes
 pub struct MutationNode<Out>(pub SelectNodeErased, pub PhantomData<(Out,)>);
+/// Wrappers around [`SelectNodeErased`] that only holds subscription nodes.
+/// Use [`QueryNode::subscribe`] to get one.
+pub struct SubscriptionNode<Out>(pub SelectNodeErased, pub PhantomData<(Out,)>);
+
+impl<Out> QueryNode<Out> {
+    /// Receive the results of the query as they are pushed by
+    /// the typegate instead of fetching them once.
+    pub fn subscribe(self) -> SubscriptionNode<Out> {
+        SubscriptionNode(self.0, PhantomData)
+    }
+}
 
 /* /// Trait used to track the `Out` type parameter for [`QueryNode`]/[`MutationNode`]
 pub trait ToSelectNode {
@@ -197,6 +208,23 @@ where
     }
 }
 impl<Out> ToMutationDoc for MutationNode<Out> {}
+// subscriptions are limited to a single root field
+// so no tuple impls for these
+impl<Out> ToSelectDoc for SubscriptionNode<Out>
+where
+    Out: serde::de::DeserializeOwned,
+{
+    type Out = Out;
+
+    fn to_select_doc(self) -> Vec<SelectNodeErased> {
+        vec![self.0]
+    }
+
+    fn parse_response(data: Vec<serde_json::Value>) -> Result<Self::Out, serde_json::Error> {
+        let mut data = data.into_iter();
+        serde_json::from_value(data.next().unwrap())
+    }
+}
 
 #[macro_export]
 macro_rules! impl_for_tuple {
@@ -0,0 +1,116 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Support for automatic persisted queries, following the convention
+//! popularized by Apollo: the request first carries only the hash
+//! of the document and the full text is sent if the server doesn't
+//! know about the hash yet.
+
+use crate::common::*;
+use crate::interlude::*;
+use sha2::{Digest, Sha256};
+
+const NOT_FOUND: [&str; 2] = ["PersistedQueryNotFound", "PERSISTED_QUERY_NOT_FOUND"];
+
+/// The hex encoded sha256 of the document.
+pub(crate) fn hash_doc(doc: &str) -> String {
+    format!("{:x}", Sha256::digest(doc.as_bytes()))
+}
+
+/// What the `operations` of a request carry.
+#[derive(Clone, Copy)]
+pub(crate) enum DocPayload<'a> {
+    Full(&'a str),
+    HashOnly(&'a str),
+    FullWithHash { doc: &'a str, hash: &'a str },
+}
+
+impl DocPayload<'_> {
+    pub fn to_operations(self, variables: &JsonObject) -> serde_json::Value {
+        let persisted_query = |hash: &str| {
+            serde_json::json!({
+                "persistedQuery": {
+                    "version": 1,
+                    "sha256Hash": hash,
+                }
+            })
+        };
+        match self {
+            DocPayload::Full(doc) => serde_json::json!({
+                "query": doc,
+                "variables": variables,
+            }),
+            DocPayload::HashOnly(hash) => serde_json::json!({
+                "variables": variables,
+                "extensions": persisted_query(hash),
+            }),
+            DocPayload::FullWithHash { doc, hash } => serde_json::json!({
+                "query": doc,
+                "variables": variables,
+                "extensions": persisted_query(hash),
+            }),
+        }
+    }
+}
+
+/// Whether the response to a hash only request asks for
+/// the full document, through a `PersistedQueryNotFound` error.
+///
+/// Other errors are returned as they are: resending the document
+/// could repeat a mutation that the server already rejected.
+pub(crate) fn is_miss(response: &GraphQLResponse) -> bool {
+    let matches_code = |err: &serde_json::Value| {
+        let message = err.get("message").and_then(|val| val.as_str());
+        let code = err
+            .get("extensions")
+            .and_then(|ext| ext.get("code"))
+            .and_then(|val| val.as_str());
+        [message, code]
+            .into_iter()
+            .flatten()
+            .any(|val| NOT_FOUND.contains(&val))
+    };
+    response
+        .body
+        .get("errors")
+        .and_then(|errors| errors.as_array())
+        .is_some_and(|errors| errors.iter().any(matches_code))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn response(status_code: u16, body: serde_json::Value) -> GraphQLResponse {
+        GraphQLResponse {
+            status_code,
+            body: serde_json::from_value(body).unwrap(),
+        }
+    }
+
+    #[test]
+    fn is_miss_test() {
+        assert!(is_miss(&response(
+            400,
+            serde_json::json!({ "errors": [{ "message": "PersistedQueryNotFound" }] })
+        )));
+        assert!(is_miss(&response(
+            200,
+            serde_json::json!({
+                "errors": [{
+                    "message": "not found",
+                    "extensions": { "code": "PERSISTED_QUERY_NOT_FOUND" },
+                }]
+            })
+        )));
+        // other client errors are not retried with the full document
+        assert!(!is_miss(&response(
+            400,
+            serde_json::json!({ "errors": [{ "message": "variable not found: id" }] })
+        )));
+        assert!(!is_miss(&response(
+            400,
+            serde_json::json!({ "errors": [{ "message": "PersistedQueryNotSupported" }] })
+        )));
+    }
+}
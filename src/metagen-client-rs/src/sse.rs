@@ -0,0 +1,89 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Support for the `text/event-stream` responses used to deliver
+//! subscription results following the graphql-sse protocol.
+
+use crate::common::*;
+use crate::interlude::*;
+
+pub(crate) const EVENT_STREAM: &str = "text/event-stream";
+
+pub(crate) struct SseEvent {
+    pub event: String,
+    pub data: String,
+}
+
+/// Incremental parser for event streams, fed with the body chunks
+/// as they arrive.
+#[derive(Default)]
+pub(crate) struct SseParser {
+    buf: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buf.extend_from_slice(chunk);
+        let mut events = vec![];
+        while let Some(pos) = self.buf.iter().position(|byte| *byte == b'\n') {
+            let line = self.buf.drain(..=pos).collect::<Vec<_>>();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                let event = self.event.take();
+                if !self.data.is_empty() {
+                    events.push(SseEvent {
+                        event: event.unwrap_or_else(|| "message".into()),
+                        data: std::mem::take(&mut self.data).join("\n"),
+                    });
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                // comments are used as keep-alives
+                continue;
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => self.event = Some(value.into()),
+                "data" => self.data.push(value.into()),
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
+pub(crate) enum EventOutcome {
+    Next(Result<Vec<serde_json::Value>, GraphQLRequestError>),
+    Skip,
+    Complete,
+}
+
+pub(crate) fn handle_event(event: SseEvent) -> EventOutcome {
+    match &event.event[..] {
+        "next" | "message" => {
+            let body = match serde_json::from_str::<JsonObject>(&event.data) {
+                Ok(body) => body,
+                Err(error) => {
+                    return EventOutcome::Next(Err(GraphQLRequestError::BodyError {
+                        error: Box::new(error),
+                    }))
+                }
+            };
+            EventOutcome::Next(handle_response(
+                GraphQLResponse {
+                    status_code: 200,
+                    body,
+                },
+                1,
+            ))
+        }
+        "complete" => EventOutcome::Complete,
+        // unknown events are to be ignored
+        _ => EventOutcome::Skip,
+    }
+}
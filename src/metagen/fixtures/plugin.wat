@@ -0,0 +1,210 @@
+;; Minimal external generator used by the `wasm_plugin` tests: requests no
+;; inputs and emits an empty file named after each of the inputs it is given.
+(component
+  (core module (;0;)
+    (type (;0;) (func (param i32 i32 i32 i32) (result i32)))
+    (type (;1;) (func (param i32 i32) (result i32)))
+    (memory (;0;) 1)
+    (global $heap (;0;) (mut i32) i32.const 1024)
+    (export "memory" (memory 0))
+    (export "cabi_realloc" (func $realloc))
+    (export "metatype:metagen/generator#bill-of-inputs" (func 1))
+    (export "metatype:metagen/generator#generate" (func 2))
+    (func $realloc (;0;) (type 0) (param i32 i32 i32 i32) (result i32)
+      (local $ptr i32)
+      global.get $heap
+      local.get 2
+      i32.const 1
+      i32.sub
+      i32.add
+      local.get 2
+      i32.const 1
+      i32.sub
+      i32.const -1
+      i32.xor
+      i32.and
+      local.set $ptr
+      local.get $ptr
+      local.get 3
+      i32.add
+      global.set $heap
+      global.get $heap
+      memory.size
+      i32.const 65536
+      i32.mul
+      i32.gt_u
+      if ;; label = @1
+        i32.const 1
+        memory.grow
+        drop
+      end
+      local.get $ptr
+    )
+    (func (;1;) (type 1) (param i32 i32) (result i32)
+      i32.const 16
+      i32.const 0
+      i32.store8
+      i32.const 20
+      i32.const 8
+      i32.store
+      i32.const 24
+      i32.const 0
+      i32.store
+      i32.const 16
+    )
+    (func (;2;) (type 0) (param i32 i32 i32 i32) (result i32)
+      (local $out i32) (local $i i32) (local $src i32) (local $dst i32)
+      i32.const 0
+      i32.const 0
+      i32.const 4
+      local.get 3
+      i32.const 20
+      i32.mul
+      call $realloc
+      local.set $out
+      block $done
+        loop $next
+          local.get $i
+          local.get 3
+          i32.ge_u
+          br_if $done
+          local.get 2
+          local.get $i
+          i32.const 20
+          i32.mul
+          i32.add
+          local.set $src
+          local.get $out
+          local.get $i
+          i32.const 20
+          i32.mul
+          i32.add
+          local.set $dst
+          local.get $dst
+          local.get $src
+          i32.load
+          i32.store
+          local.get $dst
+          local.get $src
+          i32.load offset=4
+          i32.store offset=4
+          local.get $dst
+          i32.const 8
+          i32.store offset=8
+          local.get $dst
+          i32.const 0
+          i32.store offset=12
+          local.get $dst
+          i32.const 1
+          i32.store8 offset=16
+          local.get $i
+          i32.const 1
+          i32.add
+          local.set $i
+          br $next
+        end
+      end
+      i32.const 16
+      i32.const 0
+      i32.store8
+      i32.const 20
+      local.get $out
+      i32.store
+      i32.const 24
+      local.get 3
+      i32.store
+      i32.const 16
+    )
+    (@producers
+      (processed-by "wit-component" "0.221.3")
+    )
+  )
+  (core instance (;0;) (instantiate 0))
+  (alias core export 0 "memory" (core memory (;0;)))
+  (type (;0;) string)
+  (type (;1;) (option string))
+  (type (;2;) (record (field "path" string) (field "name" 1)))
+  (type (;3;) (record (field "typegraph" string) (field "runtime" 1)))
+  (type (;4;) (variant (case "typegraph-from-typegate" string) (case "typegraph-from-path" 2) (case "prisma-introspection" 3)))
+  (type (;5;) (tuple string 4))
+  (type (;6;) (list 5))
+  (type (;7;) (result 6 (error string)))
+  (type (;8;) (func (param "config" 0) (result 7)))
+  (alias core export 0 "metatype:metagen/generator#bill-of-inputs" (core func (;0;)))
+  (alias core export 0 "cabi_realloc" (core func (;1;)))
+  (func (;0;) (type 8) (canon lift (core func 0) (memory 0) (realloc 1) string-encoding=utf8))
+  (type (;9;) (variant (case "typegraph" 0) (case "prisma-introspection" 0)))
+  (type (;10;) (tuple string 9))
+  (type (;11;) (list 10))
+  (type (;12;) (record (field "path" string) (field "contents" string) (field "overwrite" bool)))
+  (type (;13;) (list 12))
+  (type (;14;) (result 13 (error string)))
+  (type (;15;) (func (param "config" 0) (param "inputs" 11) (result 14)))
+  (alias core export 0 "metatype:metagen/generator#generate" (core func (;2;)))
+  (func (;1;) (type 15) (canon lift (core func 2) (memory 0) (realloc 1) string-encoding=utf8))
+  (component (;0;)
+    (type (;0;) string)
+    (import "import-type-json-str" (type (;1;) (eq 0)))
+    (type (;2;) (option string))
+    (type (;3;) (record (field "path" string) (field "name" 2)))
+    (import "import-type-typegraph-from-path" (type (;4;) (eq 3)))
+    (type (;5;) (record (field "typegraph" string) (field "runtime" 2)))
+    (import "import-type-prisma-introspection" (type (;6;) (eq 5)))
+    (type (;7;) (variant (case "typegraph-from-typegate" string) (case "typegraph-from-path" 4) (case "prisma-introspection" 6)))
+    (import "import-type-input-order" (type (;8;) (eq 7)))
+    (type (;9;) (tuple string 8))
+    (type (;10;) (list 9))
+    (type (;11;) (result 10 (error string)))
+    (type (;12;) (func (param "config" 1) (result 11)))
+    (import "import-func-bill-of-inputs" (func (;0;) (type 12)))
+    (type (;13;) (variant (case "typegraph" 1) (case "prisma-introspection" 1)))
+    (import "import-type-input-resolved" (type (;14;) (eq 13)))
+    (type (;15;) (tuple string 14))
+    (type (;16;) (list 15))
+    (type (;17;) (record (field "path" string) (field "contents" string) (field "overwrite" bool)))
+    (import "import-type-generated-file" (type (;18;) (eq 17)))
+    (type (;19;) (list 18))
+    (type (;20;) (result 19 (error string)))
+    (type (;21;) (func (param "config" 1) (param "inputs" 16) (result 20)))
+    (import "import-func-generate" (func (;1;) (type 21)))
+    (type (;22;) string)
+    (export (;23;) "json-str" (type 22))
+    (type (;24;) (option string))
+    (type (;25;) (record (field "path" string) (field "name" 24)))
+    (export (;26;) "typegraph-from-path" (type 25))
+    (type (;27;) (record (field "typegraph" string) (field "runtime" 24)))
+    (export (;28;) "prisma-introspection" (type 27))
+    (type (;29;) (variant (case "typegraph-from-typegate" string) (case "typegraph-from-path" 26) (case "prisma-introspection" 28)))
+    (export (;30;) "input-order" (type 29))
+    (type (;31;) (variant (case "typegraph" 23) (case "prisma-introspection" 23)))
+    (export (;32;) "input-resolved" (type 31))
+    (type (;33;) (record (field "path" string) (field "contents" string) (field "overwrite" bool)))
+    (export (;34;) "generated-file" (type 33))
+    (type (;35;) (tuple string 30))
+    (type (;36;) (list 35))
+    (type (;37;) (result 36 (error string)))
+    (type (;38;) (func (param "config" 23) (result 37)))
+    (export (;2;) "bill-of-inputs" (func 0) (func (type 38)))
+    (type (;39;) (tuple string 32))
+    (type (;40;) (list 39))
+    (type (;41;) (list 34))
+    (type (;42;) (result 41 (error string)))
+    (type (;43;) (func (param "config" 23) (param "inputs" 40) (result 42)))
+    (export (;3;) "generate" (func 1) (func (type 43)))
+  )
+  (instance (;0;) (instantiate 0
+      (with "import-func-bill-of-inputs" (func 0))
+      (with "import-func-generate" (func 1))
+      (with "import-type-json-str" (type 0))
+      (with "import-type-typegraph-from-path" (type 2))
+      (with "import-type-prisma-introspection" (type 3))
+      (with "import-type-input-order" (type 4))
+      (with "import-type-input-resolved" (type 9))
+      (with "import-type-generated-file" (type 12))
+    )
+  )
+  (export (;1;) "metatype:metagen/generator" (instance 0))
+  (@producers
+    (processed-by "wit-component" "0.221.3")
+  )
+)
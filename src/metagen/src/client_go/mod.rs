@@ -0,0 +1,413 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+mod node_metas;
+mod selections;
+pub mod types;
+pub mod utils;
+
+use core::fmt::Write;
+
+use heck::ToPascalCase;
+use node_metas::GoNodeMeta;
+use selections::GoSelectionManifestPage;
+use shared::manifest::ManifestPage;
+use shared::node_metas::MetasPageBuilder;
+use tg_schema::EffectType;
+use typegraph::ExpansionConfig;
+use types::{GoTypesSubmanifest, OutputTypes};
+
+use crate::interlude::*;
+use crate::*;
+
+use crate::shared::client::*;
+use crate::shared::types::{type_docs, write_doc_lines};
+
+#[derive(Serialize, Deserialize, Debug, garde::Validate)]
+pub struct ClientGoGenConfig {
+    #[serde(flatten)]
+    #[garde(dive)]
+    pub base: crate::config::FdkGeneratorConfigBase,
+    #[garde(length(min = 1))]
+    package_name: Option<String>,
+    #[garde(length(min = 1))]
+    module_name: Option<String>,
+    #[garde(skip)]
+    pub skip_go_mod: Option<bool>,
+}
+
+impl ClientGoGenConfig {
+    pub fn from_json(json: serde_json::Value, workspace_path: &Path) -> anyhow::Result<Self> {
+        let mut config: ClientGoGenConfig = serde_json::from_value(json)?;
+        config.base.path = workspace_path.join(config.base.path);
+        config.base.typegraph_path = config
+            .base
+            .typegraph_path
+            .as_ref()
+            .map(|path| workspace_path.join(path));
+        Ok(config)
+    }
+}
+
+pub(super) struct Maps {
+    pub(super) input_types: IndexMap<TypeKey, String>,
+    pub(super) output_types: IndexMap<TypeKey, String>,
+    pub(super) partial_output_types: IndexMap<TypeKey, String>,
+    node_metas: IndexMap<TypeKey, String>,
+    selections: IndexMap<TypeKey, String>,
+}
+
+pub(super) struct GoClientManifest {
+    tg: Arc<Typegraph>,
+    types: GoTypesSubmanifest,
+    node_metas: ManifestPage<GoNodeMeta>,
+    selections: GoSelectionManifestPage,
+    pub maps: Maps,
+}
+
+pub struct GoClientManifestOpts {
+    pub non_partial_output_types: bool,
+}
+
+impl GoClientManifest {
+    pub(super) fn new(tg: Arc<Typegraph>, opts: &GoClientManifestOpts) -> anyhow::Result<Self> {
+        let types = GoTypesSubmanifest::new(
+            &tg,
+            &if opts.non_partial_output_types {
+                OutputTypes::Both
+            } else {
+                OutputTypes::Partial
+            },
+        );
+        let input_types_memo = types.inputs.get_cached_refs();
+        let output_types_memo = types
+            .outputs
+            .as_ref()
+            .map(|o| o.get_cached_refs())
+            .unwrap_or_default();
+        let partial_output_types_memo = types
+            .partial_outputs
+            .as_ref()
+            .map(|p| p.get_cached_refs())
+            .unwrap_or_default();
+
+        let node_metas = MetasPageBuilder::new(tg.clone())?.build();
+        node_metas.cache_references();
+        let node_metas_memo = node_metas.get_cached_refs();
+
+        let selections = selections::manifest_page(&tg, input_types_memo.clone());
+        selections.cache_references();
+        let selections_memo = selections.get_cached_refs();
+
+        Ok(Self {
+            tg,
+            types,
+            node_metas,
+            selections,
+            maps: Maps {
+                input_types: input_types_memo,
+                output_types: output_types_memo,
+                partial_output_types: partial_output_types_memo,
+                node_metas: node_metas_memo,
+                selections: selections_memo,
+            },
+        })
+    }
+}
+
+pub struct Generator {
+    config: ClientGoGenConfig,
+}
+
+impl Generator {
+    pub const INPUT_TG: &'static str = "tg_name";
+    pub fn new(config: ClientGoGenConfig) -> Result<Self, garde::Report> {
+        use garde::Validate;
+        config.validate()?;
+        Ok(Self { config })
+    }
+}
+
+impl crate::Plugin for Generator {
+    fn bill_of_inputs(&self) -> IndexMap<String, GeneratorInputOrder> {
+        [(
+            Self::INPUT_TG.to_string(),
+            if let Some(tg_name) = &self.config.base.typegraph_name {
+                GeneratorInputOrder::TypegraphFromTypegate {
+                    name: tg_name.clone(),
+                }
+            } else if let Some(tg_path) = &self.config.base.typegraph_path {
+                GeneratorInputOrder::TypegraphFromPath {
+                    path: tg_path.clone(),
+                    name: self.config.base.typegraph_name.clone(),
+                }
+            } else {
+                unreachable!()
+            },
+        )]
+        .into_iter()
+        .collect()
+    }
+
+    fn generate(
+        &self,
+        inputs: IndexMap<String, GeneratorInputResolved>,
+    ) -> anyhow::Result<GeneratorOutput> {
+        let tg = match inputs
+            .get(Self::INPUT_TG)
+            .context("missing generator input")?
+        {
+            GeneratorInputResolved::TypegraphFromTypegate { raw } => raw.clone(),
+            GeneratorInputResolved::TypegraphFromPath { raw } => raw.clone(),
+            _ => bail!("unexpected input type"),
+        };
+        let tg = ExpansionConfig::with_naming_strategy(self.config.base.naming_strategy.clone())?
+            .expand(tg)?;
+        let mut out = IndexMap::new();
+        let package_name = self
+            .config
+            .package_name
+            .clone()
+            .unwrap_or_else(|| utils::normalize_package_name(tg.name()));
+        info!("building render manifest");
+        let manif = GoClientManifest::new(
+            tg.clone(),
+            &GoClientManifestOpts {
+                non_partial_output_types: false,
+            },
+        )?;
+        let mut buf = String::new();
+        info!("rendering...");
+        manif.render(&mut buf, &package_name)?;
+        info!("rendering done successfully");
+        out.insert(
+            self.config.base.path.join("client.go"),
+            GeneratedFile {
+                contents: buf,
+                overwrite: true,
+            },
+        );
+        if !matches!(self.config.skip_go_mod, Some(true)) {
+            let module_name = self.config.module_name.as_ref().unwrap_or(&package_name);
+            out.insert(
+                self.config.base.path.join("go.mod"),
+                GeneratedFile {
+                    contents: gen_go_mod(module_name),
+                    overwrite: false,
+                },
+            );
+        }
+
+        Ok(GeneratorOutput(out))
+    }
+}
+
+pub struct GenClientGoOpts {
+    pub hostcall: bool,
+}
+
+impl GoClientManifest {
+    fn render(&self, dest: &mut impl Write, package_name: &str) -> anyhow::Result<()> {
+        writeln!(
+            dest,
+            "// This file was @generated by metagen and is intended"
+        )?;
+        writeln!(dest, "// to be generated again on subsequent metagen runs.")?;
+        writeln!(dest)?;
+        writeln!(dest, "package {package_name}")?;
+
+        self.render_client(dest, &GenClientGoOpts { hostcall: false })?;
+        Ok(())
+    }
+
+    pub fn render_client(
+        &self,
+        dest: &mut impl Write,
+        opts: &GenClientGoOpts,
+    ) -> anyhow::Result<()> {
+        render_static(dest, opts.hostcall)?;
+        writeln!(dest)?;
+
+        self.node_metas.render_all(dest)?;
+
+        self.types.render_all(dest)?;
+
+        self.selections.render_all(dest)?;
+
+        self.render_query_graph(dest)?;
+        Ok(())
+    }
+
+    fn render_query_graph(&self, dest: &mut impl Write) -> anyhow::Result<()> {
+        let gql_types = get_gql_types(&self.tg);
+
+        writeln!(dest, "func NewQueryGraph() *QueryGraph {{")?;
+        writeln!(dest, "\treturn &QueryGraph{{")?;
+        writeln!(dest, "\t\ttyToGqlTyMap: map[string]string{{")?;
+        for (key, gql_ty) in gql_types.into_iter() {
+            let ty_name = self.tg.find_type(key).unwrap().name();
+            writeln!(dest, "\t\t\t{ty_name:?}: {gql_ty:?},")?;
+        }
+        writeln!(dest, "\t\t}},")?;
+        writeln!(dest, "\t}}")?;
+        writeln!(dest, "}}")?;
+
+        self.render_root_functions(dest)?;
+
+        Ok(())
+    }
+
+    fn render_root_functions(&self, dest: &mut impl Write) -> anyhow::Result<()> {
+        for func in self.tg.root_functions() {
+            let (path, ty) = func?;
+
+            let node_name = path.join("_");
+            let method_name = node_name.to_pascal_case();
+            let out_ty_name = self
+                .maps
+                .partial_output_types
+                .get(&ty.output().key())
+                .unwrap();
+
+            let arg_ty = ty
+                .non_empty_input()
+                .map(|ty| self.maps.input_types.get(&ty.key()).unwrap());
+
+            let select_ty = self.maps.selections.get(&ty.output().key());
+
+            let (node_ty, new_node) = match ty.effect() {
+                EffectType::Read => ("QueryNode", "newQueryNode"),
+                EffectType::Update | EffectType::Delete | EffectType::Create => {
+                    ("MutationNode", "newMutationNode")
+                }
+            };
+
+            let meta_fn = self
+                .maps
+                .node_metas
+                .get(&ty.key())
+                .map(|s| s.as_str())
+                .unwrap_or("scalarNodeMeta");
+
+            let mut params = vec![];
+            let mut selection = vec![];
+            if let Some(arg_ty) = &arg_ty {
+                params.push(format!("args NodeArgs[{arg_ty}]"));
+                selection.push("args: &args.erased");
+            }
+            if let Some(select_ty) = select_ty {
+                params.push(format!("sel {select_ty}"));
+                selection.push("sub: erase(sel)");
+            }
+            let params = params.join(", ");
+            let selection = selection.join(", ");
+
+            writeln!(dest)?;
+            write_doc_lines(dest, &type_docs(ty.base()), "//")?;
+            if let Some(deprecation) = ty.deprecated() {
+                writeln!(dest, "//")?;
+                writeln!(dest, "// Deprecated: {}", deprecation.message())?;
+            }
+            writeln!(
+                dest,
+                "func (g *QueryGraph) {method_name}({params}) {node_ty}[{out_ty_name}] {{"
+            )?;
+            writeln!(
+                dest,
+                "\treturn {new_node}[{out_ty_name}]({node_name:?}, {meta_fn}, &nodeSelection{{{selection}}})"
+            )?;
+            writeln!(dest, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Render the common sections like the transports
+fn render_static(dest: &mut impl Write, hostcall: bool) -> anyhow::Result<()> {
+    let client_go = include_str!("static/client.go");
+    crate::utils::processed_write(
+        dest,
+        client_go,
+        &[("HOSTCALL".to_string(), hostcall)].into_iter().collect(),
+    )?;
+    Ok(())
+}
+
+pub fn gen_go_mod(module_name: &str) -> String {
+    format!(
+        r#"module {module_name}
+
+go 1.21
+"#
+    )
+}
+
+#[test]
+fn e2e() -> anyhow::Result<()> {
+    use crate::tests::*;
+
+    if !has_toolchain("go")? {
+        eprintln!("skipping client_go e2e test: go is not installed");
+        return Ok(());
+    }
+
+    let tg_name = "gen-test";
+    let config = config::Config {
+        generators: Default::default(),
+        targets: [(
+            "default".to_string(),
+            config::Target(
+                [GeneratorConfig {
+                    generator_name: "client_go".to_string(),
+                    other: serde_json::to_value(client_go::ClientGoGenConfig {
+                        skip_go_mod: None,
+                        package_name: None,
+                        module_name: None,
+                        base: config::FdkGeneratorConfigBase {
+                            typegraph_name: Some(tg_name.into()),
+                            typegraph_path: None,
+                            // NOTE: root will map to the test's tempdir
+                            path: "./".into(),
+                            template_dir: None,
+                            naming_strategy: Default::default(),
+                        },
+                    })?,
+                }]
+                .into_iter()
+                .collect(),
+            ),
+        )]
+        .into_iter()
+        .collect(),
+    };
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_stack_size(16 * 1024 * 1024)
+        .build()?
+        .block_on(async {
+            let tg = test_typegraph_3().await?;
+            e2e_test(vec![E2eTestCase {
+                typegraphs: [(tg_name.to_string(), tg)].into_iter().collect(),
+                target: "default".into(),
+                config,
+                build_fn: |args| {
+                    Box::pin(async move {
+                        let status = tokio::process::Command::new("go")
+                            .args("vet ./...".split(' ').collect::<Vec<_>>())
+                            .current_dir(args.path)
+                            .kill_on_drop(true)
+                            .spawn()?
+                            .wait()
+                            .await?;
+                        if !status.success() {
+                            anyhow::bail!("error vetting generated package");
+                        }
+                        Ok(())
+                    })
+                },
+                target_dir: None,
+            }])
+            .await
+        })?;
+    Ok(())
+}
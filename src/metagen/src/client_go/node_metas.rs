@@ -0,0 +1,258 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use std::fmt::Write;
+
+use typegraph::{FunctionType, ObjectType};
+
+use super::shared::files::{get_path_to_files, TypePath};
+use super::shared::manifest::{ManifestEntry, ManifestPage};
+use super::shared::node_metas::{MetaFactory, MetasPageBuilder};
+use super::utils::normalize_type_title;
+use crate::interlude::*;
+
+#[derive(Debug)]
+pub enum GoNodeMeta {
+    Scalar,
+    Alias { target: TypeKey },
+    Object(Object),
+    Union(Union),
+    Function(Function),
+}
+
+#[derive(Debug)]
+pub struct Object {
+    props: IndexMap<Arc<str>, TypeKey>,
+    name: String,
+}
+
+#[derive(Debug)]
+pub struct Union {
+    variants: IndexMap<Arc<str>, TypeKey>,
+    name: String,
+}
+
+#[derive(Debug)]
+pub struct Function {
+    name: String,
+    return_ty: TypeKey,
+    argument_fields: Option<BTreeMap<Arc<str>, Arc<str>>>,
+    input_files: Option<String>,
+}
+
+impl ManifestEntry for GoNodeMeta {
+    type Extras = ();
+
+    fn render(&self, out: &mut impl Write, page: &ManifestPage<Self>) -> std::fmt::Result {
+        match self {
+            Self::Alias { .. } | Self::Scalar => {}
+            Self::Object(obj) => {
+                obj.render(page, out)?;
+            }
+            Self::Union(union) => {
+                union.render(page, out)?;
+            }
+            Self::Function(func) => {
+                func.render(page, out)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_reference_expr(&self, page: &ManifestPage<Self>) -> Option<String> {
+        match self {
+            Self::Alias { target } => page.get_ref(target),
+            Self::Scalar => Some("scalarNodeMeta".to_string()),
+            Self::Object(obj) => Some(obj.name.clone()),
+            Self::Union(union) => Some(union.name.clone()),
+            Self::Function(func) => Some(func.name.clone()),
+        }
+    }
+}
+
+fn render_node_map(
+    dest: &mut impl Write,
+    field: &str,
+    nodes: &IndexMap<Arc<str>, TypeKey>,
+    page: &ManifestPage<GoNodeMeta>,
+) -> std::fmt::Result {
+    writeln!(dest, "\t\t{field}: map[string]NodeMetaFn{{")?;
+    for (key, ty) in nodes {
+        let node_ref = page.get_ref(ty).unwrap();
+        writeln!(dest, "\t\t\t{key:?}: {node_ref},")?;
+    }
+    writeln!(dest, "\t\t}},")?;
+    Ok(())
+}
+
+impl Object {
+    pub fn render(
+        &self,
+        page: &ManifestPage<GoNodeMeta>,
+        dest: &mut impl Write,
+    ) -> std::fmt::Result {
+        writeln!(dest, "func {}() NodeMeta {{", self.name)?;
+        writeln!(dest, "\treturn NodeMeta{{")?;
+        render_node_map(dest, "SubNodes", &self.props, page)?;
+        writeln!(dest, "\t}}")?;
+        writeln!(dest, "}}")?;
+        writeln!(dest)?;
+        Ok(())
+    }
+}
+
+impl Union {
+    pub fn render(
+        &self,
+        page: &ManifestPage<GoNodeMeta>,
+        dest: &mut impl Write,
+    ) -> std::fmt::Result {
+        writeln!(dest, "func {}() NodeMeta {{", self.name)?;
+        writeln!(dest, "\treturn NodeMeta{{")?;
+        render_node_map(dest, "Variants", &self.variants, page)?;
+        writeln!(dest, "\t}}")?;
+        writeln!(dest, "}}")?;
+        writeln!(dest)?;
+        Ok(())
+    }
+}
+
+impl Function {
+    pub fn render(
+        &self,
+        page: &ManifestPage<GoNodeMeta>,
+        dest: &mut impl Write,
+    ) -> std::fmt::Result {
+        let return_node = page.get_ref(&self.return_ty).unwrap();
+        writeln!(dest, "func {}() NodeMeta {{", self.name)?;
+        writeln!(dest, "\tmeta := {return_node}()")?;
+        if let Some(fields) = &self.argument_fields {
+            writeln!(dest, "\tmeta.ArgTypes = map[string]string{{")?;
+            for (key, ty) in fields {
+                writeln!(dest, "\t\t{key:?}: {ty:?},")?;
+            }
+            writeln!(dest, "\t}}")?;
+        }
+        if let Some(input_files) = &self.input_files {
+            writeln!(dest, "\tmeta.InputFiles = {input_files}")?;
+        }
+        writeln!(dest, "\treturn meta")?;
+        writeln!(dest, "}}")?;
+        writeln!(dest)?;
+        Ok(())
+    }
+}
+
+impl MetaFactory<GoNodeMeta> for MetasPageBuilder {
+    fn build_meta(&self, ty: Type) -> GoNodeMeta {
+        match ty {
+            Type::Boolean(_)
+            | Type::Float(_)
+            | Type::Integer(_)
+            | Type::String(_)
+            | Type::File(_) => GoNodeMeta::Scalar,
+            Type::Optional(ty) => self.alias(ty.item().clone()),
+            Type::List(ty) => self.alias(ty.item().clone()),
+            Type::Union(ty) => self.build_union(ty.clone()),
+            Type::Function(ty) => self.build_func(ty.clone()),
+            Type::Object(ty) => self.build_object(ty.clone()),
+        }
+    }
+}
+
+trait GoMetasExt {
+    fn alias(&self, ty: Type) -> GoNodeMeta;
+    fn build_func(&self, ty: Arc<FunctionType>) -> GoNodeMeta;
+    fn build_object(&self, ty: Arc<ObjectType>) -> GoNodeMeta;
+    fn build_union(&self, ty: Arc<UnionType>) -> GoNodeMeta;
+}
+
+impl GoMetasExt for MetasPageBuilder {
+    fn alias(&self, ty: Type) -> GoNodeMeta {
+        let key = ty.key();
+        self.push(ty);
+        GoNodeMeta::Alias { target: key }
+    }
+
+    fn build_object(&self, ty: Arc<ObjectType>) -> GoNodeMeta {
+        let props = ty.properties();
+        let props = props
+            .iter()
+            .map(|(name, prop)| {
+                self.push(prop.ty.clone());
+                (name.clone(), prop.ty.key())
+            })
+            .collect::<IndexMap<_, _>>();
+
+        GoNodeMeta::Object(Object {
+            props,
+            name: meta_fn_name(&ty.name()),
+        })
+    }
+
+    fn build_union(&self, ty: Arc<UnionType>) -> GoNodeMeta {
+        let mut variants = IndexMap::new();
+        for variant in ty.variants().iter() {
+            if variant.is_composite() {
+                let key = variant.key();
+                variants.insert(variant.name(), key);
+            }
+            self.push(variant.clone());
+        }
+        if variants.is_empty() {
+            GoNodeMeta::Scalar
+        } else {
+            let name = meta_fn_name(&ty.name());
+            GoNodeMeta::Union(Union { variants, name })
+        }
+    }
+
+    fn build_func(&self, ty: Arc<FunctionType>) -> GoNodeMeta {
+        let out = ty.output();
+        let out_key = out.key();
+        self.push(out.clone());
+
+        let props = ty.input().properties();
+        let props = if !props.is_empty() {
+            let mut res = BTreeMap::new();
+            for (name, prop) in props.iter() {
+                res.insert(name.clone(), prop.ty.name());
+            }
+            Some(res)
+        } else {
+            None
+        };
+
+        let input_files = get_path_to_files(ty.clone());
+
+        GoNodeMeta::Function(Function {
+            return_ty: out_key,
+            argument_fields: props,
+            input_files: serialize_files(&input_files),
+            name: meta_fn_name(&ty.name()),
+        })
+    }
+}
+
+/// Node metas are unexported package level functions
+fn meta_fn_name(ty_name: &str) -> String {
+    format!("nodeMeta{}", normalize_type_title(ty_name))
+}
+
+fn serialize_files(paths: &[TypePath]) -> Option<String> {
+    (!paths.is_empty()).then(|| {
+        let paths = paths
+            .iter()
+            .map(|path| {
+                let segments = path
+                    .to_vec_str()
+                    .iter()
+                    .map(|segment| format!("{segment:?}"))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", segments.join(", "))
+            })
+            .collect::<Vec<_>>();
+        format!("[][]string{{{}}}", paths.join(", "))
+    })
+}
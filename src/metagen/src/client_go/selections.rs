@@ -0,0 +1,204 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use std::fmt::Write;
+
+use typegraph::TypeNodeExt as _;
+
+use super::{
+    shared::manifest::{ManifestEntry, ManifestPage},
+    utils::*,
+};
+use crate::{
+    interlude::*,
+    shared::client::*,
+    shared::types::{field_docs, write_doc_lines},
+};
+
+#[derive(Debug)]
+pub struct SelectionProp {
+    /// name of the struct field
+    name: String,
+    /// name of the node, or of the variant type for unions
+    node_name: String,
+    select_ty: SelectionTy,
+    docs: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum GoSelection {
+    Struct {
+        name: String,
+        props: Vec<SelectionProp>,
+    },
+    Union {
+        name: String,
+        variants: Vec<SelectionProp>,
+    },
+}
+
+impl ManifestEntry for GoSelection {
+    type Extras = Extras;
+
+    fn render(&self, out: &mut impl Write, page: &ManifestPage<Self, Extras>) -> std::fmt::Result {
+        match self {
+            Self::Struct { name, props } => {
+                writeln!(out, "type {name} struct {{")?;
+                writeln!(out, "\tSelectAll bool")?;
+                render_fields(out, props, page)?;
+                writeln!(out, "}}")?;
+                writeln!(out)?;
+                writeln!(out, "func (s {name}) erased() selectionErased {{")?;
+                writeln!(out, "\treturn selectionErased{{")?;
+                writeln!(out, "\t\tselectAll: s.SelectAll,")?;
+                render_erased_map(out, "nodes", props)?;
+            }
+            Self::Union { name, variants } => {
+                writeln!(out, "type {name} struct {{")?;
+                render_fields(out, variants, page)?;
+                writeln!(out, "}}")?;
+                writeln!(out)?;
+                writeln!(out, "func (s {name}) erased() selectionErased {{")?;
+                writeln!(out, "\treturn selectionErased{{")?;
+                render_erased_map(out, "variants", variants)?;
+            }
+        }
+        writeln!(out, "\t}}")?;
+        writeln!(out, "}}")?;
+        writeln!(out)?;
+
+        Ok(())
+    }
+
+    fn get_reference_expr(&self, _page: &ManifestPage<Self, Extras>) -> Option<String> {
+        match self {
+            Self::Struct { name, .. } | Self::Union { name, .. } => Some(name.clone()),
+        }
+    }
+}
+
+fn render_fields(
+    dest: &mut impl Write,
+    props: &[SelectionProp],
+    page: &ManifestPage<GoSelection, Extras>,
+) -> std::fmt::Result {
+    for SelectionProp {
+        name,
+        select_ty,
+        docs,
+        ..
+    } in props
+    {
+        write_doc_lines(dest, docs, "\t//")?;
+        use SelectionTy as S;
+        match select_ty {
+            S::Scalar => writeln!(dest, "\t{name} *ScalarSelect")?,
+            S::ScalarArgs { arg_ty } => {
+                let arg_ty = page.extras.input_types.get(arg_ty).unwrap();
+                writeln!(dest, "\t{name} *ScalarSelectArgs[{arg_ty}]")?
+            }
+            S::Composite { select_ty } => {
+                let select_ty = page.get_ref(select_ty).unwrap();
+                writeln!(dest, "\t{name} *CompositeSelect[{select_ty}]")?
+            }
+            S::CompositeArgs { arg_ty, select_ty } => {
+                let arg_ty = page.extras.input_types.get(arg_ty).unwrap();
+                let select_ty = page.get_ref(select_ty).unwrap();
+                writeln!(dest, "\t{name} *CompositeSelectArgs[{arg_ty}, {select_ty}]")?
+            }
+        };
+    }
+    Ok(())
+}
+
+fn render_erased_map(
+    dest: &mut impl Write,
+    field: &str,
+    props: &[SelectionProp],
+) -> std::fmt::Result {
+    writeln!(dest, "\t\t{field}: map[string]*nodeSelection{{")?;
+    for SelectionProp {
+        name, node_name, ..
+    } in props
+    {
+        writeln!(dest, "\t\t\t{node_name:?}: s.{name}.selected(),")?;
+    }
+    writeln!(dest, "\t\t}},")?;
+    Ok(())
+}
+
+pub struct Extras {
+    input_types: IndexMap<TypeKey, String>,
+}
+
+pub fn manifest_page(
+    tg: &typegraph::Typegraph,
+    input_types: IndexMap<TypeKey, String>,
+) -> ManifestPage<GoSelection, Extras> {
+    let mut map = IndexMap::new();
+
+    for (key, ty) in tg.output_types.iter() {
+        if !ty.is_composite() {
+            continue;
+        }
+        match ty {
+            Type::Boolean(_)
+            | Type::Float(_)
+            | Type::Integer(_)
+            | Type::String(_)
+            | Type::File(_) => unreachable!("scalars don't get to have selections"),
+            Type::Optional(_) | Type::List(_) | Type::Function(_) => {}
+            Type::Object(ty) => {
+                let props = ty
+                    .properties()
+                    .iter()
+                    .map(|(prop_name, prop)| {
+                        let name = match normalize_struct_prop_name(prop_name) {
+                            // taken by the flag
+                            name if name == "SelectAll" => format!("{name}_"),
+                            name => name,
+                        };
+                        SelectionProp {
+                            name,
+                            node_name: prop_name.to_string(),
+                            select_ty: selection_for_field(&prop.ty),
+                            docs: field_docs(&prop.ty),
+                        }
+                    })
+                    .collect();
+                map.insert(
+                    *key,
+                    GoSelection::Struct {
+                        props,
+                        name: format!("{}Selections", normalize_type_title(&ty.name())),
+                    },
+                );
+            }
+            Type::Union(ty) => {
+                let mut variants = vec![];
+                for variant in ty.variants() {
+                    if !variant.is_composite() {
+                        continue;
+                    }
+                    variants.push(SelectionProp {
+                        name: normalize_struct_prop_name(variant.title()),
+                        node_name: variant.name().to_string(),
+                        select_ty: selection_for_field(variant),
+                        docs: vec![],
+                    });
+                }
+                map.insert(
+                    *key,
+                    GoSelection::Union {
+                        variants,
+                        name: format!("{}Selections", normalize_type_title(&ty.name())),
+                    },
+                );
+            }
+        }
+    }
+
+    ManifestPage::with_extras(map, Extras { input_types })
+}
+
+pub type GoSelectionManifestPage = ManifestPage<GoSelection, Extras>;
@@ -0,0 +1,1136 @@
+// metagen-skip
+package client
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"mime"
+	"mime/multipart"
+	"net/http"
+	"net/textproto"
+	"path/filepath"
+	"reflect"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+//
+// --- --- Graph node types --- --- //
+//
+
+// NodeMetaFn describes a node lazily, allowing for recursive types
+type NodeMetaFn func() NodeMeta
+
+type NodeMeta struct {
+	SubNodes map[string]NodeMetaFn
+	Variants map[string]NodeMetaFn
+	// names of the argument types, by argument
+	ArgTypes map[string]string
+	// paths to the files in the arguments
+	InputFiles [][]string
+}
+
+func scalarNodeMeta() NodeMeta {
+	return NodeMeta{}
+}
+
+type selectNode struct {
+	nodeName     string
+	instanceName string
+	args         map[string]nodeArgValue
+	// nil for scalar nodes
+	subNodes []selectNode
+	// union/either selection, by variant type name
+	variants map[string][]selectNode
+}
+
+type nodeArgValue struct {
+	typeName string
+	value    json.RawMessage
+	// set for prepared requests, name of the value provided at perform time
+	placeholder string
+	files       []fileAt
+}
+
+// QueryNode is a root node of a query, decoded into Out
+type QueryNode[Out any] struct {
+	node selectNode
+	err  error
+}
+
+// MutationNode is a root node of a mutation, decoded into Out
+type MutationNode[Out any] struct {
+	node selectNode
+	err  error
+}
+
+// AnyQueryNode is a QueryNode of any output type
+type AnyQueryNode interface {
+	queryNode() (selectNode, error)
+}
+
+// AnyMutationNode is a MutationNode of any output type
+type AnyMutationNode interface {
+	mutationNode() (selectNode, error)
+}
+
+func (n QueryNode[Out]) queryNode() (selectNode, error) {
+	return n.node, n.err
+}
+
+func (n MutationNode[Out]) mutationNode() (selectNode, error) {
+	return n.node, n.err
+}
+
+// Decode the result of this node in the results of a request for many nodes
+func (n QueryNode[Out]) Decode(results Results, key string) (Out, error) {
+	return decodeResult[Out](results, key)
+}
+
+// Decode the result of this node in the results of a request for many nodes
+func (n MutationNode[Out]) Decode(results Results, key string) (Out, error) {
+	return decodeResult[Out](results, key)
+}
+
+func newQueryNode[Out any](name string, meta NodeMetaFn, sel *nodeSelection) QueryNode[Out] {
+	node, err := rootNode(name, meta, sel)
+	return QueryNode[Out]{node, err}
+}
+
+func newMutationNode[Out any](name string, meta NodeMetaFn, sel *nodeSelection) MutationNode[Out] {
+	node, err := rootNode(name, meta, sel)
+	return MutationNode[Out]{node, err}
+}
+
+func rootNode(name string, meta NodeMetaFn, sel *nodeSelection) (selectNode, error) {
+	nodes, err := selectionToNodes(
+		selectionErased{nodes: map[string]*nodeSelection{name: sel}},
+		map[string]NodeMetaFn{name: meta},
+		"$q",
+	)
+	if err != nil {
+		return selectNode{}, err
+	}
+	if len(nodes) != 1 {
+		return selectNode{}, fmt.Errorf("unexpected selection for root node %s", name)
+	}
+	return nodes[0], nil
+}
+
+//
+// --- --- Argument types --- --- //
+//
+
+// NodeArgs are the arguments of a node: a value of the input type, or
+// placeholders for values provided when performing a prepared request
+type NodeArgs[A any] struct {
+	erased argsErased
+}
+
+type argsErased struct {
+	value        any
+	placeholders map[string]string
+}
+
+// Args of a node
+func Args[A any](value A) NodeArgs[A] {
+	return NodeArgs[A]{argsErased{value: value}}
+}
+
+// Placeholders maps the arguments of a node to the names of the values
+// provided to PreparedRequest.Perform
+func Placeholders[A any](placeholders map[string]string) NodeArgs[A] {
+	return NodeArgs[A]{argsErased{placeholders: placeholders}}
+}
+
+func convertArgs(args *argsErased, meta NodeMeta, path string) (map[string]nodeArgValue, error) {
+	out := map[string]nodeArgValue{}
+	if args.placeholders != nil {
+		for key, placeholder := range args.placeholders {
+			typeName, ok := meta.ArgTypes[key]
+			if !ok {
+				return nil, fmt.Errorf("unexpected argument %s at %s", key, path)
+			}
+			out[key] = nodeArgValue{typeName: typeName, placeholder: placeholder}
+		}
+		return out, nil
+	}
+
+	files, err := extractFiles(args.value, meta.InputFiles)
+	if err != nil {
+		return nil, fmt.Errorf("error extracting files at %s: %w", path, err)
+	}
+	raw, err := json.Marshal(args.value)
+	if err != nil {
+		return nil, fmt.Errorf("error serializing arguments at %s: %w", path, err)
+	}
+	var fields map[string]json.RawMessage
+	if err := json.Unmarshal(raw, &fields); err != nil {
+		return nil, fmt.Errorf("arguments at %s should be an object: %w", path, err)
+	}
+	for key, value := range fields {
+		typeName, ok := meta.ArgTypes[key]
+		if !ok {
+			return nil, fmt.Errorf("unexpected argument %s at %s", key, path)
+		}
+		out[key] = nodeArgValue{typeName: typeName, value: value, files: files[key]}
+	}
+	return out, nil
+}
+
+//
+// --- --- Selection types --- --- //
+//
+
+// selection is implemented by the generated selection types
+type selection interface {
+	erased() selectionErased
+}
+
+type selectionErased struct {
+	selectAll bool
+	// unset nodes are nil
+	nodes    map[string]*nodeSelection
+	variants map[string]*nodeSelection
+}
+
+type nodeSelection struct {
+	skip    bool
+	args    *argsErased
+	sub     *selectionErased
+	aliases map[string]*nodeSelection
+}
+
+func erase(sel selection) *selectionErased {
+	erased := sel.erased()
+	return &erased
+}
+
+// ScalarSelect is the selection of a scalar node without arguments
+type ScalarSelect struct {
+	node nodeSelection
+}
+
+// Get selects the node
+func Get() *ScalarSelect {
+	return &ScalarSelect{}
+}
+
+// Skip excludes the node from a SelectAll selection
+func Skip() *ScalarSelect {
+	return &ScalarSelect{nodeSelection{skip: true}}
+}
+
+// GetAliased selects the node under each of the aliases
+func GetAliased(aliases ...string) *ScalarSelect {
+	sel := &ScalarSelect{nodeSelection{aliases: map[string]*nodeSelection{}}}
+	for _, alias := range aliases {
+		sel.node.aliases[alias] = &nodeSelection{}
+	}
+	return sel
+}
+
+func (s *ScalarSelect) selected() *nodeSelection {
+	if s == nil {
+		return nil
+	}
+	return &s.node
+}
+
+// ScalarSelectArgs is the selection of a scalar node with arguments
+type ScalarSelectArgs[A any] struct {
+	node nodeSelection
+}
+
+// GetArgs selects the node with the arguments
+func GetArgs[A any](args NodeArgs[A]) *ScalarSelectArgs[A] {
+	return &ScalarSelectArgs[A]{nodeSelection{args: &args.erased}}
+}
+
+// GetArgsAliased selects the node under each of the aliases, with their arguments
+func GetArgsAliased[A any](aliases map[string]NodeArgs[A]) *ScalarSelectArgs[A] {
+	sel := &ScalarSelectArgs[A]{nodeSelection{aliases: map[string]*nodeSelection{}}}
+	for alias, args := range aliases {
+		args := args
+		sel.node.aliases[alias] = &nodeSelection{args: &args.erased}
+	}
+	return sel
+}
+
+func (s *ScalarSelectArgs[A]) selected() *nodeSelection {
+	if s == nil {
+		return nil
+	}
+	return &s.node
+}
+
+// CompositeSelect is the selection of a composite node without arguments
+type CompositeSelect[S selection] struct {
+	node nodeSelection
+}
+
+// Select selects the node with the sub-selection
+func Select[S selection](sel S) *CompositeSelect[S] {
+	return &CompositeSelect[S]{nodeSelection{sub: erase(sel)}}
+}
+
+// SelectAliased selects the node under each of the aliases, with their sub-selection
+func SelectAliased[S selection](aliases map[string]S) *CompositeSelect[S] {
+	sel := &CompositeSelect[S]{nodeSelection{aliases: map[string]*nodeSelection{}}}
+	for alias, sub := range aliases {
+		sel.node.aliases[alias] = &nodeSelection{sub: erase(sub)}
+	}
+	return sel
+}
+
+func (s *CompositeSelect[S]) selected() *nodeSelection {
+	if s == nil {
+		return nil
+	}
+	return &s.node
+}
+
+// CompositeSelectArgs is the selection of a composite node with arguments
+type CompositeSelectArgs[A any, S selection] struct {
+	node nodeSelection
+}
+
+// ArgsSelection is an aliased selection of a composite node with arguments
+type ArgsSelection[A any, S selection] struct {
+	Args   NodeArgs[A]
+	Select S
+}
+
+// SelectArgs selects the node with the arguments and the sub-selection
+func SelectArgs[A any, S selection](args NodeArgs[A], sel S) *CompositeSelectArgs[A, S] {
+	return &CompositeSelectArgs[A, S]{nodeSelection{args: &args.erased, sub: erase(sel)}}
+}
+
+// SelectArgsAliased selects the node under each of the aliases, with their
+// arguments and sub-selection
+func SelectArgsAliased[A any, S selection](aliases map[string]ArgsSelection[A, S]) *CompositeSelectArgs[A, S] {
+	sel := &CompositeSelectArgs[A, S]{nodeSelection{aliases: map[string]*nodeSelection{}}}
+	for alias, item := range aliases {
+		item := item
+		sel.node.aliases[alias] = &nodeSelection{args: &item.Args.erased, sub: erase(item.Select)}
+	}
+	return sel
+}
+
+func (s *CompositeSelectArgs[A, S]) selected() *nodeSelection {
+	if s == nil {
+		return nil
+	}
+	return &s.node
+}
+
+func selectionToNodes(
+	sel selectionErased,
+	metas map[string]NodeMetaFn,
+	parentPath string,
+) ([]selectNode, error) {
+	for nodeName := range sel.nodes {
+		if _, ok := metas[nodeName]; !ok {
+			return nil, fmt.Errorf("unexpected node %s found in selection set at %s", nodeName, parentPath)
+		}
+	}
+
+	out := []selectNode{}
+	for _, nodeName := range sortedKeys(metas) {
+		nodeSel := sel.nodes[nodeName]
+		implicit := nodeSel == nil
+		if implicit {
+			if !sel.selectAll {
+				continue
+			}
+			nodeSel = &nodeSelection{}
+		}
+		if nodeSel.skip {
+			continue
+		}
+
+		meta := metas[nodeName]()
+		if implicit && (meta.ArgTypes != nil || meta.SubNodes != nil || meta.Variants != nil) {
+			// nodes with arguments and composite nodes are only selected explicitly
+			continue
+		}
+
+		// we splat out any aliasing of nodes here
+		instances := map[string]*nodeSelection{nodeName: nodeSel}
+		if nodeSel.aliases != nil {
+			instances = nodeSel.aliases
+		}
+
+		for _, instanceName := range sortedKeys(instances) {
+			instance := instances[instanceName]
+			path := parentPath + "." + instanceName
+			if instance.aliases != nil {
+				return nil, fmt.Errorf("nested alias node discovered at %s", path)
+			}
+			node := selectNode{nodeName: nodeName, instanceName: instanceName}
+
+			if meta.ArgTypes != nil {
+				if instance.args == nil {
+					return nil, fmt.Errorf("node at %s requires arguments", path)
+				}
+				args, err := convertArgs(instance.args, meta, path)
+				if err != nil {
+					return nil, err
+				}
+				node.args = args
+			} else if instance.args != nil {
+				return nil, fmt.Errorf("node at %s does not take arguments", path)
+			}
+
+			if meta.SubNodes != nil || meta.Variants != nil {
+				if instance.sub == nil {
+					return nil, fmt.Errorf("node at %s is a composite that requires a selection", path)
+				}
+				if meta.SubNodes != nil {
+					subNodes, err := selectionToNodes(*instance.sub, meta.SubNodes, path)
+					if err != nil {
+						return nil, err
+					}
+					node.subNodes = subNodes
+				} else {
+					variants, err := variantsToNodes(*instance.sub, meta.Variants, path)
+					if err != nil {
+						return nil, err
+					}
+					node.variants = variants
+				}
+			}
+
+			out = append(out, node)
+		}
+	}
+	return out, nil
+}
+
+func variantsToNodes(
+	sel selectionErased,
+	metas map[string]NodeMetaFn,
+	path string,
+) (map[string][]selectNode, error) {
+	for variantTy := range sel.variants {
+		if _, ok := metas[variantTy]; !ok {
+			return nil, fmt.Errorf("node at %s has no variant called %s", path, variantTy)
+		}
+	}
+
+	out := map[string][]selectNode{}
+	for _, variantTy := range sortedKeys(metas) {
+		variantMeta := metas[variantTy]()
+		// this union member is a scalar
+		if variantMeta.SubNodes == nil {
+			continue
+		}
+
+		nodes := []selectNode{}
+		if variantSel := sel.variants[variantTy]; variantSel != nil && variantSel.sub != nil {
+			subNodes, err := selectionToNodes(
+				*variantSel.sub,
+				variantMeta.SubNodes,
+				fmt.Sprintf("%s.variant(%s)", path, variantTy),
+			)
+			if err != nil {
+				return nil, err
+			}
+			nodes = subNodes
+		}
+		// we select __typename for each variant
+		// even if the user is not interested in the variant
+		nodes = append(nodes, selectNode{nodeName: "__typename", instanceName: "__typename"})
+		out[variantTy] = nodes
+	}
+	return out, nil
+}
+
+//
+// --- --- Value types --- --- //
+//
+
+// File to upload
+type File struct {
+	Content  []byte
+	Name     string
+	MimeType string
+}
+
+// MarshalJSON encodes files as null: their content is sent in the multipart request
+func (f File) MarshalJSON() ([]byte, error) {
+	return []byte("null"), nil
+}
+
+func (f *File) mimeType() string {
+	if f.MimeType != "" {
+		return f.MimeType
+	}
+	if mimeType := mime.TypeByExtension(filepath.Ext(f.Name)); mimeType != "" {
+		return mimeType
+	}
+	return "application/octet-stream"
+}
+
+type fileAt struct {
+	// relative to the argument value, e.g. `.0` for the first item of a list
+	path string
+	file *File
+}
+
+// extractFiles collects the files of an argument object, by argument
+func extractFiles(value any, paths [][]string) (map[string][]fileAt, error) {
+	out := map[string][]fileAt{}
+	for _, path := range paths {
+		if len(path) == 0 || !strings.HasPrefix(path[0], ".") {
+			continue
+		}
+		key := path[0][1:]
+		field, ok := fieldByJSONName(reflect.ValueOf(value), key)
+		if !ok {
+			continue
+		}
+		err := collectFiles(field, path[1:], "", func(path string, file *File) {
+			out[key] = append(out[key], fileAt{path, file})
+		})
+		if err != nil {
+			return nil, err
+		}
+	}
+	return out, nil
+}
+
+func collectFiles(value reflect.Value, path []string, current string, found func(string, *File)) error {
+	if len(path) == 0 {
+		if !value.IsValid() {
+			return nil
+		}
+		switch file := value.Interface().(type) {
+		case *File:
+			if file != nil {
+				found(current, file)
+			}
+			return nil
+		case File:
+			found(current, &file)
+			return nil
+		}
+		return fmt.Errorf("expected File at %q", current)
+	}
+
+	for value.IsValid() && (value.Kind() == reflect.Pointer || value.Kind() == reflect.Interface) {
+		if value.IsNil() {
+			return nil
+		}
+		value = value.Elem()
+	}
+	if !value.IsValid() {
+		return nil
+	}
+
+	switch segment := path[0]; {
+	case segment == "?":
+		return collectFiles(value, path[1:], current, found)
+	case segment == "[]":
+		if value.Kind() != reflect.Slice && value.Kind() != reflect.Array {
+			return fmt.Errorf("expected array at %q", current)
+		}
+		for idx := 0; idx < value.Len(); idx++ {
+			err := collectFiles(value.Index(idx), path[1:], current+"."+strconv.Itoa(idx), found)
+			if err != nil {
+				return err
+			}
+		}
+		return nil
+	case strings.HasPrefix(segment, "."):
+		field, ok := fieldByJSONName(value, segment[1:])
+		if !ok {
+			return nil
+		}
+		return collectFiles(field, path[1:], current+segment, found)
+	default:
+		return fmt.Errorf("unexpected path segment %q", segment)
+	}
+}
+
+func fieldByJSONName(value reflect.Value, name string) (reflect.Value, bool) {
+	for value.IsValid() && (value.Kind() == reflect.Pointer || value.Kind() == reflect.Interface) {
+		if value.IsNil() {
+			return reflect.Value{}, false
+		}
+		value = value.Elem()
+	}
+	switch value.Kind() {
+	case reflect.Struct:
+		ty := value.Type()
+		for idx := 0; idx < ty.NumField(); idx++ {
+			field := ty.Field(idx)
+			tag := strings.Split(field.Tag.Get("json"), ",")[0]
+			if tag == name || (tag == "" && field.Name == name) {
+				return value.Field(idx), true
+			}
+		}
+	case reflect.Map:
+		if value.Type().Key().Kind() == reflect.String {
+			field := value.MapIndex(reflect.ValueOf(name).Convert(value.Type().Key()))
+			return field, field.IsValid()
+		}
+	}
+	return reflect.Value{}, false
+}
+
+// Union holds a value of one of the variants of a union type
+type Union struct {
+	value any
+	raw   json.RawMessage
+}
+
+// NewUnion wraps a value of one of the variant types
+func NewUnion(value any) Union {
+	return Union{value: value}
+}
+
+func (u Union) MarshalJSON() ([]byte, error) {
+	if u.raw != nil {
+		return u.raw, nil
+	}
+	return json.Marshal(u.value)
+}
+
+func (u *Union) UnmarshalJSON(data []byte) error {
+	u.value = nil
+	u.raw = append(json.RawMessage(nil), data...)
+	return nil
+}
+
+// As decodes the value into target, a pointer to one of the variant types
+func (u Union) As(target any) error {
+	raw, err := u.MarshalJSON()
+	if err != nil {
+		return err
+	}
+	return json.Unmarshal(raw, target)
+}
+
+// Typename is the GraphQL type of a composite variant in query results,
+// empty for scalar variants
+func (u Union) Typename() string {
+	var value struct {
+		Typename string `json:"__typename"`
+	}
+	if err := u.As(&value); err != nil {
+		return ""
+	}
+	return value.Typename
+}
+
+//
+// --- --- QueryGraph types --- --- //
+//
+
+type QueryGraph struct {
+	tyToGqlTyMap map[string]string
+}
+
+//
+// --- --- Transports --- --- //
+//
+
+// Transport is implemented by GraphQlTransport and HostcallTransport
+type Transport interface {
+	base() *transportBase
+}
+
+// transportBase holds the request building logic shared by the transports
+type transportBase struct {
+	tyToGqlTyMap map[string]string
+	fetch        func(ctx context.Context, doc string, variables map[string]any, files map[string]*File) (Results, error)
+}
+
+func (t *transportBase) base() *transportBase {
+	return t
+}
+
+// GraphQlTransport talks to the typegate using GraphQL over HTTP
+type GraphQlTransport struct {
+	transportBase
+	Addr   string
+	Client *http.Client
+	// added to each request
+	Headers http.Header
+}
+
+func NewGraphQlTransport(qg *QueryGraph, addr string) *GraphQlTransport {
+	t := &GraphQlTransport{
+		Addr:    addr,
+		Client:  http.DefaultClient,
+		Headers: http.Header{},
+	}
+	t.transportBase = transportBase{tyToGqlTyMap: qg.tyToGqlTyMap, fetch: t.fetchHTTP}
+	return t
+}
+
+// metagen-genif HOSTCALL
+
+// HostcallBinding calls into the typegate, e.g. through the `hostcall`
+// import of the wit-wire world
+type HostcallBinding func(opName string, json string) (string, error)
+
+// HostError is the error returned by the typegate on hostcalls, as JSON
+type HostError string
+
+func (e HostError) Error() string {
+	return "host error: " + string(e)
+}
+
+// HostcallTransport is used by custom functions running in the typegate
+// to access typegraphs
+type HostcallTransport struct {
+	transportBase
+	binding HostcallBinding
+}
+
+func NewHostcallTransport(qg *QueryGraph, binding HostcallBinding) *HostcallTransport {
+	t := &HostcallTransport{binding: binding}
+	t.transportBase = transportBase{tyToGqlTyMap: qg.tyToGqlTyMap, fetch: t.fetchHostcall}
+	return t
+}
+
+func (t *HostcallTransport) fetchHostcall(
+	_ context.Context,
+	doc string,
+	variables map[string]any,
+	files map[string]*File,
+) (Results, error) {
+	if len(files) > 0 {
+		return nil, fmt.Errorf("file uploads are not supported through hostcalls")
+	}
+	payload, err := json.Marshal(map[string]any{"query": doc, "variables": variables})
+	if err != nil {
+		return nil, err
+	}
+	res, err := t.binding("gql", string(payload))
+	if err != nil {
+		return nil, err
+	}
+	return parseResponse(http.StatusOK, []byte(res))
+}
+
+// metagen-endif
+
+// GraphQlError is returned when the typegate responds with an error
+type GraphQlError struct {
+	Status int
+	Errors []json.RawMessage
+	Body   []byte
+}
+
+func (e *GraphQlError) Error() string {
+	if len(e.Errors) > 0 {
+		errors := make([]string, len(e.Errors))
+		for idx, err := range e.Errors {
+			errors[idx] = string(err)
+		}
+		return fmt.Sprintf("graphql errors in response: %s", strings.Join(errors, ", "))
+	}
+	return fmt.Sprintf("graphql request failed with status %d: %s", e.Status, e.Body)
+}
+
+// Results of a request for many nodes, by key
+type Results map[string]json.RawMessage
+
+func decodeResult[Out any](results Results, key string) (Out, error) {
+	var out Out
+	raw, ok := results[key]
+	if !ok {
+		return out, fmt.Errorf("no result for node %s", key)
+	}
+	if err := json.Unmarshal(raw, &out); err != nil {
+		return out, fmt.Errorf("error decoding result for node %s: %w", key, err)
+	}
+	return out, nil
+}
+
+// Query performs a query for a single node
+func Query[Out any](ctx context.Context, t Transport, node QueryNode[Out]) (Out, error) {
+	results, err := t.base().QueryMany(ctx, map[string]AnyQueryNode{"value": node})
+	if err != nil {
+		var out Out
+		return out, err
+	}
+	return node.Decode(results, "value")
+}
+
+// Mutation performs a mutation for a single node
+func Mutation[Out any](ctx context.Context, t Transport, node MutationNode[Out]) (Out, error) {
+	results, err := t.base().MutationMany(ctx, map[string]AnyMutationNode{"value": node})
+	if err != nil {
+		var out Out
+		return out, err
+	}
+	return node.Decode(results, "value")
+}
+
+// QueryMany performs a query for many nodes, see QueryNode.Decode
+func (t *transportBase) QueryMany(ctx context.Context, nodes map[string]AnyQueryNode) (Results, error) {
+	req, err := t.prepare("query", queryNodes(nodes))
+	if err != nil {
+		return nil, err
+	}
+	return req.perform(ctx, nil)
+}
+
+// MutationMany performs a mutation for many nodes, see MutationNode.Decode
+func (t *transportBase) MutationMany(ctx context.Context, nodes map[string]AnyMutationNode) (Results, error) {
+	req, err := t.prepare("mutation", mutationNodes(nodes))
+	if err != nil {
+		return nil, err
+	}
+	return req.perform(ctx, nil)
+}
+
+func queryNodes(nodes map[string]AnyQueryNode) func() (map[string]selectNode, error) {
+	return func() (map[string]selectNode, error) {
+		out := map[string]selectNode{}
+		for key, node := range nodes {
+			selected, err := node.queryNode()
+			if err != nil {
+				return nil, err
+			}
+			out[key] = selected
+		}
+		return out, nil
+	}
+}
+
+func mutationNodes(nodes map[string]AnyMutationNode) func() (map[string]selectNode, error) {
+	return func() (map[string]selectNode, error) {
+		out := map[string]selectNode{}
+		for key, node := range nodes {
+			selected, err := node.mutationNode()
+			if err != nil {
+				return nil, err
+			}
+			out[key] = selected
+		}
+		return out, nil
+	}
+}
+
+// PreparedRequest is a request whose document is built once, to be performed
+// many times with the values of its placeholders
+type PreparedRequest[Out any] struct {
+	req    *preparedRequest
+	decode func(Results) (Out, error)
+}
+
+// Perform the request with the values of the placeholders, by name
+func (p *PreparedRequest[Out]) Perform(ctx context.Context, values map[string]any) (Out, error) {
+	results, err := p.req.perform(ctx, values)
+	if err != nil {
+		var out Out
+		return out, err
+	}
+	return p.decode(results)
+}
+
+// PrepareQuery prepares a query for a single node
+func PrepareQuery[Out any](t Transport, node QueryNode[Out]) (*PreparedRequest[Out], error) {
+	req, err := t.base().prepare("query", queryNodes(map[string]AnyQueryNode{"value": node}))
+	if err != nil {
+		return nil, err
+	}
+	return &PreparedRequest[Out]{req, func(results Results) (Out, error) {
+		return node.Decode(results, "value")
+	}}, nil
+}
+
+// PrepareMutation prepares a mutation for a single node
+func PrepareMutation[Out any](t Transport, node MutationNode[Out]) (*PreparedRequest[Out], error) {
+	req, err := t.base().prepare("mutation", mutationNodes(map[string]AnyMutationNode{"value": node}))
+	if err != nil {
+		return nil, err
+	}
+	return &PreparedRequest[Out]{req, func(results Results) (Out, error) {
+		return node.Decode(results, "value")
+	}}, nil
+}
+
+// PrepareQueryMany prepares a query for many nodes
+func (t *transportBase) PrepareQueryMany(nodes map[string]AnyQueryNode) (*PreparedRequest[Results], error) {
+	req, err := t.prepare("query", queryNodes(nodes))
+	if err != nil {
+		return nil, err
+	}
+	return &PreparedRequest[Results]{req, func(results Results) (Results, error) {
+		return results, nil
+	}}, nil
+}
+
+// PrepareMutationMany prepares a mutation for many nodes
+func (t *transportBase) PrepareMutationMany(nodes map[string]AnyMutationNode) (*PreparedRequest[Results], error) {
+	req, err := t.prepare("mutation", mutationNodes(nodes))
+	if err != nil {
+		return nil, err
+	}
+	return &PreparedRequest[Results]{req, func(results Results) (Results, error) {
+		return results, nil
+	}}, nil
+}
+
+type preparedRequest struct {
+	t         *transportBase
+	doc       string
+	variables map[string]nodeArgValue
+	files     map[string]*File
+}
+
+func (t *transportBase) prepare(
+	ty string,
+	getNodes func() (map[string]selectNode, error),
+) (*preparedRequest, error) {
+	nodes, err := getNodes()
+	if err != nil {
+		return nil, err
+	}
+	variables := map[string]nodeArgValue{}
+	files := map[string]*File{}
+	var rootNodes strings.Builder
+	for _, key := range sortedKeys(nodes) {
+		node := nodes[key]
+		node.instanceName = key
+		gql, err := convertNodeGql(t.tyToGqlTyMap, node, variables, files)
+		if err != nil {
+			return nil, err
+		}
+		fmt.Fprintf(&rootNodes, "  %s\n", gql)
+	}
+	argsRow := make([]string, 0, len(variables))
+	for _, name := range sortedKeys(variables) {
+		gqlTy, ok := t.tyToGqlTyMap[variables[name].typeName]
+		if !ok {
+			return nil, fmt.Errorf("unreachable: no graphql type found for %s", variables[name].typeName)
+		}
+		argsRow = append(argsRow, fmt.Sprintf("$%s: %s", name, gqlTy))
+	}
+	doc := ty
+	if len(argsRow) > 0 {
+		doc += "(" + strings.Join(argsRow, ", ") + ")"
+	}
+	doc += " {\n" + rootNodes.String() + "}"
+	return &preparedRequest{t, doc, variables, files}, nil
+}
+
+func convertNodeGql(
+	tyToGqlTyMap map[string]string,
+	node selectNode,
+	variables map[string]nodeArgValue,
+	files map[string]*File,
+) (string, error) {
+	var out strings.Builder
+	if node.instanceName != node.nodeName {
+		fmt.Fprintf(&out, "%s: %s", node.instanceName, node.nodeName)
+	} else {
+		out.WriteString(node.nodeName)
+	}
+
+	if len(node.args) > 0 {
+		args := make([]string, 0, len(node.args))
+		for _, key := range sortedKeys(node.args) {
+			arg := node.args[key]
+			name := "in" + strconv.Itoa(len(variables))
+			for _, file := range arg.files {
+				files["variables."+name+file.path] = file.file
+			}
+			variables[name] = arg
+			args = append(args, fmt.Sprintf("%s: $%s", key, name))
+		}
+		fmt.Fprintf(&out, "(%s)", strings.Join(args, ", "))
+	}
+
+	if node.variants != nil {
+		out.WriteString(" {")
+		for _, variantTy := range sortedKeys(node.variants) {
+			// fetch the gql variant name so we can do type assertions
+			gqlTy, ok := tyToGqlTyMap[variantTy]
+			if !ok {
+				return "", fmt.Errorf("unreachable: no graphql type found for variant %s", variantTy)
+			}
+			fmt.Fprintf(&out, " ... on %s {", strings.TrimSuffix(gqlTy, "!"))
+			for _, subNode := range node.variants[variantTy] {
+				gql, err := convertNodeGql(tyToGqlTyMap, subNode, variables, files)
+				if err != nil {
+					return "", err
+				}
+				out.WriteString(" " + gql)
+			}
+			out.WriteString(" }")
+		}
+		out.WriteString(" }")
+	} else if node.subNodes != nil {
+		out.WriteString(" {")
+		for _, subNode := range node.subNodes {
+			gql, err := convertNodeGql(tyToGqlTyMap, subNode, variables, files)
+			if err != nil {
+				return "", err
+			}
+			out.WriteString(" " + gql)
+		}
+		out.WriteString(" }")
+	}
+	return out.String(), nil
+}
+
+func (r *preparedRequest) perform(ctx context.Context, values map[string]any) (Results, error) {
+	variables := make(map[string]any, len(r.variables))
+	for name, arg := range r.variables {
+		if arg.placeholder == "" {
+			variables[name] = arg.value
+			continue
+		}
+		value, ok := values[arg.placeholder]
+		if !ok {
+			return nil, fmt.Errorf("missing value for placeholder %s", arg.placeholder)
+		}
+		variables[name] = value
+	}
+	return r.t.fetch(ctx, r.doc, variables, r.files)
+}
+
+func (t *GraphQlTransport) fetchHTTP(
+	ctx context.Context,
+	doc string,
+	variables map[string]any,
+	files map[string]*File,
+) (Results, error) {
+	operations, err := json.Marshal(map[string]any{"query": doc, "variables": variables})
+	if err != nil {
+		return nil, err
+	}
+
+	body := io.Reader(bytes.NewReader(operations))
+	contentType := "application/json"
+	if len(files) > 0 {
+		body, contentType, err = multipartBody(operations, files)
+		if err != nil {
+			return nil, err
+		}
+	}
+
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, t.Addr, body)
+	if err != nil {
+		return nil, err
+	}
+	for key, values := range t.Headers {
+		for _, value := range values {
+			req.Header.Add(key, value)
+		}
+	}
+	req.Header.Set("Accept", "application/json")
+	req.Header.Set("Content-Type", contentType)
+
+	res, err := t.Client.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer res.Body.Close()
+	resBody, err := io.ReadAll(res.Body)
+	if err != nil {
+		return nil, err
+	}
+
+	if res.StatusCode != http.StatusOK {
+		var parsed struct {
+			Errors []json.RawMessage `json:"errors"`
+		}
+		_ = json.Unmarshal(resBody, &parsed)
+		return nil, &GraphQlError{Status: res.StatusCode, Errors: parsed.Errors, Body: resBody}
+	}
+	if !strings.HasPrefix(res.Header.Get("Content-Type"), "application/json") {
+		return nil, fmt.Errorf("unexpected content-type in graphql response: %q", res.Header.Get("Content-Type"))
+	}
+	return parseResponse(res.StatusCode, resBody)
+}
+
+func parseResponse(status int, body []byte) (Results, error) {
+	var parsed struct {
+		Data   Results           `json:"data"`
+		Errors []json.RawMessage `json:"errors"`
+	}
+	if err := json.Unmarshal(body, &parsed); err != nil {
+		return nil, fmt.Errorf("error decoding graphql response: %w", err)
+	}
+	if len(parsed.Errors) > 0 {
+		return nil, &GraphQlError{Status: status, Errors: parsed.Errors, Body: body}
+	}
+	return parsed.Data, nil
+}
+
+// multipartBody follows the GraphQL multipart request spec
+func multipartBody(operations []byte, files map[string]*File) (io.Reader, string, error) {
+	var buf bytes.Buffer
+	writer := multipart.NewWriter(&buf)
+	if err := writer.WriteField("operations", string(operations)); err != nil {
+		return nil, "", err
+	}
+
+	// the same file may be used at many paths
+	var order []*File
+	paths := map[*File][]string{}
+	for _, path := range sortedKeys(files) {
+		file := files[path]
+		if _, ok := paths[file]; !ok {
+			order = append(order, file)
+		}
+		paths[file] = append(paths[file], path)
+	}
+	fileMap := map[string][]string{}
+	for idx, file := range order {
+		fileMap[strconv.Itoa(idx)] = paths[file]
+	}
+	mapJSON, err := json.Marshal(fileMap)
+	if err != nil {
+		return nil, "", err
+	}
+	if err := writer.WriteField("map", string(mapJSON)); err != nil {
+		return nil, "", err
+	}
+
+	quoteEscaper := strings.NewReplacer(`\`, `\\`, `"`, `\"`)
+	for idx, file := range order {
+		header := textproto.MIMEHeader{}
+		header.Set(
+			"Content-Disposition",
+			fmt.Sprintf(`form-data; name="%d"; filename="%s"`, idx, quoteEscaper.Replace(file.Name)),
+		)
+		header.Set("Content-Type", file.mimeType())
+		part, err := writer.CreatePart(header)
+		if err != nil {
+			return nil, "", err
+		}
+		if _, err := part.Write(file.Content); err != nil {
+			return nil, "", err
+		}
+	}
+	if err := writer.Close(); err != nil {
+		return nil, "", err
+	}
+	return &buf, writer.FormDataContentType(), nil
+}
+
+func sortedKeys[V any](m map[string]V) []string {
+	keys := make([]string, 0, len(m))
+	for key := range m {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
+
+//
+// --- --- Typegraph types --- --- //
+//
@@ -0,0 +1,405 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use std::fmt::Write;
+
+use super::shared::manifest::{ManifestEntry, ManifestPage};
+use super::utils::*;
+use crate::interlude::*;
+use crate::shared::types::{
+    field_deprecation, field_docs, type_body_required, type_docs, write_doc_lines,
+};
+
+#[derive(Debug)]
+pub enum GoType {
+    /// inlined builtin type
+    Builtin(&'static str),
+    /// reference to a type of another page
+    Plain { name: String },
+    Alias {
+        name: String,
+        target: &'static str,
+        docs: Vec<String>,
+    },
+    Optional {
+        item: TypeKey,
+        /// inlined if name is none
+        name: Option<String>,
+        docs: Vec<String>,
+    },
+    List {
+        item: TypeKey,
+        /// inlined if name is none
+        name: Option<String>,
+        docs: Vec<String>,
+    },
+    Struct {
+        name: String,
+        props: Vec<StructProp>,
+        partial: bool,
+        docs: Vec<String>,
+    },
+    Union {
+        name: String,
+        variants: Vec<TypeKey>,
+        partial: bool,
+        docs: Vec<String>,
+    },
+}
+
+#[derive(Debug)]
+pub struct StructProp {
+    name: String,
+    json_name: String,
+    ty: TypeKey,
+    optional: bool,
+    /// recursive field, only allowed behind a pointer
+    boxed: bool,
+    deprecated: Option<String>,
+    docs: Vec<String>,
+}
+
+pub type GoTypesPage = ManifestPage<GoType>;
+
+impl ManifestEntry for GoType {
+    type Extras = ();
+
+    fn render(&self, out: &mut impl Write, page: &GoTypesPage) -> std::fmt::Result {
+        match self {
+            Self::Builtin(_) | Self::Plain { .. } => {}
+            Self::Alias { name, target, docs } => {
+                write_doc_lines(out, docs, "//")?;
+                writeln!(out, "type {name} = {target}")?;
+                writeln!(out)?;
+            }
+            Self::Optional {
+                name: Some(name),
+                docs,
+                ..
+            }
+            | Self::List {
+                name: Some(name),
+                docs,
+                ..
+            } => {
+                write_doc_lines(out, docs, "//")?;
+                writeln!(out, "type {name} = {}", self.inline_ref(page))?;
+                writeln!(out)?;
+            }
+            Self::Optional { name: None, .. } | Self::List { name: None, .. } => {}
+            Self::Struct {
+                name,
+                props,
+                partial,
+                docs,
+            } => {
+                write_doc_lines(out, docs, "//")?;
+                writeln!(out, "type {} struct {{", name_with_suffix(name, *partial))?;
+                for prop in props {
+                    write_doc_lines(out, &prop.docs, "\t//")?;
+                    if let Some(message) = &prop.deprecated {
+                        if !prop.docs.is_empty() {
+                            writeln!(out, "\t//")?;
+                        }
+                        writeln!(out, "\t// Deprecated: {message}")?;
+                    }
+                    let ty_ref = page.get_ref(&prop.ty).unwrap();
+                    let ty_ref = if (*partial || prop.boxed) && !is_nilable(&ty_ref) {
+                        format!("*{ty_ref}")
+                    } else {
+                        ty_ref
+                    };
+                    let omitempty = if *partial || prop.optional {
+                        ",omitempty"
+                    } else {
+                        ""
+                    };
+                    writeln!(
+                        out,
+                        "\t{} {ty_ref} `json:\"{}{omitempty}\"`",
+                        prop.name, prop.json_name
+                    )?;
+                }
+                writeln!(out, "}}")?;
+                writeln!(out)?;
+            }
+            Self::Union {
+                name,
+                variants,
+                partial,
+                docs,
+            } => {
+                write_doc_lines(out, docs, "//")?;
+                if !docs.is_empty() {
+                    writeln!(out, "//")?;
+                }
+                let variants = variants
+                    .iter()
+                    .map(|variant| page.get_ref(variant).unwrap())
+                    .collect::<Vec<_>>();
+                writeln!(out, "// One of: {}", variants.join(", "))?;
+                writeln!(out, "type {} = Union", name_with_suffix(name, *partial))?;
+                writeln!(out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_reference_expr(&self, page: &GoTypesPage) -> Option<String> {
+        Some(match self {
+            Self::Builtin(name) => name.to_string(),
+            Self::Plain { name } | Self::Alias { name, .. } => name.clone(),
+            Self::Optional {
+                name: Some(name), ..
+            }
+            | Self::List {
+                name: Some(name), ..
+            } => name.clone(),
+            Self::Optional { name: None, .. } | Self::List { name: None, .. } => {
+                self.inline_ref(page)
+            }
+            Self::Struct { name, partial, .. } | Self::Union { name, partial, .. } => {
+                name_with_suffix(name, *partial)
+            }
+        })
+    }
+}
+
+impl GoType {
+    fn inline_ref(&self, page: &GoTypesPage) -> String {
+        match self {
+            Self::Optional { item, .. } => {
+                let item_ref = page.get_ref(item).unwrap();
+                if is_nilable(&item_ref) {
+                    item_ref
+                } else {
+                    format!("*{item_ref}")
+                }
+            }
+            Self::List { item, .. } => format!("[]{}", page.get_ref(item).unwrap()),
+            _ => unreachable!("only containers are inlined"),
+        }
+    }
+
+    fn with_docs(mut self, new_docs: Vec<String>) -> GoType {
+        match &mut self {
+            GoType::Alias { docs, .. }
+            | GoType::Optional { docs, .. }
+            | GoType::List { docs, .. }
+            | GoType::Struct { docs, .. }
+            | GoType::Union { docs, .. } => *docs = new_docs,
+            GoType::Builtin(_) | GoType::Plain { .. } => {}
+        }
+        self
+    }
+
+    fn builtin(ty: &Type) -> &'static str {
+        match ty {
+            Type::Boolean(_) => "bool",
+            Type::Integer(_) => "int64",
+            Type::Float(_) => "float64",
+            Type::String(_) => "string",
+            Type::File(_) => "*File",
+            _ => unreachable!("unexpected non-scalar type: {:?}", ty.tag()),
+        }
+    }
+
+    pub fn new(ty: &Type, partial: bool) -> GoType {
+        if !type_body_required(ty) {
+            return GoType::Builtin(Self::builtin(ty));
+        }
+        let name = normalize_type_title(&ty.name());
+        match ty {
+            Type::Boolean(_) | Type::Integer(_) | Type::Float(_) | Type::File(_) => GoType::Alias {
+                name,
+                target: Self::builtin(ty),
+                docs: vec![],
+            },
+            Type::String(ty) => {
+                let name = if let (Some(format), true) =
+                    (ty.format_only(), ty.title().starts_with("string_"))
+                {
+                    normalize_type_title(&format!("string_{format}_{}", ty.idx()))
+                } else {
+                    name
+                };
+                GoType::Alias {
+                    name,
+                    target: "string",
+                    docs: vec![],
+                }
+            }
+            Type::Optional(ty) => {
+                let inlined = ty.default_value.is_none() && ty.title().starts_with("optional_");
+                GoType::Optional {
+                    item: ty.item().key(),
+                    name: (!inlined).then(|| name_with_suffix(&name, partial)),
+                    docs: vec![],
+                }
+            }
+            Type::List(ty) => {
+                let inlined = matches!((ty.min_items, ty.max_items), (None, None))
+                    && ty.title().starts_with("list_");
+                GoType::List {
+                    item: ty.item().key(),
+                    name: (!inlined).then(|| name_with_suffix(&name, partial)),
+                    docs: vec![],
+                }
+            }
+            Type::Object(ty) => {
+                let props = ty
+                    .properties()
+                    .iter()
+                    .filter(|(_, prop)| !prop.is_injected())
+                    .map(|(prop_name, prop)| {
+                        let (optional, boxed) = match &prop.ty {
+                            Type::Optional(_) => (true, false),
+                            _ => (false, ty.is_descendant_of(&prop.ty)),
+                        };
+                        StructProp {
+                            name: normalize_struct_prop_name(prop_name),
+                            json_name: prop_name.to_string(),
+                            ty: prop.ty.key(),
+                            optional,
+                            boxed,
+                            deprecated: field_deprecation(&prop.ty),
+                            docs: field_docs(&prop.ty),
+                        }
+                    })
+                    .collect();
+                GoType::Struct {
+                    name,
+                    props,
+                    partial,
+                    docs: vec![],
+                }
+            }
+            Type::Union(ty) => GoType::Union {
+                name,
+                variants: ty.variants().iter().map(|v| v.key()).collect(),
+                partial,
+                docs: vec![],
+            },
+            Type::Function(_) => unreachable!("unexpected function type"),
+        }
+        .with_docs(type_docs(ty.base()))
+    }
+}
+
+fn name_with_suffix(name: &str, partial: bool) -> String {
+    if partial {
+        format!("{}Partial", name)
+    } else {
+        name.to_string()
+    }
+}
+
+pub struct GoTypesSubmanifest {
+    pub inputs: GoTypesPage,
+    pub outputs: Option<GoTypesPage>,
+    pub partial_outputs: Option<GoTypesPage>,
+}
+
+pub enum OutputTypes {
+    Both,
+    Partial,
+    NonPartial,
+}
+
+impl GoTypesSubmanifest {
+    pub fn new(tg: &Typegraph, output_types: &OutputTypes) -> Self {
+        let inputs = Self::get_inputs(tg);
+        inputs.cache_references();
+
+        let outputs = if matches!(output_types, OutputTypes::NonPartial | OutputTypes::Both) {
+            let outputs = Self::get_outputs(tg, &inputs);
+            outputs.cache_references();
+            Some(outputs)
+        } else {
+            None
+        };
+
+        let partial_outputs = if matches!(output_types, OutputTypes::Partial | OutputTypes::Both) {
+            let partial_outputs = Self::get_partial_outputs(tg, &inputs, outputs.as_ref());
+            partial_outputs.cache_references();
+            Some(partial_outputs)
+        } else {
+            None
+        };
+
+        Self {
+            inputs,
+            outputs,
+            partial_outputs,
+        }
+    }
+
+    fn get_inputs(tg: &Typegraph) -> GoTypesPage {
+        let mut map = IndexMap::new();
+
+        for (key, ty) in tg.input_types.iter() {
+            map.insert(*key, GoType::new(ty, false));
+        }
+
+        map.into()
+    }
+
+    fn get_outputs(tg: &Typegraph, inputs: &GoTypesPage) -> GoTypesPage {
+        let mut map = IndexMap::new();
+
+        for (key, ty) in tg.output_types.iter() {
+            if let Some(inp_ref) = inputs.get_ref(&ty.key()) {
+                map.insert(*key, GoType::Plain { name: inp_ref });
+            } else {
+                map.insert(*key, GoType::new(ty, false));
+            }
+        }
+
+        map.into()
+    }
+
+    fn get_partial_outputs(
+        tg: &Typegraph,
+        inputs: &GoTypesPage,
+        outputs: Option<&GoTypesPage>,
+    ) -> GoTypesPage {
+        let mut map = IndexMap::new();
+
+        for (key, ty) in tg.output_types.iter() {
+            let partial = ty.is_composite();
+            if !partial {
+                // alias to input type if exists
+                if let Some(inp_ref) = inputs.get_ref(&ty.key()) {
+                    map.insert(*key, GoType::Plain { name: inp_ref });
+                    continue;
+                }
+
+                // alias to output type if exists
+                if let Some(out_ref) = outputs.and_then(|page| page.get_ref(&ty.key())) {
+                    map.insert(*key, GoType::Plain { name: out_ref });
+                    continue;
+                }
+            }
+            map.insert(*key, GoType::new(ty, partial));
+        }
+
+        map.into()
+    }
+
+    pub fn render_all(&self, out: &mut impl Write) -> std::fmt::Result {
+        writeln!(out, "// input types")?;
+        writeln!(out)?;
+        self.inputs.render_all(out)?;
+        if let Some(page) = &self.partial_outputs {
+            writeln!(out, "// partial output types")?;
+            writeln!(out)?;
+            page.render_all(out)?;
+        }
+        if let Some(page) = &self.outputs {
+            writeln!(out, "// output types")?;
+            writeln!(out)?;
+            page.render_all(out)?;
+        }
+        Ok(())
+    }
+}
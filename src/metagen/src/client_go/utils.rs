@@ -0,0 +1,46 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use heck::*;
+use once_cell::sync::Lazy;
+
+/// Exported identifier for the type: go keywords are all lowercase
+/// so these never collide
+pub fn normalize_type_title(title: &str) -> String {
+    static RE: Lazy<regex::Regex> =
+        Lazy::new(|| regex::Regex::new(r"^(?<startd>\d+)(?<rest>.*)").unwrap());
+
+    // TODO: clean up non valid chars
+
+    // clean out underscores at start/end
+    let title = title.trim_matches('_');
+    // move any numbers at start to end
+    let title = title.to_pascal_case();
+    let title = RE.replace(&title, "$rest$startd");
+    title.to_string()
+}
+
+/// Struct fields are exported for `encoding/json` to see them
+pub fn normalize_struct_prop_name(name: &str) -> String {
+    normalize_type_title(name)
+}
+
+/// Name of the go package, from the typegraph name
+pub fn normalize_package_name(name: &str) -> String {
+    let name = name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    match name.chars().next() {
+        None => "client".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("tg{name}"),
+        Some(_) => name,
+    }
+}
+
+/// Whether the zero value of the referenced type is `nil`, in which
+/// case it doesn't need to be wrapped in a pointer to be optional
+pub fn is_nilable(ty_ref: &str) -> bool {
+    ty_ref.starts_with('*') || ty_ref.starts_with("[]") || ty_ref.starts_with("map[")
+}
@@ -8,7 +8,7 @@ mod utils;
 
 use core::fmt::Write;
 
-use fdk_py::types::PyTypesPage;
+use fdk_py::types::{write_docstring, PyTypesPage};
 use node_metas::PyNodeMetasPage;
 use selections::PySelectionsPage;
 use shared::node_metas::MetasPageBuilder;
@@ -19,6 +19,7 @@ use crate::interlude::*;
 use crate::*;
 
 use crate::shared::client::*;
+use crate::shared::types::type_docs;
 
 #[derive(Serialize, Deserialize, Debug, garde::Validate)]
 pub struct ClientPyGenConfig {
@@ -88,7 +89,8 @@ impl crate::Plugin for Generator {
         };
         let mut out = IndexMap::new();
         info!("building render manifest");
-        let tg = ExpansionConfig::with_default_engines().expand(tg)?;
+        let tg = ExpansionConfig::with_naming_strategy(self.config.base.naming_strategy.clone())?
+            .expand(tg)?;
         let manif = ClientPyManifest::new(tg.clone())?;
         let mut contents = String::new();
         info!("rendering...");
@@ -252,10 +254,25 @@ class QueryGraph(QueryGraphBase):
                 EffectType::Update | EffectType::Delete | EffectType::Create => "MutationNode",
             };
 
+            let docs = type_docs(ty.base());
+            let docstring = if docs.is_empty() {
+                String::new()
+            } else {
+                let mut buf = String::new();
+                write_docstring(&mut buf, &docs, "        ")?;
+                format!("\n{}", buf.trim_end())
+            };
+            let warning = match ty.deprecated() {
+                Some(deprecation) => format!(
+                    "\n        self._warn_deprecated({:?})",
+                    format!("{method_name} is deprecated: {}", deprecation.message())
+                ),
+                None => String::new(),
+            };
             write!(
                 dest,
                 r#"
-    def {method_name}({args_row}) -> {node_type}[{out_ty_name}]:
+    def {method_name}({args_row}) -> {node_type}[{out_ty_name}]:{docstring}{warning}
         node = selection_to_nodes(
             {{"{node_name}": {args_selection}}}, 
             {{"{node_name}": NodeDescs.{meta_method}}}, 
@@ -302,6 +319,7 @@ fn e2e() -> anyhow::Result<()> {
 
     let tg_name = "gen-test";
     let config = config::Config {
+        generators: Default::default(),
         targets: [(
             "default".to_string(),
             config::Target(
@@ -314,6 +332,7 @@ fn e2e() -> anyhow::Result<()> {
                             // NOTE: root will map to the test's tempdir
                             path: "./".into(),
                             template_dir: None,
+                            naming_strategy: Default::default(),
                         },
                     })?,
                 }]
@@ -7,7 +7,11 @@ use heck::ToPascalCase as _;
 
 use super::shared::manifest::{ManifestEntry, ManifestPage};
 use super::utils::*;
-use crate::{interlude::*, shared::client::*};
+use crate::{
+    interlude::*,
+    shared::client::*,
+    shared::types::{field_docs, write_doc_lines},
+};
 
 pub struct Extras {
     input_types: IndexMap<TypeKey, String>,
@@ -43,6 +47,7 @@ impl ManifestEntry for PySelection {
 pub struct ObjectProp {
     name: String,
     select_ty: SelectionTy,
+    docs: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -58,6 +63,7 @@ impl Object {
         writeln!(dest, r#"    "_": SelectionFlags,"#)?;
         for prop in self.props.iter() {
             let name = &prop.name;
+            write_doc_lines(dest, &prop.docs, "    #")?;
             use SelectionTy::*;
             match &prop.select_ty {
                 Scalar => writeln!(dest, r#"    "{name}": ScalarSelectNoArgs,"#)?,
@@ -165,6 +171,7 @@ impl PySelectionsPage {
                         .map(|(prop_name, prop)| ObjectProp {
                             name: normalize_struct_prop_name(prop_name),
                             select_ty: selection_for_field(&prop.ty),
+                            docs: field_docs(&prop.ty),
                         })
                         .collect();
                     map.insert(
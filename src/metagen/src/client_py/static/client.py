@@ -1,4 +1,5 @@
 import dataclasses as dc
+import hashlib
 import http.client as http_c
 import io
 import json
@@ -15,6 +16,7 @@ import urllib
 import urllib.error
 import urllib.request as request
 import uuid
+import warnings
 from abc import ABC, abstractmethod
 
 
@@ -248,7 +250,14 @@ class SelectNode(typing.Generic[Out]):
 
 @dc.dataclass
 class QueryNode(SelectNode[Out]):
-    pass
+    def subscribe(self) -> "SubscriptionNode[Out]":
+        """
+        Receive the results of the query as they are pushed by
+        the typegate instead of fetching them once.
+        """
+        return SubscriptionNode(
+            self.node_name, self.instance_name, self.args, self.sub_nodes, self.files
+        )
 
 
 @dc.dataclass
@@ -256,6 +265,11 @@ class MutationNode(SelectNode[Out]):
     pass
 
 
+@dc.dataclass
+class SubscriptionNode(SelectNode[Out]):
+    pass
+
+
 NodeMetaFn = typing.Callable[[], "NodeMeta"]
 
 
@@ -447,6 +461,10 @@ SelectionErased = typing.Mapping[
 @dc.dataclass
 class GraphQLTransportOptions:
     headers: typing.Dict[str, str]
+    # send queries and mutations as automatic persisted queries:
+    # only the hash of the document is sent unless the server
+    # asks for the full text
+    persisted_queries: bool = False
 
 
 @dc.dataclass
@@ -465,6 +483,40 @@ class GraphQLResponse:
     body: bytes
 
 
+PERSISTED_QUERY_MISS_MARKERS = (
+    "PersistedQueryNotFound",
+    "PERSISTED_QUERY_NOT_FOUND",
+)
+
+
+def hash_doc(doc: str) -> str:
+    return hashlib.sha256(doc.encode("utf-8")).hexdigest()
+
+
+def is_persisted_query_miss(res: "GraphQLResponse") -> bool:
+    """
+    Whether the response to a hash only request asks for the full
+    document, through a `PersistedQueryNotFound` error. Other errors
+    are returned as they are.
+    """
+    try:
+        body = json.loads(res.body)
+    except ValueError:
+        body = None
+    if not isinstance(body, dict):
+        body = {}
+    for err in body.get("errors") or []:
+        if not isinstance(err, dict):
+            continue
+        code = (err.get("extensions") or {}).get("code")
+        if (
+            err.get("message") in PERSISTED_QUERY_MISS_MARKERS
+            or code in PERSISTED_QUERY_MISS_MARKERS
+        ):
+            return True
+    return False
+
+
 def convert_query_node_gql(
     ty_to_gql_ty_map: typing.Dict[str, str],
     node: SelectNode,
@@ -591,7 +643,11 @@ class GraphQLTransportBase(ABC):
     def build_gql(
         self,
         query: typing.Mapping[str, SelectNode],
-        ty: typing.Union[typing.Literal["query"], typing.Literal["mutation"]],
+        ty: typing.Union[
+            typing.Literal["query"],
+            typing.Literal["mutation"],
+            typing.Literal["subscription"],
+        ],
         name: str = "",
     ):
         variables: typing.Dict[str, NodeArgValue] = {}
@@ -616,10 +672,12 @@ class GraphQLTransportBase(ABC):
 
     def build_req(
         self,
-        doc: str,
+        doc: typing.Optional[str],
         variables: typing.Dict[str, typing.Any],
         opts: typing.Optional[GraphQLTransportOptions] = None,
         files: typing.Optional[typing.Dict[str, File]] = None,
+        accept: str = "application/json",
+        doc_hash: typing.Optional[str] = None,
     ):
         if files is None:
             files = {}
@@ -627,9 +685,17 @@ class GraphQLTransportBase(ABC):
         headers.update(self.opts.headers)
         if opts:
             headers.update(opts.headers)
-        headers.update({"accept": "application/json"})
-
-        body = json.dumps({"query": doc, "variables": variables})
+        headers.update({"accept": accept})
+
+        operations: typing.Dict[str, typing.Any] = {"variables": variables}
+        # the document is left out of hash only requests
+        if doc is not None:
+            operations["query"] = doc
+        if doc_hash is not None:
+            operations["extensions"] = {
+                "persistedQuery": {"version": 1, "sha256Hash": doc_hash}
+            }
+        body = json.dumps(operations)
 
         if len(files) > 0:
             form_data = MultiPartForm()
@@ -675,6 +741,11 @@ class GraphQLTransportBase(ABC):
             raise Exception("graphql errors in response", parsed)
         return parsed["data"]
 
+    def use_persisted_queries(self, opts: typing.Optional[GraphQLTransportOptions]):
+        return self.opts.persisted_queries or (
+            opts is not None and opts.persisted_queries
+        )
+
     @abstractmethod
     def fetch(
         self,
@@ -682,6 +753,7 @@ class GraphQLTransportBase(ABC):
         variables: typing.Dict[str, typing.Any],
         opts: typing.Optional[GraphQLTransportOptions],
         files: typing.Optional[typing.Dict[str, File]] = None,
+        doc_hash: typing.Optional[str] = None,
     ) -> typing.Any: ...
 
     @typing.overload
@@ -739,17 +811,92 @@ class GraphQLTransportBase(ABC):
         return result["value"] if isinstance(inp, MutationNode) else result
 
 
+def read_sse_events(
+    lines: typing.Iterable[bytes],
+) -> typing.Iterator[typing.Tuple[str, str]]:
+    """
+    Read the (event, data) pairs of a `text/event-stream` body.
+    """
+    event = ""
+    data: typing.List[str] = []
+    for raw in lines:
+        line = raw.decode("utf-8").rstrip("\r\n")
+        if line == "":
+            if len(data) > 0:
+                yield (event or "message", "\n".join(data))
+            event = ""
+            data = []
+            continue
+        if line.startswith(":"):
+            # comments are used as keep-alives
+            continue
+        field, _, value = line.partition(":")
+        if value.startswith(" "):
+            value = value[1:]
+        if field == "event":
+            event = value
+        elif field == "data":
+            data.append(value)
+
+
 class GraphQLTransportUrlib(GraphQLTransportBase):
+    def send(self, req: GraphQLRequest) -> GraphQLResponse:
+        try:
+            with request.urlopen(
+                request.Request(
+                    url=req.addr, method=req.method, headers=req.headers, data=req.body
+                )
+            ) as res:
+                http_res: http_c.HTTPResponse = res
+                return GraphQLResponse(
+                    req,
+                    status=http_res.status,
+                    body=http_res.read(),
+                    headers={key: val for key, val in http_res.headers.items()},
+                )
+        except request.HTTPError as res:
+            return GraphQLResponse(
+                req,
+                status=res.status or 599,
+                body=res.read(),
+                headers={key: val for key, val in res.headers.items()},
+            )
+        except urllib.error.URLError as err:
+            raise Exception(f"URL error: {err.reason}") from err
+
     def fetch(
         self,
         doc: str,
         variables: typing.Dict[str, typing.Any],
         opts: typing.Optional[GraphQLTransportOptions],
         files: typing.Optional[typing.Dict[str, File]] = None,
+        doc_hash: typing.Optional[str] = None,
     ):
         if files is None:
             files = {}
-        req = self.build_req(doc, variables, opts, files)
+        # multipart bodies aren't worth the round trip
+        if self.use_persisted_queries(opts) and len(files) == 0:
+            doc_hash = doc_hash or hash_doc(doc)
+            res = self.send(self.build_req(None, variables, opts, doc_hash=doc_hash))
+            if not is_persisted_query_miss(res):
+                return self.handle_response(res)
+            req = self.build_req(doc, variables, opts, doc_hash=doc_hash)
+        else:
+            req = self.build_req(doc, variables, opts, files)
+        return self.handle_response(self.send(req))
+
+    def subscription(
+        self,
+        inp: SubscriptionNode[Out],
+        opts: typing.Optional[GraphQLTransportOptions] = None,
+        name: str = "",
+    ) -> typing.Iterator[Out]:
+        """
+        Make a subscription request following the graphql-sse protocol,
+        the results are yielded as they are pushed by the typegate.
+        """
+        doc, variables, _ = self.build_gql({"value": inp}, "subscription", name)
+        req = self.build_req(doc, variables, opts, accept="text/event-stream")
         try:
             with request.urlopen(
                 request.Request(
@@ -757,16 +904,33 @@ class GraphQLTransportUrlib(GraphQLTransportBase):
                 )
             ) as res:
                 http_res: http_c.HTTPResponse = res
-                return self.handle_response(
-                    GraphQLResponse(
-                        req,
-                        status=http_res.status,
-                        body=http_res.read(),
-                        headers={key: val for key, val in http_res.headers.items()},
+                content_type = http_res.headers.get("content-type") or ""
+                if not content_type.startswith("text/event-stream"):
+                    # errors and servers without support for streaming
+                    # respond with a single json body
+                    result = self.handle_response(
+                        GraphQLResponse(
+                            req,
+                            status=http_res.status,
+                            body=http_res.read(),
+                            headers={
+                                key: val for key, val in http_res.headers.items()
+                            },
+                        )
                     )
-                )
+                    yield result["value"]
+                    return
+                for event, data in read_sse_events(http_res):
+                    if event == "complete":
+                        return
+                    if event not in ("next", "message"):
+                        continue
+                    parsed = json.loads(data)
+                    if parsed.get("errors"):
+                        raise Exception("graphql errors in response", parsed)
+                    yield parsed["data"]["value"]
         except request.HTTPError as res:
-            return self.handle_response(
+            self.handle_response(
                 GraphQLResponse(
                     req,
                     status=res.status or 599,
@@ -856,8 +1020,9 @@ class HostcallTransport(GraphQLTransportBase):
         variables: typing.Dict[str, typing.Any],
         opts: typing.Optional[GraphQLTransportOptions],
         files: typing.Optional[typing.Dict[str, File]] = None,
+        doc_hash: typing.Optional[str] = None,
     ):
-        _ = opts
+        _ = opts, doc_hash
 
         if files is None:
             files = {}
@@ -948,6 +1113,8 @@ class PreparedRequest(typing.Generic[Out, PreparedOut]):
         doc, variables, files = transport.build_gql(query, ty, name)
         self.single_node = isinstance(dry_run_node, SelectNode)
         self.doc = doc
+        # hashed once for use as a persisted query
+        self.doc_hash = hash_doc(doc)
         self._mapping = variables
         self.transport = transport
         self.files = files
@@ -973,7 +1140,9 @@ class PreparedRequest(typing.Generic[Out, PreparedOut]):
         opts: typing.Optional[GraphQLTransportOptions] = None,
     ) -> PreparedOut:
         resolved_vars = self.resolve_vars(args, self._mapping)
-        result = self.transport.fetch(self.doc, resolved_vars, opts)
+        result = self.transport.fetch(
+            self.doc, resolved_vars, opts, doc_hash=self.doc_hash
+        )
         if self.single_node:
             return result["value"]
         else:
@@ -989,6 +1158,9 @@ class QueryGraphBase:
     def __init__(self, ty_to_gql_ty_map: typing.Dict[str, str]):
         self.ty_to_gql_ty_map = ty_to_gql_ty_map
 
+    def _warn_deprecated(self, message: str):
+        warnings.warn(message, DeprecationWarning, stacklevel=3)
+
 
 class Transports:
     @staticmethod
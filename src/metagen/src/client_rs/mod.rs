@@ -19,6 +19,7 @@ use crate::*;
 
 use crate::fdk_rs::utils;
 use crate::shared::client::*;
+use crate::shared::types::type_docs;
 
 #[derive(Serialize, Deserialize, Debug, garde::Validate)]
 pub struct ClienRsGenConfig {
@@ -159,7 +160,8 @@ impl crate::Plugin for Generator {
             GeneratorInputResolved::TypegraphFromPath { raw } => raw.clone(),
             _ => bail!("unexpected input type"),
         };
-        let tg = ExpansionConfig::with_default_engines().expand(tg)?;
+        let tg = ExpansionConfig::with_naming_strategy(self.config.base.naming_strategy.clone())?
+            .expand(tg)?;
         let mut out = IndexMap::new();
         info!("building render manifest");
         let manif = RsClientManifest::new(
@@ -321,6 +323,21 @@ pub fn query_graph() -> QueryGraph {{
                 None => "".into(),
             };
 
+            for line in type_docs(ty.base()) {
+                if line.is_empty() {
+                    write!(dest, "\n    ///")?;
+                } else {
+                    write!(dest, "\n    /// {line}")?;
+                }
+            }
+            if let Some(deprecation) = ty.deprecated() {
+                write!(
+                    dest,
+                    "\n    #[deprecated(note = {:?})]",
+                    deprecation.message()
+                )?;
+            }
+
             match select_ty {
                 Some(select_ty) => {
                     let arg_value = match &arg_ty {
@@ -513,6 +530,7 @@ fn e2e() -> anyhow::Result<()> {
 
     let tg_name = "gen-test";
     let config = config::Config {
+        generators: Default::default(),
         targets: [(
             "default".to_string(),
             config::Target(
@@ -528,6 +546,7 @@ fn e2e() -> anyhow::Result<()> {
                             // NOTE: root will map to the test's tempdir
                             path: "./".into(),
                             template_dir: None,
+                            naming_strategy: Default::default(),
                         },
                     })?,
                 }]
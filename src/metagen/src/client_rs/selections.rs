@@ -9,7 +9,11 @@ use super::{
     shared::manifest::{ManifestEntry, ManifestPage},
     utils::*,
 };
-use crate::{interlude::*, shared::client::*};
+use crate::{
+    interlude::*,
+    shared::client::*,
+    shared::types::{field_docs, write_doc_lines},
+};
 
 #[derive(Debug)]
 pub struct UnionProp {
@@ -22,7 +26,8 @@ pub struct UnionProp {
 pub enum RustSelection {
     Struct {
         name: String,
-        props: Vec<(String, SelectionTy)>,
+        /// name, selection and docs of the fields
+        props: Vec<(String, SelectionTy, Vec<String>)>,
     },
     Union {
         name: String,
@@ -58,14 +63,15 @@ impl RustSelection {
         &self,
         dest: &mut impl Write,
         name: &str,
-        props: &[(String, SelectionTy)],
+        props: &[(String, SelectionTy, Vec<String>)],
         page: &ManifestPage<Self, Extras>,
     ) -> std::fmt::Result {
         // derive prop
         writeln!(dest, "#[derive(Default, Debug)]")?;
         writeln!(dest, "pub struct {name}<ATy = NoAlias> {{")?;
 
-        for (name, select_ty) in props {
+        for (name, select_ty, docs) in props {
+            write_doc_lines(dest, docs, "    ///")?;
             use SelectionTy as S;
             match select_ty {
                 S::Scalar => writeln!(dest, r#"    pub {name}: ScalarSelect<ATy>,"#)?,
@@ -93,7 +99,7 @@ impl RustSelection {
         writeln!(dest, "}}")?;
         write!(dest, "impl_selection_traits!({name}, ")?;
         let len = props.len();
-        for (idx, (name, _)) in props.iter().enumerate() {
+        for (idx, (name, ..)) in props.iter().enumerate() {
             if idx < len - 1 {
                 write!(dest, "{name}, ")?;
             } else {
@@ -189,6 +195,7 @@ pub fn manifest_page(
                         (
                             normalize_struct_prop_name(prop_name),
                             selection_for_field(&prop.ty),
+                            field_docs(&prop.ty),
                         )
                         //
                     })
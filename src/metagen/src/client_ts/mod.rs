@@ -6,7 +6,7 @@ mod selections;
 
 use core::fmt::Write;
 
-use fdk_ts::types::{TsType, TsTypesPage};
+use fdk_ts::types::{write_tsdoc, TsType, TsTypesPage};
 use node_metas::TsNodeMeta;
 use selections::TsSelectionManifestPage;
 use shared::manifest::ManifestPage;
@@ -20,6 +20,7 @@ use crate::*;
 
 use crate::fdk_ts::utils;
 use crate::shared::client::*;
+use crate::shared::types::{deprecation_lines, type_docs};
 
 #[derive(Serialize, Deserialize, Debug, garde::Validate)]
 pub struct ClienTsGenConfig {
@@ -130,7 +131,8 @@ impl crate::Plugin for Generator {
             _ => bail!("unexpected input type"),
         };
 
-        let tg = ExpansionConfig::with_default_engines().expand(tg)?;
+        let tg = ExpansionConfig::with_naming_strategy(self.config.base.naming_strategy.clone())?
+            .expand(tg)?;
         let mut out = IndexMap::new();
         info!("building render manifest");
         let manif = TsClientManifest::new(tg.clone(), false)?;
@@ -292,6 +294,15 @@ export class QueryGraph extends _QueryGraphBase {{
                 EffectType::Update | EffectType::Delete | EffectType::Create => "MutationNode",
             };
 
+            let mut docs = type_docs(ty.base());
+            if let Some(deprecation) = ty.deprecated() {
+                docs.extend(deprecation_lines("@deprecated ", &deprecation.message()));
+            }
+            if !docs.is_empty() {
+                let mut buf = String::new();
+                write_tsdoc(&mut buf, &docs, "  ")?;
+                write!(out, "\n{}", buf.trim_end())?;
+            }
             write!(
                 out,
                 r#"
@@ -316,6 +327,7 @@ fn e2e() -> anyhow::Result<()> {
 
     let tg_name = "gen-test";
     let config = config::Config {
+        generators: Default::default(),
         targets: [(
             "default".to_string(),
             config::Target(
@@ -328,6 +340,7 @@ fn e2e() -> anyhow::Result<()> {
                             // NOTE: root will map to the test's tempdir
                             path: "./".into(),
                             template_dir: None,
+                            naming_strategy: Default::default(),
                         },
                     })?,
                 }]
@@ -383,3 +396,97 @@ fn e2e() -> anyhow::Result<()> {
         })?;
     Ok(())
 }
+
+#[test]
+fn naming_strategy() -> anyhow::Result<()> {
+    use crate::tests::*;
+    use tg_schema::*;
+
+    // `MyInput` is duplicated for the function injecting `user`
+    let tg = create_typegraph(
+        "naming".into(),
+        vec![TypeNode::String {
+            data: StringTypeData {
+                format: None,
+                pattern: None,
+                min_length: None,
+                max_length: None,
+            },
+            base: TypeNodeBase {
+                title: "user_id".into(),
+                ..default_type_node_base()
+            },
+        }],
+    )?;
+    let mut tg = Arc::unwrap_or_clone(tg);
+    if let TypeNode::Object { data, .. } = &mut tg.types[2] {
+        data.properties.insert("user".into(), 4);
+    }
+    let mut injected = tg.types[1].clone();
+    if let TypeNode::Function { data, base } = &mut injected {
+        base.title = "MyInjectedFunction".into();
+        data.injections = [(
+            "user".to_string(),
+            InjectionNode::Leaf {
+                injection: Injection::Context(InjectionData::SingleValue(SingleValue {
+                    value: "user.id".into(),
+                })),
+            },
+        )]
+        .into_iter()
+        .collect();
+    }
+    tg.types.push(injected);
+    if let TypeNode::Object { data, .. } = &mut tg.types[0] {
+        data.properties.insert("injected".into(), 5);
+    }
+    let tg = Arc::new(tg);
+
+    let generate = |naming_strategy: serde_json::Value| -> anyhow::Result<String> {
+        let config = ClienTsGenConfig::from_json(
+            serde_json::json!({
+                "typegraph_name": "naming",
+                "path": "./",
+                "naming_strategy": naming_strategy,
+            }),
+            Path::new("/"),
+        )?;
+        let generator = Generator::new(config)?;
+        let out = generator.generate(
+            [(
+                Generator::INPUT_TG.to_string(),
+                GeneratorInputResolved::TypegraphFromTypegate { raw: tg.clone() },
+            )]
+            .into_iter()
+            .collect(),
+        )?;
+        Ok(out.0[&PathBuf::from("/client.ts")].contents.clone())
+    };
+
+    let client = generate(serde_json::json!({}))?;
+    assert!(client.contains("export type MyInput1 = "));
+    assert!(client.contains("export type UserId1 = string;"));
+
+    // variants are named from the canonical form of their injections
+    let client = generate(serde_json::json!({ "stable_variants": true }))?;
+    let strategy = tg_schema::naming::NamingStrategy {
+        stable_variants: true,
+        ..Default::default()
+    };
+    let user_id = fdk_ts::utils::normalize_type_title(&strategy.variant_name(
+        "UserId",
+        1,
+        r#"{"data":"user.id","source":"context"}"#,
+    ));
+    assert!(
+        client.contains(&format!("export type {user_id} = string;")),
+        "{user_id} not found in:\n{client}"
+    );
+    assert!(!client.contains("UserId1"));
+
+    // the case and the affixes are applied by the serialization
+    let err = generate(serde_json::json!({ "prefix": "api" })).unwrap_err();
+    assert!(err.to_string().contains("unsupported prefix"), "{err}");
+
+    Ok(())
+}
@@ -5,8 +5,10 @@ use std::fmt::Write;
 
 use typegraph::TypeNodeExt as _;
 
+use super::fdk_ts::types::write_tsdoc;
 use super::shared::manifest::{ManifestEntry, ManifestPage};
 use super::utils::*;
+use crate::shared::types::field_docs;
 use crate::{interlude::*, shared::client::*};
 
 pub struct TsSelectionExtras {
@@ -25,7 +27,8 @@ pub enum TsSelection {
 #[derive(Debug)]
 pub struct Object {
     name: String,
-    props: Vec<(String, SelectionTy)>,
+    /// name, selection and docs of the fields
+    props: Vec<(String, SelectionTy, Vec<String>)>,
 }
 
 impl Object {
@@ -36,7 +39,8 @@ impl Object {
   _?: SelectionFlags;",
             self.name,
         )?;
-        for (name, select_ty) in &self.props {
+        for (name, select_ty, docs) in &self.props {
+            write_tsdoc(dest, docs, "  ")?;
             use SelectionTy::*;
             match select_ty {
                 Scalar => writeln!(dest, r#"  {name}?: ScalarSelectNoArgs;"#)?,
@@ -162,7 +166,7 @@ pub fn manifest_page(
                 for (prop_name, prop) in ty_props {
                     let prop_name = normalize_struct_prop_name(prop_name);
                     let select_ty = selection_for_field(&prop.ty);
-                    props.push((prop_name, select_ty));
+                    props.push((prop_name, select_ty, field_docs(&prop.ty)));
                 }
                 map.insert(
                     *key,
@@ -192,6 +192,14 @@ export class QueryNode<Out> {
   inner() {
     return this.#inner;
   }
+
+  /**
+   * Receive the results of the query as they are pushed by
+   * the typegate instead of fetching them once.
+   */
+  subscribe(): SubscriptionNode<Out> {
+    return new SubscriptionNode(this.#inner);
+  }
 }
 
 export class MutationNode<Out> {
@@ -205,7 +213,19 @@ export class MutationNode<Out> {
   }
 }
 
-type SelectNodeOut<T> = T extends QueryNode<infer O> | MutationNode<infer O> ? O
+export class SubscriptionNode<Out> {
+  #inner: SelectNode<Out>;
+  constructor(inner: SelectNode<Out>) {
+    this.#inner = inner;
+  }
+
+  inner() {
+    return this.#inner;
+  }
+}
+
+type SelectNodeOut<T> = T extends
+  QueryNode<infer O> | MutationNode<infer O> | SubscriptionNode<infer O> ? O
   : never;
 type QueryOut<T> = T extends
   Record<string, QueryNode<unknown> | MutationNode<unknown>> ? {
@@ -437,6 +457,12 @@ export type GraphQlTransportOptions = Omit<RequestInit, "body"> & {
    * {@link fetch} implementaiton to use. Defaults to the one found in the environment
    */
   fetch?: typeof fetch;
+  /**
+   * Send queries and mutations as automatic persisted queries:
+   * only the hash of the document is sent unless the server asks
+   * for the full text.
+   */
+  persistedQueries?: boolean;
 };
 
 function convertQueryNodeGql(
@@ -518,7 +544,7 @@ function convertQueryNodeGql(
 function buildGql(
   typeToGqlTypeMap: Record<string, string>,
   query: Record<string, SelectNode>,
-  ty: "query" | "mutation",
+  ty: "query" | "mutation" | "subscription",
   // deno-lint-ignore no-inferrable-types
   name: string = "",
 ) {
@@ -553,17 +579,75 @@ function buildGql(
   };
 }
 
+const persistedQueryMissMarkers = [
+  "PersistedQueryNotFound",
+  "PERSISTED_QUERY_NOT_FOUND",
+];
+
+/**
+ * The hex encoded sha256 of the document.
+ */
+async function hashDoc(doc: string) {
+  const digest = await crypto.subtle.digest(
+    "SHA-256",
+    new TextEncoder().encode(doc),
+  );
+  return [...new Uint8Array(digest)]
+    .map((byte) => byte.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+/**
+ * Whether the response to a hash only request asks for the full
+ * document, through a `PersistedQueryNotFound` error. Other errors
+ * are returned as they are.
+ */
+async function isPersistedQueryMiss(res: Response) {
+  const body = await res.clone().json().catch(() => null);
+  const errors = (body?.errors ?? []) as {
+    message?: string;
+    extensions?: { code?: string };
+  }[];
+  return errors.some((err) =>
+    persistedQueryMissMarkers.includes(err?.message ?? "") ||
+    persistedQueryMissMarkers.includes(err?.extensions?.code ?? "")
+  );
+}
+
 async function fetchGql(
   addr: URL,
   doc: string,
   variables: Record<string, unknown>,
   options: GraphQlTransportOptions,
   files?: Map<string, File>,
+  docHash?: () => Promise<string>,
 ) {
-  let body: FormData | string = JSON.stringify({
-    query: doc,
-    variables,
-  });
+  // multipart bodies aren't worth the round trip
+  if (options.persistedQueries && !(files && files.size > 0)) {
+    const hash = await (docHash ?? (() => hashDoc(doc)))();
+    const extensions = { persistedQuery: { version: 1, sha256Hash: hash } };
+    const res = await sendGql(addr, { variables, extensions }, options);
+    if (!(await isPersistedQueryMiss(res))) {
+      return await readGqlResponse(addr, res);
+    }
+    return await readGqlResponse(
+      addr,
+      await sendGql(addr, { query: doc, variables, extensions }, options),
+    );
+  }
+  return await readGqlResponse(
+    addr,
+    await sendGql(addr, { query: doc, variables }, options, files),
+  );
+}
+
+async function sendGql(
+  addr: URL,
+  operations: Record<string, unknown>,
+  options: GraphQlTransportOptions,
+  files?: Map<string, File>,
+) {
+  let body: FormData | string = JSON.stringify(operations);
 
   const additionalHeaders: HeadersInit = {};
 
@@ -609,7 +693,10 @@ async function fetchGql(
     },
     body,
   });
+  return res;
+}
 
+async function readGqlResponse(addr: URL, res: Response) {
   if (!res.ok) {
     const body = await res.text().catch((err) => `error reading body: ${err} `);
     throw new (Error as ErrorPolyfill)(
@@ -633,6 +720,120 @@ async function fetchGql(
   return (await res.json()) as { data: unknown; errors?: object[] };
 }
 
+/**
+ * Read the events of a `text/event-stream` body as they arrive.
+ */
+async function* readSseEvents(
+  body: ReadableStream<Uint8Array>,
+): AsyncGenerator<{ event: string; data: string }> {
+  const reader = body.pipeThrough(new TextDecoderStream()).getReader();
+  let buffer = "";
+  let event = "";
+  let data: string[] = [];
+  try {
+    while (true) {
+      const { value, done } = await reader.read();
+      if (done) {
+        return;
+      }
+      buffer += value;
+      let newline;
+      while ((newline = buffer.indexOf("\n")) >= 0) {
+        const line = buffer.slice(0, newline).replace(/\r$/, "");
+        buffer = buffer.slice(newline + 1);
+        if (line == "") {
+          if (data.length > 0) {
+            yield { event: event || "message", data: data.join("\n") };
+          }
+          event = "";
+          data = [];
+          continue;
+        }
+        if (line.startsWith(":")) {
+          // comments are used as keep-alives
+          continue;
+        }
+        const sep = line.indexOf(":");
+        const field = sep < 0 ? line : line.slice(0, sep);
+        const fieldValue = sep < 0 ? "" : line.slice(sep + 1).replace(/^ /, "");
+        if (field == "event") {
+          event = fieldValue;
+        } else if (field == "data") {
+          data.push(fieldValue);
+        }
+      }
+    }
+  } finally {
+    await reader.cancel();
+  }
+}
+
+/**
+ * Make a subscription request following the graphql-sse protocol
+ * and yield the results as they arrive.
+ */
+async function* subscribeGql(
+  addr: URL,
+  doc: string,
+  variables: Record<string, unknown>,
+  options: GraphQlTransportOptions,
+): AsyncGenerator<unknown> {
+  const fetchImpl = options.fetch ?? fetch;
+  const res = await fetchImpl(addr, {
+    ...options,
+    method: "POST",
+    headers: {
+      "content-type": "application/json",
+      ...(options.headers ?? {}),
+      accept: "text/event-stream",
+    },
+    body: JSON.stringify({ query: doc, variables }),
+  });
+
+  if (!res.ok) {
+    const body = await res.text().catch((err) => `error reading body: ${err} `);
+    throw new (Error as ErrorPolyfill)(
+      `graphql request to ${addr} failed with status ${res.status}: ${body} `,
+      {
+        cause: {
+          response: res,
+          body,
+        },
+      },
+    );
+  }
+
+  const contentType = res.headers.get("content-type") ?? "";
+  if (!contentType.startsWith("text/event-stream") || !res.body) {
+    // errors and servers without support for streaming
+    // respond with a single json body
+    const body = (await res.json()) as { data: unknown; errors?: object[] };
+    if ("errors" in body) {
+      throw new (Error as ErrorPolyfill)("graphql errors on response", {
+        cause: body.errors,
+      });
+    }
+    yield body.data;
+    return;
+  }
+
+  for await (const { event, data } of readSseEvents(res.body)) {
+    if (event == "complete") {
+      return;
+    }
+    if (event != "next" && event != "message") {
+      continue;
+    }
+    const payload = JSON.parse(data) as { data: unknown; errors?: object[] };
+    if ("errors" in payload) {
+      throw new (Error as ErrorPolyfill)("graphql errors on response", {
+        cause: payload.errors,
+      });
+    }
+    yield payload.data;
+  }
+}
+
 /**
  * Access the typegraph over it's exposed GraphQL API.
  */
@@ -648,6 +849,7 @@ export class GraphQLTransport {
     variables: Record<string, unknown>,
     options: GraphQlTransportOptions,
     files?: Map<string, File>,
+    docHash?: () => Promise<string>,
   ) {
     const res = await fetchGql(
       this.address,
@@ -655,6 +857,7 @@ export class GraphQLTransport {
       variables,
       { ...this.options, ...options },
       files,
+      docHash,
     );
     if ("errors" in res) {
       throw new (Error as ErrorPolyfill)("graphql errors on response", {
@@ -736,6 +939,37 @@ export class GraphQLTransport {
     return result as QueryOut<Q>;
   }
 
+  /**
+   * Make a subscription request to the typegraph, the results
+   * are yielded as they are pushed by the typegate. Breaking
+   * out of the loop closes the subscription.
+   */
+  async *subscription<Out>(
+    node: SubscriptionNode<Out>,
+    {
+      options,
+      name = "",
+    }: {
+      options?: GraphQlTransportOptions;
+      name?: string;
+    } = {},
+  ): AsyncGenerator<Out> {
+    const { variables, doc } = buildGql(
+      this.typeToGqlTypeMap,
+      { value: node.inner() },
+      "subscription",
+      name,
+    );
+    for await (
+      const result of subscribeGql(this.address, doc, variables, {
+        ...this.options,
+        ...options,
+      })
+    ) {
+      yield (result as { value: Out }).value;
+    }
+  }
+
   /**
    * Prepare an ahead of time query {@link PreparedRequest}.
    */
@@ -747,7 +981,8 @@ export class GraphQLTransport {
     { name = "" }: { name?: string } = {},
   ): PreparedRequest<T, Q> {
     return new PreparedRequest(
-      (doc, vars, opts) => this.request(doc, vars, opts),
+      (doc, vars, opts, docHash) =>
+        this.request(doc, vars, opts, undefined, docHash),
       this.typeToGqlTypeMap,
       fun,
       "query",
@@ -766,7 +1001,8 @@ export class GraphQLTransport {
     { name = "" }: { name?: string } = {},
   ): PreparedRequest<T, Q> {
     return new PreparedRequest(
-      (doc, vars, opts) => this.request(doc, vars, opts),
+      (doc, vars, opts, docHash) =>
+        this.request(doc, vars, opts, undefined, docHash),
       this.typeToGqlTypeMap,
       fun,
       "mutation",
@@ -809,6 +1045,13 @@ export class HostcallTransport extends GraphQLTransport {
     }
     return res.data;
   }
+
+  // deno-lint-ignore require-yield
+  override async *subscription<Out>(
+    _node: SubscriptionNode<Out>,
+  ): AsyncGenerator<Out> {
+    throw new Error("no support for subscriptions on HostcallTransport");
+  }
 }
 // metagen-endif
 
@@ -825,6 +1068,7 @@ export class PreparedRequest<
     | Record<string, QueryNode<unknown> | MutationNode<unknown>>,
 > {
   public doc: string;
+  #docHash?: Promise<string>;
   #mappings: Record<string, unknown>;
   private singleNode: boolean;
 
@@ -835,6 +1079,7 @@ export class PreparedRequest<
       doc: string,
       variables: Record<string, unknown>,
       opts: GraphQlTransportOptions,
+      docHash: () => Promise<string>,
     ) => Promise<unknown>,
     typeToGqlTypeMap: Record<string, string>,
     fun: (args: PreparedArgs<T>) => Q,
@@ -885,9 +1130,13 @@ export class PreparedRequest<
     //   resolvedVariables,
     //   mapping: this.#mappings,
     // });
-    let result = await this.gqlFn(this.doc, resolvedVariables, {
-      ...opts,
-    });
+    let result = await this.gqlFn(
+      this.doc,
+      resolvedVariables,
+      { ...opts },
+      // only hashed once, when first used as a persisted query
+      () => (this.#docHash ??= hashDoc(this.doc)),
+    );
     if (this.singleNode) {
       result = (result as { value: SelectNodeOut<Q> }).value;
     }
@@ -3,27 +3,42 @@
 
 //!  Sample yaml:
 //!  ```yaml
-//!  generators: # this section only required when we support external generators
-//!     - fdk_rs
-//!     - fdk_ts
-//!     - xgraph_ts
-//!     - name: my_custom
-//!       wasm_module: wasm.io/custom_gen
+//!  generators: # only required for external generators
+//!     my_custom:
+//!       wasm: ./generators/custom_gen.wasm
 //!  targets:
 //!     default:
 //!         # config for any configured generatour under this name
-//!         generator: fdk_rs
-//!         typegraph: console
-//!         path: ./mats/gen
-//!         annotate_debug: true
+//!         - generator: fdk_rs
+//!           typegraph: console
+//!           path: ./mats/gen
+//!           annotate_debug: true
+//!         - generator: my_custom
+//!           typegraph: console
+//!           path: ./mats/custom
 //! ```
 use crate::interlude::*;
+use tg_schema::naming::NamingStrategy;
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct Config {
+    /// External generators, keyed by the name used in the targets.
+    #[serde(default)]
+    pub generators: IndexMap<String, ExternalGeneratorConfig>,
     pub targets: IndexMap<String, Target>,
 }
 
+/// A generator implemented as a wasm component exporting the
+/// `metatype:metagen/generator` interface.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExternalGeneratorConfig {
+    /// Path to the component, relative to the workspace.
+    pub wasm: PathBuf,
+    /// Fuel available to each call into the component, roughly the number
+    /// of wasm instructions it may execute.
+    pub fuel: Option<u64>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct Target(pub Vec<GeneratorConfig>);
 
@@ -50,6 +65,10 @@ pub struct FdkGeneratorConfigBase {
     // TODO validation??
     #[garde(skip)]
     pub template_dir: Option<PathBuf>,
+    /// naming of the duplicated types and resolution of name collisions
+    #[garde(skip)]
+    #[serde(default)]
+    pub naming_strategy: NamingStrategy,
 }
 
 fn either_typegraph_name_or_path(config: &FdkGeneratorConfigBase) -> garde::Result {
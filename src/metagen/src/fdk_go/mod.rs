@@ -0,0 +1,549 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Generates typegraph types and fdk interface for go.
+//! - dir/go.mod
+//!  - Will not be replaced on second generation.
+//! - dir/fdk.go
+//!  - Contains generated types, client and fdk interface.
+//! - dir/wire.go
+//!  - Wires the fdk to the component bindings generated in dir/internal.
+//! - dir/wit/wit-wire.wit
+//!  - The world to generate the component bindings from.
+//! - dir/wit/fdk-go.wit
+//!  - The world to build the component with, including the wasi imports
+//!    of the TinyGo runtime.
+//! - dir/main.go
+//!  - Entrypoint registering the handlers.
+//!  - Will not be replaced on second generation.
+
+mod stubs;
+
+use client_go::types::{GoTypesSubmanifest, OutputTypes};
+use client_go::{GenClientGoOpts, GoClientManifest, GoClientManifestOpts};
+use typegraph::ExpansionConfig;
+
+use crate::interlude::*;
+use crate::shared::*;
+use crate::utils::*;
+use crate::*;
+use std::borrow::Cow;
+use std::fmt::Write;
+
+pub const DEFAULT_TEMPLATE: &[(&str, &str)] = &[("fdk.go", include_str!("static/fdk.go"))];
+
+#[derive(Serialize, Deserialize, Debug, garde::Validate)]
+pub struct FdkGoGenConfig {
+    #[serde(flatten)]
+    #[garde(dive)]
+    pub base: crate::config::FdkGeneratorConfigBase,
+    /// Runtimes to generate stubbed materializer implementations for.
+    #[garde(skip)]
+    pub stubbed_runtimes: Option<Vec<String>>,
+    /// Name of the go package, `main` by default as required by
+    /// the component build
+    #[garde(length(min = 1))]
+    package_name: Option<String>,
+    /// Name of the module to be put in the generated `go.mod`
+    #[garde(length(min = 1))]
+    module_name: Option<String>,
+    #[garde(skip)]
+    pub skip_go_mod: Option<bool>,
+    #[garde(skip)]
+    pub skip_main_go: Option<bool>,
+    #[garde(skip)]
+    pub exclude_client: Option<bool>,
+}
+
+impl FdkGoGenConfig {
+    pub fn from_json(json: serde_json::Value, workspace_path: &Path) -> anyhow::Result<Self> {
+        let mut config: FdkGoGenConfig = serde_json::from_value(json)?;
+        config.base.path = workspace_path.join(config.base.path);
+        config.base.typegraph_path = config
+            .base
+            .typegraph_path
+            .as_ref()
+            .map(|path| workspace_path.join(path));
+        Ok(config)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FdkGoTemplate {
+    fdk_go: Cow<'static, str>,
+}
+
+impl From<FdkTemplate> for FdkGoTemplate {
+    fn from(template: FdkTemplate) -> Self {
+        let mut template = template.entries;
+        Self {
+            fdk_go: template.swap_remove("fdk.go").unwrap(),
+        }
+    }
+}
+
+pub struct Generator {
+    config: FdkGoGenConfig,
+}
+
+impl Generator {
+    pub const INPUT_TG: &'static str = "tg_name";
+    pub fn new(config: FdkGoGenConfig) -> Result<Self, garde::Report> {
+        use garde::Validate;
+        config.validate()?;
+        Ok(Self { config })
+    }
+}
+
+impl crate::Plugin for Generator {
+    fn bill_of_inputs(&self) -> IndexMap<String, GeneratorInputOrder> {
+        [(
+            Self::INPUT_TG.to_string(),
+            if let Some(tg_name) = &self.config.base.typegraph_name {
+                GeneratorInputOrder::TypegraphFromTypegate {
+                    name: tg_name.clone(),
+                }
+            } else if let Some(tg_path) = &self.config.base.typegraph_path {
+                GeneratorInputOrder::TypegraphFromPath {
+                    path: tg_path.clone(),
+                    name: self.config.base.typegraph_name.clone(),
+                }
+            } else {
+                unreachable!()
+            },
+        )]
+        .into_iter()
+        .chain(std::iter::once((
+            "template_dir".to_string(),
+            GeneratorInputOrder::LoadFdkTemplate {
+                default: DEFAULT_TEMPLATE,
+                override_path: self.config.base.template_dir.clone(),
+            },
+        )))
+        .collect()
+    }
+
+    fn generate(
+        &self,
+        mut inputs: IndexMap<String, GeneratorInputResolved>,
+    ) -> anyhow::Result<GeneratorOutput> {
+        let tg = match inputs
+            .swap_remove(Self::INPUT_TG)
+            .context("missing generator input for typegraph")?
+        {
+            GeneratorInputResolved::TypegraphFromTypegate { raw } => raw,
+            GeneratorInputResolved::TypegraphFromPath { raw } => raw,
+            _ => bail!("unexpected generator input variant"),
+        };
+
+        let template: FdkGoTemplate = match inputs
+            .swap_remove("template_dir")
+            .context("missing generator input for template_dir")?
+        {
+            GeneratorInputResolved::FdkTemplate { template } => template.into(),
+            _ => bail!("unexpected generator input variant"),
+        };
+
+        let mut out = IndexMap::new();
+
+        let tg = ExpansionConfig::with_naming_strategy(self.config.base.naming_strategy.clone())?
+            .conservative()
+            .expand(tg)?;
+
+        out.insert(
+            self.config.base.path.join("fdk.go"),
+            GeneratedFile {
+                contents: template.gen_fdk_go(&self.config, tg.clone())?,
+                overwrite: true,
+            },
+        );
+        let module_name = self
+            .config
+            .module_name
+            .clone()
+            .unwrap_or_else(|| client_go::utils::normalize_package_name(tg.name()));
+        out.insert(
+            self.config.base.path.join("wire.go"),
+            GeneratedFile {
+                contents: gen_wire_go(
+                    self.config.package_name.as_deref().unwrap_or("main"),
+                    &module_name,
+                ),
+                overwrite: true,
+            },
+        );
+        out.insert(
+            self.config.base.path.join("wit/wit-wire.wit"),
+            GeneratedFile {
+                contents: include_str!("../../../wit/wit-wire.wit").to_string(),
+                overwrite: true,
+            },
+        );
+        out.insert(
+            self.config.base.path.join("wit/fdk-go.wit"),
+            GeneratedFile {
+                contents: FDK_GO_WIT.to_string(),
+                overwrite: true,
+            },
+        );
+        if !matches!(self.config.skip_go_mod, Some(true)) {
+            out.insert(
+                self.config.base.path.join("go.mod"),
+                GeneratedFile {
+                    contents: client_go::gen_go_mod(&module_name),
+                    overwrite: false,
+                },
+            );
+        }
+        if !matches!(self.config.skip_main_go, Some(true)) {
+            out.insert(
+                self.config.base.path.join("main.go"),
+                GeneratedFile {
+                    contents: gen_main_go(),
+                    overwrite: false,
+                },
+            );
+        }
+        Ok(GeneratorOutput(out))
+    }
+}
+
+#[derive(Debug)]
+struct Maps {
+    inputs: IndexMap<TypeKey, String>,
+    outputs: IndexMap<TypeKey, String>,
+}
+
+impl FdkGoTemplate {
+    fn gen_fdk_go(&self, config: &FdkGoGenConfig, tg: Arc<Typegraph>) -> anyhow::Result<String> {
+        let mut fdk_go = GenDestBuf {
+            buf: Default::default(),
+        };
+        writeln!(
+            &mut fdk_go.buf,
+            "// This file was @generated by metagen and is intended"
+        )?;
+        writeln!(
+            &mut fdk_go.buf,
+            "// to be generated again on subsequent metagen runs."
+        )?;
+        writeln!(&mut fdk_go.buf)?;
+        writeln!(
+            &mut fdk_go.buf,
+            "package {}",
+            config.package_name.as_deref().unwrap_or("main")
+        )?;
+
+        // the import block has to come first, it's either
+        // in the client or in the fdk static
+        let maps = if config.exclude_client.unwrap_or_default() {
+            self.gen_static(&mut fdk_go, config)?;
+
+            info!("building render manifest...");
+            let manifest = GoTypesSubmanifest::new(&tg, &OutputTypes::NonPartial);
+            info!("rendering...");
+            manifest.render_all(&mut fdk_go.buf)?;
+            info!("rendering done successfully");
+
+            Maps {
+                inputs: manifest.inputs.get_cached_refs(),
+                outputs: manifest
+                    .outputs
+                    .as_ref()
+                    .map(|p| p.get_cached_refs())
+                    .unwrap_or_default(),
+            }
+        } else {
+            info!("building render manifest...");
+            let manifest = GoClientManifest::new(
+                tg.clone(),
+                &GoClientManifestOpts {
+                    non_partial_output_types: true,
+                },
+            )?;
+            info!("rendering...");
+            manifest.render_client(&mut fdk_go.buf, &GenClientGoOpts { hostcall: true })?;
+            info!("rendering done successfully");
+
+            self.gen_static(&mut fdk_go, config)?;
+
+            Maps {
+                inputs: manifest.maps.input_types,
+                outputs: manifest.maps.output_types,
+            }
+        };
+
+        writeln!(&mut fdk_go.buf, "// --- --- Stubs --- --- //")?;
+        writeln!(&mut fdk_go.buf)?;
+        let gen_stub_opts = stubs::GenStubOptions {};
+        let stubbed_rts = config
+            .stubbed_runtimes
+            .clone()
+            .unwrap_or_else(|| vec!["wasm_wire".to_string()]);
+        let stubbed_funs = filter_stubbed_funcs(&tg, &stubbed_rts).wrap_err_with(|| {
+            format!("error collecting materializers for runtimes {stubbed_rts:?}")
+        })?;
+        let mut op_to_mat_map = BTreeMap::new();
+        for fun in &stubbed_funs {
+            let trait_name = stubs::gen_stub(fun, &mut fdk_go, &maps, &gen_stub_opts)?;
+            if let Some(Some(op_name)) =
+                fun.materializer.data.get("op_name").map(|val| val.as_str())
+            {
+                op_to_mat_map.insert(op_name.to_string(), trait_name);
+            }
+        }
+        stubs::gen_op_to_mat_map(&op_to_mat_map, &mut fdk_go, &gen_stub_opts)?;
+
+        Ok(fdk_go.buf)
+    }
+
+    fn gen_static(&self, dest: &mut GenDestBuf, config: &FdkGoGenConfig) -> anyhow::Result<()> {
+        let fdk_go = self
+            .fdk_go
+            .replace("__METATYPE_VERSION__", std::env!("CARGO_PKG_VERSION"));
+        let flags = [(
+            "HOSTCALL".to_string(),
+            !config.exclude_client.unwrap_or_default(),
+        )]
+        .into_iter()
+        .collect();
+        processed_write(dest, &fdk_go, &flags)?;
+        writeln!(dest)?;
+        Ok(())
+    }
+}
+
+/// The world built by TinyGo: the runtime imports the wasi interfaces,
+/// see [gen_main_go] for the dependencies
+const FDK_GO_WIT: &str = r#"package metatype:wit-wire;
+
+world fdk-go {
+  include wit-wire;
+  include wasi:cli/imports@0.2.0;
+}
+"#;
+
+/// Glue between the bindings generated by `wit-bindgen-go` and the fdk.
+/// The result shapes of the exports are inferred from the bindings.
+pub fn gen_wire_go(package_name: &str, module_name: &str) -> String {
+    format!(
+        r#"// This file was @generated by metagen and is intended
+// to be generated again on subsequent metagen runs.
+
+package {package_name}
+
+import (
+	"go.bytecodealliance.org/cm"
+
+	matwire "{module_name}/internal/metatype/wit-wire/mat-wire"
+	typegatewire "{module_name}/internal/metatype/wit-wire/typegate-wire"
+)
+
+// Export serves the materializers built by hook through the exports
+// of the wit-wire world, it's to be called from an init function
+func Export(hook func() (*MatBuilder, error)) {{
+	exportInit(&matwire.Exports.Init, hook)
+	exportHandle(&matwire.Exports.Handle)
+}}
+
+func hostcall(opName string, json string) (string, error) {{
+	res := typegatewire.Hostcall(opName, json)
+	if err := res.Err(); err != nil {{
+		return "", HostError(*err)
+	}}
+	return *res.OK(), nil
+}}
+
+func exportInit[Shape any](
+	export *func(matwire.InitArgs) cm.Result[Shape, matwire.InitResponse, matwire.InitError],
+	hook func() (*MatBuilder, error),
+) {{
+	*export = func(args matwire.InitArgs) cm.Result[Shape, matwire.InitResponse, matwire.InitError] {{
+		var expectedOps []MatInfo
+		for _, info := range args.ExpectedOps.Slice() {{
+			expectedOps = append(expectedOps, MatInfo{{
+				OpName:      info.OpName,
+				MatTitle:    info.MatTitle,
+				MatHash:     info.MatHash,
+				MatDataJson: info.MatDataJSON,
+			}})
+		}}
+		res, err := Init(hook, hostcall, InitArgs{{
+			MetatypeVersion: args.MetatypeVersion,
+			ExpectedOps:     expectedOps,
+		}})
+		if err != nil {{
+			var wireErr matwire.InitError
+			switch err.Kind {{
+			case InitErrorVersionMismatch:
+				wireErr = matwire.InitErrorVersionMismatch(err.Version)
+			case InitErrorUnexpectedMat:
+				wireErr = matwire.InitErrorUnexpectedMat(matwire.MatInfo{{
+					OpName:      err.Mat.OpName,
+					MatTitle:    err.Mat.MatTitle,
+					MatHash:     err.Mat.MatHash,
+					MatDataJSON: err.Mat.MatDataJson,
+				}})
+			default:
+				wireErr = matwire.InitErrorOther(err.Message)
+			}}
+			return cm.Err[cm.Result[Shape, matwire.InitResponse, matwire.InitError]](wireErr)
+		}}
+		return cm.OK[cm.Result[Shape, matwire.InitResponse, matwire.InitError]](
+			matwire.InitResponse{{Ok: res.Ok}},
+		)
+	}}
+}}
+
+func exportHandle[Shape any, Out ~string](
+	export *func(matwire.HandleReq) cm.Result[Shape, Out, matwire.HandleErr],
+) {{
+	*export = func(req matwire.HandleReq) cm.Result[Shape, Out, matwire.HandleErr] {{
+		out, err := Handle(HandleReq{{OpName: req.OpName, InJson: string(req.InJSON)}})
+		if err != nil {{
+			var wireErr matwire.HandleErr
+			switch err.Kind {{
+			case HandleErrNoHandler:
+				wireErr = matwire.HandleErrNoHandler()
+			case HandleErrInJson:
+				wireErr = matwire.HandleErrInJSONErr(err.Message)
+			default:
+				wireErr = matwire.HandleErrHandlerErr(err.Message)
+			}}
+			return cm.Err[cm.Result[Shape, Out, matwire.HandleErr]](wireErr)
+		}}
+		return cm.OK[cm.Result[Shape, Out, matwire.HandleErr]](Out(out))
+	}}
+}}
+"#
+    )
+}
+
+pub fn gen_main_go() -> String {
+    r#"package main
+
+// Generate the component bindings into ./internal with:
+//go:generate go run go.bytecodealliance.org/cmd/wit-bindgen-go@v0.6.2 generate --world wit-wire -o internal ./wit/wit-wire.wit
+
+// The component is built with TinyGo against the fdk-go world, that
+// includes the wasi imports of the TinyGo runtime:
+//
+//	root=$(tinygo env TINYGOROOT)
+//	mkdir -p wit/deps/cli
+//	cp -r "$root"/lib/wasi-cli/wit/deps/* wit/deps/
+//	cp "$root"/lib/wasi-cli/wit/*.wit wit/deps/cli/
+//	go mod tidy
+//	tinygo build -target=wasip2 --wit-package ./wit --wit-world fdk-go -o mat.wasm .
+
+func init() {
+	Export(func() (*MatBuilder, error) {
+		// initialize global stuff here if you need it
+		builder := NewMatBuilder()
+		// register function handlers here, e.g.
+		// builder.RegisterHandler(ErasedMyFunc(MyMat{}))
+		return builder, nil
+	})
+}
+
+func main() {}
+
+/*
+type MyMat struct{}
+
+// FIXME: use actual types from your fdk here
+func (MyMat) Handle(input MyFuncIn, cx *Ctx) (MyFuncOut, error) {
+	panic("unimplemented")
+}
+*/
+"#
+    .into()
+}
+
+#[test]
+fn e2e() -> anyhow::Result<()> {
+    use crate::tests::*;
+
+    if !has_toolchain("go")? {
+        eprintln!("skipping fdk_go e2e test: go is not installed");
+        return Ok(());
+    }
+
+    let tg_name = "gen-test";
+    let config = config::Config {
+        generators: Default::default(),
+        targets: [(
+            "default".to_string(),
+            config::Target(
+                [GeneratorConfig {
+                    generator_name: "fdk_go".to_string(),
+                    other: serde_json::to_value(fdk_go::FdkGoGenConfig {
+                        skip_go_mod: None,
+                        skip_main_go: None,
+                        stubbed_runtimes: Some(vec!["wasm_wire".into()]),
+                        package_name: None,
+                        module_name: None,
+                        exclude_client: None,
+                        base: config::FdkGeneratorConfigBase {
+                            typegraph_name: Some(tg_name.into()),
+                            typegraph_path: None,
+                            // NOTE: root will map to the test's tempdir
+                            path: "./".into(),
+                            template_dir: None,
+                            naming_strategy: Default::default(),
+                        },
+                    })?,
+                }]
+                .into_iter()
+                .collect(),
+            ),
+        )]
+        .into_iter()
+        .collect(),
+    };
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_stack_size(16 * 1024 * 1024)
+        .build()?
+        .block_on(async {
+            let tg = test_typegraph_1().await?;
+            e2e_test(vec![E2eTestCase {
+                typegraphs: [(tg_name.to_string(), tg)].into_iter().collect(),
+                target: "default".into(),
+                config,
+                build_fn: |args| {
+                    Box::pin(async move {
+                        // the commands from the generated main.go
+                        let mut steps = vec![
+                            "go generate ./...",
+                            "go mod tidy",
+                            "go vet ./...",
+                        ];
+                        if has_toolchain("tinygo")? {
+                            steps.extend([
+                                r#"root=$(tinygo env TINYGOROOT) && mkdir -p wit/deps/cli && cp -r "$root"/lib/wasi-cli/wit/deps/* wit/deps/ && cp "$root"/lib/wasi-cli/wit/*.wit wit/deps/cli/"#,
+                                "tinygo build -target=wasip2 --wit-package ./wit --wit-world fdk-go -o mat.wasm .",
+                            ]);
+                        } else {
+                            eprintln!("skipping the fdk_go component build: tinygo is not installed");
+                        }
+                        for step in steps {
+                            let status = tokio::process::Command::new("sh")
+                                .args(["-c", step])
+                                .current_dir(&args.path)
+                                .kill_on_drop(true)
+                                .spawn()?
+                                .wait()
+                                .await?;
+                            if !status.success() {
+                                anyhow::bail!("error building generated package: {step}");
+                            }
+                        }
+                        Ok(())
+                    })
+                },
+                target_dir: None,
+            }])
+            .await
+        })?;
+    Ok(())
+}
@@ -0,0 +1,264 @@
+// metagen-skip
+package main
+
+// metagen-genif-not HOSTCALL
+import (
+	"encoding/json"
+	"fmt"
+)
+
+// HostcallBinding calls into the typegate, e.g. through the `hostcall`
+// import of the wit-wire world
+type HostcallBinding func(opName string, json string) (string, error)
+
+// HostError is the error returned by the typegate on hostcalls, as JSON
+type HostError string
+
+func (e HostError) Error() string {
+	return "host error: " + string(e)
+}
+
+// File uploads are not available to materializers
+type File struct{}
+
+// metagen-endif
+
+// metagen-genif IGNORE
+// these are stubs to items that come from client.go and the stubs
+type QueryGraph struct{}
+
+func NewQueryGraph() *QueryGraph {
+	return &QueryGraph{}
+}
+
+type HostcallTransport struct{}
+
+func NewHostcallTransport(_ *QueryGraph, _ HostcallBinding) *HostcallTransport {
+	return &HostcallTransport{}
+}
+
+func opToTraitName(opName string) string {
+	return ""
+}
+
+// metagen-endif
+
+// --- --- FDK --- --- //
+
+const MetatypeVersion = "__METATYPE_VERSION__"
+
+// MatInfo mirrors the `mat-info` record of the wit-wire world
+type MatInfo struct {
+	OpName      string
+	MatTitle    string
+	MatHash     string
+	MatDataJson string
+}
+
+// InitArgs mirrors the `init-args` record of the wit-wire world
+type InitArgs struct {
+	MetatypeVersion string
+	ExpectedOps     []MatInfo
+}
+
+// InitResponse mirrors the `init-response` record of the wit-wire world
+type InitResponse struct {
+	Ok bool
+}
+
+type InitErrorKind int
+
+const (
+	InitErrorVersionMismatch InitErrorKind = iota
+	InitErrorUnexpectedMat
+	InitErrorOther
+)
+
+// InitError mirrors the `init-error` variant of the wit-wire world
+type InitError struct {
+	Kind InitErrorKind
+	// set on InitErrorVersionMismatch
+	Version string
+	// set on InitErrorUnexpectedMat
+	Mat MatInfo
+	// set on InitErrorOther
+	Message string
+}
+
+func (e *InitError) Error() string {
+	switch e.Kind {
+	case InitErrorVersionMismatch:
+		return fmt.Sprintf("version mismatch: fdk was generated for %s", e.Version)
+	case InitErrorUnexpectedMat:
+		return fmt.Sprintf("no handler registered for op %q", e.Mat.OpName)
+	default:
+		return e.Message
+	}
+}
+
+// HandleReq mirrors the `handle-req` record of the wit-wire world
+type HandleReq struct {
+	OpName string
+	InJson string
+}
+
+type HandleErrKind int
+
+const (
+	HandleErrNoHandler HandleErrKind = iota
+	HandleErrInJson
+	HandleErrHandler
+)
+
+// HandleErr mirrors the `handle-err` variant of the wit-wire world
+type HandleErr struct {
+	Kind HandleErrKind
+	// set on HandleErrInJson and HandleErrHandler
+	Message string
+}
+
+func (e *HandleErr) Error() string {
+	switch e.Kind {
+	case HandleErrNoHandler:
+		return "no handler"
+	case HandleErrInJson:
+		return "invalid input json: " + e.Message
+	default:
+		return "handler error: " + e.Message
+	}
+}
+
+type HandlerFn func(inJSON string, cx *Ctx) (string, *HandleErr)
+
+type ErasedHandler struct {
+	MatId     string
+	MatTitle  string
+	MatTrait  string
+	HandlerFn HandlerFn
+}
+
+func erasedHandler[In, Out any](
+	matId string,
+	matTitle string,
+	matTrait string,
+	handle func(In, *Ctx) (Out, error),
+) ErasedHandler {
+	return ErasedHandler{
+		MatId:    matId,
+		MatTitle: matTitle,
+		MatTrait: matTrait,
+		HandlerFn: func(inJSON string, cx *Ctx) (string, *HandleErr) {
+			var input In
+			if err := json.Unmarshal([]byte(inJSON), &input); err != nil {
+				return "", &HandleErr{Kind: HandleErrInJson, Message: err.Error()}
+			}
+			out, err := handle(input, cx)
+			if err != nil {
+				return "", &HandleErr{Kind: HandleErrHandler, Message: err.Error()}
+			}
+			res, err := json.Marshal(out)
+			if err != nil {
+				return "", &HandleErr{Kind: HandleErrHandler, Message: err.Error()}
+			}
+			return string(res), nil
+		},
+	}
+}
+
+type MatBuilder struct {
+	handlers map[string]ErasedHandler
+}
+
+func NewMatBuilder() *MatBuilder {
+	return &MatBuilder{handlers: map[string]ErasedHandler{}}
+}
+
+func (b *MatBuilder) RegisterHandler(handler ErasedHandler) *MatBuilder {
+	b.handlers[handler.MatTrait] = handler
+	return b
+}
+
+type Router struct {
+	handlers map[string]ErasedHandler
+	binding  HostcallBinding
+}
+
+func NewRouter(builder *MatBuilder, binding HostcallBinding) *Router {
+	return &Router{handlers: builder.handlers, binding: binding}
+}
+
+func (r *Router) Init(args InitArgs) (InitResponse, *InitError) {
+	if args.MetatypeVersion != MetatypeVersion {
+		return InitResponse{}, &InitError{Kind: InitErrorVersionMismatch, Version: MetatypeVersion}
+	}
+	for _, info := range args.ExpectedOps {
+		if _, ok := r.handlers[opToTraitName(info.OpName)]; !ok {
+			return InitResponse{}, &InitError{Kind: InitErrorUnexpectedMat, Mat: info}
+		}
+	}
+	return InitResponse{Ok: true}, nil
+}
+
+func (r *Router) Handle(req HandleReq) (string, *HandleErr) {
+	handler, ok := r.handlers[opToTraitName(req.OpName)]
+	if !ok {
+		return "", &HandleErr{Kind: HandleErrNoHandler}
+	}
+	cx := &Ctx{binding: r.binding}
+	// metagen-genif HOSTCALL
+	cx.Qg = NewQueryGraph()
+	cx.Host = NewHostcallTransport(cx.Qg, r.binding)
+	// metagen-endif
+	return handler.HandlerFn(req.InJson, cx)
+}
+
+var matState *Router
+
+// Init is to be called from the `init` export of the wit-wire world
+func Init(
+	hook func() (*MatBuilder, error),
+	binding HostcallBinding,
+	args InitArgs,
+) (InitResponse, *InitError) {
+	builder, err := hook()
+	if err != nil {
+		return InitResponse{}, &InitError{Kind: InitErrorOther, Message: err.Error()}
+	}
+	router := NewRouter(builder, binding)
+	res, initErr := router.Init(args)
+	if initErr != nil {
+		return res, initErr
+	}
+	matState = router
+	return res, nil
+}
+
+// Handle is to be called from the `handle` export of the wit-wire world
+func Handle(req HandleReq) (string, *HandleErr) {
+	if matState == nil {
+		return "", &HandleErr{Kind: HandleErrHandler, Message: "materializer has not been initialized"}
+	}
+	return matState.Handle(req)
+}
+
+type Ctx struct {
+	binding HostcallBinding
+	// metagen-genif HOSTCALL
+	Qg   *QueryGraph
+	Host *HostcallTransport
+	// metagen-endif
+}
+
+// Gql runs a raw graphql query against the typegraph and decodes
+// the `data` of the response into out
+func (cx *Ctx) Gql(query string, variables map[string]any, out any) error {
+	payload, err := json.Marshal(map[string]any{"query": query, "variables": variables})
+	if err != nil {
+		return err
+	}
+	res, err := cx.binding("gql", string(payload))
+	if err != nil {
+		return err
+	}
+	return json.Unmarshal([]byte(res), out)
+}
@@ -0,0 +1,68 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use typegraph::FunctionType;
+use typegraph::TypeNodeExt as _;
+
+use crate::client_go::utils::normalize_type_title;
+use crate::interlude::*;
+use crate::shared::types::{type_docs, write_doc_lines};
+use crate::utils::*;
+use std::fmt::Write;
+
+pub struct GenStubOptions {}
+
+pub fn gen_stub(
+    fun: &Arc<FunctionType>,
+    dest: &mut GenDestBuf,
+    maps: &super::Maps,
+    _opts: &GenStubOptions,
+) -> anyhow::Result<String> {
+    let inp_ty = maps
+        .inputs
+        .get(&fun.input().key())
+        .map(|s| s.as_str())
+        .unwrap_or("struct{}");
+    let out_ty = maps.outputs.get(&fun.output().key()).unwrap();
+    let title = &fun.name();
+    let trait_name: String = normalize_type_title(title);
+    // FIXME: use hash or other stable id
+    let id = title;
+    write_doc_lines(&mut dest.buf, &type_docs(fun.base()), "//")?;
+    writeln!(
+        &mut dest.buf,
+        r#"type {trait_name}Handler interface {{
+	Handle(input {inp_ty}, cx *Ctx) ({out_ty}, error)
+}}
+
+func Erased{trait_name}(handler {trait_name}Handler) ErasedHandler {{
+	return erasedHandler({id:?}, {title:?}, {trait_name:?}, handler.Handle)
+}}
+"#
+    )?;
+    Ok(trait_name)
+}
+
+pub fn gen_op_to_mat_map(
+    op_to_trait_map: &BTreeMap<String, String>,
+    dest: &mut GenDestBuf,
+    _opts: &GenStubOptions,
+) -> anyhow::Result<()> {
+    writeln!(
+        &mut dest.buf,
+        r#"func opToTraitName(opName string) string {{
+	switch opName {{"#
+    )?;
+    for (op_name, trait_name) in op_to_trait_map {
+        writeln!(&mut dest.buf, "\tcase {op_name:?}:")?;
+        writeln!(&mut dest.buf, "\t\treturn {trait_name:?}")?;
+    }
+    writeln!(
+        &mut dest.buf,
+        r#"	default:
+		return ""
+	}}
+}}"#
+    )?;
+    Ok(())
+}
@@ -5,12 +5,13 @@ use core::fmt::Write;
 use std::borrow::Cow;
 
 use crate::interlude::*;
+use crate::shared::types::type_docs;
 use crate::shared::*;
 use crate::utils::GenDestBuf;
 use crate::*;
 use client_py::ClientPyManifest;
 use typegraph::ExpansionConfig;
-use types::PyTypesPage;
+use types::{write_docstring, PyTypesPage};
 
 pub mod types;
 
@@ -127,11 +128,13 @@ impl FdkPythonTemplate {
                 let out_ty = ty_name_memo
                     .get(&fun.output().key())
                     .ok_or_eyre("output type for function not found")?;
+                let mut docstring = String::new();
+                write_docstring(&mut docstring, &type_docs(fun.base()), "    ")?;
                 writeln!(
                     &mut fdk_py,
                     r#"
 def handler_{def_name}(user_fn: typing.Callable[[{inp_ty}, Ctx], {out_ty}]):
-    def wrapper(raw_inp, gql_fn):
+{docstring}    def wrapper(raw_inp, gql_fn):
         qg = QueryGraph()
         host = Transports.hostcall(qg, gql_fn)
         cx = Ctx(gql_fn, qg, host)
@@ -208,7 +211,7 @@ impl crate::Plugin for Generator {
         };
 
         let mut out = IndexMap::new();
-        let tg = ExpansionConfig::with_default_engines()
+        let tg = ExpansionConfig::with_naming_strategy(self.config.base.naming_strategy.clone())?
             .conservative()
             .expand(tg)?;
         out.insert(
@@ -229,6 +232,7 @@ fn e2e() -> anyhow::Result<()> {
 
     let tg_name = "gen-test";
     let config = config::Config {
+        generators: Default::default(),
         targets: [(
             "default".to_string(),
             config::Target(
@@ -243,6 +247,7 @@ fn e2e() -> anyhow::Result<()> {
                             // NOTE: root will map to the test's tempdir
                             path: "./".into(),
                             template_dir: None,
+                            naming_strategy: Default::default(),
                         },
                     })?,
                 }]
@@ -10,9 +10,29 @@ use indexmap::IndexMap;
 use super::{
     fdk_rs::utils::{normalize_struct_prop_name, normalize_type_title},
     manifest::{ManifestEntry, ManifestPage},
-    shared::types::type_body_required,
+    shared::types::{
+        deprecated_values, deprecation_lines, field_deprecation, field_docs, type_body_required,
+        type_docs, write_doc_lines,
+    },
 };
 
+/// Write documentation lines as a docstring at the given indentation
+pub fn write_docstring(out: &mut impl Write, lines: &[String], indent: &str) -> std::fmt::Result {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, r#"{indent}""""#)?;
+    for line in lines {
+        if line.is_empty() {
+            writeln!(out)?;
+        } else {
+            let line = line.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(out, "{indent}{line}")?;
+        }
+    }
+    writeln!(out, r#"{indent}""""#)
+}
+
 #[derive(Debug)]
 pub enum AliasTarget {
     BuiltIn(&'static str),
@@ -25,6 +45,7 @@ pub struct Alias {
     target: AliasTarget,
     container: Option<&'static str>,
     quote: bool,
+    docs: Vec<String>,
 }
 
 impl From<AliasTarget> for Alias {
@@ -34,6 +55,7 @@ impl From<AliasTarget> for Alias {
             target,
             container: None,
             quote: false,
+            docs: vec![],
         }
     }
 }
@@ -64,6 +86,7 @@ impl Alias {
 impl Alias {
     fn render(&self, dest: &mut impl Write, page: &PyTypesPage) -> std::fmt::Result {
         if let Some(alias_name) = &self.name {
+            write_doc_lines(dest, &self.docs, "#")?;
             let aliased_ty = self.target_name(page, self.container);
             writeln!(dest, "{alias_name} = {aliased_ty}")?;
             writeln!(dest)?;
@@ -97,17 +120,22 @@ struct ObjectProperty {
     ty: TypeKey,
     optional: bool,
     quoted: bool,
+    /// documented only: the keys of a `TypedDict` can't emit a `DeprecationWarning`
+    deprecated: Option<String>,
+    docs: Vec<String>,
 }
 
 #[derive(Debug)]
 pub struct Object {
     name: String,
     props: Vec<ObjectProperty>,
+    docs: Vec<String>,
 }
 
 impl Object {
     fn render(&self, dest: &mut impl Write, page: &PyTypesPage) -> std::fmt::Result {
         let name = &self.name;
+        write_doc_lines(dest, &self.docs, "#")?;
         writeln!(dest, r#"{name} = typing.TypedDict("{name}", {{"#)?;
         for prop in self.props.iter() {
             let ty_ref = page.get_quoted_ref(&prop.ty);
@@ -117,6 +145,10 @@ impl Object {
                 ty_ref
             };
             let prop_key = &prop.name;
+            write_doc_lines(dest, &prop.docs, "    #")?;
+            if let Some(message) = &prop.deprecated {
+                write_doc_lines(dest, &deprecation_lines("deprecated: ", message), "    #")?;
+            }
             // FIXME: NotRequired is only avail on python 3.11
             if prop.optional {
                 // writeln!(dest, r#"    "{name}": typing.NotRequired[{ty_name}],"#)?;
@@ -150,10 +182,23 @@ fn render_union(
 pub struct LiteralEnum {
     name: String,
     variants: Vec<String>,
+    deprecated: Vec<(String, String)>,
+    docs: Vec<String>,
 }
 
 impl LiteralEnum {
     fn render(&self, dest: &mut impl Write) -> std::fmt::Result {
+        write_doc_lines(dest, &self.docs, "#")?;
+        if !self.deprecated.is_empty() {
+            writeln!(dest, "# deprecated values:")?;
+            for (value, message) in &self.deprecated {
+                write_doc_lines(
+                    dest,
+                    &deprecation_lines(&format!("- {value}: "), message),
+                    "#",
+                )?;
+            }
+        }
         render_union(
             dest,
             &self.name,
@@ -166,10 +211,12 @@ impl LiteralEnum {
 pub struct Union {
     name: String,
     variants: Vec<TypeKey>,
+    docs: Vec<String>,
 }
 
 impl Union {
     fn render(&self, dest: &mut impl Write, page: &PyTypesPage) -> std::fmt::Result {
+        write_doc_lines(dest, &self.docs, "#")?;
         render_union(
             dest,
             &self.name,
@@ -237,6 +284,22 @@ impl ManifestEntry for PyType {
 
 impl From<&Type> for PyType {
     fn from(ty: &Type) -> Self {
+        PyType::new(ty).with_docs(type_docs(ty.base()))
+    }
+}
+
+impl PyType {
+    fn with_docs(mut self, new_docs: Vec<String>) -> Self {
+        match &mut self {
+            PyType::Alias(Alias { docs, .. })
+            | PyType::LiteralEnum(LiteralEnum { docs, .. })
+            | PyType::Object(Object { docs, .. })
+            | PyType::Union(Union { docs, .. }) => *docs = new_docs,
+        }
+        self
+    }
+
+    fn new(ty: &Type) -> Self {
         let alias_required = type_body_required(ty);
         match ty {
             Type::Function(_) => unreachable!(),
@@ -261,6 +324,8 @@ impl From<&Type> for PyType {
                     return PyType::LiteralEnum(LiteralEnum {
                         name: ty_name,
                         variants: enum_variants.iter().map(|v| v.to_string()).collect(),
+                        deprecated: deprecated_values(ty),
+                        docs: vec![],
                     });
                 }
                 if let (Some(format), true) = (ty.format_only(), ty.title().starts_with("string_"))
@@ -295,12 +360,15 @@ impl From<&Type> for PyType {
                             ty: prop.ty.key(),
                             optional,
                             quoted,
+                            deprecated: field_deprecation(&prop.ty),
+                            docs: field_docs(&prop.ty),
                         }
                     })
                     .collect();
                 PyType::Object(Object {
                     name: normalize_type_title(&ty.name()),
                     props,
+                    docs: vec![],
                 })
             }
             Type::Union(ty) => {
@@ -308,6 +376,7 @@ impl From<&Type> for PyType {
                 PyType::Union(Union {
                     name: normalize_type_title(&ty.name()),
                     variants,
+                    docs: vec![],
                 })
             }
             Type::Optional(ty) if ty.title().starts_with("optional_") => {
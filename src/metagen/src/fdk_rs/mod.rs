@@ -141,7 +141,7 @@ impl crate::Plugin for Generator {
 
         let mut out = IndexMap::new();
 
-        let tg = ExpansionConfig::with_default_engines()
+        let tg = ExpansionConfig::with_naming_strategy(self.config.base.naming_strategy.clone())?
             .conservative()
             .expand(tg)?;
 
@@ -412,6 +412,7 @@ fn e2e() -> anyhow::Result<()> {
 
     let tg_name = "gen-test";
     let config = config::Config {
+        generators: Default::default(),
         targets: [(
             "default".to_string(),
             config::Target(
@@ -429,6 +430,7 @@ fn e2e() -> anyhow::Result<()> {
                             // NOTE: root will map to the test's tempdir
                             path: "./".into(),
                             template_dir: None,
+                            naming_strategy: Default::default(),
                         },
                     })?,
                 }]
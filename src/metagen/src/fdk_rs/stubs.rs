@@ -6,6 +6,7 @@ use typegraph::TypeNodeExt as _;
 
 use super::utils::normalize_type_title;
 use crate::interlude::*;
+use crate::shared::types::{type_docs, write_doc_lines};
 use crate::utils::*;
 use std::fmt::Write;
 
@@ -27,6 +28,7 @@ pub fn gen_stub(
     let trait_name: String = normalize_type_title(title);
     // FIXME: use hash or other stable id
     let id = title;
+    write_doc_lines(&mut mod_stub_traits.buf, &type_docs(fun.base()), "///")?;
     writeln!(
         &mut mod_stub_traits.buf,
         r#"pub trait {trait_name}: Sized + 'static {{
@@ -182,6 +184,7 @@ mod test {
                 typegraph_name: Some(tg_name.clone()),
                 typegraph_path: None,
                 template_dir: None,
+                naming_strategy: Default::default(),
             },
             stubbed_runtimes: Some(vec!["wasm".into()]),
             crate_name: None,
@@ -4,7 +4,10 @@
 use super::manifest::{ManifestEntry, ManifestPage};
 use super::{indent_lines_into, utils::*};
 use crate::interlude::*;
-use crate::shared::types::type_body_required;
+use crate::shared::types::{
+    deprecation_lines, field_deprecation, field_docs, type_body_required, type_docs,
+    write_doc_lines,
+};
 use heck::ToPascalCase as _;
 use std::fmt::Write;
 
@@ -33,18 +36,21 @@ pub enum RustType {
         alias: Alias,
         /// inlined if name is none
         name: Option<String>,
+        docs: Vec<String>,
     },
     Struct {
         name: String,
         derive: Derive,
         properties: Vec<StructProp>,
         partial: bool,
+        docs: Vec<String>,
     },
     Enum {
         name: String,
         derive: Derive,
         variants: Vec<(String, TypeKey)>,
         partial: bool,
+        docs: Vec<String>,
     },
 }
 
@@ -55,6 +61,9 @@ pub struct StructProp {
     ty: TypeKey,
     optional: bool,
     boxed: bool,
+    /// documented only: `#[deprecated]` would warn on the derived impls
+    deprecated: Option<String>,
+    docs: Vec<String>,
 }
 
 impl ManifestEntry for RustType {
@@ -65,9 +74,10 @@ impl ManifestEntry for RustType {
             Self::Alias {
                 alias,
                 name: alias_name,
-                ..
+                docs,
             } => {
                 if let Some(alias_name) = alias_name {
+                    write_doc_lines(out, docs, "///")?;
                     match alias {
                         Alias::BuiltIn(name) => {
                             writeln!(out, "pub type {} = {};", alias_name, name)
@@ -101,7 +111,9 @@ impl ManifestEntry for RustType {
                 derive,
                 properties,
                 partial,
+                docs,
             } => {
+                write_doc_lines(out, docs, "///")?;
                 RustType::render_derive(out, derive)?;
                 let name = if *partial {
                     format!("{}Partial", name)
@@ -110,6 +122,17 @@ impl ManifestEntry for RustType {
                 };
                 writeln!(out, "pub struct {} {{", name)?;
                 for prop in properties.iter() {
+                    write_doc_lines(out, &prop.docs, "    ///")?;
+                    if let Some(message) = &prop.deprecated {
+                        if !prop.docs.is_empty() {
+                            writeln!(out, "    ///")?;
+                        }
+                        write_doc_lines(
+                            out,
+                            &deprecation_lines("Deprecated: ", message),
+                            "    ///",
+                        )?;
+                    }
                     if let Some(rename) = &prop.rename {
                         writeln!(out, r#"    #[serde(rename = "{}")]"#, rename)?;
                     }
@@ -130,12 +153,14 @@ impl ManifestEntry for RustType {
                 derive,
                 variants,
                 partial,
+                docs,
             } => {
                 let name = if *partial {
                     format!("{}Partial", name)
                 } else {
                     name.clone()
                 };
+                write_doc_lines(out, docs, "///")?;
                 RustType::render_derive(out, derive)?;
                 writeln!(out, "#[allow(clippy::large_enum_variant)]")?;
                 writeln!(out, "#[serde(untagged)]")?;
@@ -150,7 +175,7 @@ impl ManifestEntry for RustType {
 
     fn get_reference_expr(&self, page: &RustTypeManifestPage) -> Option<String> {
         Some(match self {
-            Self::Alias { name, alias } => {
+            Self::Alias { name, alias, .. } => {
                 if let Some(name) = name {
                     name.clone()
                 } else {
@@ -223,6 +248,7 @@ impl RustType {
         RustType::Alias {
             alias: Alias::BuiltIn(target),
             name,
+            docs: vec![],
         }
     }
 
@@ -239,7 +265,17 @@ impl RustType {
                 boxed,
             },
             name,
+            docs: vec![],
+        }
+    }
+
+    fn with_docs(mut self, new_docs: Vec<String>) -> RustType {
+        match &mut self {
+            RustType::Alias { docs, .. }
+            | RustType::Struct { docs, .. }
+            | RustType::Enum { docs, .. } => *docs = new_docs,
         }
+        self
     }
 
     fn new(ty: &Type, partial: bool) -> RustType {
@@ -329,6 +365,8 @@ impl RustType {
                                 ty: prop.ty.key(),
                                 optional,
                                 boxed,
+                                deprecated: field_deprecation(&prop.ty),
+                                docs: field_docs(&prop.ty),
                             }
                         })
                         .collect();
@@ -340,6 +378,7 @@ impl RustType {
                         },
                         properties: props,
                         partial,
+                        docs: vec![],
                     }
                 }
 
@@ -357,11 +396,13 @@ impl RustType {
                         },
                         variants,
                         partial,
+                        docs: vec![],
                     }
                 }
 
                 Type::Function(_) => unreachable!("unexpected function type"),
             }
+            .with_docs(type_docs(ty.base()))
         } else {
             RustType::builtin(
                 match ty {
@@ -487,7 +528,14 @@ impl RustTypesSubmanifest {
                 let alias = Alias::Plain {
                     name: inp_ref.clone(),
                 };
-                map.insert(*key, RustType::Alias { alias, name: None });
+                map.insert(
+                    *key,
+                    RustType::Alias {
+                        alias,
+                        name: None,
+                        docs: vec![],
+                    },
+                );
             } else {
                 map.insert(*key, RustType::new(ty, false));
             }
@@ -512,7 +560,14 @@ impl RustTypesSubmanifest {
                     let alias = Alias::Plain {
                         name: inp_ref.clone(),
                     };
-                    map.insert(*key, RustType::Alias { alias, name: None });
+                    map.insert(
+                        *key,
+                        RustType::Alias {
+                            alias,
+                            name: None,
+                            docs: vec![],
+                        },
+                    );
                     continue;
                 }
 
@@ -521,7 +576,14 @@ impl RustTypesSubmanifest {
                     let alias = Alias::Plain {
                         name: out_ref.clone(),
                     };
-                    map.insert(*key, RustType::Alias { alias, name: None });
+                    map.insert(
+                        *key,
+                        RustType::Alias {
+                            alias,
+                            name: None,
+                            docs: vec![],
+                        },
+                    );
                     continue;
                 }
 
@@ -1012,6 +1074,160 @@ pub struct ObjB {
 pub enum CEither {
     ObjA(ObjA),
 }
+"#,
+            ),
+            (
+                "docs",
+                vec![
+                    TypeNode::String {
+                        data: StringTypeData {
+                            format: None,
+                            pattern: None,
+                            min_length: None,
+                            max_length: None,
+                        },
+                        base: TypeNodeBase {
+                            title: "string_0".into(),
+                            description: Some("Name of the thing".into()),
+                            ..default_type_node_base()
+                        },
+                    },
+                    TypeNode::Object {
+                        data: ObjectTypeData {
+                            properties: [("name".to_string(), 4)].into_iter().collect(),
+                            policies: Default::default(),
+                            id: vec![],
+                            required: ["name"].into_iter().map(Into::into).collect(),
+                            additional_props: false,
+                        },
+                        base: TypeNodeBase {
+                            title: "my_obj".into(),
+                            description: Some("A thing".into()),
+                            ..default_type_node_base()
+                        },
+                    },
+                ],
+                "MyObj",
+                r#"/// A thing
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct MyObj {
+    /// Name of the thing
+    pub name: String,
+}
+"#,
+            ),
+            (
+                "formats",
+                vec![
+                    TypeNode::String {
+                        data: StringTypeData {
+                            format: Some(StringFormat::Ipv4),
+                            pattern: None,
+                            min_length: None,
+                            max_length: None,
+                        },
+                        base: TypeNodeBase {
+                            title: "string_ipv4_0".into(),
+                            ..default_type_node_base()
+                        },
+                    },
+                    TypeNode::String {
+                        data: StringTypeData {
+                            format: Some(StringFormat::CountryCode),
+                            pattern: None,
+                            min_length: None,
+                            max_length: None,
+                        },
+                        base: TypeNodeBase {
+                            title: "string_country_code_1".into(),
+                            ..default_type_node_base()
+                        },
+                    },
+                    TypeNode::String {
+                        data: StringTypeData {
+                            format: Some(StringFormat::Custom("postal-code".into())),
+                            pattern: None,
+                            min_length: None,
+                            max_length: None,
+                        },
+                        base: TypeNodeBase {
+                            title: "string_custom_2".into(),
+                            ..default_type_node_base()
+                        },
+                    },
+                    TypeNode::Object {
+                        data: ObjectTypeData {
+                            properties: [
+                                ("ip".to_string(), 4),
+                                ("country".to_string(), 5),
+                                ("postal_code".to_string(), 6),
+                            ]
+                            .into_iter()
+                            .collect(),
+                            policies: Default::default(),
+                            id: vec![],
+                            required: vec![],
+                            additional_props: false,
+                        },
+                        base: TypeNodeBase {
+                            title: "my_obj".into(),
+                            ..default_type_node_base()
+                        },
+                    },
+                ],
+                "MyObj",
+                r#"pub type StringIpv44 = String;
+pub type StringCountryCode5 = String;
+pub type StringPostalCode6 = String;
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct MyObj {
+    pub ip: StringIpv44,
+    pub country: StringCountryCode5,
+    pub postal_code: StringPostalCode6,
+}
+"#,
+            ),
+            (
+                "deprecation",
+                vec![
+                    TypeNode::String {
+                        data: StringTypeData {
+                            format: None,
+                            pattern: None,
+                            min_length: None,
+                            max_length: None,
+                        },
+                        base: TypeNodeBase {
+                            title: "string_0".into(),
+                            deprecated: Some(Deprecation {
+                                reason: Some("use `name`\nwill be removed soon".into()),
+                                since: None,
+                                removal: None,
+                            }),
+                            ..default_type_node_base()
+                        },
+                    },
+                    TypeNode::Object {
+                        data: ObjectTypeData {
+                            properties: [("nickname".to_string(), 4)].into_iter().collect(),
+                            policies: Default::default(),
+                            id: vec![],
+                            required: ["nickname"].into_iter().map(Into::into).collect(),
+                            additional_props: false,
+                        },
+                        base: TypeNodeBase {
+                            title: "my_obj".into(),
+                            ..default_type_node_base()
+                        },
+                    },
+                ],
+                "MyObj",
+                r#"#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct MyObj {
+    /// Deprecated: use `name`
+    /// will be removed soon
+    pub nickname: String,
+}
 "#,
             ),
         ];
@@ -14,6 +14,7 @@ use typegraph::TypeNodeExt as _;
 use types::TsTypesPage;
 
 use crate::interlude::*;
+use crate::shared::types::type_docs;
 use crate::shared::*;
 use crate::utils::processed_write;
 use crate::*;
@@ -124,6 +125,7 @@ impl FdkTypescriptTemplate {
                 let inp_ty = map.get(&fun.input().key().original()).unwrap();
                 let out_ty = map.get(&fun.output().key()).unwrap();
                 let type_name: String = utils::normalize_type_title(&fun.name());
+                types::write_tsdoc(&mut fdk_ts, &type_docs(fun.base()), "")?;
                 writeln!(
                     &mut fdk_ts,
                     "export type {type_name}Handler = Handler<{inp_ty}, {out_ty}>;"
@@ -189,7 +191,7 @@ impl crate::Plugin for Generator {
             _ => unreachable!(),
         };
 
-        let tg = ExpansionConfig::with_default_engines()
+        let tg = ExpansionConfig::with_naming_strategy(self.config.base.naming_strategy.clone())?
             .conservative()
             .expand(tg)?;
 
@@ -212,6 +214,7 @@ fn e2e() -> anyhow::Result<()> {
 
     let tg_name = "gen-test";
     let config = config::Config {
+        generators: Default::default(),
         targets: [(
             "default".to_string(),
             config::Target(
@@ -226,6 +229,7 @@ fn e2e() -> anyhow::Result<()> {
                             // NOTE: root will map to the test's tempdir
                             path: "./".into(),
                             template_dir: None,
+                            naming_strategy: Default::default(),
                         },
                     })?,
                 }]
@@ -4,7 +4,10 @@
 use std::fmt::Write;
 
 use super::manifest::{ManifestEntry, ManifestPage};
-use super::shared::types::type_body_required;
+use super::shared::types::{
+    deprecated_values, deprecation_lines, field_deprecation, field_docs, type_body_required,
+    type_docs,
+};
 use super::utils::{normalize_struct_prop_name, normalize_type_title};
 use crate::interlude::*;
 
@@ -23,26 +26,50 @@ pub enum TsType {
         alias: Alias,
         /// inlined if name is none
         name: Option<String>,
+        docs: Vec<String>,
     },
     Object {
         name: String,
         properties: Vec<ObjectProp>,
+        docs: Vec<String>,
     },
     Enum {
         name: String,
         variants: Vec<TypeKey>,
+        docs: Vec<String>,
     },
     LiteralEnum {
         name: String,
         variants: Vec<String>,
+        deprecated: Vec<(String, String)>,
+        docs: Vec<String>,
     },
 }
 
+/// Write a TSDoc comment; the lines are split on line breaks, and `*/` is
+/// escaped so that it does not end the comment
+pub fn write_tsdoc(out: &mut impl Write, lines: &[String], indent: &str) -> std::fmt::Result {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "{indent}/**")?;
+    for line in lines.iter().flat_map(|line| line.split('\n')) {
+        let line = line.trim_end_matches('\r').replace("*/", "*\\/");
+        if line.is_empty() {
+            writeln!(out, "{indent} *")?;
+        } else {
+            writeln!(out, "{indent} * {line}")?;
+        }
+    }
+    writeln!(out, "{indent} */")
+}
+
 impl TsType {
     fn builtin(target: &'static str, name: Option<String>) -> Self {
         Self::Alias {
             alias: Alias::BuiltIn(target),
             name,
+            docs: vec![],
         }
     }
 
@@ -51,6 +78,8 @@ impl TsType {
             TsType::LiteralEnum {
                 name,
                 variants: variants.clone(),
+                deprecated: deprecated_values(ty),
+                docs: vec![],
             }
         } else if let Some(format) = ty.format_only() {
             let ty_name = normalize_type_title(&format!("string_{format}_{}", ty.idx()));
@@ -66,6 +95,7 @@ impl TsType {
         TsType::Alias {
             alias: Alias::Optional(item_ty.key()),
             name: explicit_alias.then_some(name),
+            docs: vec![],
         }
     }
 
@@ -80,6 +110,7 @@ impl TsType {
                 item: ty.item().key(),
             },
             name,
+            docs: vec![],
         }
     }
 
@@ -98,12 +129,15 @@ impl TsType {
                     name: normalize_struct_prop_name(&name[..]),
                     ty,
                     optional,
+                    deprecated: field_deprecation(&prop.ty),
+                    docs: field_docs(&prop.ty),
                 }
             })
             .collect::<Vec<_>>();
         TsType::Object {
             name,
             properties: props,
+            docs: vec![],
         }
     }
 
@@ -113,7 +147,21 @@ impl TsType {
             .iter()
             .map(|variant| variant.key())
             .collect::<Vec<_>>();
-        TsType::Enum { name, variants }
+        TsType::Enum {
+            name,
+            variants,
+            docs: vec![],
+        }
+    }
+
+    fn with_docs(mut self, new_docs: Vec<String>) -> TsType {
+        match &mut self {
+            TsType::Alias { docs, .. }
+            | TsType::Object { docs, .. }
+            | TsType::Enum { docs, .. }
+            | TsType::LiteralEnum { docs, .. } => *docs = new_docs,
+        }
+        self
     }
 }
 
@@ -122,6 +170,8 @@ pub struct ObjectProp {
     name: String,
     ty: TypeKey,
     optional: bool,
+    deprecated: Option<String>,
+    docs: Vec<String>,
 }
 
 impl ManifestEntry for TsType {
@@ -129,8 +179,9 @@ impl ManifestEntry for TsType {
 
     fn render(&self, out: &mut impl Write, page: &TsTypesPage) -> std::fmt::Result {
         match self {
-            TsType::Alias { name, alias } => {
+            TsType::Alias { name, alias, docs } => {
                 if let Some(name) = name {
+                    write_tsdoc(out, docs, "")?;
                     match alias {
                         Alias::BuiltIn(target) => {
                             writeln!(out, "export type {name} = {target};")?;
@@ -152,7 +203,12 @@ impl ManifestEntry for TsType {
                     }
                 }
             }
-            TsType::Object { name, properties } => {
+            TsType::Object {
+                name,
+                properties,
+                docs,
+            } => {
+                write_tsdoc(out, docs, "")?;
                 if properties.is_empty() {
                     writeln!(out, "export type {name} = Record<string, never>;")?;
                 } else {
@@ -160,6 +216,11 @@ impl ManifestEntry for TsType {
                     for prop in properties {
                         let prop_name = &prop.name;
                         let prop_ty = page.get_ref(&prop.ty).unwrap_or_else(|| "void".to_owned());
+                        let mut docs = prop.docs.clone();
+                        if let Some(message) = &prop.deprecated {
+                            docs.extend(deprecation_lines("@deprecated ", message));
+                        }
+                        write_tsdoc(out, &docs, "  ")?;
                         if prop.optional {
                             writeln!(out, "  {prop_name}?: {prop_ty};")?;
                         } else {
@@ -169,7 +230,12 @@ impl ManifestEntry for TsType {
                     writeln!(out, "}};")?;
                 }
             }
-            TsType::Enum { name, variants } => {
+            TsType::Enum {
+                name,
+                variants,
+                docs,
+            } => {
+                write_tsdoc(out, docs, "")?;
                 write!(out, "export type {name} =")?;
                 for variant in variants {
                     let variant_name = page.get_ref(variant).unwrap();
@@ -177,7 +243,23 @@ impl ManifestEntry for TsType {
                 }
                 writeln!(out, ";")?;
             }
-            TsType::LiteralEnum { name, variants } => {
+            TsType::LiteralEnum {
+                name,
+                variants,
+                deprecated,
+                docs,
+            } => {
+                let mut lines = docs.clone();
+                if !deprecated.is_empty() {
+                    if !lines.is_empty() {
+                        lines.push(String::new());
+                    }
+                    lines.push("Deprecated values:".to_string());
+                    for (value, message) in deprecated {
+                        lines.extend(deprecation_lines(&format!("- {value}: "), message));
+                    }
+                }
+                write_tsdoc(out, &lines, "")?;
                 writeln!(out, "export type {name} =")?;
                 for variant in variants {
                     write!(out, "\n  | {variant}")?;
@@ -191,7 +273,7 @@ impl ManifestEntry for TsType {
 
     fn get_reference_expr(&self, page: &TsTypesPage) -> Option<String> {
         match self {
-            TsType::Alias { name, alias } => {
+            TsType::Alias { name, alias, .. } => {
                 if let Some(name) = name {
                     Some(name.clone())
                 } else {
@@ -235,6 +317,7 @@ impl From<&Type> for TsType {
 
                 Type::Function(_) => unreachable!("unexpected function type"),
             }
+            .with_docs(type_docs(ty.base()))
         } else {
             TsType::builtin(
                 match ty {
@@ -275,3 +358,30 @@ impl TsTypesPage {
         map.into()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tsdoc_test() -> anyhow::Result<()> {
+        let mut out = String::new();
+        let mut docs = vec!["Matches `/api/*/`".to_string(), String::new()];
+        docs.extend(deprecation_lines(
+            "@deprecated ",
+            "use `url`\nremoved in v2",
+        ));
+        write_tsdoc(&mut out, &docs, "  ")?;
+        assert_str_eq!(
+            out,
+            r#"  /**
+   * Matches `/api/*\/`
+   *
+   * @deprecated use `url`
+   * removed in v2
+   */
+"#
+        );
+        Ok(())
+    }
+}
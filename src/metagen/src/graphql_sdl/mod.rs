@@ -0,0 +1,350 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Writes the GraphQL schema of a typegraph, as SDL and as introspection JSON,
+//! for GraphQL tooling (IDEs, linters, codegen) that can not query the typegate.
+
+mod schema;
+
+use core::fmt::Write;
+
+use typegraph::ExpansionConfig;
+
+use crate::interlude::*;
+use crate::*;
+
+use schema::Schema;
+
+#[derive(Serialize, Deserialize, Debug, garde::Validate)]
+pub struct GraphqlSdlGenConfig {
+    #[serde(flatten)]
+    #[garde(dive)]
+    pub base: crate::config::FdkGeneratorConfigBase,
+}
+
+impl GraphqlSdlGenConfig {
+    pub fn from_json(json: serde_json::Value, workspace_path: &Path) -> anyhow::Result<Self> {
+        let mut config: GraphqlSdlGenConfig = serde_json::from_value(json)?;
+        config.base.path = workspace_path.join(config.base.path);
+        config.base.typegraph_path = config
+            .base
+            .typegraph_path
+            .as_ref()
+            .map(|path| workspace_path.join(path));
+        Ok(config)
+    }
+}
+
+pub struct Generator {
+    config: GraphqlSdlGenConfig,
+}
+
+impl Generator {
+    pub const INPUT_TG: &'static str = "tg_name";
+    pub fn new(config: GraphqlSdlGenConfig) -> Result<Self, garde::Report> {
+        use garde::Validate;
+        config.validate()?;
+        Ok(Self { config })
+    }
+}
+
+impl crate::Plugin for Generator {
+    fn bill_of_inputs(&self) -> IndexMap<String, GeneratorInputOrder> {
+        [(
+            Self::INPUT_TG.to_string(),
+            if let Some(tg_name) = &self.config.base.typegraph_name {
+                GeneratorInputOrder::TypegraphFromTypegate {
+                    name: tg_name.clone(),
+                }
+            } else if let Some(tg_path) = &self.config.base.typegraph_path {
+                GeneratorInputOrder::TypegraphFromPath {
+                    path: tg_path.clone(),
+                    name: self.config.base.typegraph_name.clone(),
+                }
+            } else {
+                unreachable!()
+            },
+        )]
+        .into_iter()
+        .collect()
+    }
+
+    fn generate(
+        &self,
+        inputs: IndexMap<String, GeneratorInputResolved>,
+    ) -> anyhow::Result<GeneratorOutput> {
+        let tg = match inputs
+            .get(Self::INPUT_TG)
+            .context("missing generator input")?
+        {
+            GeneratorInputResolved::TypegraphFromTypegate { raw } => raw.clone(),
+            GeneratorInputResolved::TypegraphFromPath { raw } => raw.clone(),
+            _ => bail!("unexpected input type"),
+        };
+        let tg = ExpansionConfig::with_naming_strategy(self.config.base.naming_strategy.clone())?
+            .expand(tg)?;
+        let schema = Schema::new(&tg)?;
+
+        let mut sdl = String::new();
+        writeln!(
+            &mut sdl,
+            "# This file was @generated by metagen and is intended"
+        )?;
+        writeln!(
+            &mut sdl,
+            "# to be generated again on subsequent metagen runs."
+        )?;
+        writeln!(&mut sdl)?;
+        schema.render_sdl(&mut sdl)?;
+        let introspection = serde_json::to_string_pretty(&schema.introspection())?;
+
+        let mut out = IndexMap::new();
+        out.insert(
+            self.config.base.path.join("schema.graphql"),
+            GeneratedFile {
+                contents: sdl,
+                overwrite: true,
+            },
+        );
+        out.insert(
+            self.config.base.path.join("introspection.json"),
+            GeneratedFile {
+                contents: format!("{introspection}\n"),
+                overwrite: true,
+            },
+        );
+        Ok(GeneratorOutput(out))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::{create_typegraph, default_type_node_base};
+
+    #[test]
+    fn schema_generation_test() -> anyhow::Result<()> {
+        use tg_schema::*;
+        let tg = create_typegraph(
+            "graphql_sdl".into(),
+            vec![
+                TypeNode::String {
+                    data: StringTypeData {
+                        format: None,
+                        pattern: None,
+                        min_length: None,
+                        max_length: None,
+                    },
+                    base: TypeNodeBase {
+                        title: "my_str".into(),
+                        description: Some("Some \"name\"".into()),
+                        ..default_type_node_base()
+                    },
+                },
+                TypeNode::List {
+                    data: ListTypeData {
+                        items: 4,
+                        max_items: None,
+                        min_items: None,
+                        unique_items: None,
+                    },
+                    base: TypeNodeBase {
+                        title: "my_str_list".into(),
+                        ..default_type_node_base()
+                    },
+                },
+                TypeNode::Optional {
+                    data: OptionalTypeData {
+                        item: 7,
+                        default_value: None,
+                    },
+                    base: TypeNodeBase {
+                        title: "my_int_maybe".into(),
+                        deprecated: Some(Deprecation {
+                            reason: Some("use my_str".into()),
+                            since: None,
+                            removal: None,
+                        }),
+                        ..default_type_node_base()
+                    },
+                },
+                TypeNode::Integer {
+                    data: IntegerTypeData {
+                        maximum: None,
+                        multiple_of: None,
+                        exclusive_minimum: None,
+                        exclusive_maximum: None,
+                        minimum: None,
+                    },
+                    base: TypeNodeBase {
+                        title: "my_int".into(),
+                        ..default_type_node_base()
+                    },
+                },
+            ],
+        )?;
+        let tg = ExpansionConfig::with_default_engines().expand(tg)?;
+        let schema = Schema::new(&tg)?;
+
+        let mut sdl = String::new();
+        schema.render_sdl(&mut sdl)?;
+        assert_str_eq!(
+            sdl,
+            r#"type Query {
+  root: MyOutput!
+}
+
+type MyOutput {
+  """
+  Some "name"
+  """
+  my_str: String!
+  my_str_list: [String!]!
+  my_int_maybe: Int @deprecated(reason: "use my_str")
+  my_int: Int!
+}
+"#
+        );
+
+        let introspection = schema.introspection();
+        assert_eq!(
+            introspection["__schema"]["queryType"],
+            serde_json::json!({ "name": "Query" })
+        );
+        assert_eq!(
+            introspection["__schema"]["mutationType"],
+            serde_json::Value::Null
+        );
+        let directives = introspection["__schema"]["directives"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|directive| directive["name"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(directives, ["include", "skip", "deprecated"]);
+        assert_eq!(
+            introspection["__schema"]["directives"][2]["args"][0]["defaultValue"],
+            serde_json::json!("\"No longer supported\"")
+        );
+        Ok(())
+    }
+    #[test]
+    fn namespaces_and_unions_test() -> anyhow::Result<()> {
+        let object = |title: &str, properties: serde_json::Value| {
+            serde_json::json!({
+                "type": "object",
+                "title": title,
+                "properties": properties,
+                "id": [],
+                "required": [],
+            })
+        };
+        let function = |title: &str, input: u32, output: u32, materializer: u32| {
+            serde_json::json!({
+                "type": "function",
+                "title": title,
+                "input": input,
+                "output": output,
+                "runtimeConfig": null,
+                "materializer": materializer,
+                "rate_weight": null,
+                "rate_calls": false,
+            })
+        };
+        let materializer = |effect: &str| {
+            serde_json::json!({
+                "name": "function",
+                "runtime": 0,
+                "effect": { "effect": effect, "idempotent": effect == "read" },
+                "data": {},
+            })
+        };
+        let tg: tg_schema::Typegraph = serde_json::from_value(serde_json::json!({
+            "types": [
+                object("graphql_sdl", serde_json::json!({ "getPost": 1, "blog": 6, "search": 9 })),
+                function("getPost", 2, 4, 0),
+                object("GetPostInput", serde_json::json!({ "id": 3, "author": 5 })),
+                { "type": "integer", "title": "integer" },
+                object("Post", serde_json::json!({ "id": 3, "title": 7, "author": 5 })),
+                // used as input and as output
+                object("Author", serde_json::json!({ "id": 3, "name": 7 })),
+                object("blog", serde_json::json!({ "createPost": 8 })),
+                { "type": "string", "title": "string" },
+                function("createPost", 11, 4, 1),
+                function("search", 12, 13, 0),
+                { "type": "union", "title": "SearchResult", "anyOf": [4, 5] },
+                object("CreatePostInput", serde_json::json!({ "title": 7 })),
+                object("SearchInput", serde_json::json!({ "query": 7 })),
+                { "type": "list", "title": "SearchResult_list", "items": 10 },
+            ],
+            "materializers": [materializer("read"), materializer("create"), materializer("read")],
+            "runtimes": [{ "name": "deno", "data": { "worker": "default", "permissions": {} } }],
+            "policies": [{ "name": "public", "materializer": 2 }],
+            "meta": {
+                "prefix": null,
+                "secrets": [],
+                "queries": { "dynamic": true, "endpoints": [] },
+                "cors": {
+                    "allow_origin": ["*"],
+                    "allow_headers": [],
+                    "expose_headers": [],
+                    "allow_methods": [],
+                    "allow_credentials": true,
+                    "max_age_sec": null,
+                },
+                "auths": [],
+                "rate": null,
+                "version": "0.0.4",
+                "randomSeed": null,
+                "artifacts": {},
+            },
+        }))?;
+        let tg = ExpansionConfig::with_default_engines().expand(Arc::new(tg))?;
+        let schema = Schema::new(&tg)?;
+
+        let mut sdl = String::new();
+        schema.render_sdl(&mut sdl)?;
+        // the `blog` namespace has no query: it is not in `Query`
+        assert_str_eq!(
+            sdl,
+            r#"type Query {
+  getPost(id: Int!, author: Author!): Post!
+  search(query: String!): [SearchResult!]!
+}
+
+input Author {
+  id: Int!
+  name: String!
+}
+
+type Post {
+  id: Int!
+  title: String!
+  author: AuthorOutput!
+}
+
+type AuthorOutput {
+  id: Int!
+  name: String!
+}
+
+union SearchResult = Post | AuthorOutput
+
+type Mutation {
+  blog: BlogMutation!
+}
+
+type BlogMutation {
+  createPost(title: String!): Post!
+}
+"#
+        );
+
+        let introspection = schema.introspection();
+        assert_eq!(
+            introspection["__schema"]["mutationType"],
+            serde_json::json!({ "name": "Mutation" })
+        );
+        Ok(())
+    }
+}
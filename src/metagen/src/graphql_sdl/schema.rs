@@ -0,0 +1,690 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! GraphQL schema of a typegraph, rendered as SDL or as introspection JSON.
+//!
+//! The type names are the ones referenced by the generated clients
+//! (see [crate::shared::get_gql_type]); an output type that has the title of
+//! an input type is suffixed with `Output`.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use heck::ToPascalCase as _;
+use serde_json::{json, Value};
+use tg_schema::EffectType;
+
+use crate::interlude::*;
+use crate::shared::get_gql_type;
+use crate::shared::types::{field_deprecation, field_docs};
+
+const BUILTIN_SCALARS: [&str; 5] = ["Boolean", "Int", "Float", "String", "ID"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeRef {
+    Named(String),
+    List(Box<TypeRef>),
+    NonNull(Box<TypeRef>),
+}
+
+impl TypeRef {
+    fn named(name: impl Into<String>) -> Self {
+        Self::Named(name.into())
+    }
+
+    fn nullable(self) -> Self {
+        match self {
+            Self::NonNull(inner) => *inner,
+            ty => ty,
+        }
+    }
+
+    fn introspection(&self, schema: &Schema) -> Value {
+        match self {
+            Self::Named(name) => json!({
+                "kind": schema.kind_of(name),
+                "name": name,
+                "ofType": null,
+            }),
+            Self::List(inner) => json!({
+                "kind": "LIST",
+                "name": null,
+                "ofType": inner.introspection(schema),
+            }),
+            Self::NonNull(inner) => json!({
+                "kind": "NON_NULL",
+                "name": null,
+                "ofType": inner.introspection(schema),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for TypeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Named(name) => write!(f, "{name}"),
+            Self::List(inner) => write!(f, "[{inner}]"),
+            Self::NonNull(inner) => write!(f, "{inner}!"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Field {
+    pub name: String,
+    pub description: Option<String>,
+    pub args: Vec<InputValue>,
+    pub ty: TypeRef,
+    pub deprecation: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct InputValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub ty: TypeRef,
+    /// GraphQL literal
+    pub default_value: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Definition {
+    Scalar {
+        description: Option<String>,
+    },
+    Object {
+        description: Option<String>,
+        fields: Vec<Field>,
+    },
+    InputObject {
+        description: Option<String>,
+        fields: Vec<InputValue>,
+    },
+    Union {
+        description: Option<String>,
+        members: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Scalar,
+    Output,
+    Input,
+}
+
+impl Definition {
+    fn kind(&self) -> Kind {
+        match self {
+            Self::Scalar { .. } => Kind::Scalar,
+            Self::Object { .. } | Self::Union { .. } => Kind::Output,
+            Self::InputObject { .. } => Kind::Input,
+        }
+    }
+
+    fn introspection_kind(&self) -> &'static str {
+        match self {
+            Self::Scalar { .. } => "SCALAR",
+            Self::Object { .. } => "OBJECT",
+            Self::InputObject { .. } => "INPUT_OBJECT",
+            Self::Union { .. } => "UNION",
+        }
+    }
+
+    fn description(&self) -> Option<&String> {
+        match self {
+            Self::Scalar { description }
+            | Self::Object { description, .. }
+            | Self::InputObject { description, .. }
+            | Self::Union { description, .. } => description.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Schema {
+    pub definitions: IndexMap<String, Definition>,
+    pub has_query: bool,
+    pub has_mutation: bool,
+}
+
+impl Schema {
+    pub fn new(tg: &Typegraph) -> Result<Self> {
+        let mut queries = vec![];
+        let mut mutations = vec![];
+        for func in tg.root_functions() {
+            let (path, func) = func?;
+            match func.effect() {
+                EffectType::Read => queries.push((path, func)),
+                EffectType::Create | EffectType::Update | EffectType::Delete => {
+                    mutations.push((path, func))
+                }
+            }
+        }
+
+        let mut builder = SchemaBuilder::new(tg);
+        // empty root types are invalid in GraphQL
+        if !queries.is_empty() {
+            builder.root("Query".into(), &queries)?;
+        }
+        if !mutations.is_empty() {
+            builder.root("Mutation".into(), &mutations)?;
+        }
+
+        Ok(Self {
+            definitions: builder.definitions,
+            has_query: !queries.is_empty(),
+            has_mutation: !mutations.is_empty(),
+        })
+    }
+
+    fn kind_of(&self, name: &str) -> &'static str {
+        self.definitions
+            .get(name)
+            .map(|def| def.introspection_kind())
+            .unwrap_or("SCALAR")
+    }
+
+    pub fn render_sdl(&self, out: &mut impl Write) -> std::fmt::Result {
+        for (idx, (name, def)) in self.definitions.iter().enumerate() {
+            if idx > 0 {
+                writeln!(out)?;
+            }
+            write_description(out, def.description(), "")?;
+            match def {
+                Definition::Scalar { .. } => writeln!(out, "scalar {name}")?,
+                Definition::Object { fields, .. } => {
+                    writeln!(out, "type {name} {{")?;
+                    for field in fields {
+                        write_description(out, field.description.as_ref(), "  ")?;
+                        write!(out, "  {}", field.name)?;
+                        if !field.args.is_empty() {
+                            let args = field
+                                .args
+                                .iter()
+                                .map(|arg| match &arg.default_value {
+                                    Some(default) => {
+                                        format!("{}: {} = {default}", arg.name, arg.ty)
+                                    }
+                                    None => format!("{}: {}", arg.name, arg.ty),
+                                })
+                                .collect::<Vec<_>>();
+                            write!(out, "({})", args.join(", "))?;
+                        }
+                        write!(out, ": {}", field.ty)?;
+                        if let Some(reason) = &field.deprecation {
+                            write!(out, " @deprecated(reason: {})", string_literal(reason))?;
+                        }
+                        writeln!(out)?;
+                    }
+                    writeln!(out, "}}")?;
+                }
+                Definition::InputObject { fields, .. } => {
+                    writeln!(out, "input {name} {{")?;
+                    for field in fields {
+                        write_description(out, field.description.as_ref(), "  ")?;
+                        match &field.default_value {
+                            Some(default) => {
+                                writeln!(out, "  {}: {} = {default}", field.name, field.ty)?
+                            }
+                            None => writeln!(out, "  {}: {}", field.name, field.ty)?,
+                        }
+                    }
+                    writeln!(out, "}}")?;
+                }
+                Definition::Union { members, .. } => {
+                    writeln!(out, "union {name} = {}", members.join(" | "))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Result of the standard introspection query, as consumed by GraphQL tooling
+    pub fn introspection(&self) -> Value {
+        let mut types = BUILTIN_SCALARS
+            .iter()
+            .map(|name| {
+                json!({
+                    "kind": "SCALAR",
+                    "name": name,
+                    "description": null,
+                    "fields": null,
+                    "inputFields": null,
+                    "interfaces": null,
+                    "enumValues": null,
+                    "possibleTypes": null,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for (name, def) in self.definitions.iter() {
+            let mut ty = json!({
+                "kind": def.introspection_kind(),
+                "name": name,
+                "description": def.description(),
+                "fields": null,
+                "inputFields": null,
+                "interfaces": null,
+                "enumValues": null,
+                "possibleTypes": null,
+            });
+            match def {
+                Definition::Scalar { .. } => {}
+                Definition::Object { fields, .. } => {
+                    let fields = fields
+                        .iter()
+                        .map(|field| {
+                            json!({
+                                "name": field.name,
+                                "description": field.description,
+                                "args": field
+                                    .args
+                                    .iter()
+                                    .map(|arg| self.input_value_introspection(arg))
+                                    .collect::<Vec<_>>(),
+                                "type": field.ty.introspection(self),
+                                "isDeprecated": field.deprecation.is_some(),
+                                "deprecationReason": field.deprecation,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    ty["fields"] = fields.into();
+                    ty["interfaces"] = json!([]);
+                }
+                Definition::InputObject { fields, .. } => {
+                    ty["inputFields"] = fields
+                        .iter()
+                        .map(|field| self.input_value_introspection(field))
+                        .collect::<Vec<_>>()
+                        .into();
+                }
+                Definition::Union { members, .. } => {
+                    ty["possibleTypes"] = members
+                        .iter()
+                        .map(|member| TypeRef::named(member).introspection(self))
+                        .collect::<Vec<_>>()
+                        .into();
+                }
+            }
+            types.push(ty);
+        }
+
+        json!({
+            "__schema": {
+                "queryType": self.has_query.then(|| json!({ "name": "Query" })),
+                "mutationType": self.has_mutation.then(|| json!({ "name": "Mutation" })),
+                "subscriptionType": null,
+                "types": types,
+                "directives": self.directives_introspection(),
+            }
+        })
+    }
+
+    /// Definitions of the directives of the GraphQL specification
+    fn directives_introspection(&self) -> Vec<Value> {
+        let condition = |description: &str| InputValue {
+            name: "if".to_string(),
+            description: Some(description.to_string()),
+            ty: TypeRef::NonNull(Box::new(TypeRef::named("Boolean"))),
+            default_value: None,
+        };
+        let directive = |name: &str, description: &str, locations: &[&str], arg: InputValue| {
+            json!({
+                "name": name,
+                "description": description,
+                "locations": locations,
+                "args": [self.input_value_introspection(&arg)],
+                "isRepeatable": false,
+            })
+        };
+        vec![
+            directive(
+                "include",
+                "Directs the executor to include this field or fragment only when the `if` argument is true.",
+                &["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+                condition("Included when true."),
+            ),
+            directive(
+                "skip",
+                "Directs the executor to skip this field or fragment when the `if` argument is true.",
+                &["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+                condition("Skipped when true."),
+            ),
+            directive(
+                "deprecated",
+                "Marks an element of a GraphQL schema as no longer supported.",
+                &[
+                    "FIELD_DEFINITION",
+                    "ARGUMENT_DEFINITION",
+                    "INPUT_FIELD_DEFINITION",
+                    "ENUM_VALUE",
+                ],
+                InputValue {
+                    name: "reason".to_string(),
+                    description: Some(
+                        "Explains why this element was deprecated, usually also including a suggestion for how to access supported similar data. Formatted using the Markdown syntax, as specified by [CommonMark](https://commonmark.org/).".to_string(),
+                    ),
+                    ty: TypeRef::named("String"),
+                    default_value: Some(string_literal("No longer supported")),
+                },
+            ),
+        ]
+    }
+
+    fn input_value_introspection(&self, value: &InputValue) -> Value {
+        json!({
+            "name": value.name,
+            "description": value.description,
+            "type": value.ty.introspection(self),
+            "defaultValue": value.default_value,
+            "isDeprecated": false,
+            "deprecationReason": null,
+        })
+    }
+}
+
+struct SchemaBuilder {
+    definitions: IndexMap<String, Definition>,
+    /// names of the named input types, other than the function inputs
+    input_names: HashSet<String>,
+}
+
+impl SchemaBuilder {
+    fn new(tg: &Typegraph) -> Self {
+        let fn_inputs = tg
+            .functions
+            .values()
+            .map(|func| func.input().key())
+            .collect::<HashSet<_>>();
+        let input_names = tg
+            .input_types
+            .iter()
+            .filter(|(key, _)| !fn_inputs.contains(key))
+            .filter(|(_, ty)| matches!(ty, Type::Object(_) | Type::Union(_) | Type::File(_)))
+            .map(|(_, ty)| get_gql_type(ty, false, true))
+            .collect();
+        Self {
+            definitions: Default::default(),
+            input_names,
+        }
+    }
+
+    /// Root object type for the functions, with an object type per namespace
+    fn root(
+        &mut self,
+        name: String,
+        functions: &[(Vec<Arc<str>>, Arc<FunctionType>)],
+    ) -> Result<()> {
+        // reserve the name
+        self.definitions.insert(
+            name.clone(),
+            Definition::Object {
+                description: None,
+                fields: vec![],
+            },
+        );
+
+        let mut fields = vec![];
+        let mut namespaces: IndexMap<Arc<str>, Vec<_>> = IndexMap::new();
+        for (path, func) in functions {
+            match &path[..] {
+                [] => bail!("unexpected empty path for root function"),
+                [fn_name] => fields.push(self.function_field(fn_name, func)?),
+                [ns, rest @ ..] => namespaces
+                    .entry(ns.clone())
+                    .or_default()
+                    .push((rest.to_vec(), func.clone())),
+            }
+        }
+        for (ns, functions) in namespaces {
+            let ns_type_name = format!("{}{name}", ns.to_pascal_case());
+            self.root(ns_type_name.clone(), &functions)?;
+            fields.push(Field {
+                name: ns.to_string(),
+                description: None,
+                args: vec![],
+                ty: TypeRef::NonNull(TypeRef::named(ns_type_name).into()),
+                deprecation: None,
+            });
+        }
+
+        self.definitions.insert(
+            name,
+            Definition::Object {
+                description: None,
+                fields,
+            },
+        );
+        Ok(())
+    }
+
+    fn function_field(&mut self, name: &str, func: &Arc<FunctionType>) -> Result<Field> {
+        let args = func
+            .input()
+            .properties()
+            .iter()
+            .filter(|(_, prop)| !prop.is_injected())
+            .map(|(name, prop)| self.input_value(name, prop))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Field {
+            name: name.to_string(),
+            description: func.base.description.clone(),
+            args,
+            ty: self.type_ref(func.output(), false, Kind::Output)?,
+            deprecation: func.deprecated().map(|d| d.message()),
+        })
+    }
+
+    fn input_value(&mut self, name: &str, prop: &ObjectProperty) -> Result<InputValue> {
+        let default_value = match &prop.ty {
+            Type::Optional(ty) => ty.default_value.as_ref().map(graphql_literal),
+            _ => None,
+        };
+        Ok(InputValue {
+            name: name.to_string(),
+            description: docs_description(field_docs(&prop.ty)),
+            ty: self.type_ref(&prop.ty, prop.as_id, Kind::Input)?,
+            default_value,
+        })
+    }
+
+    fn type_ref(&mut self, ty: &Type, as_id: bool, kind: Kind) -> Result<TypeRef> {
+        let named = match ty {
+            Type::Optional(ty) => return Ok(self.type_ref(ty.item(), false, kind)?.nullable()),
+            Type::List(ty) => TypeRef::List(self.type_ref(ty.item(), false, kind)?.into()),
+            Type::Boolean(_) => TypeRef::named("Boolean"),
+            Type::Integer(_) => TypeRef::named("Int"),
+            Type::Float(_) => TypeRef::named("Float"),
+            Type::String(_) if as_id => TypeRef::named("ID"),
+            Type::String(_) => TypeRef::named("String"),
+            Type::File(_) => TypeRef::named(self.scalar(ty, kind)),
+            Type::Object(ty) => TypeRef::named(self.object(ty, kind)?),
+            Type::Union(ty) => TypeRef::named(self.union(ty, kind)?),
+            Type::Function(_) => bail!("unexpected function type at {:?}", ty.title()),
+        };
+        Ok(TypeRef::NonNull(named.into()))
+    }
+
+    /// Name of the definition of a named type, in an input or output context;
+    /// the input types keep the name referenced in the client queries
+    fn type_name(&self, ty: &Type, kind: Kind) -> String {
+        let name = get_gql_type(ty, false, true);
+        let is_file = matches!(ty, Type::File(_));
+        if kind == Kind::Output && !is_file && self.input_names.contains(&name) {
+            format!("{name}Output")
+        } else {
+            name
+        }
+    }
+
+    fn scalar(&mut self, ty: &Type, kind: Kind) -> String {
+        let name = self.type_name(ty, kind);
+        if !self.definitions.contains_key(&name) {
+            self.definitions.insert(
+                name.clone(),
+                Definition::Scalar {
+                    description: ty.base().description.clone(),
+                },
+            );
+        }
+        name
+    }
+
+    fn object(&mut self, ty: &Arc<ObjectType>, kind: Kind) -> Result<String> {
+        let props = ty
+            .properties()
+            .iter()
+            .filter(|(_, prop)| kind == Kind::Output || !prop.is_injected())
+            .collect::<Vec<_>>();
+        if props.is_empty() {
+            // empty objects are emitted as scalars, like in the typegate
+            return Ok(self.scalar(&ty.wrap(), kind));
+        }
+
+        let name = self.type_name(&ty.wrap(), kind);
+        if self.definitions.contains_key(&name) {
+            return Ok(name);
+        }
+        let description = ty.base.description.clone();
+
+        if kind == Kind::Input {
+            // placeholder for recursive references
+            self.definitions.insert(
+                name.clone(),
+                Definition::InputObject {
+                    description: None,
+                    fields: vec![],
+                },
+            );
+            let fields = props
+                .into_iter()
+                .map(|(prop_name, prop)| self.input_value(prop_name, prop))
+                .collect::<Result<Vec<_>>>()?;
+            self.definitions.insert(
+                name.clone(),
+                Definition::InputObject {
+                    description,
+                    fields,
+                },
+            );
+        } else {
+            // placeholder for recursive references
+            self.definitions.insert(
+                name.clone(),
+                Definition::Object {
+                    description: None,
+                    fields: vec![],
+                },
+            );
+            let mut fields = Vec::with_capacity(props.len());
+            for (prop_name, prop) in props {
+                let field = match &prop.ty {
+                    Type::Function(func) => self.function_field(prop_name, func)?,
+                    _ => Field {
+                        name: prop_name.to_string(),
+                        description: docs_description(field_docs(&prop.ty)),
+                        args: vec![],
+                        ty: self.type_ref(&prop.ty, prop.as_id, Kind::Output)?,
+                        deprecation: field_deprecation(&prop.ty),
+                    },
+                };
+                fields.push(field);
+            }
+            self.definitions.insert(
+                name.clone(),
+                Definition::Object {
+                    description,
+                    fields,
+                },
+            );
+        }
+        Ok(name)
+    }
+
+    /// GraphQL unions only have object members, and there is no input union:
+    /// the other unions are emitted as scalars
+    fn union(&mut self, ty: &Arc<UnionType>, kind: Kind) -> Result<String> {
+        let variants = ty
+            .variants()
+            .iter()
+            .map(|variant| match variant {
+                Type::Object(obj) if kind == Kind::Output => Some(obj),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        let Some(variants) = variants else {
+            return Ok(self.scalar(&ty.wrap(), kind));
+        };
+
+        let name = self.type_name(&ty.wrap(), kind);
+        if self.definitions.contains_key(&name) {
+            return Ok(name);
+        }
+        let mut members = Vec::with_capacity(variants.len());
+        for variant in variants {
+            let member = self.object(variant, Kind::Output)?;
+            if self.definitions[&member].kind() != Kind::Output {
+                // empty object
+                return Ok(self.scalar(&ty.wrap(), kind));
+            }
+            members.push(member);
+        }
+        self.definitions.insert(
+            name.clone(),
+            Definition::Union {
+                description: ty.base.description.clone(),
+                members,
+            },
+        );
+        Ok(name)
+    }
+}
+
+fn docs_description(lines: Vec<String>) -> Option<String> {
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn write_description(
+    out: &mut impl Write,
+    description: Option<&String>,
+    indent: &str,
+) -> std::fmt::Result {
+    if let Some(description) = description {
+        writeln!(out, "{indent}\"\"\"")?;
+        for line in description.replace("\"\"\"", "\\\"\"\"").lines() {
+            if line.is_empty() {
+                writeln!(out)?;
+            } else {
+                writeln!(out, "{indent}{line}")?;
+            }
+        }
+        writeln!(out, "{indent}\"\"\"")?;
+    }
+    Ok(())
+}
+
+fn string_literal(value: &str) -> String {
+    // JSON strings are valid GraphQL strings
+    serde_json::to_string(value).unwrap()
+}
+
+/// GraphQL value literal for a JSON value
+fn graphql_literal(value: &Value) -> String {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::String(s) => string_literal(s),
+        Value::Array(items) => {
+            let items = items.iter().map(graphql_literal).collect::<Vec<_>>();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(fields) => {
+            let fields = fields
+                .iter()
+                .map(|(key, value)| format!("{key}: {}", graphql_literal(value)))
+                .collect::<Vec<_>>();
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
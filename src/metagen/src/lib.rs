@@ -29,24 +29,35 @@ mod macros;
 // used by the NamingPostProcessor
 pub mod shared;
 
+mod fdk_go;
 mod fdk_py;
 mod fdk_rs;
 mod fdk_substantial;
 mod fdk_ts;
 
+mod client_go;
 mod client_py;
 mod client_rs;
 mod client_ts;
 
+mod graphql_sdl;
+
+mod prisma_models;
+
+#[cfg(feature = "plugins")]
+mod wasm_plugin;
+
 #[cfg(test)]
 mod tests;
 mod utils;
 
 use crate::interlude::*;
+use tg_schema::runtimes::prisma::PrismaIntrospection;
 
 pub use config::*;
 pub use shared::FdkTemplate;
 
+pub use fdk_go::DEFAULT_TEMPLATE as FDK_GO_DEFAULT_TEMPLATE;
 pub use fdk_py::DEFAULT_TEMPLATE as FDK_PY_DEFAULT_TEMPLATE;
 pub use fdk_rs::DEFAULT_TEMPLATE as FDK_RS_DEFAULT_TEMPLATE;
 pub use fdk_ts::DEFAULT_TEMPLATE as FDK_TS_DEFAULT_TEMPLATE;
@@ -70,6 +81,10 @@ pub enum GeneratorInputOrder {
         default: &'static [(&'static str, &'static str)],
         override_path: Option<PathBuf>,
     },
+    PrismaIntrospection {
+        typegraph: String,
+        runtime: Option<String>,
+    },
 }
 
 /// Response types for the command object API implemented
@@ -79,6 +94,7 @@ pub enum GeneratorInputResolved {
     TypegraphFromTypegate { raw: Arc<tg_schema::Typegraph> },
     TypegraphFromPath { raw: Arc<tg_schema::Typegraph> },
     FdkTemplate { template: FdkTemplate },
+    PrismaIntrospection { raw: Arc<PrismaIntrospection> },
 }
 
 /// This type plays the "dispatcher" role to the command object
@@ -156,6 +172,16 @@ impl GeneratorRunner {
                         },
                     },
                 ),
+                (
+                    "fdk_go".to_string(),
+                    GeneratorRunner {
+                        op: |workspace_path: &Path, val| {
+                            let config = fdk_go::FdkGoGenConfig::from_json(val, workspace_path)?;
+                            let generator = fdk_go::Generator::new(config)?;
+                            Ok(Box::new(generator))
+                        },
+                    },
+                ),
                 (
                     "fdk_substantial".to_string(),
                     GeneratorRunner {
@@ -206,6 +232,36 @@ impl GeneratorRunner {
                         },
                     },
                 ),
+                (
+                    "client_go".to_string(),
+                    GeneratorRunner {
+                        op: |workspace_path: &Path, val| {
+                            let config = client_go::ClientGoGenConfig::from_json(val, workspace_path)?;
+                            let generator = client_go::Generator::new(config)?;
+                            Ok(Box::new(generator))
+                        },
+                    },
+                ),
+                (
+                    "graphql_sdl".to_string(),
+                    GeneratorRunner {
+                        op: |workspace_path: &Path, val| {
+                            let config = graphql_sdl::GraphqlSdlGenConfig::from_json(val, workspace_path)?;
+                            let generator = graphql_sdl::Generator::new(config)?;
+                            Ok(Box::new(generator))
+                        },
+                    },
+                ),
+                (
+                    "prisma_models".to_string(),
+                    GeneratorRunner {
+                        op: |workspace_path: &Path, val| {
+                            let config = prisma_models::PrismaModelsGenConfig::from_json(val, workspace_path)?;
+                            let generator = prisma_models::Generator::new(config)?;
+                            Ok(Box::new(generator))
+                        },
+                    },
+                ),
             ]);
         }
 
@@ -213,12 +269,59 @@ impl GeneratorRunner {
     }
 }
 
+/// Instantiate the generator under `gen_name`, either builtin or
+/// external, with its config.
+#[cfg_attr(not(feature = "plugins"), allow(unused_variables))]
+fn load_generator(
+    config: &config::Config,
+    gen_name: &str,
+    workspace_path: &Path,
+    value: serde_json::Value,
+) -> PluginOutputResult {
+    if let Some(runner) = GeneratorRunner::get(gen_name) {
+        return runner.exec(workspace_path, value);
+    }
+    let Some(external) = config.generators.get(gen_name) else {
+        bail!("generator \"{gen_name}\" not found in config");
+    };
+    #[cfg(feature = "plugins")]
+    {
+        let generator = wasm_plugin::WasmGenerator::load(
+            &workspace_path.join(&external.wasm),
+            workspace_path,
+            external.fuel,
+            value,
+        )
+        .wrap_err_with(|| format!("error loading external generator \"{gen_name}\""))?;
+        Ok(Box::new(generator))
+    }
+    #[cfg(not(feature = "plugins"))]
+    {
+        bail!(
+            "external generator \"{gen_name}\" at {:?} can't be loaded: metagen was built without plugin support",
+            external.wasm
+        )
+    }
+}
+
 // FIXME: Bring FutureGroup back after multithreading support for typegraph core (MET-755)
 pub async fn generate_target(
     config: &config::Config,
     target_name: &str,
     workspace_path: PathBuf,
     resolver: impl InputResolver + Send + Sync + 'static,
+) -> anyhow::Result<GeneratorOutput> {
+    generate_target_filtered(config, target_name, workspace_path, resolver, |_| true).await
+}
+
+/// Like [generate_target] but only runs the generators for which
+/// `filter` accepts the bill of inputs.
+pub async fn generate_target_filtered(
+    config: &config::Config,
+    target_name: &str,
+    workspace_path: PathBuf,
+    resolver: impl InputResolver + Send + Sync + 'static,
+    filter: impl Fn(&IndexMap<String, GeneratorInputOrder>) -> bool,
 ) -> anyhow::Result<GeneratorOutput> {
     let target_conf = config
         .targets
@@ -227,14 +330,18 @@ pub async fn generate_target(
 
     let mut out = IndexMap::new();
 
-    for config in &target_conf.0 {
-        let gen_name = &config.generator_name;
-        let config = config.other.to_owned();
-        let get_gen_op = GeneratorRunner::get(gen_name)
-            .with_context(|| format!("generator \"{gen_name}\" not found in config"))?;
-
-        let gen_impl = get_gen_op.exec(&workspace_path, config)?;
+    for gen_config in &target_conf.0 {
+        let gen_name = &gen_config.generator_name;
+        let gen_impl = load_generator(
+            config,
+            gen_name,
+            &workspace_path,
+            gen_config.other.to_owned(),
+        )?;
         let bill = gen_impl.bill_of_inputs();
+        if !filter(&bill) {
+            continue;
+        }
         let gen_name: Arc<str> = gen_name[..].into();
         let mut inputs = IndexMap::new();
 
@@ -271,14 +378,9 @@ pub fn generate_target_sync(
         .with_context(|| format!("target \"{target_name}\" not found in config"))?;
 
     let mut generate_set = vec![];
-    for config in &target_conf.0 {
-        let gen_name = &config.generator_name;
-        let config = config.other.clone();
-
-        let get_gen_op = GeneratorRunner::get(gen_name)
-            .with_context(|| format!("generator \"{gen_name}\" not found in config"))?;
-
-        let gen_impl = get_gen_op.exec(&workspace_path, config)?;
+    for gen_config in &target_conf.0 {
+        let gen_name = &gen_config.generator_name;
+        let gen_impl = load_generator(config, gen_name, &workspace_path, gen_config.other.clone())?;
         let bill = gen_impl.bill_of_inputs();
 
         let resolve_set = bill.into_iter().map(|(name, order)| {
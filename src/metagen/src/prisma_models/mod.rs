@@ -0,0 +1,615 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Generate typegraph model definitions from the introspection of
+//! the database of a deployed prisma runtime.
+
+use core::fmt::Write;
+
+use tg_schema::runtimes::prisma::{
+    Cardinality, IntrospectedDefault, IntrospectedField, IntrospectedFieldKind, IntrospectedModel,
+    PrismaIntrospection,
+};
+
+use crate::interlude::*;
+use crate::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelsLang {
+    #[default]
+    Python,
+    Typescript,
+}
+
+#[derive(Serialize, Deserialize, Debug, garde::Validate)]
+pub struct PrismaModelsGenConfig {
+    /// Name of the deployed typegraph that defines the prisma runtime
+    #[garde(length(min = 1))]
+    #[serde(rename = "typegraph")]
+    pub typegraph_name: String,
+    /// Required if the typegraph has more than one prisma runtime
+    #[garde(skip)]
+    pub runtime: Option<String>,
+    #[garde(skip)]
+    pub path: PathBuf,
+    #[garde(skip)]
+    #[serde(default)]
+    pub lang: ModelsLang,
+}
+
+impl PrismaModelsGenConfig {
+    pub fn from_json(json: serde_json::Value, workspace_path: &Path) -> anyhow::Result<Self> {
+        let mut config: PrismaModelsGenConfig = serde_json::from_value(json)?;
+        config.path = workspace_path.join(config.path);
+        Ok(config)
+    }
+}
+
+pub struct Generator {
+    config: PrismaModelsGenConfig,
+}
+
+impl Generator {
+    pub const INPUT_INTROSPECTION: &'static str = "introspection";
+
+    pub fn new(config: PrismaModelsGenConfig) -> Result<Self, garde::Report> {
+        use garde::Validate;
+        config.validate()?;
+        Ok(Self { config })
+    }
+}
+
+impl crate::Plugin for Generator {
+    fn bill_of_inputs(&self) -> IndexMap<String, GeneratorInputOrder> {
+        [(
+            Self::INPUT_INTROSPECTION.to_string(),
+            GeneratorInputOrder::PrismaIntrospection {
+                typegraph: self.config.typegraph_name.clone(),
+                runtime: self.config.runtime.clone(),
+            },
+        )]
+        .into_iter()
+        .collect()
+    }
+
+    fn generate(
+        &self,
+        inputs: IndexMap<String, GeneratorInputResolved>,
+    ) -> anyhow::Result<GeneratorOutput> {
+        let Some(GeneratorInputResolved::PrismaIntrospection { raw }) =
+            inputs.get(Self::INPUT_INTROSPECTION)
+        else {
+            bail!("unexpected input type");
+        };
+
+        let (file_name, contents) = match self.config.lang {
+            ModelsLang::Python => ("models.py", render(raw, ModelsLang::Python)?),
+            ModelsLang::Typescript => ("models.ts", render(raw, ModelsLang::Typescript)?),
+        };
+
+        let mut files = IndexMap::new();
+        files.insert(
+            self.config.path.join(file_name),
+            GeneratedFile {
+                contents,
+                overwrite: true,
+            },
+        );
+        Ok(GeneratorOutput(files))
+    }
+}
+
+#[derive(Default)]
+struct RenderCtx {
+    warnings: Vec<String>,
+    inject_create: bool,
+    inject_update: bool,
+}
+
+fn render(introspection: &PrismaIntrospection, lang: ModelsLang) -> anyhow::Result<String> {
+    let mut ctx = RenderCtx {
+        warnings: introspection.warnings.clone(),
+        ..Default::default()
+    };
+
+    let mut models = String::new();
+    for model in &introspection.models {
+        render_model(&mut models, &mut ctx, introspection, model, lang)?;
+    }
+
+    let mut out = String::new();
+    match lang {
+        ModelsLang::Python => {
+            writeln!(
+                &mut out,
+                "# This file was @generated by metagen from a prisma introspection."
+            )?;
+            render_warnings(&mut out, &ctx, "#")?;
+            writeln!(&mut out)?;
+            writeln!(&mut out, "from typegraph import Graph, t")?;
+            let effects = [(ctx.inject_create, "CREATE"), (ctx.inject_update, "UPDATE")]
+                .into_iter()
+                .filter_map(|(used, name)| used.then_some(name))
+                .collect::<Vec<_>>();
+            if !effects.is_empty() {
+                writeln!(
+                    &mut out,
+                    "from typegraph.effects import {}",
+                    effects.join(", ")
+                )?;
+            }
+            writeln!(
+                &mut out,
+                "from typegraph.providers.prisma import PrismaRuntime"
+            )?;
+            writeln!(&mut out)?;
+            writeln!(&mut out)?;
+            writeln!(&mut out, "def prisma_models(g: Graph, db: PrismaRuntime):")?;
+            writeln!(&mut out, "    return {{")?;
+            out.push_str(&models);
+            writeln!(&mut out, "    }}")?;
+        }
+        ModelsLang::Typescript => {
+            writeln!(
+                &mut out,
+                "// This file was @generated by metagen from a prisma introspection."
+            )?;
+            render_warnings(&mut out, &ctx, "//")?;
+            writeln!(&mut out)?;
+            writeln!(&mut out, "import {{ t }} from \"@typegraph/sdk\";")?;
+            let effects = [(ctx.inject_create, "CREATE"), (ctx.inject_update, "UPDATE")]
+                .into_iter()
+                .filter_map(|(used, name)| used.then_some(name))
+                .collect::<Vec<_>>();
+            if !effects.is_empty() {
+                writeln!(
+                    &mut out,
+                    "import {{ {} }} from \"@typegraph/sdk/effects\";",
+                    effects.join(", ")
+                )?;
+            }
+            writeln!(
+                &mut out,
+                "import type {{ PrismaRuntime }} from \"@typegraph/sdk/providers/prisma\";"
+            )?;
+            writeln!(
+                &mut out,
+                "import type {{ TypegraphBuilderArgs }} from \"@typegraph/sdk/typegraph\";"
+            )?;
+            writeln!(&mut out)?;
+            writeln!(
+                &mut out,
+                "export function prismaModels(g: TypegraphBuilderArgs, db: PrismaRuntime) {{"
+            )?;
+            writeln!(&mut out, "  return {{")?;
+            out.push_str(&models);
+            writeln!(&mut out, "  }};")?;
+            writeln!(&mut out, "}}")?;
+        }
+    }
+    Ok(out)
+}
+
+fn render_warnings(out: &mut String, ctx: &RenderCtx, comment: &str) -> anyhow::Result<()> {
+    if ctx.warnings.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "{comment}")?;
+    writeln!(out, "{comment} warnings:")?;
+    for warning in &ctx.warnings {
+        for (i, line) in warning.lines().enumerate() {
+            let bullet = if i == 0 { "-" } else { " " };
+            writeln!(out, "{comment} {bullet} {line}")?;
+        }
+    }
+    Ok(())
+}
+
+fn render_model(
+    out: &mut String,
+    ctx: &mut RenderCtx,
+    introspection: &PrismaIntrospection,
+    model: &IntrospectedModel,
+    lang: ModelsLang,
+) -> anyhow::Result<()> {
+    // foreign key columns are generated from the relationships:
+    // map them to the name of the relationship field
+    let mut fkeys = IndexMap::new();
+    for field in &model.fields {
+        if let Some(relation) = &field.relation {
+            for fkey in &relation.fields {
+                fkeys.insert(fkey.as_str(), field.name.as_str());
+            }
+        }
+    }
+    let map_fields = |fields: &[String]| -> Vec<String> {
+        let mut res = IndexSet::new();
+        for field in fields {
+            res.insert(
+                fkeys
+                    .get(field.as_str())
+                    .copied()
+                    .unwrap_or(field.as_str())
+                    .to_string(),
+            );
+        }
+        res.into_iter().collect()
+    };
+
+    let (indent, key) = match lang {
+        ModelsLang::Python => ("    ", format!("{:?}", model.name)),
+        ModelsLang::Typescript => ("  ", ts_key(&model.name)),
+    };
+    writeln!(out, "{indent}{indent}{key}: t.struct(")?;
+    writeln!(out, "{indent}{indent}{indent}{{")?;
+    for field in &model.fields {
+        if fkeys.contains_key(field.name.as_str()) {
+            continue;
+        }
+        let expr = render_field(ctx, introspection, model, field, lang)?;
+        let key = match lang {
+            ModelsLang::Python => format!("{:?}", field.name),
+            ModelsLang::Typescript => ts_key(&field.name),
+        };
+        writeln!(out, "{indent}{indent}{indent}{indent}{key}: {expr},")?;
+    }
+    writeln!(out, "{indent}{indent}{indent}}},")?;
+
+    let mut struct_config = serde_json::Map::new();
+    if !model.id_fields.is_empty() {
+        struct_config.insert("id".into(), map_fields(&model.id_fields).into());
+    }
+    if !model.unique_constraints.is_empty() {
+        let constraints = model
+            .unique_constraints
+            .iter()
+            .map(|c| map_fields(c).into())
+            .collect::<Vec<serde_json::Value>>();
+        struct_config.insert("unique".into(), constraints.into());
+    }
+
+    match lang {
+        ModelsLang::Python => {
+            writeln!(out, "{indent}{indent}{indent}name={:?},", model.name)?;
+            if !struct_config.is_empty() {
+                writeln!(
+                    out,
+                    "{indent}{indent}{indent}config={},",
+                    py_value(&struct_config.into())
+                )?;
+            }
+        }
+        ModelsLang::Typescript => {
+            let mut base = serde_json::Map::new();
+            base.insert("name".into(), model.name.clone().into());
+            if !struct_config.is_empty() {
+                base.insert("config".into(), struct_config.into());
+            }
+            writeln!(out, "{indent}{indent}{indent}{},", ts_value(&base.into()))?;
+        }
+    }
+    writeln!(out, "{indent}{indent}),")?;
+    Ok(())
+}
+
+fn render_field(
+    ctx: &mut RenderCtx,
+    introspection: &PrismaIntrospection,
+    model: &IntrospectedModel,
+    field: &IntrospectedField,
+    lang: ModelsLang,
+) -> anyhow::Result<String> {
+    let py = lang == ModelsLang::Python;
+
+    if field.kind == IntrospectedFieldKind::Relation {
+        let relation = field.relation.as_ref().context("relation expected")?;
+        let target = format!("g.ref({:?})", field.field_type);
+        let target = match field.cardinality {
+            Cardinality::One => target,
+            Cardinality::Optional => format!("{target}.optional()"),
+            Cardinality::Many => format!("t.list({target})"),
+        };
+        let fkey = match (relation.fields.is_empty(), py) {
+            (true, _) => "",
+            (false, true) => ", fkey=True",
+            (false, false) => ", { fkey: true }",
+        };
+        return Ok(format!("db.link({target}, {:?}{fkey})", relation.name));
+    }
+
+    let mut config = serde_json::Map::new();
+    let mut inject = None;
+    if field.unique {
+        config.insert("unique".into(), true.into());
+    }
+    match &field.default {
+        None => {}
+        Some(IntrospectedDefault::AutoIncrement | IntrospectedDefault::Uuid) => {
+            config.insert("auto".into(), true.into());
+        }
+        Some(IntrospectedDefault::Now) => {
+            ctx.inject_create = true;
+            inject = Some("CREATE");
+        }
+        Some(IntrospectedDefault::Cuid | IntrospectedDefault::DbGenerated) => {
+            ctx.warnings.push(format!(
+                "{}.{}: unsupported default value, ignored",
+                model.name, field.name
+            ));
+        }
+        Some(IntrospectedDefault::Value { value }) => {
+            config.insert("default".into(), value.clone());
+        }
+    }
+    if field.updated_at {
+        ctx.inject_update = true;
+        inject = Some("UPDATE");
+    }
+
+    let config = (!config.is_empty()).then(|| serde_json::Value::Object(config));
+    let config_arg = |prefix: &str| match (&config, py) {
+        (None, _) => String::new(),
+        (Some(c), true) => format!("{prefix}config={}", py_value(c)),
+        (Some(c), false) => format!("{prefix}{{ config: {} }}", ts_value(c)),
+    };
+
+    let expr = if field.kind == IntrospectedFieldKind::Enum {
+        let variants = introspection
+            .enums
+            .iter()
+            .find(|e| e.name == field.field_type)
+            .map(|e| e.values.clone())
+            .context("enum not found")?;
+        let variants = serde_json::Value::from(variants);
+        match (&config, py) {
+            (None, true) => format!("t.enum({})", py_value(&variants)),
+            (Some(_), true) => {
+                format!("t.string(enum={}{})", py_value(&variants), config_arg(", "))
+            }
+            (_, false) => format!("t.enum_({}{})", ts_value(&variants), config_arg(", ")),
+        }
+    } else {
+        let (ctor, format) = match field.field_type.as_str() {
+            "String" if field.native_type.as_deref() == Some("Uuid") => ("uuid", None),
+            "String" => ("string", None),
+            "Boolean" => ("boolean", None),
+            "Int" | "BigInt" => ("integer", None),
+            "Float" | "Decimal" => ("float", None),
+            "DateTime" => ("datetime", Some("date-time")),
+            "Json" => ("json", Some("json")),
+            t => {
+                ctx.warnings.push(format!(
+                    "{}.{}: unsupported type {t}, mapped to a string",
+                    model.name, field.name
+                ));
+                ("string", None)
+            }
+        };
+        match (ctor, format, py) {
+            (_, Some(_), _) if config.is_none() => format!("t.{ctor}()"),
+            (_, Some(format), true) => format!("t.string(format={format:?}{})", config_arg(", ")),
+            (_, Some(format), false) => {
+                format!("t.string({{ format: {format:?} }}{})", config_arg(", "))
+            }
+            (_, None, true) => format!("t.{ctor}({})", config_arg("")),
+            ("uuid" | "boolean", None, false) => format!("t.{ctor}({})", config_arg("")),
+            (_, None, false) => match &config {
+                None => format!("t.{ctor}()"),
+                Some(_) => format!("t.{ctor}({{}}{})", config_arg(", ")),
+            },
+        }
+    };
+
+    let mut expr = expr;
+    if field.id {
+        expr.push_str(".id()");
+    }
+    let mut expr = match field.cardinality {
+        Cardinality::One => expr,
+        Cardinality::Optional => format!("{expr}.optional()"),
+        Cardinality::Many => format!("t.list({expr})"),
+    };
+    if let Some(effect) = inject {
+        if py {
+            write!(&mut expr, ".inject({{{effect}: \"now\"}})")?;
+        } else {
+            write!(&mut expr, ".inject({{ [{effect}]: \"now\" }})")?;
+        }
+    }
+    Ok(expr)
+}
+
+fn is_ts_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn ts_key(name: &str) -> String {
+    if is_ts_identifier(name) {
+        name.to_string()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+fn ts_value(value: &serde_json::Value) -> String {
+    use serde_json::Value;
+    match value {
+        Value::Array(items) => format!(
+            "[{}]",
+            items.iter().map(ts_value).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Object(map) => format!(
+            "{{ {} }}",
+            map.iter()
+                .map(|(k, v)| format!("{}: {}", ts_key(k), ts_value(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        v => v.to_string(),
+    }
+}
+
+fn py_value(value: &serde_json::Value) -> String {
+    use serde_json::Value;
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Array(items) => format!(
+            "[{}]",
+            items.iter().map(py_value).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Object(map) => format!(
+            "{{{}}}",
+            map.iter()
+                .map(|(k, v)| format!("{k:?}: {}", py_value(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        v => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn introspection() -> PrismaIntrospection {
+        serde_json::from_value(serde_json::json!({
+            "models": [
+                {
+                    "name": "User",
+                    "id_fields": [],
+                    "unique_constraints": [],
+                    "fields": [
+                        {
+                            "name": "id", "kind": "scalar", "field_type": "String",
+                            "native_type": "Uuid", "cardinality": "one", "id": true,
+                            "unique": false, "default": { "kind": "uuid" }
+                        },
+                        {
+                            "name": "role", "kind": "enum", "field_type": "Role",
+                            "cardinality": "one", "id": false, "unique": false,
+                            "default": { "kind": "value", "value": "USER" }
+                        },
+                        {
+                            "name": "Post", "kind": "relation", "field_type": "Post",
+                            "cardinality": "many", "id": false, "unique": false,
+                            "relation": { "name": "PostToUser", "fields": [], "references": [] }
+                        }
+                    ]
+                },
+                {
+                    "name": "Post",
+                    "id_fields": ["authorId", "slug"],
+                    "unique_constraints": [],
+                    "fields": [
+                        {
+                            "name": "slug", "kind": "scalar", "field_type": "String",
+                            "cardinality": "one", "id": false, "unique": false
+                        },
+                        {
+                            "name": "authorId", "kind": "scalar", "field_type": "String",
+                            "native_type": "Uuid", "cardinality": "one", "id": false,
+                            "unique": false
+                        },
+                        {
+                            "name": "User", "kind": "relation", "field_type": "User",
+                            "cardinality": "one", "id": false, "unique": false,
+                            "relation": {
+                                "name": "PostToUser", "fields": ["authorId"], "references": ["id"]
+                            }
+                        },
+                        {
+                            "name": "createdAt", "kind": "scalar", "field_type": "DateTime",
+                            "cardinality": "optional", "id": false, "unique": false,
+                            "default": { "kind": "now" }
+                        }
+                    ]
+                }
+            ],
+            "enums": [{ "name": "Role", "values": ["USER", "ADMIN"] }],
+            "warnings": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn render_python() -> anyhow::Result<()> {
+        let out = render(&introspection(), ModelsLang::Python)?;
+        assert_str_eq!(
+            out,
+            r#"# This file was @generated by metagen from a prisma introspection.
+
+from typegraph import Graph, t
+from typegraph.effects import CREATE
+from typegraph.providers.prisma import PrismaRuntime
+
+
+def prisma_models(g: Graph, db: PrismaRuntime):
+    return {
+        "User": t.struct(
+            {
+                "id": t.uuid(config={"auto": True}).id(),
+                "role": t.string(enum=["USER", "ADMIN"], config={"default": "USER"}),
+                "Post": db.link(t.list(g.ref("Post")), "PostToUser"),
+            },
+            name="User",
+        ),
+        "Post": t.struct(
+            {
+                "slug": t.string(),
+                "User": db.link(g.ref("User"), "PostToUser", fkey=True),
+                "createdAt": t.datetime().optional().inject({CREATE: "now"}),
+            },
+            name="Post",
+            config={"id": ["User", "slug"]},
+        ),
+    }
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn render_typescript() -> anyhow::Result<()> {
+        let out = render(&introspection(), ModelsLang::Typescript)?;
+        assert_str_eq!(
+            out,
+            r#"// This file was @generated by metagen from a prisma introspection.
+
+import { t } from "@typegraph/sdk";
+import { CREATE } from "@typegraph/sdk/effects";
+import type { PrismaRuntime } from "@typegraph/sdk/providers/prisma";
+import type { TypegraphBuilderArgs } from "@typegraph/sdk/typegraph";
+
+export function prismaModels(g: TypegraphBuilderArgs, db: PrismaRuntime) {
+  return {
+    User: t.struct(
+      {
+        id: t.uuid({ config: { auto: true } }).id(),
+        role: t.enum_(["USER", "ADMIN"], { config: { default: "USER" } }),
+        Post: db.link(t.list(g.ref("Post")), "PostToUser"),
+      },
+      { name: "User" },
+    ),
+    Post: t.struct(
+      {
+        slug: t.string(),
+        User: db.link(g.ref("User"), "PostToUser", { fkey: true }),
+        createdAt: t.datetime().optional().inject({ [CREATE]: "now" }),
+      },
+      { name: "Post", config: { id: ["User", "slug"] } },
+    ),
+  };
+}
+"#
+        );
+        Ok(())
+    }
+}
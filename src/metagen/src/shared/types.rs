@@ -36,3 +36,82 @@ pub fn type_body_required(ty: &Type) -> bool {
         _ => true,
     }
 }
+
+/// Deprecation notice of a field: set on its type, or on the item of its
+/// optional type
+pub fn field_deprecation(ty: &Type) -> Option<String> {
+    if let Some(deprecation) = ty.deprecated() {
+        return Some(deprecation.message());
+    }
+    match ty {
+        Type::Optional(ty) => field_deprecation(ty.item()),
+        _ => None,
+    }
+}
+
+/// Documentation of a field, from the description of its type, or of
+/// the item of its optional type
+pub fn field_docs(ty: &Type) -> Vec<String> {
+    match (&ty.base().description, ty) {
+        (Some(description), _) => description.lines().map(|line| line.to_string()).collect(),
+        (None, Type::Optional(ty)) => field_docs(ty.item()),
+        (None, _) => vec![],
+    }
+}
+
+/// Lines of a deprecation notice, for `write_doc_lines`; the first line is
+/// prefixed with `label`
+pub fn deprecation_lines(label: &str, message: &str) -> Vec<String> {
+    let mut lines = message.lines();
+    let first = format!("{label}{}", lines.next().unwrap_or_default());
+    std::iter::once(first.trim_end().to_string())
+        .chain(lines.map(|line| line.to_string()))
+        .collect()
+}
+
+/// Deprecated values of a string enum, as (JSON-serialized value, notice)
+pub fn deprecated_values(ty: &StringType) -> Vec<(String, String)> {
+    ty.deprecated_values
+        .iter()
+        .flatten()
+        .map(|(value, deprecation)| (value.clone(), deprecation.message()))
+        .collect()
+}
+
+/// Documentation of a type, from its description and examples, as lines
+pub fn type_docs(base: &TypeBase) -> Vec<String> {
+    let mut lines = base
+        .description
+        .iter()
+        .flat_map(|description| description.lines())
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+    if let Some(examples) = base.examples.as_ref().filter(|e| !e.is_empty()) {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push("Examples:".to_string());
+        lines.extend(
+            examples
+                .iter()
+                .map(|example| format!("- `{}`", serde_json::to_string(example).unwrap())),
+        );
+    }
+    lines
+}
+
+/// Write documentation lines with a line comment prefix, e.g. `///` or `#`
+pub fn write_doc_lines(
+    out: &mut impl std::fmt::Write,
+    docs: &[String],
+    prefix: &str,
+) -> std::fmt::Result {
+    for line in docs {
+        if line.is_empty() {
+            writeln!(out, "{prefix}")?;
+        } else {
+            writeln!(out, "{prefix} {line}")?;
+        }
+    }
+    Ok(())
+}
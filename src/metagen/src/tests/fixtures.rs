@@ -206,6 +206,9 @@ pub fn default_type_node_base() -> tg_schema::TypeNodeBase {
         title: String::new(),
         description: None,
         enumeration: None,
+        examples: None,
+        deprecated: None,
+        deprecated_values: None,
     }
 }
 
@@ -30,6 +30,9 @@ impl InputResolver for TestCtx {
             } => Ok(GeneratorInputResolved::FdkTemplate {
                 template: load_fdk_template(default, override_path.as_deref()).await?,
             }),
+            GeneratorInputOrder::PrismaIntrospection { .. } => {
+                bail!("database introspection is only available through `meta gen`")
+            }
         }
     }
 }
@@ -65,6 +68,22 @@ async fn load_fdk_template(
     Ok(FdkTemplate { entries })
 }
 
+/// Whether a toolchain is on the `PATH`, for the e2e tests that build with
+/// a toolchain outside of the dev environment.
+/// The toolchain is required on CI, so that the tests are not skipped there.
+pub fn has_toolchain(command: &str) -> anyhow::Result<bool> {
+    let found = std::process::Command::new(command)
+        .arg("version")
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if !found && std::env::var_os("CI").is_some() {
+        bail!("{command} is required on CI");
+    }
+    Ok(found)
+}
+
 #[derive(Debug)]
 pub struct BuildArgs {
     pub path: PathBuf,
@@ -0,0 +1,310 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Host for the external generators implemented as wasm components.
+//! See `wit/metagen.wit` for the interface.
+
+use crate::interlude::*;
+use crate::*;
+use std::sync::Mutex;
+use wasmtime::component::{Component, Linker};
+use wit::exports::metatype::metagen::generator::{InputOrder, InputResolved};
+
+mod wit {
+    wasmtime::component::bindgen!({
+        world: "metagen-plugin",
+        path: "wit/",
+    });
+}
+
+struct PluginState {
+    table: wasmtime_wasi::ResourceTable,
+    ctx: wasmtime_wasi::WasiCtx,
+}
+
+impl wasmtime_wasi::WasiView for PluginState {
+    fn table(&mut self) -> &mut wasmtime_wasi::ResourceTable {
+        &mut self.table
+    }
+
+    fn ctx(&mut self) -> &mut wasmtime_wasi::WasiCtx {
+        &mut self.ctx
+    }
+}
+
+/// Fuel available to each call into a component unless configured, enough
+/// for the generation of large typegraphs while stopping runaway loops
+pub const DEFAULT_FUEL: u64 = 50_000_000_000;
+
+pub struct WasmGenerator {
+    bindings: wit::MetagenPlugin,
+    store: Mutex<wasmtime::Store<PluginState>>,
+    config: String,
+    workspace_path: PathBuf,
+    fuel: u64,
+    // the component returns a result, so we
+    // query it once on load
+    bill: Vec<(String, InputOrder)>,
+}
+
+impl WasmGenerator {
+    pub fn load(
+        wasm_path: &Path,
+        workspace_path: &Path,
+        fuel: Option<u64>,
+        config: serde_json::Value,
+    ) -> anyhow::Result<Self> {
+        let fuel = fuel.unwrap_or(DEFAULT_FUEL);
+        let mut engine_config = wasmtime::Config::new();
+        engine_config.wasm_component_model(true);
+        engine_config.consume_fuel(true);
+        let engine = wasmtime::Engine::new(&engine_config).map_err(anyhow_to_eyre!())?;
+
+        let component = Component::from_file(&engine, wasm_path)
+            .map_err(anyhow_to_eyre!())
+            .wrap_err_with(|| format!("error loading generator component at {wasm_path:?}"))?;
+
+        let mut linker = Linker::<PluginState>::new(&engine);
+        // the generators have no access to the filesystem or the network,
+        // only stderr is made available for debugging
+        wasmtime_wasi::add_to_linker_sync(&mut linker).map_err(anyhow_to_eyre!())?;
+        let mut store = wasmtime::Store::new(
+            &engine,
+            PluginState {
+                table: wasmtime_wasi::ResourceTable::new(),
+                ctx: wasmtime_wasi::WasiCtxBuilder::new()
+                    .inherit_stderr()
+                    .build(),
+            },
+        );
+        let bindings = wit::MetagenPlugin::instantiate(&mut store, &component, &linker)
+            .map_err(anyhow_to_eyre!())
+            .wrap_err_with(|| format!("error instantiating generator at {wasm_path:?}"))?;
+
+        let config = serde_json::to_string(&config)?;
+        store.set_fuel(fuel).map_err(anyhow_to_eyre!())?;
+        let bill = bindings
+            .metatype_metagen_generator()
+            .call_bill_of_inputs(&mut store, &config)
+            .map_err(anyhow_to_eyre!())
+            .wrap_err_with(|| format!("error running generator at {wasm_path:?}"))?
+            .map_err(|err| format_err!("generator at {wasm_path:?} rejected config: {err}"))?;
+
+        Ok(Self {
+            bindings,
+            store: Mutex::new(store),
+            config,
+            workspace_path: workspace_path.to_path_buf(),
+            fuel,
+            bill,
+        })
+    }
+}
+
+impl Plugin for WasmGenerator {
+    fn bill_of_inputs(&self) -> IndexMap<String, GeneratorInputOrder> {
+        self.bill
+            .iter()
+            .map(|(name, order)| {
+                let order = match order {
+                    InputOrder::TypegraphFromTypegate(name) => {
+                        GeneratorInputOrder::TypegraphFromTypegate { name: name.clone() }
+                    }
+                    InputOrder::TypegraphFromPath(order) => {
+                        GeneratorInputOrder::TypegraphFromPath {
+                            path: self.workspace_path.join(&order.path),
+                            name: order.name.clone(),
+                        }
+                    }
+                    InputOrder::PrismaIntrospection(order) => {
+                        GeneratorInputOrder::PrismaIntrospection {
+                            typegraph: order.typegraph.clone(),
+                            runtime: order.runtime.clone(),
+                        }
+                    }
+                };
+                (name.clone(), order)
+            })
+            .collect()
+    }
+
+    fn generate(
+        &self,
+        inputs: IndexMap<String, GeneratorInputResolved>,
+    ) -> anyhow::Result<GeneratorOutput> {
+        let inputs = inputs
+            .into_iter()
+            .map(|(name, input)| {
+                let input = match input {
+                    GeneratorInputResolved::TypegraphFromTypegate { raw }
+                    | GeneratorInputResolved::TypegraphFromPath { raw } => {
+                        InputResolved::Typegraph(serde_json::to_string(&*raw)?)
+                    }
+                    GeneratorInputResolved::PrismaIntrospection { raw } => {
+                        InputResolved::PrismaIntrospection(serde_json::to_string(&*raw)?)
+                    }
+                    GeneratorInputResolved::FdkTemplate { .. } => {
+                        bail!("fdk templates are not supported for external generators")
+                    }
+                };
+                Ok((name, input))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut store = self.store.lock().expect("poisoned lock");
+        store.set_fuel(self.fuel).map_err(anyhow_to_eyre!())?;
+        let files = self
+            .bindings
+            .metatype_metagen_generator()
+            .call_generate(&mut *store, &self.config, &inputs)
+            .map_err(anyhow_to_eyre!())
+            .wrap_err("error running external generator")?
+            .map_err(|err| format_err!("external generator failed: {err}"))?;
+
+        let files = files
+            .into_iter()
+            .map(|file| {
+                let path = workspace_relative_path(&file.path)?;
+                Ok((
+                    self.workspace_path.join(path),
+                    GeneratedFile {
+                        contents: file.contents,
+                        overwrite: file.overwrite,
+                    },
+                ))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(GeneratorOutput(files))
+    }
+}
+
+/// Validate the path of a file emitted by an external generator:
+/// it must stay inside the workspace directory.
+fn workspace_relative_path(path: &str) -> anyhow::Result<&Path> {
+    let path = Path::new(path);
+    let is_relative = path.components().all(|comp| {
+        matches!(
+            comp,
+            std::path::Component::Normal(_) | std::path::Component::CurDir
+        )
+    });
+    if path.as_os_str().is_empty() || !is_relative {
+        bail!(
+            "external generator output path {path:?} must be relative to the workspace, without `..`"
+        );
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tg_schema::runtimes::prisma::PrismaIntrospection;
+
+    #[test]
+    fn output_paths_test() -> anyhow::Result<()> {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/plugin.wat");
+        let generator = WasmGenerator::load(
+            &fixture,
+            Path::new("/workspace"),
+            None,
+            serde_json::json!({}),
+        )?;
+        assert!(generator.bill_of_inputs().is_empty());
+
+        let generate = |name: &str| {
+            let raw = Arc::new(PrismaIntrospection {
+                models: vec![],
+                enums: vec![],
+                warnings: vec![],
+            });
+            generator.generate(
+                [(
+                    name.to_owned(),
+                    GeneratorInputResolved::PrismaIntrospection { raw },
+                )]
+                .into_iter()
+                .collect(),
+            )
+        };
+
+        let GeneratorOutput(files) = generate("out/ok.txt")?;
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec![Path::new("/workspace/out/ok.txt")]
+        );
+        for path in ["../escape.txt", "out/../../escape.txt", "/etc/passwd", ""] {
+            assert!(generate(path).is_err(), "{path:?} should be rejected");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn typegraph_path_test() -> anyhow::Result<()> {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/plugin.wat");
+        let mut generator = WasmGenerator::load(
+            &fixture,
+            Path::new("/workspace"),
+            None,
+            serde_json::json!({}),
+        )?;
+        generator.bill = vec![(
+            "tg".to_owned(),
+            InputOrder::TypegraphFromPath(
+                wit::exports::metatype::metagen::generator::TypegraphFromPath {
+                    path: "typegraphs/example.py".to_owned(),
+                    name: None,
+                },
+            ),
+        )];
+        assert!(matches!(
+            &generator.bill_of_inputs()["tg"],
+            GeneratorInputOrder::TypegraphFromPath { path, .. }
+                if path == Path::new("/workspace/typegraphs/example.py")
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn fuel_test() -> anyhow::Result<()> {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/plugin.wat");
+        assert!(WasmGenerator::load(
+            &fixture,
+            Path::new("/workspace"),
+            Some(1),
+            serde_json::json!({})
+        )
+        .is_err());
+
+        let generator = WasmGenerator::load(
+            &fixture,
+            Path::new("/workspace"),
+            Some(1_000),
+            serde_json::json!({}),
+        )?;
+        let generate = |count: usize| {
+            let raw = Arc::new(PrismaIntrospection {
+                models: vec![],
+                enums: vec![],
+                warnings: vec![],
+            });
+            generator.generate(
+                (0..count)
+                    .map(|idx| {
+                        (
+                            format!("out/{idx}.txt"),
+                            GeneratorInputResolved::PrismaIntrospection { raw: raw.clone() },
+                        )
+                    })
+                    .collect(),
+            )
+        };
+        // the fuel is reset on each call
+        for _ in 0..3 {
+            assert_eq!(generate(1)?.0.len(), 1);
+        }
+        let err = generate(1000).unwrap_err();
+        assert!(format!("{err:?}").contains("fuel"), "{err:?}");
+        Ok(())
+    }
+}
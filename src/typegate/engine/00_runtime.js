@@ -34,10 +34,14 @@ const Meta = {
     unregisterEngine: getOp("op_prisma_unregister_engine"),
     query: getOp("op_prisma_query"),
     diff: getOp("op_prisma_diff"),
+    introspect: getOp("op_prisma_introspect"),
+    metrics: getOp("op_prisma_metrics"),
     apply: getOp("op_prisma_apply"),
     deploy: getOp("op_prisma_deploy"),
     create: getOp("op_prisma_create"),
     reset: getOp("op_prisma_reset"),
+    squash: getOp("op_prisma_squash"),
+    baseline: getOp("op_prisma_baseline"),
     unpack: getOp("op_unpack"),
     archive: getOp("op_archive"),
   },
@@ -6,10 +6,15 @@ import type {
   GrpcRegisterInput,
   ParsedDiff,
   PrismaApplyOut,
+  PrismaBaselineOut,
   PrismaCreateOut,
   PrismaDeployOut,
+  PrismaIntrospectInp,
+  PrismaIntrospectOut,
+  PrismaMetricsOut,
   PrismaQueryInp,
   PrismaRegisterEngineInp,
+  PrismaSquashOut,
   TemporalRegisterInput,
   TemporalWorkflowDescribeInput,
   TemporalWorkflowDescribeOutput,
@@ -318,6 +323,44 @@ export async function prisma_diff(
   return await Meta.prisma.diff(a0);
 }
 
+export type PrismaIntrospectResult =
+  | {
+    Err: {
+      message: string;
+    };
+  }
+  | { Ok: PrismaIntrospectOut };
+
+export async function prisma_introspect(
+  a0: PrismaIntrospectInp,
+): Promise<PrismaIntrospectResult> {
+  try {
+    const res = await Meta.prisma.introspect(a0);
+    return { Ok: res };
+  } catch (err: any) {
+    return { Err: { message: err.toString() } };
+  }
+}
+
+export type PrismaMetricsResult =
+  | {
+    Err: {
+      message: string;
+    };
+  }
+  | { Ok: PrismaMetricsOut };
+
+export async function prisma_metrics(
+  engine_name: string,
+): Promise<PrismaMetricsResult> {
+  try {
+    const res = await Meta.prisma.metrics(engine_name);
+    return { Ok: res };
+  } catch (err: any) {
+    return { Err: { message: err.toString() } };
+  }
+}
+
 export type PrismaApplyResult =
   | {
     Err: {
@@ -414,6 +457,55 @@ export async function prisma_reset(
     return { Err: { message: err.toString() } };
   }
 }
+export type PrismaSquashInp = {
+  datasource: string;
+  datamodel: string;
+  migrations: string | undefined | null;
+  migration_name: string;
+  mark_applied: boolean;
+};
+export type PrismaSquashResult =
+  | {
+    Err: {
+      message: string;
+    };
+  }
+  | { Ok: PrismaSquashOut };
+
+export async function prisma_squash(
+  a0: PrismaSquashInp,
+): Promise<PrismaSquashResult> {
+  try {
+    const res = await Meta.prisma.squash(a0);
+    return { Ok: res };
+  } catch (err: any) {
+    return { Err: { message: err.toString() } };
+  }
+}
+export type PrismaBaselineInp = {
+  datasource: string;
+  datamodel: string;
+  migrations: string | undefined | null;
+  migration_name: string;
+};
+export type PrismaBaselineResult =
+  | {
+    Err: {
+      message: string;
+    };
+  }
+  | { Ok: PrismaBaselineOut };
+
+export async function prisma_baseline(
+  a0: PrismaBaselineInp,
+): Promise<PrismaBaselineResult> {
+  try {
+    const res = await Meta.prisma.baseline(a0);
+    return { Ok: res };
+  } catch (err: any) {
+    return { Err: { message: err.toString() } };
+  }
+}
 export type UnpackInp = {
   dest: string;
   migrations: string;
@@ -16,10 +16,14 @@ export type MetaNS = {
     diff: (
       inp: PrismaDiffInp,
     ) => Promise<[string, ParsedDiff[]] | undefined | null>;
+    introspect: (inp: PrismaIntrospectInp) => Promise<PrismaIntrospectOut>;
+    metrics: (engine_name: string) => Promise<PrismaMetricsOut>;
     apply: (inp: PrismaDevInp) => Promise<PrismaApplyOut>;
     deploy: (inp: PrismaDeployInp) => Promise<PrismaDeployOut>;
     create: (inp: PrismaCreateInp) => Promise<PrismaCreateOut>;
     reset: (datasource: string) => Promise<boolean>;
+    squash: (inp: PrismaSquashInp) => Promise<PrismaSquashOut>;
+    baseline: (inp: PrismaBaselineInp) => Promise<PrismaBaselineOut>;
     unpack: (inp: UnpackInp) => void;
     archive: (path: string) => string | undefined | null;
   };
@@ -102,6 +106,7 @@ interface WasmInput {
 interface PrismaRegisterEngineInp {
   datamodel: string;
   engine_name: string;
+  slow_query_threshold_ms?: number;
 }
 interface PrismaQueryInp {
   engine_name: string;
@@ -148,6 +153,28 @@ interface PrismaCreateOut {
   migrations: string | undefined | null;
   apply_err: string | undefined | null;
 }
+interface PrismaSquashInp {
+  datasource: string;
+  datamodel: string;
+  migrations: string | undefined | null;
+  migration_name: string;
+  mark_applied: boolean;
+}
+interface PrismaSquashOut {
+  squashed_migration_name: string | undefined | null;
+  squashed_count: number;
+  migrations: string | undefined | null;
+}
+interface PrismaBaselineInp {
+  datasource: string;
+  datamodel: string;
+  migrations: string | undefined | null;
+  migration_name: string;
+}
+interface PrismaBaselineOut {
+  baseline_migration_name: string;
+  migrations: string;
+}
 interface PrismaDeployInp {
   datasource: string;
   datamodel: string;
@@ -224,6 +251,45 @@ interface WasiVmInitConfig {
   wasi_mod_path: string;
   preopens: Array<string>;
 }
+interface PrismaIntrospectInp {
+  datasource: string;
+}
+interface PrismaIntrospectOut {
+  datamodel: string;
+  introspection: {
+    models: Array<{
+      name: string;
+      fields: Array<Record<string, unknown>>;
+      id_fields: Array<string>;
+      unique_constraints: Array<Array<string>>;
+    }>;
+    enums: Array<{ name: string; values: Array<string> }>;
+    warnings: Array<string>;
+  };
+}
+interface PrismaMetricsOut {
+  connected: boolean;
+  queries: { total: number; errors: number };
+  latency: {
+    mean_ms: number;
+    max_ms: number;
+    p50_ms: number;
+    p95_ms: number;
+    p99_ms: number;
+  };
+  in_flight: { current: number; max: number };
+  pool: { open: number; busy: number; idle: number; waiting: number };
+  slow_queries: {
+    threshold_ms: number;
+    count: number;
+    samples: Array<{
+      model?: string | null;
+      action: string;
+      duration_ms: number;
+      timestamp: string;
+    }>;
+  };
+}
 interface TableDiff {
   column: string;
   diff: {
@@ -32,10 +32,14 @@ deno_core::extension!(
         prisma::op_prisma_unregister_engine,
         prisma::op_prisma_query,
         prisma::op_prisma_diff,
+        prisma::op_prisma_introspect,
+        prisma::op_prisma_metrics,
         prisma::op_prisma_apply,
         prisma::op_prisma_deploy,
         prisma::op_prisma_create,
         prisma::op_prisma_reset,
+        prisma::op_prisma_squash,
+        prisma::op_prisma_baseline,
         prisma::op_unpack,
         prisma::op_archive,
         wit_wire::op_wit_wire_init,
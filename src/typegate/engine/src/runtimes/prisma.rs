@@ -5,6 +5,8 @@
 
 pub mod engine;
 pub mod engine_import;
+pub mod introspection;
+pub mod metrics;
 pub mod migration;
 pub mod utils;
 
@@ -14,12 +16,14 @@ use dashmap::DashMap;
 use deno_core::OpState;
 
 use self::migration::{
-    MigrationContextBuilder, ParsedDiff, PrismaApplyResult, PrismaCreateResult, PrismaDeployOut,
+    MigrationContextBuilder, ParsedDiff, PrismaApplyResult, PrismaBaselineResult,
+    PrismaCreateResult, PrismaDeployOut, PrismaSquashResult,
 };
 
 #[derive(Clone)]
 pub struct Ctx {
     pub engines: Arc<DashMap<String, engine_import::QueryEngine>>,
+    pub metrics: Arc<DashMap<String, Arc<metrics::EngineMetrics>>>,
     pub tmp_dir: Arc<Path>,
 }
 
@@ -27,6 +31,7 @@ impl Ctx {
     pub fn new(tmp_dir: Arc<Path>) -> Self {
         Self {
             engines: Default::default(),
+            metrics: Default::default(),
             tmp_dir,
         }
     }
@@ -42,6 +47,8 @@ fn reformat_datamodel(datamodel: &str) -> Option<String> {
 pub struct PrismaRegisterEngineInp {
     datamodel: String,
     engine_name: String,
+    #[serde(default)]
+    slow_query_threshold_ms: Option<u64>,
 }
 
 #[deno_core::op2(async)]
@@ -59,7 +66,12 @@ pub async fn op_prisma_register_engine(
         state.borrow::<Ctx>().clone()
     };
 
-    engine::register_engine(&ctx, datamodel, input.engine_name)
+    let slow_query_threshold = input
+        .slow_query_threshold_ms
+        .map(std::time::Duration::from_millis)
+        .unwrap_or(metrics::DEFAULT_SLOW_QUERY_THRESHOLD);
+
+    engine::register_engine(&ctx, datamodel, input.engine_name, slow_query_threshold)
         .await
         .tap_err(|e| error!("Error registering engine: {:?}", e))
         .map_err(OpErr::map())
@@ -76,6 +88,7 @@ pub async fn op_prisma_unregister_engine(
     let (_, engine) = {
         let state = state.borrow();
         let ctx = state.borrow::<Ctx>();
+        ctx.metrics.remove(&engine_name);
         ctx.engines.remove(&engine_name).with_context(|| {
             format!("Could not remove engine {:?}: entry not found.", {
                 engine_name
@@ -113,6 +126,32 @@ pub async fn op_prisma_query(
         .map_err(OpErr::map())
 }
 
+// metrics
+
+#[derive(Serialize)]
+#[serde(crate = "serde")]
+pub struct PrismaMetricsOut {
+    connected: bool,
+    #[serde(flatten)]
+    metrics: metrics::EngineMetricsSnapshot,
+}
+
+#[deno_core::op2(async)]
+#[serde]
+pub async fn op_prisma_metrics(
+    state: Rc<RefCell<OpState>>,
+    #[string] engine_name: String,
+) -> Result<PrismaMetricsOut, OpErr> {
+    let ctx = {
+        let state = state.borrow();
+        state.borrow::<Ctx>().clone()
+    };
+    engine::metrics(&ctx, &engine_name)
+        .await
+        .map(|(connected, metrics)| PrismaMetricsOut { connected, metrics })
+        .map_err(OpErr::map())
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(crate = "serde")]
 pub struct PrismaDiffInp {
@@ -135,6 +174,23 @@ pub async fn op_prisma_diff(
         .map_err(OpErr::map())
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(crate = "serde")]
+pub struct PrismaIntrospectInp {
+    datasource: String,
+}
+
+#[tracing::instrument(level = "debug")]
+#[deno_core::op2(async)]
+#[serde]
+pub async fn op_prisma_introspect(
+    #[serde] input: PrismaIntrospectInp,
+) -> Result<introspection::PrismaIntrospectOut, OpErr> {
+    introspection::introspect(input.datasource)
+        .await
+        .map_err(OpErr::map())
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(crate = "serde")]
 pub struct PrismaDevInp {
@@ -249,6 +305,71 @@ pub async fn op_prisma_reset(
         .map_err(OpErr::map())
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(crate = "serde")]
+pub struct PrismaSquashInp {
+    datasource: String,
+    datamodel: String,
+    migrations: Option<String>,
+    migration_name: String,
+    mark_applied: bool,
+}
+
+#[tracing::instrument(ret, level = "debug", skip(state))]
+#[deno_core::op2(async)]
+#[serde]
+pub async fn op_prisma_squash(
+    state: Rc<RefCell<OpState>>,
+    #[serde] input: PrismaSquashInp,
+) -> Result<PrismaSquashResult, OpErr> {
+    let datamodel = reformat_datamodel(&input.datamodel)
+        .context("Error formatting datamodel")
+        .map_err(OpErr::map())?;
+    let tmp_dir = {
+        let state = state.borrow();
+        state.borrow::<Ctx>().tmp_dir.clone()
+    };
+    MigrationContextBuilder::new(input.datasource, datamodel, tmp_dir)
+        .with_migrations(input.migrations)
+        .build()
+        .map_err(OpErr::map())?
+        .squash(input.migration_name, input.mark_applied)
+        .await
+        .map_err(OpErr::map())
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(crate = "serde")]
+pub struct PrismaBaselineInp {
+    datasource: String,
+    datamodel: String,
+    migrations: Option<String>,
+    migration_name: String,
+}
+
+#[tracing::instrument(ret, level = "debug", skip(state))]
+#[deno_core::op2(async)]
+#[serde]
+pub async fn op_prisma_baseline(
+    state: Rc<RefCell<OpState>>,
+    #[serde] input: PrismaBaselineInp,
+) -> Result<PrismaBaselineResult, OpErr> {
+    let datamodel = reformat_datamodel(&input.datamodel)
+        .context("Error formatting datamodel")
+        .map_err(OpErr::map())?;
+    let tmp_dir = {
+        let state = state.borrow();
+        state.borrow::<Ctx>().tmp_dir.clone()
+    };
+    MigrationContextBuilder::new(input.datasource, datamodel, tmp_dir)
+        .with_migrations(input.migrations)
+        .build()
+        .map_err(OpErr::map())?
+        .baseline(input.migration_name)
+        .await
+        .map_err(OpErr::map())
+}
+
 #[derive(Deserialize)]
 #[serde(crate = "serde")]
 pub struct UnpackInp {
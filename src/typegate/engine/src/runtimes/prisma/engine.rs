@@ -1,17 +1,20 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 use crate::interlude::*;
-use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr, time::Duration};
 
 use anyhow::{Context, Result};
 use query_core::protocol::EngineProtocol;
 
+use super::metrics::{is_error_response, EngineMetrics, EngineMetricsSnapshot, PoolUsage};
+
 pub(super) const CONFIG_DIR: &str = ".";
 
 pub async fn register_engine(
     ctx: &super::Ctx,
     datamodel: String,
     engine_name: String,
+    slow_query_threshold: Duration,
 ) -> Result<()> {
     let conf = super::engine_import::ConstructorOptions {
         datamodel,
@@ -26,6 +29,10 @@ pub async fn register_engine(
     let engine = super::engine_import::QueryEngine::new(conf)
         .with_context(|| format!("Error while registering engine {engine_name}"))?;
     // do not check connection here as on typegate reload this could crash the whole system
+    ctx.metrics.insert(
+        engine_name.clone(),
+        Arc::new(EngineMetrics::new(slow_query_threshold)),
+    );
     ctx.engines.insert(engine_name, engine);
     Ok(())
 }
@@ -46,6 +53,28 @@ pub async fn query(
             .await
             .with_context(|| format!("Error while connecting engine {engine_name}"))?;
     }
-    let res = engine.query(serde_json::to_string(&query)?, None).await?;
-    Ok(res)
+    let metrics = ctx
+        .metrics
+        .get(&engine_name)
+        .map(|m| m.value().clone())
+        .with_context(|| format!("Could not find metrics of engine '{engine_name}'"))?;
+
+    let timer = metrics.start();
+    let res = engine.query(serde_json::to_string(&query)?, None).await;
+    let success = res.as_ref().is_ok_and(|res| !is_error_response(res));
+    timer.finish(&query, success);
+    Ok(res?)
+}
+
+pub async fn metrics(ctx: &super::Ctx, engine_name: &str) -> Result<(bool, EngineMetricsSnapshot)> {
+    let engine = ctx
+        .engines
+        .get(engine_name)
+        .with_context(|| format!("Could not find engine '{engine_name}'"))?;
+    let metrics = ctx
+        .metrics
+        .get(engine_name)
+        .with_context(|| format!("Could not find metrics of engine '{engine_name}'"))?;
+    let pool = PoolUsage::from_registry_json(&engine.metrics().to_json(Default::default()));
+    Ok((engine.is_connected().await, metrics.snapshot(pool)))
 }
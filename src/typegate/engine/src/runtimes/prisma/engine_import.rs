@@ -27,6 +27,7 @@ use query_core::{
     schema::{self, QuerySchema},
     QueryExecutor, TransactionOptions, TxId,
 };
+use query_engine_metrics::MetricRegistry;
 use request_handlers::{load_executor, render_graphql_schema, RequestBody, RequestHandler};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -37,6 +38,8 @@ use std::{
 };
 use thiserror::Error;
 use tokio::sync::RwLock;
+use tracing::{instrument::WithSubscriber, Dispatch};
+use tracing_subscriber::layer::SubscriberExt;
 
 type Result<T> = std::result::Result<T, ApiError>;
 type Executor = Box<dyn query_core::QueryExecutor + Send + Sync>;
@@ -44,6 +47,10 @@ type Executor = Box<dyn query_core::QueryExecutor + Send + Sync>;
 /// The main query engine used by JS
 pub struct QueryEngine {
     inner: RwLock<Inner>,
+    /// Metrics recorded by the core, including the connection pool gauges
+    metrics: MetricRegistry,
+    /// The core reports its metrics as tracing events, collected by `metrics`
+    dispatcher: Dispatch,
 }
 
 /// The state of the engine.
@@ -70,7 +77,6 @@ pub struct ConnectedEngine {
     executor: Executor,
     config_dir: PathBuf,
     env: HashMap<String, String>,
-    // metrics: Option<MetricRegistry>,
     engine_protocol: EngineProtocol,
 }
 
@@ -177,7 +183,6 @@ impl QueryEngine {
             .validate_that_one_datasource_is_provided()
             .map_err(|errors| ApiError::conversion(errors, schema.db.source(FileId(0))))?;
 
-        // let enable_metrics = config.preview_features().contains(PreviewFeature::Metrics);
         // let enable_tracing = config.preview_features().contains(PreviewFeature::Tracing);
         let engine_protocol = engine_protocol.unwrap_or(EngineProtocol::Json);
 
@@ -188,8 +193,14 @@ impl QueryEngine {
             env,
         };
 
+        query_engine_metrics::setup();
+        let metrics = MetricRegistry::new();
+        let dispatcher = Dispatch::new(tracing_subscriber::registry().with(metrics.clone()));
+
         Ok(Self {
             inner: RwLock::new(Inner::Builder(builder)),
+            metrics,
+            dispatcher,
         })
     }
 
@@ -258,6 +269,7 @@ impl QueryEngine {
                 engine_protocol: builder.engine_protocol,
             }) as Result<ConnectedEngine>
         }
+        .with_subscriber(self.dispatcher.clone())
         .await?;
 
         *inner = Inner::Connected(engine);
@@ -265,6 +277,10 @@ impl QueryEngine {
         Ok(())
     }
 
+    pub fn metrics(&self) -> &MetricRegistry {
+        &self.metrics
+    }
+
     pub async fn is_connected(&self) -> bool {
         matches!(*self.inner.read().await, Inner::Connected(_))
     }
@@ -301,7 +317,10 @@ impl QueryEngine {
             engine.query_schema(),
             engine.engine_protocol(),
         );
-        let response = handler.handle(query, tx_id.map(TxId::from), None).await;
+        let response = handler
+            .handle(query, tx_id.map(TxId::from), None)
+            .with_subscriber(self.dispatcher.clone())
+            .await;
 
         Ok(serde_json::to_string(&response)?)
     }
@@ -0,0 +1,285 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+// https://github.com/prisma/prisma-engines/blob/main/schema-engine/core/src/commands/introspect.rs
+
+use crate::interlude::*;
+use anyhow::{anyhow, Result};
+use psl::parser_database::{
+    walkers::{DefaultValueWalker, RefinedFieldWalker},
+    ScalarFieldType,
+};
+use psl::schema_ast::ast::{self, FieldArity};
+use schema_core::json_rpc::types::{IntrospectParams, SchemaContainer, SchemasContainer};
+use serde::Serialize;
+use std::collections::HashSet;
+use tg_schema::runtimes::prisma::{
+    Cardinality, IntrospectedDefault, IntrospectedEnum, IntrospectedField, IntrospectedFieldKind,
+    IntrospectedModel, IntrospectedRelation, PrismaIntrospection,
+};
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "serde")]
+pub struct PrismaIntrospectOut {
+    datamodel: String,
+    introspection: PrismaIntrospection,
+}
+
+pub async fn introspect(datasource: String) -> Result<PrismaIntrospectOut> {
+    let api = schema_core::schema_api(Some(datasource.clone()), None)?;
+    let res = api
+        .introspect(IntrospectParams {
+            schema: SchemasContainer {
+                files: vec![SchemaContainer {
+                    content: datasource,
+                    path: "schema_introspect.generated.prisma".into(),
+                }],
+            },
+            base_directory_path: super::engine::CONFIG_DIR.into(),
+            force: true,
+            composite_type_depth: -1,
+            namespaces: None,
+        })
+        .await
+        .map_err(|e| anyhow!(e.to_user_facing().message().to_string()))?;
+
+    let datamodel = res
+        .schema
+        .files
+        .into_iter()
+        .map(|f| f.content)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut introspection = parse_datamodel(&datamodel)?;
+    if let Some(warnings) = res.warnings {
+        introspection.warnings.push(warnings);
+    }
+
+    Ok(PrismaIntrospectOut {
+        datamodel,
+        introspection,
+    })
+}
+
+/// Read the models and enums of an introspected prisma schema.
+pub fn parse_datamodel(datamodel: &str) -> Result<PrismaIntrospection> {
+    let schema = psl::parse_schema(datamodel).map_err(|e| anyhow!(e))?;
+    let mut res = PrismaIntrospection::default();
+
+    for enum_ in schema.db.walk_enums() {
+        res.enums.push(IntrospectedEnum {
+            name: enum_.name().to_string(),
+            values: enum_.values().map(|v| v.name().to_string()).collect(),
+        });
+    }
+
+    for model in schema.db.walk_models() {
+        let model_name = model.name().to_string();
+        let primary_key = model.primary_key();
+        let id_field = primary_key
+            .filter(|pk| pk.is_defined_on_field())
+            .and_then(|pk| pk.fields().next())
+            .map(|f| f.name());
+        let unique_fields = model
+            .indexes()
+            .filter(|idx| idx.is_unique() && idx.is_defined_on_field())
+            .filter_map(|idx| idx.fields().next().map(|f| f.name()))
+            .collect::<HashSet<_>>();
+
+        let mut fields = vec![];
+        for field in model.fields() {
+            let name = field.name();
+            let field = match field.refine() {
+                RefinedFieldWalker::Scalar(sf) => {
+                    let field_type = match sf.scalar_field_type() {
+                        ScalarFieldType::BuiltInScalar(ty) => ty.as_str().to_string(),
+                        ScalarFieldType::Enum(id) => schema.db.walk(id).name().to_string(),
+                        _ => {
+                            res.warnings.push(format!(
+                                "model {model_name}: field {name:?} has an unsupported type; field skipped"
+                            ));
+                            continue;
+                        }
+                    };
+                    IntrospectedField {
+                        name: name.to_string(),
+                        kind: match sf.scalar_field_type() {
+                            ScalarFieldType::Enum(_) => IntrospectedFieldKind::Enum,
+                            _ => IntrospectedFieldKind::Scalar,
+                        },
+                        field_type,
+                        native_type: sf
+                            .raw_native_type()
+                            .map(|(_, native_type, _, _)| native_type.to_string()),
+                        cardinality: cardinality(sf.ast_field().arity),
+                        id: id_field == Some(name),
+                        unique: unique_fields.contains(name),
+                        updated_at: sf.is_updated_at(),
+                        default: sf.default_value().map(default_value),
+                        relation: None,
+                    }
+                }
+                RefinedFieldWalker::Relation(rf) => IntrospectedField {
+                    name: name.to_string(),
+                    kind: IntrospectedFieldKind::Relation,
+                    field_type: rf.related_model().name().to_string(),
+                    native_type: None,
+                    cardinality: cardinality(rf.ast_field().arity),
+                    id: false,
+                    unique: false,
+                    updated_at: false,
+                    default: None,
+                    relation: Some(IntrospectedRelation {
+                        name: rf.relation_name().to_string(),
+                        fields: rf
+                            .referencing_fields()
+                            .map(|fields| fields.map(|f| f.name().to_string()).collect())
+                            .unwrap_or_default(),
+                        references: rf
+                            .referenced_fields()
+                            .map(|fields| fields.map(|f| f.name().to_string()).collect())
+                            .unwrap_or_default(),
+                    }),
+                },
+            };
+            fields.push(field);
+        }
+
+        res.models.push(IntrospectedModel {
+            name: model_name,
+            fields,
+            id_fields: primary_key
+                .filter(|pk| !pk.is_defined_on_field())
+                .map(|pk| pk.fields().map(|f| f.name().to_string()).collect())
+                .unwrap_or_default(),
+            unique_constraints: model
+                .indexes()
+                .filter(|idx| idx.is_unique() && !idx.is_defined_on_field())
+                .map(|idx| idx.fields().map(|f| f.name().to_string()).collect())
+                .collect(),
+        });
+    }
+
+    Ok(res)
+}
+
+fn cardinality(arity: FieldArity) -> Cardinality {
+    match arity {
+        FieldArity::Required => Cardinality::One,
+        FieldArity::Optional => Cardinality::Optional,
+        FieldArity::List => Cardinality::Many,
+    }
+}
+
+fn default_value(default: DefaultValueWalker<'_>) -> IntrospectedDefault {
+    if default.is_autoincrement() {
+        return IntrospectedDefault::AutoIncrement;
+    }
+    if default.is_uuid() {
+        return IntrospectedDefault::Uuid;
+    }
+    if default.is_cuid() {
+        return IntrospectedDefault::Cuid;
+    }
+    if default.is_now() {
+        return IntrospectedDefault::Now;
+    }
+    let value = match default.value() {
+        ast::Expression::StringValue(s, _) => serde_json::Value::String(s.clone()),
+        // numbers and booleans; anything else is an enum value
+        ast::Expression::NumericValue(s, _) | ast::Expression::ConstantValue(s, _) => {
+            serde_json::from_str(s).unwrap_or_else(|_| serde_json::Value::String(s.clone()))
+        }
+        // dbgenerated(), sequence() and the other database side functions
+        _ => return IntrospectedDefault::DbGenerated,
+    };
+    IntrospectedDefault::Value { value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_datamodel() -> Result<()> {
+        let datamodel = r#"
+datasource db {
+  provider = "postgresql"
+  url      = "postgresql://localhost/db"
+}
+
+model Post {
+  id        Int      @id @default(autoincrement())
+  title     String   @db.VarChar(255)
+  published Boolean  @default(false)
+  authorId  String   @db.Uuid
+  User      User     @relation(fields: [authorId], references: [id], onDelete: Cascade)
+  createdAt DateTime @default(now()) @db.Timestamp(6)
+  updatedAt DateTime @updatedAt
+}
+
+model User {
+  id    String  @id @default(uuid()) @db.Uuid
+  email String  @unique
+  role  Role    @default(USER)
+  note  String? // a comment
+  Post  Post[]
+  site  String  @default("https://metatype.dev") // not a comment in the string
+}
+
+model Membership {
+  userId  Int
+  groupId Int
+
+  @@id([userId, groupId])
+  @@unique([groupId, userId], map: "membership_idx")
+}
+
+enum Role {
+  USER
+  ADMIN
+}
+"#;
+        let res = parse_datamodel(datamodel)?;
+        assert_eq!(res.models.len(), 3);
+        assert_eq!(res.enums.len(), 1);
+        assert_eq!(res.enums[0].values, vec!["USER", "ADMIN"]);
+        assert!(res.warnings.is_empty(), "{:?}", res.warnings);
+
+        let post = &res.models[0];
+        assert_eq!(post.fields.len(), 7);
+        assert!(post.fields[6].updated_at);
+        assert!(post.fields[0].id);
+        assert!(matches!(
+            post.fields[0].default,
+            Some(IntrospectedDefault::AutoIncrement)
+        ));
+        assert_eq!(post.fields[1].native_type.as_deref(), Some("VarChar"));
+        let author = &post.fields[4];
+        assert_eq!(author.kind, IntrospectedFieldKind::Relation);
+        let relation = author.relation.as_ref().unwrap();
+        assert_eq!(relation.name, "PostToUser");
+        assert_eq!(relation.fields, vec!["authorId"]);
+        assert_eq!(relation.references, vec!["id"]);
+
+        let user = &res.models[1];
+        assert!(user.fields[1].unique);
+        assert_eq!(user.fields[2].kind, IntrospectedFieldKind::Enum);
+        assert!(matches!(user.fields[3].cardinality, Cardinality::Optional));
+        assert!(matches!(user.fields[4].cardinality, Cardinality::Many));
+        assert_eq!(user.fields[4].relation.as_ref().unwrap().name, "PostToUser");
+        assert!(matches!(
+            &user.fields[5].default,
+            Some(IntrospectedDefault::Value { value }) if value == "https://metatype.dev"
+        ));
+
+        let membership = &res.models[2];
+        assert_eq!(membership.id_fields, vec!["userId", "groupId"]);
+        assert_eq!(
+            membership.unique_constraints,
+            vec![vec!["groupId".to_string(), "userId".to_string()]]
+        );
+        Ok(())
+    }
+}
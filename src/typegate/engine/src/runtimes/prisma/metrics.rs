@@ -0,0 +1,371 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::interlude::*;
+use serde::de::IgnoredAny;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of recent query durations used to compute the percentiles
+const LATENCY_WINDOW: usize = 1000;
+/// Number of slow queries kept as sample
+const SLOW_QUERY_SAMPLES: usize = 20;
+
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(1000);
+
+// gauges of the query engine metric registry
+const POOL_CONNECTIONS_OPEN: &str = "prisma_pool_connections_open";
+const POOL_CONNECTIONS_BUSY: &str = "prisma_pool_connections_busy";
+const POOL_CONNECTIONS_IDLE: &str = "prisma_pool_connections_idle";
+const CLIENT_QUERIES_WAIT: &str = "prisma_client_queries_wait";
+
+/// Query metrics of a single prisma engine, recorded by [super::engine::query].
+pub struct EngineMetrics {
+    slow_query_threshold: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    query_count: u64,
+    error_count: u64,
+    total_duration: Duration,
+    max_duration: Duration,
+    recent_durations: VecDeque<Duration>,
+    // queries awaiting the engine
+    in_flight: u64,
+    max_in_flight: u64,
+    slow_query_count: u64,
+    slow_queries: VecDeque<SlowQuery>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(crate = "serde")]
+pub struct SlowQuery {
+    model: Option<String>,
+    action: String,
+    duration_ms: f64,
+    timestamp: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "serde")]
+pub struct QueryCounts {
+    total: u64,
+    errors: u64,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "serde")]
+pub struct Latency {
+    mean_ms: f64,
+    max_ms: f64,
+    p50_ms: f64,
+    p95_ms: f64,
+    p99_ms: f64,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "serde")]
+pub struct InFlightQueries {
+    current: u64,
+    max: u64,
+}
+
+/// Connection pool state, as reported by the query engine
+#[derive(Serialize, Debug, Default, PartialEq)]
+#[serde(crate = "serde")]
+pub struct PoolUsage {
+    open: u64,
+    busy: u64,
+    idle: u64,
+    /// queries waiting for a connection
+    waiting: u64,
+}
+
+impl PoolUsage {
+    /// Read the pool gauges from the json export of a `MetricRegistry`
+    pub fn from_registry_json(metrics: &serde_json::Value) -> Self {
+        let gauge = |key: &str| -> u64 {
+            metrics
+                .get("gauges")
+                .and_then(|g| g.as_array())
+                .into_iter()
+                .flatten()
+                .filter(|g| g.get("key").and_then(|k| k.as_str()) == Some(key))
+                .filter_map(|g| g.get("value").and_then(|v| v.as_f64()))
+                .sum::<f64>()
+                .max(0.0) as u64
+        };
+        Self {
+            open: gauge(POOL_CONNECTIONS_OPEN),
+            busy: gauge(POOL_CONNECTIONS_BUSY),
+            idle: gauge(POOL_CONNECTIONS_IDLE),
+            waiting: gauge(CLIENT_QUERIES_WAIT),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "serde")]
+pub struct SlowQueries {
+    threshold_ms: f64,
+    count: u64,
+    samples: Vec<SlowQuery>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "serde")]
+pub struct EngineMetricsSnapshot {
+    queries: QueryCounts,
+    latency: Latency,
+    in_flight: InFlightQueries,
+    pool: PoolUsage,
+    slow_queries: SlowQueries,
+}
+
+fn as_ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Nearest-rank percentile on sorted durations
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+// only the error markers of the response are checked, the data is skipped
+#[derive(Deserialize)]
+#[serde(crate = "serde")]
+struct ResponseShape {
+    errors: Option<IgnoredAny>,
+    #[serde(rename = "batchResult")]
+    batch_result: Option<Vec<BatchItemShape>>,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "serde")]
+struct BatchItemShape {
+    errors: Option<IgnoredAny>,
+}
+
+pub fn is_error_response(res: &str) -> bool {
+    match serde_json::from_str::<ResponseShape>(res) {
+        Ok(shape) => {
+            shape.errors.is_some()
+                || shape
+                    .batch_result
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|item| item.errors.is_some())
+        }
+        Err(_) => true,
+    }
+}
+
+/// Model and action of a json protocol query
+fn describe_query(query: &serde_json::Value) -> (Option<String>, String) {
+    if query.get("batch").is_some() {
+        return (None, "batch".to_string());
+    }
+    let model = query
+        .get("modelName")
+        .and_then(|m| m.as_str())
+        .map(|m| m.to_string());
+    let action = query
+        .get("action")
+        .and_then(|a| a.as_str())
+        .unwrap_or("unknown")
+        .to_string();
+    (model, action)
+}
+
+impl EngineMetrics {
+    pub fn new(slow_query_threshold: Duration) -> Self {
+        Self {
+            slow_query_threshold,
+            state: Default::default(),
+        }
+    }
+
+    /// Start tracking a query; the returned timer must be finished
+    /// with the outcome of the query.
+    pub fn start(&self) -> QueryTimer<'_> {
+        let mut state = self.state.lock().unwrap();
+        state.in_flight += 1;
+        state.max_in_flight = state.max_in_flight.max(state.in_flight);
+        QueryTimer {
+            metrics: self,
+            start: Instant::now(),
+            finished: false,
+        }
+    }
+
+    fn record(&self, duration: Duration, success: bool, query: Option<&serde_json::Value>) {
+        let mut state = self.state.lock().unwrap();
+        state.in_flight = state.in_flight.saturating_sub(1);
+        state.query_count += 1;
+        if !success {
+            state.error_count += 1;
+        }
+        state.total_duration += duration;
+        state.max_duration = state.max_duration.max(duration);
+        if state.recent_durations.len() == LATENCY_WINDOW {
+            state.recent_durations.pop_front();
+        }
+        state.recent_durations.push_back(duration);
+
+        if duration >= self.slow_query_threshold {
+            state.slow_query_count += 1;
+            let (model, action) = query
+                .map(describe_query)
+                .unwrap_or((None, "unknown".to_string()));
+            if state.slow_queries.len() == SLOW_QUERY_SAMPLES {
+                state.slow_queries.pop_front();
+            }
+            state.slow_queries.push_back(SlowQuery {
+                model,
+                action,
+                duration_ms: as_ms(duration),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+    }
+
+    pub fn snapshot(&self, pool: PoolUsage) -> EngineMetricsSnapshot {
+        let state = self.state.lock().unwrap();
+        let mut sorted = state.recent_durations.iter().copied().collect::<Vec<_>>();
+        sorted.sort_unstable();
+        let mean = if state.query_count == 0 {
+            Duration::ZERO
+        } else {
+            state.total_duration.div_f64(state.query_count as f64)
+        };
+
+        EngineMetricsSnapshot {
+            queries: QueryCounts {
+                total: state.query_count,
+                errors: state.error_count,
+            },
+            latency: Latency {
+                mean_ms: as_ms(mean),
+                max_ms: as_ms(state.max_duration),
+                p50_ms: as_ms(percentile(&sorted, 50.0)),
+                p95_ms: as_ms(percentile(&sorted, 95.0)),
+                p99_ms: as_ms(percentile(&sorted, 99.0)),
+            },
+            in_flight: InFlightQueries {
+                current: state.in_flight,
+                max: state.max_in_flight,
+            },
+            pool,
+            slow_queries: SlowQueries {
+                threshold_ms: as_ms(self.slow_query_threshold),
+                count: state.slow_query_count,
+                // most recent first
+                samples: state.slow_queries.iter().rev().cloned().collect(),
+            },
+        }
+    }
+}
+
+pub struct QueryTimer<'a> {
+    metrics: &'a EngineMetrics,
+    start: Instant,
+    finished: bool,
+}
+
+impl QueryTimer<'_> {
+    pub fn finish(mut self, query: &serde_json::Value, success: bool) {
+        self.finished = true;
+        self.metrics
+            .record(self.start.elapsed(), success, Some(query));
+    }
+}
+
+impl Drop for QueryTimer<'_> {
+    fn drop(&mut self) {
+        // the query future was dropped or failed before completion
+        if !self.finished {
+            self.metrics.record(self.start.elapsed(), false, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_metrics() {
+        let metrics = EngineMetrics::new(Duration::from_millis(100));
+        let query = serde_json::json!({ "modelName": "User", "action": "findMany" });
+
+        for ms in 1..=100 {
+            metrics.record(Duration::from_millis(ms), true, Some(&query));
+        }
+        metrics.record(Duration::from_millis(500), false, Some(&query));
+
+        let timer = metrics.start();
+        assert_eq!(metrics.snapshot(Default::default()).in_flight.current, 1);
+        drop(timer);
+
+        let snapshot = metrics.snapshot(Default::default());
+        assert_eq!(snapshot.queries.total, 102);
+        assert_eq!(snapshot.queries.errors, 2);
+        assert_eq!(snapshot.in_flight.current, 0);
+        assert_eq!(snapshot.in_flight.max, 1);
+        assert_eq!(snapshot.latency.p50_ms, 50.0);
+        assert_eq!(snapshot.latency.max_ms, 500.0);
+        assert_eq!(snapshot.slow_queries.count, 2);
+        assert_eq!(snapshot.slow_queries.samples.len(), 2);
+        assert_eq!(snapshot.slow_queries.samples[0].duration_ms, 500.0);
+        assert_eq!(
+            snapshot.slow_queries.samples[0].model.as_deref(),
+            Some("User")
+        );
+    }
+
+    #[test]
+    fn test_pool_usage() {
+        let metrics = serde_json::json!({
+            "counters": [
+                { "key": "prisma_pool_connections_opened_total", "labels": {}, "value": 9 }
+            ],
+            "gauges": [
+                { "key": "prisma_pool_connections_open", "labels": {}, "value": 4.0 },
+                { "key": "prisma_pool_connections_busy", "labels": {}, "value": 3.0 },
+                { "key": "prisma_pool_connections_idle", "labels": {}, "value": 1.0 },
+                { "key": "prisma_client_queries_wait", "labels": {}, "value": 2.0 }
+            ],
+            "histograms": []
+        });
+        assert_eq!(
+            PoolUsage::from_registry_json(&metrics),
+            PoolUsage {
+                open: 4,
+                busy: 3,
+                idle: 1,
+                waiting: 2
+            }
+        );
+        assert_eq!(
+            PoolUsage::from_registry_json(&serde_json::json!({})),
+            PoolUsage::default()
+        );
+    }
+
+    #[test]
+    fn test_error_response() {
+        assert!(!is_error_response(r#"{"data":{"findManyUser":[]}}"#));
+        assert!(is_error_response(r#"{"errors":[{"error":"oops"}]}"#));
+        assert!(!is_error_response(r#"{"batchResult":[{"data":{}}]}"#));
+        assert!(is_error_response(
+            r#"{"batchResult":[{"data":{}},{"errors":[]}]}"#
+        ));
+    }
+}
@@ -5,15 +5,16 @@
 // https://github.com/prisma/prisma-engines/blob/main/migration-engine/core/src/api.rs
 
 use crate::interlude::*;
-use anyhow::anyhow;
 use anyhow::Result;
+use anyhow::{anyhow, bail};
 use convert_case::{Case, Casing};
 use regex::Regex;
 use schema_core::json_rpc::types::SchemasContainer;
 use schema_core::json_rpc::types::SchemasWithConfigDir;
 use schema_core::json_rpc::types::{
     ApplyMigrationsInput, CreateMigrationInput, DevAction, DevDiagnosticInput, DevDiagnosticOutput,
-    DiffParams, DiffTarget, EvaluateDataLossInput, ListMigrationDirectoriesInput, SchemaContainer,
+    DiffParams, DiffTarget, EvaluateDataLossInput, ListMigrationDirectoriesInput,
+    MarkMigrationAppliedInput, SchemaContainer,
 };
 use schema_core::{CoreError, CoreResult, GenericApi};
 use serde::Serialize;
@@ -433,6 +434,181 @@ impl MigrationContext {
     }
 }
 
+#[derive(Serialize, Debug)]
+#[serde(crate = "serde")]
+pub struct PrismaSquashResult {
+    squashed_migration_name: Option<String>,
+    squashed_count: usize,
+    migrations: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "serde")]
+pub struct PrismaBaselineResult {
+    baseline_migration_name: String,
+    migrations: String,
+}
+
+impl MigrationContext {
+    async fn list_migrations(&self) -> Result<Vec<String>> {
+        let res = self
+            .api
+            .list_migration_directories(ListMigrationDirectoriesInput {
+                migrations_directory_path: self.migrations_dir.to_string(),
+            })
+            .await
+            .format_error()?;
+        Ok(res.migrations)
+    }
+
+    /// SQL script that brings an empty database to the current datamodel
+    async fn script_from_empty(&self) -> Result<Option<String>> {
+        let buffer = Arc::new(Mutex::new(Some(String::default())));
+        let api = schema_core::schema_api(
+            Some(self.builder.datasource.clone()),
+            Some(Arc::new(super::utils::StringBuffer::new(Arc::clone(
+                &buffer,
+            )))),
+        )
+        .format_error()?;
+
+        let res = api
+            .diff(DiffParams {
+                exit_code: None,
+                from: DiffTarget::Empty,
+                script: true,
+                shadow_database_url: None,
+                to: DiffTarget::SchemaDatamodel(self.schema()),
+            })
+            .await
+            .format_error()?;
+        assert!(res.exit_code == 0);
+
+        let script = buffer.lock().unwrap().take();
+        // FIXME the text might change??
+        Ok(script.filter(|script| !script.contains("This is an empty migration")))
+    }
+
+    /// Write the script into a new migration directory; returns the migration name
+    fn write_migration(&self, name: &str, script: &str) -> Result<String> {
+        let migration_name = format!(
+            "{}_{}",
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            name.to_case(Case::Snake)
+        );
+        let dir = self.migrations_dir.as_ref().join(&migration_name);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create migration directory {dir:?}"))?;
+        std::fs::write(dir.join("migration.sql"), script)
+            .with_context(|| format!("Failed to write migration script in {dir:?}"))?;
+        Ok(migration_name)
+    }
+
+    /// Fail if the database does not match the datamodel: marking a migration
+    /// that creates the datamodel as applied would hide the difference.
+    async fn ensure_no_drift(&self, action: &str) -> Result<()> {
+        let diff = diff(
+            self.builder.datasource.clone(),
+            self.builder.datamodel.clone(),
+            true,
+        )
+        .await?;
+        if let Some((script, _)) = diff {
+            bail!("{action}: the database does not match the datamodel:\n{script}");
+        }
+        Ok(())
+    }
+
+    async fn mark_applied(&self, migration_name: &str) -> Result<()> {
+        self.api
+            .mark_migration_applied(MarkMigrationAppliedInput {
+                migration_name: migration_name.to_string(),
+                migrations_directory_path: self.migrations_dir.to_string(),
+            })
+            .await
+            .format_error()?;
+        Ok(())
+    }
+}
+
+impl MigrationContext {
+    /// Replace all the migrations of the folder by a single migration that
+    /// creates the current datamodel from an empty database.
+    pub async fn squash(&self, name: String, mark_applied: bool) -> Result<PrismaSquashResult> {
+        trace!("Migrations::squash (name={name:?}, mark_applied={mark_applied})");
+        let existing = self.list_migrations().await?;
+        if existing.is_empty() {
+            return Ok(PrismaSquashResult {
+                squashed_migration_name: None,
+                squashed_count: 0,
+                migrations: None,
+            });
+        }
+
+        if mark_applied {
+            let action = "cannot mark the squashed migration as applied";
+            // the squashed migrations must all have been applied as they are
+            if let DevAction::Reset(reset) = self.dev_diagnostic().await.format_error()?.action {
+                bail!("{action}: {}", reset.reason);
+            }
+            self.ensure_no_drift(action).await?;
+        }
+
+        let script = self
+            .script_from_empty()
+            .await?
+            .context("cannot squash migrations: the datamodel is empty")?;
+
+        for migration in existing.iter() {
+            let dir = self.migrations_dir.as_ref().join(migration);
+            std::fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to remove migration directory {dir:?}"))?;
+        }
+
+        let migration_name = self.write_migration(&name, &script)?;
+        if mark_applied {
+            self.mark_applied(&migration_name).await?;
+        }
+
+        Ok(PrismaSquashResult {
+            squashed_migration_name: Some(migration_name),
+            squashed_count: existing.len(),
+            migrations: self.migrations_dir.serialize()?,
+        })
+    }
+
+    /// Create the first migration of an existing database and mark it as
+    /// applied, without resetting the database.
+    pub async fn baseline(&self, name: String) -> Result<PrismaBaselineResult> {
+        trace!("Migrations::baseline (name={name:?})");
+        let existing = self.list_migrations().await?;
+        if !existing.is_empty() {
+            bail!(
+                "cannot baseline: the migrations folder already has {} migration(s)",
+                existing.len()
+            );
+        }
+
+        self.ensure_no_drift("cannot baseline").await?;
+
+        let script = self
+            .script_from_empty()
+            .await?
+            .context("cannot baseline: the datamodel is empty")?;
+
+        let migration_name = self.write_migration(&name, &script)?;
+        self.mark_applied(&migration_name).await?;
+
+        Ok(PrismaBaselineResult {
+            baseline_migration_name: migration_name,
+            migrations: self
+                .migrations_dir
+                .serialize()?
+                .context("migrations folder is empty")?,
+        })
+    }
+}
+
 #[derive(Debug)]
 struct MigrationsFolder {
     dir: TempDir,
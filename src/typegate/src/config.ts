@@ -66,6 +66,11 @@ export const defaultTypegateConfigBase = {
   substantial_poll_interval_sec: 1,
   substantial_lease_lifespan_sec: 2,
   substantial_max_acquire_per_tick: 3,
+  prisma_slow_query_threshold_ms: 1000,
+  subscription_poll_interval_ms: 1000,
+  subscription_max_per_client: 10,
+  subscription_max_lifetime_sec: 3600,
+  subscription_introspection: false,
 };
 
 const SYNC_PREFIX = "sync_";
@@ -120,6 +120,16 @@ export const typegateConfigBaseSchema = z.object({
   substantial_lease_lifespan_sec: z.coerce.number().positive().min(1),
   /** Maximum amount of new acquired replay requests per tick */
   substantial_max_acquire_per_tick: z.coerce.number().positive().min(1),
+  /** Duration above which a prisma query is reported as slow */
+  prisma_slow_query_threshold_ms: z.coerce.number().positive(),
+  /** Rate at which subscriptions re-run their query */
+  subscription_poll_interval_ms: z.coerce.number().positive().min(50),
+  /** Maximum amount of open subscriptions per client and typegraph */
+  subscription_max_per_client: z.coerce.number().positive().min(1),
+  /** Duration after which a subscription is completed */
+  subscription_max_lifetime_sec: z.coerce.number().positive().min(1),
+  /** Expose the query fields as the subscription root in the introspection */
+  subscription_introspection: zBooleanString,
 });
 
 export type TypegateConfigBase = z.infer<typeof typegateConfigBaseSchema>;
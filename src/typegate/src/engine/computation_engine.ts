@@ -88,7 +88,7 @@ export class ComputationEngine {
     limit: RateLimit | null,
     verbose: boolean,
   ): Promise<Record<string, JSONValue>> {
-    await policies.authorize(context, info, verbose);
+    await policies.authorize(context, info, variables, verbose);
 
     const computationEngine = new ComputationEngine(
       context,
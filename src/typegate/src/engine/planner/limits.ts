@@ -0,0 +1,268 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+import { Kind } from "graphql";
+import type * as ast from "graphql/ast";
+import type { FragmentDefs } from "../../transports/graphql/graphql.ts";
+import type { TypeGraph } from "../../typegraph/mod.ts";
+import type { QueryLimits } from "../../typegraph/types.ts";
+import type { Variables } from "../../types.ts";
+import { Type } from "../../typegraph/type_node.ts";
+import { BaseError, ErrorKind } from "../../errors.ts";
+
+export class QueryLimitExceeded extends BaseError {
+  constructor(message: string) {
+    super(import.meta, ErrorKind.User, `query limit exceeded: ${message}`);
+  }
+}
+
+export interface QueryMetrics {
+  depth: number;
+  aliases: number;
+  cost: number;
+  /** whether the cost depends on a `take` or `first` variable */
+  variableTake: boolean;
+}
+
+/**
+ * Compute the depth, the number of aliases and the cost of an operation.
+ * See `tg_schema::QueryLimits` for the cost model.
+ */
+export class QueryAnalyzer {
+  #aliases = 0;
+  #variableTake = false;
+
+  /**
+   * Without `variables`, a variable `take` or `first` counts as the size
+   * bound of the list, and is reported in `QueryMetrics.variableTake`.
+   */
+  constructor(
+    private tg: TypeGraph,
+    private limits: QueryLimits,
+    private fragments: FragmentDefs,
+    private variables: Variables | null = null,
+  ) {}
+
+  analyze(operation: ast.OperationDefinitionNode): QueryMetrics {
+    const rootIdx = this.tg.type(0, Type.OBJECT).properties[
+      operation.operation
+    ];
+    if (rootIdx == null) {
+      // reported by the planner
+      return { depth: 0, aliases: 0, cost: 0, variableTake: false };
+    }
+    this.#aliases = 0;
+    this.#variableTake = false;
+    const { depth, cost } = this.#selectionSet(
+      operation.selectionSet,
+      rootIdx,
+      new Set(),
+    );
+    return {
+      depth,
+      aliases: this.#aliases,
+      cost,
+      variableTake: this.#variableTake,
+    };
+  }
+
+  /** depth and cost of a single item of the type `typeIdx` */
+  #selectionSet(
+    selectionSet: ast.SelectionSetNode,
+    typeIdx: number,
+    fragmentPath: Set<string>,
+  ): { depth: number; cost: number } {
+    const typ = this.tg.type(typeIdx);
+    if (typ.type === Type.UNION || typ.type === Type.EITHER) {
+      // only one variant is resolved for each item
+      const variants = this.tg.typeUtils.getFlatUnionVariants(typ);
+      let depth = 0;
+      let cost = 0;
+      for (const sel of selectionSet.selections) {
+        if (sel.kind !== Kind.INLINE_FRAGMENT || sel.typeCondition == null) {
+          continue;
+        }
+        const typeName = sel.typeCondition.name.value;
+        const variantIdx = variants.find((idx) =>
+          this.tg.type(idx).title === typeName
+        );
+        if (variantIdx == null) {
+          continue;
+        }
+        const res = this.#selectionSet(
+          sel.selectionSet,
+          variantIdx,
+          fragmentPath,
+        );
+        depth = Math.max(depth, res.depth);
+        cost = Math.max(cost, res.cost);
+      }
+      return { depth, cost };
+    }
+
+    if (typ.type !== Type.OBJECT) {
+      return { depth: 0, cost: 0 };
+    }
+
+    let depth = 0;
+    let cost = 0;
+    for (const sel of selectionSet.selections) {
+      switch (sel.kind) {
+        case Kind.FIELD: {
+          const res = this.#field(sel, typ.properties, fragmentPath);
+          depth = Math.max(depth, res.depth);
+          cost += res.cost;
+          break;
+        }
+        case Kind.INLINE_FRAGMENT: {
+          const res = this.#selectionSet(
+            sel.selectionSet,
+            typeIdx,
+            fragmentPath,
+          );
+          depth = Math.max(depth, res.depth);
+          cost += res.cost;
+          break;
+        }
+        case Kind.FRAGMENT_SPREAD: {
+          const name = sel.name.value;
+          const fragment = this.fragments[name];
+          if (fragment == null) {
+            // reported by the planner
+            break;
+          }
+          if (fragmentPath.has(name)) {
+            throw new QueryLimitExceeded(`fragment '${name}' is recursive`);
+          }
+          const res = this.#selectionSet(
+            fragment.selectionSet,
+            typeIdx,
+            new Set([...fragmentPath, name]),
+          );
+          depth = Math.max(depth, res.depth);
+          cost += res.cost;
+          break;
+        }
+      }
+    }
+    return { depth, cost };
+  }
+
+  #field(
+    field: ast.FieldNode,
+    props: Record<string, number>,
+    fragmentPath: Set<string>,
+  ): { depth: number; cost: number } {
+    if (field.alias != null) {
+      this.#aliases += 1;
+    }
+    const name = field.name.value;
+    const propIdx = props[name];
+    if (propIdx == null) {
+      // __typename, or unknown field reported by the planner
+      return { depth: 1, cost: 0 };
+    }
+
+    let idx = propIdx;
+    let typ = this.tg.type(idx);
+    let fieldCost = 0;
+    let take: number | null = null;
+    if (typ.type === Type.FUNCTION) {
+      fieldCost = typ.rate_weight ?? 1;
+      take = this.#take(field);
+      idx = typ.output;
+      typ = this.tg.type(idx);
+    }
+
+    let cardinality = 1;
+    while (typ.type === Type.OPTIONAL || typ.type === Type.LIST) {
+      if (typ.type === Type.LIST) {
+        // `take` applies to the outermost list
+        cardinality *= take ?? typ.maxItems ?? this.limits.list_size;
+        take = null;
+        idx = typ.items;
+      } else {
+        idx = typ.item;
+      }
+      typ = this.tg.type(idx);
+    }
+
+    if (field.selectionSet == null) {
+      return { depth: 1, cost: fieldCost };
+    }
+    const nested = this.#selectionSet(field.selectionSet, idx, fragmentPath);
+    return {
+      depth: nested.depth + 1,
+      cost: Math.max(fieldCost, 1) + cardinality * nested.cost,
+    };
+  }
+
+  /** value of the `take` or `first` argument (Prisma pagination) */
+  #take(field: ast.FieldNode): number | null {
+    for (const arg of field.arguments ?? []) {
+      if (arg.name.value !== "take" && arg.name.value !== "first") {
+        continue;
+      }
+      switch (arg.value.kind) {
+        case Kind.INT:
+          return Math.abs(parseInt(arg.value.value, 10));
+        case Kind.VARIABLE: {
+          this.#variableTake = true;
+          const value = this.variables?.[arg.value.name.value];
+          return typeof value === "number" ? Math.abs(value) : null;
+        }
+      }
+    }
+    return null;
+  }
+}
+
+/**
+ * Check the limits of the operation.
+ *
+ * Returns the check to run on each execution when the cost depends on the
+ * variables, e.g. `nodes(take: $count)`, as the plans are cached.
+ */
+export function checkQueryLimits(
+  tg: TypeGraph,
+  operation: ast.OperationDefinitionNode,
+  fragments: FragmentDefs,
+): ((variables: Variables) => void) | null {
+  const limits = tg.tg.meta.limits;
+  if (limits == null) {
+    return null;
+  }
+
+  const metrics = new QueryAnalyzer(tg, limits, fragments).analyze(operation);
+  if (!metrics.variableTake) {
+    assertWithinLimits(limits, metrics);
+    return null;
+  }
+  return (variables) => {
+    assertWithinLimits(
+      limits,
+      new QueryAnalyzer(tg, limits, fragments, variables).analyze(operation),
+    );
+  };
+}
+
+function assertWithinLimits(
+  limits: QueryLimits,
+  { depth, aliases, cost }: QueryMetrics,
+) {
+  if (limits.max_depth != null && depth > limits.max_depth) {
+    throw new QueryLimitExceeded(
+      `depth ${depth} is greater than the maximum of ${limits.max_depth}`,
+    );
+  }
+  if (limits.max_aliases != null && aliases > limits.max_aliases) {
+    throw new QueryLimitExceeded(
+      `${aliases} aliases, the maximum is ${limits.max_aliases}`,
+    );
+  }
+  if (limits.max_cost != null && cost > limits.max_cost) {
+    throw new QueryLimitExceeded(
+      `cost ${cost} is greater than the maximum of ${limits.max_cost}`,
+    );
+  }
+}
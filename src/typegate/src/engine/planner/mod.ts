@@ -117,6 +117,7 @@ export class Planner {
         typeIdx: stage.props.typeIdx,
         isTopLevel: stage.props.parent ? false : true,
         node: stage.props.node, // actual non aliased name
+        args: stage.props.args,
       });
     }
 
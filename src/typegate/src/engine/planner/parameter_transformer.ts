@@ -6,8 +6,8 @@ import type { TypeGraph } from "../../typegraph/mod.ts";
 import { Type } from "../../typegraph/type_node.ts";
 import type { ParameterTransformNode } from "../../typegraph/types.ts";
 import {
+  getValidationContext,
   type ValidationContext,
-  validationContext,
 } from "../typecheck/common.ts";
 import { generateListValidator } from "../typecheck/inline_validators/list.ts";
 import { generateNumberValidator } from "../typecheck/inline_validators/number.ts";
@@ -53,6 +53,7 @@ export function compileParameterTransformer(
   const ctx = new TransformerCompilationContext(typegraph, parentProps);
   const { fnBody, deps } = ctx.compile(transformerTreeRoot);
   const fn = new Function("input", "context", fnBody) as CompiledTransformer;
+  const validationContext = getValidationContext(typegraph);
   return ({ args, context, parent }) => {
     const getContext = compileContextQueries(deps.contexts)(context);
     const res = fn({ args, getContext, parent }, validationContext);
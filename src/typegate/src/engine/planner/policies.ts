@@ -10,6 +10,7 @@ import type {
   Resolver,
   StageId,
   TypeIdx,
+  Variables,
 } from "../../types.ts";
 import type { EffectType, PolicyIndices } from "../../typegraph/types.ts";
 import { ensure } from "../../utils.ts";
@@ -23,6 +24,11 @@ import {
   Type,
 } from "../../typegraph/type_node.ts";
 import { BadContext } from "../../errors.ts";
+import type { ComputeArg } from "./args.ts";
+import {
+  type ContextRule,
+  ruleUsesArgs,
+} from "../../runtimes/deno/context_rule.ts";
 
 const logger = getLogger(import.meta);
 
@@ -30,6 +36,7 @@ export type PolicyResolverOutput = "DENY" | "ALLOW" | "PASS" | (unknown & any);
 type GetResolverResult = (
   polIdx: PolicyIdx,
   effect: EffectType,
+  stageId: StageId,
 ) => Promise<PolicyResolverOutput | undefined>;
 
 export interface StageMetadata {
@@ -37,6 +44,7 @@ export interface StageMetadata {
   typeIdx: TypeIdx;
   isTopLevel: boolean;
   node: string;
+  args?: ComputeArg<Record<string, unknown>> | null;
 }
 
 interface ComposePolicyOperand {
@@ -72,6 +80,8 @@ const EXPOSE_STAGE_ID = "<root>";
 export class OperationPolicies {
   #stageToPolicies: Map<StageId, Array<PolicyForStage>> = new Map();
   #resolvers: Map<PolicyIdx, Resolver> = new Map();
+  /** context rule policies that read the arguments of the function */
+  #argDependentPolicies: Set<PolicyIdx> = new Set();
 
   constructor(
     private tg: TypeGraph,
@@ -147,6 +157,13 @@ export class OperationPolicies {
             polIdx,
             runtime.delegate(mat, false, this.config.timer_policy_eval_retries),
           );
+          if (
+            mat.name === "predefined_function" &&
+            mat.data.name === "context_rule" &&
+            ruleUsesArgs(mat.data.param as ContextRule)
+          ) {
+            this.#argDependentPolicies.add(polIdx);
+          }
         }
       }
     }
@@ -159,10 +176,15 @@ export class OperationPolicies {
    * - `ALLOW`: stops evaluation for parent, and skip any child stage
    * - `DENY`: throw an error and stopping everything
    */
-  public async authorize(context: Context, info: Info, verbose: boolean) {
-    const outputCache = new Map<PolicyIdx, PolicyResolverOutput>();
+  public async authorize(
+    context: Context,
+    info: Info,
+    variables: Variables,
+    verbose: boolean,
+  ) {
+    const outputCache = new Map<PolicyIdx | string, PolicyResolverOutput>();
     const getResolverResult = this.#createPolicyEvaluator(
-      { context, info },
+      { context, info, variables },
       outputCache,
       verbose,
     );
@@ -257,16 +279,20 @@ export class OperationPolicies {
   }
 
   #createPolicyEvaluator(
-    partialResolverInput: { context: Context; info: Info },
-    outputCache: Map<PolicyIdx, PolicyResolverOutput>,
+    partialResolverInput: { context: Context; info: Info; variables: Variables },
+    outputCache: Map<PolicyIdx | string, PolicyResolverOutput>,
     verbose: boolean,
   ): GetResolverResult {
     return async (
       polIdx: PolicyIdx,
       effect: EffectType,
+      stageId: StageId,
     ): Promise<PolicyResolverOutput | undefined> => {
-      if (outputCache.has(polIdx)) {
-        const res = outputCache.get(polIdx);
+      // the result of policies reading the arguments is specific to the stage
+      const usesArgs = this.#argDependentPolicies.has(polIdx);
+      const cacheKey = usesArgs ? `${polIdx}@${stageId}` : polIdx;
+      if (outputCache.has(cacheKey)) {
+        const res = outputCache.get(cacheKey);
         verbose &&
           logger.debug(
             `policy cache hit`,
@@ -288,7 +314,11 @@ export class OperationPolicies {
         }'; effect=${effect}`,
       );
 
+      const args = usesArgs
+        ? this.#computeStageArgs(stageId, effect, partialResolverInput)
+        : {};
       const res = (await resolver!({
+        ...args,
         _: {
           parent: {},
           context: partialResolverInput.context,
@@ -297,7 +327,7 @@ export class OperationPolicies {
           effect: effect === "read" ? null : effect,
         },
       })) as PolicyResolverOutput;
-      outputCache.set(polIdx, res);
+      outputCache.set(cacheKey, res);
       verbose &&
         logger.debug(
           `policy evaluated`,
@@ -312,6 +342,29 @@ export class OperationPolicies {
     };
   }
 
+  /**
+   * Arguments of the stage, as available before the execution: arguments
+   * computed from the parent are not resolved.
+   */
+  #computeStageArgs(
+    stageId: StageId,
+    effect: EffectType,
+    { context, variables }: { context: Context; variables: Variables },
+  ): Record<string, unknown> {
+    const stage = this.orderedStageMetadata.find((meta) =>
+      meta.stageId === stageId
+    );
+    if (stage?.args == null) {
+      return {};
+    }
+    return stage.args({
+      variables,
+      parent: {},
+      context,
+      effect: effect === "read" ? null : effect,
+    });
+  }
+
   /**
    * A single type may hold multiple policies
    *
@@ -325,12 +378,17 @@ export class OperationPolicies {
   async #composePolicies(
     policies: Array<ComposePolicyOperand>,
     effect: EffectType,
+    stageId: StageId,
     getResolverResult: GetResolverResult,
   ): Promise<CheckResult> {
     const operands = [];
     const deniersIdx = [];
     for (const policyOperand of policies) {
-      const res = await getResolverResult(policyOperand.index, effect);
+      const res = await getResolverResult(
+        policyOperand.index,
+        effect,
+        stageId,
+      );
 
       switch (res) {
         case "ALLOW": {
@@ -456,6 +514,7 @@ export class OperationPolicies {
         check: await this.#composePolicies(
           policies,
           effect,
+          selected.stageId,
           getResolverResult,
         ),
       });
@@ -20,6 +20,7 @@ import type {
   Variables,
 } from "../types.ts";
 import { Planner } from "./planner/mod.ts";
+import { checkQueryLimits } from "./planner/limits.ts";
 import type { OperationPolicies } from "./planner/policies.ts";
 import { None } from "monads";
 import type { Validator } from "./typecheck/common.ts";
@@ -28,6 +29,7 @@ import { ComputationEngine } from "./computation_engine.ts";
 import { isIntrospectionQuery } from "../services/graphql_service.ts";
 import type { ObjectNode } from "../typegraph/type_node.ts";
 import { RestSchemaGenerator } from "../transports/rest/rest_schema_generator.ts";
+import { PersistedQueryCache } from "../transports/graphql/request_parser.ts";
 import { BaseError, type ErrorConstructor, ErrorKind } from "../errors.ts";
 
 class GraphQLVariableNotFound extends BaseError {
@@ -112,6 +114,8 @@ interface Plan {
   stages: ComputeStage[];
   policies: OperationPolicies;
   validator: Validator;
+  // query limits depending on the variables
+  checkLimits: ((variables: Variables) => void) | null;
 }
 
 class QueryCache {
@@ -147,6 +151,7 @@ export interface EndpointToSchemaMap {
 export class QueryEngine implements AsyncDisposable {
   name: string;
   queryCache: QueryCache;
+  persistedQueries: PersistedQueryCache;
   logger: log.Logger;
   rest: Record<
     string,
@@ -170,6 +175,7 @@ export class QueryEngine implements AsyncDisposable {
     this.tg = tg;
     this.name = tg.name;
     this.queryCache = new QueryCache();
+    this.persistedQueries = new PersistedQueryCache();
     this.logger = log.getLogger("engine");
     this.rest = {
       GET: {},
@@ -364,6 +370,10 @@ export class QueryEngine implements AsyncDisposable {
       if (cached != null) return [cached, true];
     }
 
+    const checkLimits = isIntrospectionQuery(operation, fragments)
+      ? null
+      : checkQueryLimits(this.tg, operation, fragments);
+
     // what
     const planner = new Planner(operation, fragments, this.tg, verbose);
     const { stages, policies } = planner.getPlan();
@@ -392,6 +402,7 @@ export class QueryEngine implements AsyncDisposable {
       stages: optimizedStages,
       policies,
       validator,
+      checkLimits,
     };
 
     if (cache) {
@@ -409,7 +420,8 @@ export class QueryEngine implements AsyncDisposable {
     limit: RateLimit | null,
     verbose: boolean,
   ): Promise<JSONValue> {
-    const { stages, policies, validator } = plan;
+    const { stages, policies, validator, checkLimits } = plan;
+    checkLimits?.(variables);
 
     //logger.info("dag:", stages);
     const res = await ComputationEngine.compute(
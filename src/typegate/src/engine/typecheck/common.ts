@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MPL-2.0
 
 import type { StringFormat } from "../../typegraph/types.ts";
+import type { TypeGraph } from "../../typegraph/mod.ts";
 import * as uuid from "@std/uuid";
 import validator from "validator";
 import lodash from "lodash";
@@ -19,6 +20,12 @@ export interface ValidationContext {
   deepEqual: <T>(left: T, right: T) => boolean;
 }
 
+const timeRegex =
+  /^([01][0-9]|2[0-3]):[0-5][0-9](:([0-5][0-9]|60)(\.[0-9]+)?)?(Z|[+-]([01][0-9]|2[0-3]):[0-5][0-9])?$/;
+const durationRegex =
+  /^P([0-9]+W|([0-9]+Y)?([0-9]+M)?([0-9]+D)?(T([0-9]+H)?([0-9]+M)?([0-9]+(\.[0-9]+)?S)?)?)$/;
+const slugRegex = /^[a-z0-9]+(?:-[a-z0-9]+)*$/;
+
 const formatValidators: Record<StringFormat, FormatValidator> = {
   uuid: uuid.validate,
   json: (value: string) => {
@@ -48,6 +55,18 @@ const formatValidators: Record<StringFormat, FormatValidator> = {
   phone: validator.isMobilePhone, // ??
   date: validator.isDate,
   "date-time": validator.isISO8601,
+  ipv4: (value: string) => validator.isIP(value, 4),
+  ipv6: (value: string) => validator.isIP(value, 6),
+  cidr: (value: string) => validator.isIPRange(value),
+  time: (value: string) => timeRegex.test(value),
+  // at least one component is required, and "T" must be followed by one
+  duration: (value: string) =>
+    durationRegex.test(value) && value !== "P" && !value.endsWith("T"),
+  iban: (value: string) => validator.isIBAN(value),
+  semver: validator.isSemVer,
+  slug: (value: string) => slugRegex.test(value),
+  "country-code": validator.isISO31661Alpha2,
+  "currency-code": validator.isISO4217,
 };
 
 export const validationContext: ValidationContext = {
@@ -55,6 +74,41 @@ export const validationContext: ValidationContext = {
   deepEqual: lodash.isEqual,
 };
 
+const typegraphValidationContexts = new WeakMap<TypeGraph, ValidationContext>();
+
+/** validation context with the custom string formats of the typegraph */
+export function getValidationContext(tg: TypeGraph): ValidationContext {
+  const customFormats = Object.entries(tg.tg.meta.stringFormats ?? {});
+  if (customFormats.length === 0) {
+    return validationContext;
+  }
+
+  let context = typegraphValidationContexts.get(tg);
+  if (context == null) {
+    const customValidators = Object.fromEntries(
+      customFormats.map(([name, pattern]) => {
+        let regex: RegExp;
+        try {
+          regex = new RegExp(pattern, "u");
+        } catch (e) {
+          throw new Error(
+            `invalid pattern for string format "${name}": ${
+              (e as Error).message
+            }`,
+          );
+        }
+        return [name, (value: string) => regex.test(value)];
+      }),
+    );
+    context = {
+      ...validationContext,
+      formatValidators: { ...formatValidators, ...customValidators },
+    };
+    typegraphValidationContexts.set(tg, context);
+  }
+  return context;
+}
+
 export interface ValidatorFn {
   (
     value: unknown,
@@ -7,7 +7,7 @@ import { CodeGenerator } from "./code_generator.ts";
 import { mapValues } from "@std/collections/map-values";
 import {
   type ErrorEntry,
-  validationContext,
+  getValidationContext,
   type Validator,
   type ValidatorFn,
 } from "./common.ts";
@@ -19,9 +19,10 @@ export function generateValidator(tg: TypeGraph, typeIdx: number): Validator {
     .generate(typeIdx);
 
   const validator = new Function(validatorCode)() as ValidatorFn;
+  const context = getValidationContext(tg);
   return (value: unknown) => {
     const errors: ErrorEntry[] = [];
-    validator(value, "<value>", errors, validationContext);
+    validator(value, "<value>", errors, context);
     if (errors.length > 0) {
       const messages = errors.map(([path, msg]) => `  - at ${path}: ${msg}\n`)
         .join("");
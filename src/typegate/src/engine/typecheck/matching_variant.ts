@@ -11,7 +11,7 @@ import { CodeGenerator } from "./code_generator.ts";
 import { mapValues } from "@std/collections/map-values";
 import {
   type ErrorEntry,
-  validationContext,
+  getValidationContext,
   type ValidatorFn,
 } from "./common.ts";
 
@@ -29,12 +29,13 @@ export function generateVariantMatcher(
   const validators = new Function(
     new VariantMatcherCompiler(tg).generate(variants),
   )() as ValidatorFn[];
+  const context = getValidationContext(tg);
 
   return (value: unknown) => {
     let errors: ErrorEntry[] = [];
     for (let i = 0; i < variants.length; ++i) {
       const validator = validators[i];
-      validator(value, "<value>", errors, validationContext);
+      validator(value, "<value>", errors, context);
       if (errors.length === 0) {
         return tg.type(variants[i]).title;
       }
@@ -22,7 +22,7 @@ import { getChildTypes } from "../../typegraph/visitor.ts";
 import { mapValues } from "@std/collections/map-values";
 import {
   type ErrorEntry,
-  validationContext,
+  getValidationContext,
   type Validator,
   type ValidatorFn,
 } from "./common.ts";
@@ -37,10 +37,11 @@ export function generateValidator(
 ): Validator {
   const code = new ResultValidationCompiler(tg, fragments).generate(operation);
   const validator = new Function(code)() as ValidatorFn;
+  const context = getValidationContext(tg);
 
   return (value: unknown) => {
     const errors: ErrorEntry[] = [];
-    validator(value, "<value>", errors, validationContext);
+    validator(value, "<value>", errors, context);
     if (errors.length > 0) {
       const messages = errors
         .map(([path, msg]) => `  - at ${path}: ${msg}\n`)
@@ -0,0 +1,128 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+import lodash from "lodash";
+
+// see `tg_schema::runtimes::deno::ContextRule`
+
+export type RuleOperand =
+  | { context: string }
+  | { arg: string }
+  | { value: unknown };
+
+export type CompareOp = "eq" | "ne" | "lt" | "lte" | "gt" | "gte" | "in";
+
+export type ContextRule =
+  | { type: "not_null"; operand: RuleOperand }
+  | { type: "pattern"; operand: RuleOperand; pattern: string }
+  | { type: "compare"; left: RuleOperand; op: CompareOp; right: RuleOperand }
+  | { type: "all"; rules: ContextRule[] }
+  | { type: "any"; rules: ContextRule[] }
+  | { type: "not"; rule: ContextRule };
+
+export interface RuleInput {
+  context: Record<string, unknown>;
+  args: Record<string, unknown>;
+}
+
+type CompiledRule = (input: RuleInput) => boolean;
+
+function getPath(root: unknown, path: string): unknown {
+  let value: any = root;
+  for (const segment of path.split(".")) {
+    value = value?.[segment];
+  }
+  return value;
+}
+
+function compileOperand(operand: RuleOperand): (input: RuleInput) => unknown {
+  if ("context" in operand) {
+    return ({ context }) => getPath(context, operand.context);
+  }
+  if ("arg" in operand) {
+    return ({ args }) => getPath(args, operand.arg);
+  }
+  return () => operand.value;
+}
+
+function isComparable(left: unknown, right: unknown) {
+  return (typeof left === "number" && typeof right === "number") ||
+    (typeof left === "string" && typeof right === "string");
+}
+
+function compare(op: CompareOp, left: any, right: any): boolean {
+  switch (op) {
+    case "eq":
+      return left != null && lodash.isEqual(left, right);
+    case "ne":
+      return !lodash.isEqual(left, right);
+    case "lt":
+      return isComparable(left, right) && left < right;
+    case "lte":
+      return isComparable(left, right) && left <= right;
+    case "gt":
+      return isComparable(left, right) && left > right;
+    case "gte":
+      return isComparable(left, right) && left >= right;
+    case "in":
+      return Array.isArray(right) &&
+        right.some((item) => lodash.isEqual(item, left));
+    default:
+      throw new Error(`unknown comparison operator: ${op}`);
+  }
+}
+
+export function compileContextRule(rule: ContextRule): CompiledRule {
+  switch (rule.type) {
+    case "not_null": {
+      const operand = compileOperand(rule.operand);
+      return (input) => operand(input) != null;
+    }
+    case "pattern": {
+      const operand = compileOperand(rule.operand);
+      const regex = new RegExp(rule.pattern);
+      return (input) => {
+        const value = operand(input);
+        return typeof value === "string" && regex.test(value);
+      };
+    }
+    case "compare": {
+      const left = compileOperand(rule.left);
+      const right = compileOperand(rule.right);
+      return (input) => compare(rule.op, left(input), right(input));
+    }
+    case "all": {
+      const rules = rule.rules.map(compileContextRule);
+      return (input) => rules.every((r) => r(input));
+    }
+    case "any": {
+      const rules = rule.rules.map(compileContextRule);
+      return (input) => rules.some((r) => r(input));
+    }
+    case "not": {
+      const inner = compileContextRule(rule.rule);
+      return (input) => !inner(input);
+    }
+    default:
+      throw new Error(`unknown context rule: ${JSON.stringify(rule)}`);
+  }
+}
+
+/** whether the result of the rule depends on the arguments of the function */
+export function ruleUsesArgs(rule: ContextRule): boolean {
+  const isArg = (operand: RuleOperand) => "arg" in operand;
+  switch (rule.type) {
+    case "not_null":
+    case "pattern":
+      return isArg(rule.operand);
+    case "compare":
+      return isArg(rule.left) || isArg(rule.right);
+    case "all":
+    case "any":
+      return rule.rules.some(ruleUsesArgs);
+    case "not":
+      return ruleUsesArgs(rule.rule);
+    default:
+      return false;
+  }
+}
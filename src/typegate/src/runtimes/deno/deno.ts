@@ -26,6 +26,7 @@ import { getInjectionValues } from "../../engine/planner/injection_utils.ts";
 import DynamicInjection from "../../engine/injection/dynamic.ts";
 import { getLogger } from "../../log.ts";
 import { WorkerManager } from "./worker_manager.ts";
+import { compileContextRule, type ContextRule } from "./context_rule.ts";
 
 const logger = getLogger(import.meta);
 
@@ -65,6 +66,11 @@ const predefinedFuncs: Record<
       return check(value) ? "PASS" : ("DENY" as PolicyResolverOutput);
     };
   },
+  context_rule: (rule: ContextRule) => {
+    const check = compileContextRule(rule);
+    return ({ _: { context }, ...args }) =>
+      check({ context, args }) ? "PASS" : ("DENY" as PolicyResolverOutput);
+  },
 };
 
 export class DenoRuntime extends Runtime {
@@ -117,6 +117,50 @@ export class PrismaMigrate {
     };
   };
 
+  squash: Resolver<{ name: string; markApplied?: boolean }> = async (
+    { name: migrationName, markApplied = false },
+  ) => {
+    const { datamodel, name } = this.runtime.data;
+    logger.info("prisma squash");
+    const res = nativeResult(
+      await native.prisma_squash({
+        datasource: this.datasource,
+        datamodel,
+        migrations: this.migrations,
+        migration_name: migrationName,
+        mark_applied: markApplied,
+      }),
+    );
+    logger.info("prisma squash: successful");
+
+    return {
+      squashedMigrationName: res.squashed_migration_name,
+      squashedCount: res.squashed_count,
+      migrations: res.migrations,
+      runtimeName: name,
+    };
+  };
+
+  baseline: Resolver<{ name: string }> = async ({ name: migrationName }) => {
+    const { datamodel, name } = this.runtime.data;
+    logger.info("prisma baseline");
+    const res = nativeResult(
+      await native.prisma_baseline({
+        datasource: this.datasource,
+        datamodel,
+        migrations: this.migrations,
+        migration_name: migrationName,
+      }),
+    );
+    logger.info("prisma baseline: successful");
+
+    return {
+      baselineMigrationName: res.baseline_migration_name,
+      migrations: res.migrations,
+      runtimeName: name,
+    };
+  };
+
   reset: Resolver<boolean> = async () => {
     logger.info("prisma reset");
     nativeResult(
@@ -175,6 +219,8 @@ export class PrismaMigrationRuntime extends Runtime {
     switch (name) {
       case "apply":
       case "create":
+      case "squash":
+      case "baseline":
         resolver = (async (
           args: ResolverArgsEx,
         ) => {
@@ -221,6 +267,31 @@ export class PrismaMigrationRuntime extends Runtime {
         }) as Resolver;
         break;
 
+      case "introspect":
+        resolver = (async (args: ResolverArgsEx<{ migrations: never }>) => {
+          const { typegraph: tgName, runtime: rt } = args;
+          const [engine, runtime] = this.getMigrationTarget(tgName, rt);
+          const { connection_string_secret, name } = runtime.data;
+          const datasource = makeDatasource(
+            engine.tg.secretManager.secretOrFail(
+              connection_string_secret as string,
+            ),
+          );
+
+          logger.info("prisma introspect");
+          const res = nativeResult(
+            await native.prisma_introspect({ datasource }),
+          );
+          logger.info("prisma introspect: successful");
+
+          return {
+            datamodel: res.datamodel,
+            introspection: JSON.stringify(res.introspection),
+            runtimeName: name,
+          };
+        }) as Resolver;
+        break;
+
       case "deploy":
         resolver = (async (args: ResolverArgsEx<{ migrations: string }>) => {
           const { typegraph: tgName, runtime: rtName, migrations } = args;
@@ -8,7 +8,12 @@ import type { Resolver, RuntimeInitParams } from "../../types.ts";
 import { iterParentStages, nativeResult, nativeVoid } from "../../utils.ts";
 import { ComputeStage } from "../../engine/query_engine.ts";
 import type { ComputeArgParams } from "../../engine/planner/args.ts";
-import type { PrismaOperationMatData } from "../../typegraph/types.ts";
+import type {
+  Model,
+  PoolConfig,
+  PrismaOperationMatData,
+  Property,
+} from "../../typegraph/types.ts";
 import { getLogger, type Logger } from "../../log.ts";
 import type * as PrismaRT from "./types.ts";
 import { filterValues } from "@std/collections/filter-values";
@@ -25,6 +30,73 @@ export const makeDatasource = (uri: string) => {
   `;
 };
 
+// https://www.prisma.io/docs/orm/overview/databases/postgresql#arguments
+export const withPoolConfig = (uri: string, pool?: PoolConfig | null) => {
+  if (pool == null) {
+    return uri;
+  }
+  const toMs = (sec?: number | null) => sec == null ? null : sec * 1000;
+  const params: Array<[string, number | null | undefined]> =
+    uri.startsWith("mongodb")
+      ? [
+        ["maxPoolSize", pool.size],
+        ["connectTimeoutMS", toMs(pool.connect_timeout_sec)],
+        ["socketTimeoutMS", toMs(pool.socket_timeout_sec)],
+      ]
+      : [
+        ["connection_limit", pool.size],
+        ["connect_timeout", pool.connect_timeout_sec],
+        ["socket_timeout", pool.socket_timeout_sec],
+      ];
+  // not parsed as a URL: `file:./dev.db` would be normalized to `file:///dev.db`
+  const sep = uri.indexOf("?");
+  const base = sep === -1 ? uri : uri.slice(0, sep);
+  const search = new URLSearchParams(sep === -1 ? "" : uri.slice(sep + 1));
+  for (const [key, value] of params) {
+    if (value != null) {
+      search.set(key, value.toString());
+    }
+  }
+  const query = search.toString();
+  return query === "" ? base : `${base}?${query}`;
+};
+
+// queries that can be served by a read replica
+const readOnlyActions = new Set([
+  "findUnique",
+  "findUniqueOrThrow",
+  "findFirst",
+  "findFirstOrThrow",
+  "findMany",
+  "aggregate",
+  "groupBy",
+]);
+
+// reads inside a transaction must see the writes of the primary
+export const isReadOnly = (query: SingleQuery | BatchQuery): boolean =>
+  "batch" in query
+    ? query.transaction == null && query.batch.every(isReadOnly)
+    : readOnlyActions.has(query.action);
+
+// delete operations are turned into updates on models with soft delete
+const softDeleteRewrites: Record<string, string> = {
+  deleteOne: "updateOne",
+  deleteMany: "updateMany",
+};
+
+// the deleted records are filtered out by the default value of the
+// `deletedAt` filter in the generated `where` types
+export const withSoftDelete = (
+  operation: string,
+  args: Record<string, unknown>,
+  column: string,
+): Record<string, unknown> => {
+  if (operation in softDeleteRewrites) {
+    return { ...args, data: { [column]: new Date().toISOString() } };
+  }
+  return args;
+};
+
 interface PrismaError {
   error: string;
   user_facing_error: {
@@ -50,6 +122,8 @@ type PrismaRuntimeData = PrismaRT.DataFinal;
 type SelectionSet = Record<string, SelectionSetValue>;
 
 type SelectionSetValue = boolean | FieldQuery;
+type RelationshipProperty = Extract<Property, { type: "relationship" }>;
+
 interface FieldQuery {
   selection: SelectionSet;
   arguments?: Record<string, unknown>;
@@ -63,7 +137,7 @@ export interface SingleQuery {
 
 export interface BatchQuery {
   batch: SingleQuery[];
-  // transaction
+  transaction?: { isolationLevel?: string };
 }
 
 interface GenQuery {
@@ -72,11 +146,14 @@ interface GenQuery {
 
 export class PrismaRuntime extends Runtime {
   private logger: Logger;
+  private replicaEngines: string[] = [];
+  private nextReplica = 0;
 
   private constructor(
     typegraphName: string,
     readonly name: string,
     private datamodel: string,
+    private models: Map<string, Model>,
   ) {
     super(typegraphName);
     this.logger = getLogger(`prisma:${typegraphName}:${name}`);
@@ -85,41 +162,91 @@ export class PrismaRuntime extends Runtime {
   static async init(
     params: RuntimeInitParams,
   ): Promise<Runtime> {
-    const { typegraphName, args, secretManager } =
+    const { typegraphName, args, secretManager, typegate } =
       params as unknown as RuntimeInitParams<PrismaRuntimeData>;
 
-    const datasource = makeDatasource(secretManager.secretOrFail(
-      args.connection_string_secret as string,
+    const datasource = makeDatasource(withPoolConfig(
+      secretManager.secretOrFail(args.connection_string_secret as string),
+      args.pool,
     ));
     const datamodel = `${datasource}${args.datamodel}`;
     const instance = new PrismaRuntime(
       typegraphName,
       args.name,
       datamodel,
+      new Map(args.models.map((model) => [model.typeName, model])),
     );
+    const registerEngine = async (engineName: string, datamodel: string) => {
+      nativeVoid(
+        await native.prisma_register_engine({
+          engine_name: engineName,
+          datamodel,
+          slow_query_threshold_ms:
+            typegate.config.base.prisma_slow_query_threshold_ms,
+        }),
+      );
+    };
+
     logger.debug(
       `registering prisma engine '${instance.name}': ${instance.id}`,
     );
-    nativeVoid(
-      await native.prisma_register_engine({
-        engine_name: instance.id,
-        datamodel,
-      }),
+    await registerEngine(instance.id, datamodel);
+
+    for (const [idx, secret] of (args.read_replica_secrets ?? []).entries()) {
+      const replicaDatasource = makeDatasource(withPoolConfig(
+        secretManager.secretOrFail(secret),
+        args.pool,
+      ));
+      const engineName = `${instance.id}_replica_${idx}`;
+      logger.debug(
+        `registering read replica engine for '${instance.name}': ${engineName}`,
+      );
+      await registerEngine(engineName, `${replicaDatasource}${args.datamodel}`);
+      instance.replicaEngines.push(engineName);
+    }
+
+    logger.info(
+      `prisma engine '${instance.name}' registered with ${instance.replicaEngines.length} read replica(s)`,
     );
-    logger.info(`prisma engine '${instance.name}' registered`);
     return instance;
   }
 
   async deinit(): Promise<void> {
-    this.logger.debug(`unregistering prisma engine '${this.name}': ${this.id}`);
-    nativeVoid(
-      await native.prisma_unregister_engine({
-        engine_name: this.id,
-      }),
-    );
+    for (const engineName of [this.id, ...this.replicaEngines]) {
+      this.logger.debug(
+        `unregistering prisma engine '${this.name}': ${engineName}`,
+      );
+      nativeVoid(
+        await native.prisma_unregister_engine({
+          engine_name: engineName,
+        }),
+      );
+    }
     this.logger.info(`prisma engine '${this.name}' unregistered`);
   }
 
+  /** Engine to run the query on: mutations always go to the primary */
+  private engineFor(query: SingleQuery | BatchQuery): string {
+    if (this.replicaEngines.length === 0 || !isReadOnly(query)) {
+      return this.id;
+    }
+    const engineName = this.replicaEngines[this.nextReplica];
+    this.nextReplica = (this.nextReplica + 1) % this.replicaEngines.length;
+    return engineName;
+  }
+
+  /** Metrics of the primary engine followed by the read replicas */
+  async metrics() {
+    const engines = [
+      ["primary", this.id],
+      ...this.replicaEngines.map((name, idx) => [`replica_${idx}`, name]),
+    ];
+    return await Promise.all(engines.map(async ([engine, engineName]) => ({
+      engine,
+      ...nativeResult(await native.prisma_metrics(engineName)),
+    })));
+  }
+
   async query(query: SingleQuery | BatchQuery) {
     const isBatchQuery = "batch" in query;
     this.logger.debug(
@@ -127,7 +254,7 @@ export class PrismaRuntime extends Runtime {
     );
     const { res } = nativeResult(
       await native.prisma_query({
-        engine_name: this.id,
+        engine_name: this.engineFor(query),
         query: query,
         datamodel: this.datamodel,
       }),
@@ -160,18 +287,36 @@ export class PrismaRuntime extends Runtime {
     return ret;
   }
 
-  static buildSelectionSet(stages: ComputeStage[]): SelectionSet {
+  buildSelectionSet(stages: ComputeStage[], modelName: string): SelectionSet {
+    const model = this.models.get(modelName);
     const selectionSet: SelectionSet = {};
     iterParentStages(stages, (stage, children) => {
+      const node = stage.props.node;
       if (children.length === 0) {
-        selectionSet[stage.props.node] = true;
-      } else {
-        selectionSet[stage.props.node] = {
-          selection: PrismaRuntime.buildSelectionSet(
-            children,
-          ),
+        selectionSet[node] = true;
+        return;
+      }
+      const relation = model?.props.find(
+        (prop): prop is RelationshipProperty =>
+          prop.type === "relationship" && prop.key === node,
+      );
+      if (relation == null) {
+        // aggregates
+        selectionSet[node] = {
+          selection: this.buildSelectionSet(children, modelName),
         };
+        return;
       }
+      const fieldQuery: FieldQuery = {
+        selection: this.buildSelectionSet(children, relation.modelName),
+      };
+      // hide the soft-deleted records of the related model;
+      // to-one relations cannot be filtered
+      const softDelete = this.models.get(relation.modelName)?.softDelete;
+      if (softDelete && relation.cardinality === "many") {
+        fieldQuery.arguments = { where: { [softDelete]: null } };
+      }
+      selectionSet[node] = fieldQuery;
     });
 
     return selectionSet;
@@ -222,18 +367,27 @@ export class PrismaRuntime extends Runtime {
           };
         });
       } else {
-        renames[stage.props.node] = matData.operation + matData.table;
-        queries.push((p) => ({
-          modelName: matData.table,
-          action: matData.operation,
-          query: {
-            selection: PrismaRuntime.buildSelectionSet(children),
-            arguments: filterValues(
-              stage.props.args?.(p) ?? {},
-              (v) => v != null,
-            ),
-          },
-        }));
+        const softDelete = matData.soft_delete;
+        const action = softDelete
+          ? softDeleteRewrites[matData.operation] ?? matData.operation
+          : matData.operation;
+        renames[stage.props.node] = action + matData.table;
+        queries.push((p) => {
+          const args = filterValues(
+            stage.props.args?.(p) ?? {},
+            (v) => v != null,
+          );
+          return {
+            modelName: matData.table,
+            action,
+            query: {
+              selection: this.buildSelectionSet(children, matData.table),
+              arguments: softDelete
+                ? withSoftDelete(matData.operation, args, softDelete)
+                : args,
+            },
+          };
+        });
       }
     });
 
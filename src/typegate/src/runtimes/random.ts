@@ -78,6 +78,10 @@ export default function randomizeRecursively(
     const res = chance[gen as string](args);
     return res;
   }
+  if (typ.examples && typ.examples.length > 0) {
+    // sample values from the typegraph are preferred
+    return structuredClone(chance.pickone(typ.examples));
+  }
   switch (typ.type) {
     case "object": {
       const result: Record<string, any> = {};
@@ -143,6 +147,15 @@ export default function randomizeRecursively(
       if (typ.format == "ean") {
         return generateEAN(chance);
       }
+      if (typ.format === "ipv4") {
+        return chance.ip();
+      }
+      if (typ.format === "ipv6") {
+        return chance.ipv6();
+      }
+      if (typ.format === "country-code") {
+        return chance.country();
+      }
       if (typ.enum) {
         // remove extra " from the string
         return JSON.parse(chance.pickone(typ.enum));
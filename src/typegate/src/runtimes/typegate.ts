@@ -114,6 +114,8 @@ export class TypeGateRuntime extends Runtime {
           return this.execRawPrismaQuery;
         case "queryPrismaModel":
           return this.queryPrismaModel;
+        case "prismaMetrics":
+          return this.prismaMetrics;
         case "ping":
           return (_) => true;
         default:
@@ -307,6 +309,60 @@ export class TypeGateRuntime extends Runtime {
     }).flat();
   };
 
+  prismaMetrics: Resolver = async ({ typegraph }) => {
+    const engine = this.typegate.register.get(typegraph);
+    if (!engine) {
+      throw new Error(`typegraph not found: ${typegraph}`);
+    }
+
+    const prismaRuntimes = engine.tg.runtimeReferences.filter(
+      (_rt, idx) => engine.tg.tg.runtimes[idx].name === "prisma",
+    ) as PrismaRuntime[];
+
+    const metrics = await Promise.all(prismaRuntimes.map(async (rt) => {
+      const engines = await rt.metrics();
+      return engines.map((
+        {
+          engine: kind,
+          connected,
+          queries,
+          latency,
+          in_flight,
+          pool,
+          slow_queries,
+        },
+      ) => ({
+        runtime: rt.name,
+        engine: kind,
+        connected,
+        queries,
+        latency: {
+          meanMs: latency.mean_ms,
+          maxMs: latency.max_ms,
+          p50Ms: latency.p50_ms,
+          p95Ms: latency.p95_ms,
+          p99Ms: latency.p99_ms,
+        },
+        inFlight: {
+          current: in_flight.current,
+          max: in_flight.max,
+        },
+        pool,
+        slowQueries: {
+          thresholdMs: slow_queries.threshold_ms,
+          count: slow_queries.count,
+          samples: slow_queries.samples.map((sample) => ({
+            model: sample.model ?? null,
+            action: sample.action,
+            durationMs: sample.duration_ms,
+            timestamp: sample.timestamp,
+          })),
+        },
+      }));
+    }));
+    return metrics.flat();
+  };
+
   execRawPrismaQuery: Resolver = async (args) => {
     const { typegraph, runtime, query } = args as ResolverArgs<
       { typegraph: string; runtime: string; query: PrismaQuery }
@@ -69,6 +69,7 @@ export class TypeGraphRuntime extends Runtime {
     this.#typeGen = new IntrospectionTypeEmitter(
       this.tg,
       new TypeVisibility(this.tg, denoRuntime, config),
+      config,
     );
   }
 
@@ -115,7 +116,7 @@ export class TypeGraphRuntime extends Runtime {
       types: () => this.#typesResolver(args),
       queryType: () => this.#types.getRootSchema("Query"),
       mutationType: this.#types.getRootSchema("Mutation"),
-      subscriptionType: () => null,
+      subscriptionType: () => this.#types.getRootSchema("Subscription"),
       directives: () => [],
     };
   };
@@ -3,8 +3,16 @@
 
 import { TypeKind } from "graphql";
 import type { TypeGraphDS } from "../../typegraph/mod.ts";
-import type { ObjectNode, PolicyIndices } from "../../typegraph/types.ts";
-import { isScalar, type TypeNode } from "../../typegraph/type_node.ts";
+import type {
+  Deprecation,
+  ObjectNode,
+  PolicyIndices,
+} from "../../typegraph/types.ts";
+import {
+  isOptional,
+  isScalar,
+  type TypeNode,
+} from "../../typegraph/type_node.ts";
 
 // Note: graphql UNION output does not support scalars, only OBJECT
 export function genOutputScalarVariantWrapper(type: TypeNode, idx: number) {
@@ -82,3 +90,76 @@ export function typeGenericCustomScalar(name: string, description: string) {
     possibleTypes: () => {},
   };
 }
+
+/** deprecation of a field, set on its type or on the item of its optional type */
+export function fieldDeprecation(
+  tg: TypeGraphDS,
+  type: TypeNode,
+): Deprecation | null {
+  let current = type;
+  while (current.deprecated == null && isOptional(current)) {
+    current = tg.types[current.item];
+  }
+  return current.deprecated ?? null;
+}
+
+export function deprecationReason(deprecation: Deprecation): string {
+  const { reason, since, removal } = deprecation;
+  let ret = reason ?? "deprecated";
+  if (since != null && removal != null) {
+    ret += ` (since ${since}, to be removed in ${removal})`;
+  } else if (since != null) {
+    ret += ` (since ${since})`;
+  } else if (removal != null) {
+    ret += ` (to be removed in ${removal})`;
+  }
+  return ret;
+}
+
+/** enum values are emitted as scalars: list the deprecated ones in the description */
+export function deprecatedValuesDescription(
+  tg: TypeGraphDS,
+  type: TypeNode,
+): string {
+  let current = type;
+  while (isOptional(current)) {
+    current = tg.types[current.item];
+  }
+  const values = Object.entries(current.deprecated_values ?? {});
+  if (values.length == 0) {
+    return "";
+  }
+  return "\n\nDeprecated values:\n" +
+    values
+      .map(([value, deprecation]) =>
+        `- ${value}: ${deprecationReason(deprecation)}`
+      )
+      .join("\n");
+}
+
+/** description and examples of a field, from its type or the item of its optional type */
+export function fieldDocumentation(
+  tg: TypeGraphDS,
+  type: TypeNode,
+): string | null {
+  let current = type;
+  while (
+    current.description == null && current.examples == null &&
+    isOptional(current)
+  ) {
+    current = tg.types[current.item];
+  }
+  const parts = [];
+  if (current.description != null) {
+    parts.push(current.description);
+  }
+  if (current.examples != null && current.examples.length > 0) {
+    parts.push(
+      "Examples:\n" +
+        current.examples.map((ex) => `- \`${JSON.stringify(ex)}\``).join(
+          "\n",
+        ),
+    );
+  }
+  return parts.length > 0 ? parts.join("\n\n") : null;
+}
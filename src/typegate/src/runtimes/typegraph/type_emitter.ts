@@ -19,7 +19,11 @@ import {
 import type { AllowOrPass, LocalFieldTuple } from "./visibility.ts";
 import type { TypeVisibility } from "./visibility.ts";
 import {
+  deprecatedValuesDescription,
+  deprecationReason,
   fieldCommon,
+  fieldDeprecation,
+  fieldDocumentation,
   policyDescription,
   typeEmptyObjectScalar,
   typeGenericCustomScalar,
@@ -29,6 +33,7 @@ import type { Resolver, ResolverArgs } from "../../types.ts";
 import { getLogger } from "../../log.ts";
 import type { FunctionNode } from "../../typegraph/type_node.ts";
 import { ensure } from "../../utils.ts";
+import type { TypegateConfigBase } from "../../config.ts";
 
 const SCALAR_TYPE_MAP = {
   boolean: "Boolean",
@@ -50,7 +55,11 @@ export class IntrospectionTypeEmitter {
   #types: Array<[string, Record<string, Resolver>]>;
   #typesDefined: Set<string>;
 
-  constructor(private tg: TypeGraphDS, private visibility?: TypeVisibility) {
+  constructor(
+    private tg: TypeGraphDS,
+    private visibility?: TypeVisibility,
+    private config?: TypegateConfigBase,
+  ) {
     this.#types = [];
     this.#typesDefined = new Set();
   }
@@ -82,7 +91,7 @@ export class IntrospectionTypeEmitter {
     throw new Error(`GraphQL type "${name}" not defined yet`);
   }
 
-  getRootSchema(rootKind: "Mutation" | "Query") {
+  getRootSchema(rootKind: "Mutation" | "Query" | "Subscription") {
     const tup = this.#types.find(([name, _]) => name == rootKind);
     return tup ? tup[1] : null;
   }
@@ -145,6 +154,12 @@ export class IntrospectionTypeEmitter {
     this.#types = this.#types.map(([k, v]) => {
       return [k, toResolverMap(resolveRec(v)!)];
     });
+
+    // subscriptions select the same fields as queries
+    const query = this.getRootSchema("Query");
+    if (query && this.config?.subscription_introspection) {
+      this.#define("Subscription", { ...query, name: () => "Subscription" });
+    }
   }
 
   /** Filter according to the injections and pre-computed policies */
@@ -246,11 +261,17 @@ export class IntrospectionTypeEmitter {
           const currentVerdict = gctx.parentVerdict == "ALLOW"
             ? "ALLOW"
             : verdict;
+          const deprecation = fieldDeprecation(this.tg, fieldType);
 
           return {
-            isDeprecated: false,
+            isDeprecated: deprecation != null,
+            deprecationReason: deprecation
+              ? deprecationReason(deprecation)
+              : null,
             args: asInput ? undefined : [], // only on output OBJECT
-            description: description + polDescription,
+            description: (fieldDocumentation(this.tg, fieldType) ??
+              description) + polDescription +
+              deprecatedValuesDescription(this.tg, fieldType),
             ...this.$fieldSchema(fieldName, fieldType, {
               ...gctx,
               parentVerdict: currentVerdict,
@@ -2,12 +2,12 @@
 // SPDX-License-Identifier: MPL-2.0
 
 import { getLogger } from "../log.ts";
-import { parse } from "graphql";
+import { OperationTypeNode, parse } from "graphql";
 import type { Context, Info } from "../types.ts";
 import type { RateLimit } from "../typegate/rate_limiter.ts";
 import {
-  type Operations,
   parseRequest,
+  type ResolvedOperations,
 } from "../transports/graphql/request_parser.ts";
 import {
   findOperation,
@@ -29,6 +29,7 @@ import type { Register } from "@metatype/typegate/typegate/register.ts";
 
 const logger = getLogger(import.meta);
 const IDEMPOTENCY_HEADER = "Idempotency-Key";
+const EVENT_STREAM = "text/event-stream";
 
 class InvalidQuery extends BaseError {
   constructor(message: string) {
@@ -42,6 +43,134 @@ class GraphQLVariableNotFound extends InvalidQuery {
   }
 }
 
+class TooManySubscriptions extends BaseError {
+  constructor(max: number) {
+    super(
+      import.meta,
+      ErrorKind.User,
+      `too many open subscriptions, at most ${max} are allowed per client`,
+      429,
+    );
+  }
+}
+
+/** Number of open subscriptions, by typegraph and client identifier */
+const openSubscriptions = new Map<string, number>();
+
+/** Take a subscription slot, returning the function releasing it */
+function acquireSubscription(key: string, max: number): () => void {
+  const count = openSubscriptions.get(key) ?? 0;
+  if (count >= max) {
+    throw new TooManySubscriptions(max);
+  }
+  openSubscriptions.set(key, count + 1);
+  let released = false;
+  return () => {
+    if (released) {
+      return;
+    }
+    released = true;
+    const remaining = openSubscriptions.get(key)! - 1;
+    if (remaining > 0) {
+      openSubscriptions.set(key, remaining);
+    } else {
+      openSubscriptions.delete(key);
+    }
+  };
+}
+
+function acceptsEventStream(request: Request) {
+  return request.headers.get("accept")?.includes(EVENT_STREAM) ?? false;
+}
+
+interface Subscription {
+  compute: () => Promise<unknown>;
+  pollIntervalMs: number;
+  lifetimeMs: number;
+  release: () => void;
+}
+
+/**
+ * Respond following the "distinct connections" mode of the graphql-sse
+ * protocol: subscriptions re-run the query every `pollIntervalMs` and push
+ * the result when it changes, until the client closes the connection or
+ * the lifetime of the subscription is over.
+ * Queries and mutations push a single result.
+ */
+function eventStream(
+  first: unknown,
+  subscription: Subscription | null,
+  headers: Headers,
+): Response {
+  const encoder = new TextEncoder();
+  const event = (name: "next" | "complete", data?: unknown) =>
+    encoder.encode(
+      `event: ${name}\ndata: ${data === undefined ? "" : JSON.stringify(data)}\n\n`,
+    );
+
+  let closed = false;
+  let timer: number | undefined;
+  let last = JSON.stringify(first);
+  const deadline = Date.now() + (subscription?.lifetimeMs ?? 0);
+  const complete = (controller: ReadableStreamDefaultController) => {
+    closed = true;
+    subscription?.release();
+    controller.enqueue(event("complete"));
+    controller.close();
+  };
+  const body = new ReadableStream<Uint8Array>({
+    start(controller) {
+      controller.enqueue(event("next", { data: first }));
+      if (subscription == null) {
+        complete(controller);
+      }
+    },
+    async pull(controller) {
+      while (!closed) {
+        const remaining = deadline - Date.now();
+        if (remaining <= 0) {
+          complete(controller);
+          return;
+        }
+        await new Promise((resolve) => {
+          timer = setTimeout(
+            resolve,
+            Math.min(subscription!.pollIntervalMs, remaining),
+          );
+        });
+        if (closed || Date.now() >= deadline) {
+          continue;
+        }
+        try {
+          const data = await subscription!.compute();
+          const serialized = JSON.stringify(data);
+          if (serialized !== last) {
+            last = serialized;
+            controller.enqueue(event("next", { data }));
+            return;
+          }
+        } catch (err: any) {
+          logger.error(`subscription err: ${err.message}`);
+          controller.enqueue(
+            event("next", { errors: [{ message: err.message }] }),
+          );
+          complete(controller);
+        }
+      }
+    },
+    cancel() {
+      closed = true;
+      clearTimeout(timer);
+      subscription?.release();
+    },
+  });
+
+  const streamHeaders = new Headers(headers);
+  streamHeaders.set("content-type", EVENT_STREAM);
+  streamHeaders.set("cache-control", "no-cache");
+  return new Response(body, { headers: streamHeaders });
+}
+
 export function isIntrospectionQuery(
   operation: ast.OperationDefinitionNode,
   _fragments: FragmentDefs,
@@ -50,12 +179,14 @@ export function isIntrospectionQuery(
 }
 
 export async function handleGraphQLHelper(
-  content: Operations,
+  content: ResolvedOperations,
   engine: QueryEngine,
   context: Context,
   info: Info,
   limit: RateLimit | null,
   headers: Headers,
+  // the client identifier, when responding with an event stream
+  stream: { client: string } | null = null,
 ): Promise<Response> {
   const { query, operationName: operationNameRaw, variables } = content;
   const operationName = forceAnyToOption(operationNameRaw);
@@ -67,7 +198,21 @@ export async function handleGraphQLHelper(
     if (operation.isNone()) {
       throw Error(`operation ${operationName.unwrapOr("<none>")} not found`);
     }
-    const unwrappedOperation = operation.unwrap();
+    let unwrappedOperation = operation.unwrap();
+    const isSubscription =
+      unwrappedOperation.operation === OperationTypeNode.SUBSCRIPTION;
+    if (isSubscription) {
+      if (!stream) {
+        throw new InvalidQuery(
+          `subscriptions require the '${EVENT_STREAM}' content type to be accepted`,
+        );
+      }
+      // subscriptions select the fields of the query root
+      unwrappedOperation = {
+        ...unwrappedOperation,
+        operation: OperationTypeNode.QUERY,
+      };
+    }
 
     engine.checkVariablesPresence(
       unwrappedOperation.variableDefinitions ?? [],
@@ -90,6 +235,14 @@ export async function handleGraphQLHelper(
     );
     const planTime = performance.now();
 
+    const config = engine.tg.typegate.config.base;
+    const release = isSubscription
+      ? acquireSubscription(
+        `${engine.name}:${stream!.client}`,
+        config.subscription_max_per_client,
+      )
+      : null;
+
     //logger.info("dag:", stages);
     const res = await engine.computePlan(
       plan,
@@ -98,7 +251,10 @@ export async function handleGraphQLHelper(
       info,
       limit,
       verbose,
-    );
+    ).catch((err) => {
+      release?.();
+      throw err;
+    });
     const computeTime = performance.now();
 
     if (verbose) {
@@ -111,6 +267,21 @@ export async function handleGraphQLHelper(
       );
     }
 
+    if (stream) {
+      // the rate limit is charged once, by the first computation: the
+      // subscriptions are bounded by their number and lifetime instead
+      const subscription = release
+        ? {
+          compute: () =>
+            engine.computePlan(plan, variables, context, info, null, verbose),
+          pollIntervalMs: config.subscription_poll_interval_ms,
+          lifetimeMs: config.subscription_max_lifetime_sec * 1000,
+          release,
+        }
+        : null;
+      return eventStream(res, subscription, headers);
+    }
+
     return jsonOk({ data: { data: res }, headers });
   } catch (err: any) {
     if (err instanceof BaseError) {
@@ -145,11 +316,15 @@ export async function handleGraphQL(
   info: Info,
   limit: RateLimit | null,
   headers: Headers,
+  identifier: string,
 ): Promise<Response> {
   const key = request.headers.get(IDEMPOTENCY_HEADER);
-  let content: Operations | null = null;
+  const stream = acceptsEventStream(request) ? { client: identifier } : null;
+  let content: ResolvedOperations | null = null;
   try {
-    content = await parseRequest(key ? request.clone() : request);
+    content = await engine.persistedQueries.resolve(
+      await parseRequest(key ? request.clone() : request),
+    );
   } catch (err: any) {
     if (err instanceof BaseError) {
       return err.toResponse(headers);
@@ -158,6 +333,15 @@ export async function handleGraphQL(
   }
 
   if (key) {
+    if (stream) {
+      return jsonError({
+        status: 422,
+        message:
+          `'${IDEMPOTENCY_HEADER}' is not supported on '${EVENT_STREAM}' responses`,
+        headers,
+      });
+    }
+
     if (key.length > 255) {
       return jsonError({
         status: 422,
@@ -224,5 +408,6 @@ export async function handleGraphQL(
     info,
     limit,
     headers,
+    stream,
   );
 }
@@ -22,7 +22,8 @@ export const findOperation = (
         if (
           definition.name?.value === forceOptionToValue(operationName) &&
           (definition.operation == "query" ||
-            definition.operation == "mutation")
+            definition.operation == "mutation" ||
+            definition.operation == "subscription")
         ) {
           if (def.isSome()) {
             throw Error(
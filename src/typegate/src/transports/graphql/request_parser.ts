@@ -1,12 +1,21 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
+import { encodeHex } from "@std/encoding/hex";
+import { BaseError, ErrorKind } from "../../errors.ts";
+
 export interface Operations {
-  query: string;
+  /** missing when the request only carries the hash of a persisted query */
+  query?: string;
   operationName?: string;
   variables: Record<string, unknown>;
+  extensions?: {
+    persistedQuery?: { version: number; sha256Hash: string };
+  };
 }
 
+export type ResolvedOperations = Operations & { query: string };
+
 const contentTypes = {
   json: "application/json",
   formData: "multipart/form-data",
@@ -103,3 +112,71 @@ export class FormDataParser {
     }
   }
 }
+
+export class PersistedQueryNotFound extends BaseError {
+  constructor() {
+    super(import.meta, ErrorKind.User, "PersistedQueryNotFound");
+  }
+}
+
+class InvalidPersistedQuery extends BaseError {
+  constructor(message: string) {
+    super(import.meta, ErrorKind.User, message);
+  }
+}
+
+/**
+ * Automatic persisted queries: the clients first send the sha256 hash
+ * of the document, and the full document along with the hash when it
+ * is not known yet.
+ */
+export class PersistedQueryCache {
+  // in the order of their last use
+  #queries: Map<string, string> = new Map();
+
+  constructor(private maxSize = 1000) {}
+
+  async resolve(operations: Operations): Promise<ResolvedOperations> {
+    const { query, extensions } = operations;
+    const persisted = extensions?.persistedQuery;
+    if (persisted == null) {
+      if (typeof query !== "string") {
+        throw new InvalidPersistedQuery("query is required");
+      }
+      return { ...operations, query };
+    }
+
+    if (persisted.version !== 1) {
+      throw new InvalidPersistedQuery(
+        `unsupported persisted query version: ${persisted.version}`,
+      );
+    }
+    const hash = persisted.sha256Hash;
+
+    if (query == null) {
+      const cached = this.#queries.get(hash);
+      if (cached == null) {
+        throw new PersistedQueryNotFound();
+      }
+      this.#queries.delete(hash);
+      this.#queries.set(hash, cached);
+      return { ...operations, query: cached };
+    }
+
+    const digest = await crypto.subtle.digest(
+      "SHA-256",
+      new TextEncoder().encode(query),
+    );
+    if (encodeHex(digest) !== hash) {
+      throw new InvalidPersistedQuery(
+        "provided sha256Hash does not match query",
+      );
+    }
+    this.#queries.delete(hash);
+    this.#queries.set(hash, query);
+    if (this.#queries.size > this.maxSize) {
+      this.#queries.delete(this.#queries.keys().next().value!);
+    }
+    return { ...operations, query };
+  }
+}
@@ -362,6 +362,7 @@ export class Typegate implements AsyncDisposable {
         info,
         limit,
         headers,
+        identifier,
       );
     } catch (e) {
       Sentry.captureException(e);
@@ -44,6 +44,11 @@ import randomizeRecursively, {
 } from "../runtimes/random.ts";
 import type { Typegate } from "../typegate/mod.ts";
 import { TypeUtils } from "./utils.ts";
+import { getValidationContext } from "../engine/typecheck/common.ts";
+import {
+  compileContextRule,
+  type ContextRule,
+} from "../runtimes/deno/context_rule.ts";
 import { getLogger } from "../log.ts";
 
 const logger = getLogger(import.meta);
@@ -232,6 +237,24 @@ export class TypeGraph implements AsyncDisposable {
       introspection,
     );
 
+    // reject invalid custom string formats on deploy
+    getValidationContext(tg);
+    // and invalid context rule patterns
+    for (const policy of typegraph.policies) {
+      const mat = typegraph.materializers[policy.materializer];
+      if (
+        mat.name === "predefined_function" && mat.data.name === "context_rule"
+      ) {
+        try {
+          compileContextRule(mat.data.param as ContextRule);
+        } catch (e) {
+          throw new Error(
+            `invalid context rule "${policy.name}": ${(e as Error).message}`,
+          );
+        }
+      }
+    }
+
     for (const auth of meta.auths) {
       auths.set(
         auth.name,
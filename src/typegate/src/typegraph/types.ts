@@ -8,6 +8,11 @@ export type OptionalNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   item: number;
   default_value?: any;
 };
@@ -16,12 +21,22 @@ export type BooleanNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
 };
 export type FloatNode = {
   type: "float";
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   minimum?: number | null;
   maximum?: number | null;
   exclusiveMinimum?: number | null;
@@ -33,6 +48,11 @@ export type IntegerNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   minimum?: number | null;
   maximum?: number | null;
   exclusiveMinimum?: number | null;
@@ -44,6 +64,11 @@ export type StringNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   minLength?: number | null;
   maxLength?: number | null;
   pattern?: string | null;
@@ -54,6 +79,11 @@ export type FileNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   minSize?: number | null;
   maxSize?: number | null;
   mimeTypes?: string[] | null;
@@ -64,6 +94,11 @@ export type ObjectNode = {
   policies?: Record<string, PolicyIndices[]>;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   properties: {
     [k: string]: number;
   };
@@ -76,6 +111,11 @@ export type ListNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   items: number;
   maxItems?: number | null;
   minItems?: number | null;
@@ -89,6 +129,11 @@ export type FunctionNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   input: number;
   injections?: Record<string, InjectionNode>;
   outjections?: Record<string, InjectionNode>;
@@ -104,6 +149,11 @@ export type UnionNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   anyOf: number[];
 };
 export type EitherNode = {
@@ -111,6 +161,11 @@ export type EitherNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
   oneOf: number[];
 };
 export type AnyNode = {
@@ -118,6 +173,11 @@ export type AnyNode = {
   title: string;
   description?: string | null;
   enum?: string[] | null;
+  examples?: unknown[] | null;
+  deprecated?: Deprecation | null;
+  deprecated_values?: {
+    [k: string]: Deprecation;
+  } | null;
 };
 export type TypeNode =
   | OptionalNode
@@ -164,7 +224,18 @@ export type StringFormat =
   | "ean"
   | "date"
   | "date-time"
-  | "phone";
+  | "phone"
+  | "ipv4"
+  | "ipv6"
+  | "cidr"
+  | "time"
+  | "duration"
+  | "iban"
+  | "semver"
+  | "slug"
+  | "country-code"
+  | "currency-code"
+  | string;
 export type ParameterTransformNodeData =
   | ParameterTransformLeafNode
   | ParameterTransformParentNode;
@@ -360,16 +431,24 @@ export interface RandomRuntimeData {
 export interface PrismaRuntimeData {
   name: string;
   connection_string_secret: string;
+  pool?: PoolConfig | null;
+  read_replica_secrets?: string[] | null;
   models: Model[];
   relationships: Relationship[];
   migration_options?: MigrationOptions | null;
 }
+export interface PoolConfig {
+  size?: number | null;
+  connect_timeout_sec?: number | null;
+  socket_timeout_sec?: number | null;
+}
 export interface Model {
   typeIdx: number;
   typeName: string;
   props: Property[];
   idFields: string[];
   uniqueConstraints: string[][];
+  softDelete?: string | null;
 }
 export interface ManagedInjection {
   create?: Injection2 | null;
@@ -429,12 +508,16 @@ export interface TypeMeta {
   cors: Cors;
   auths: Auth[];
   rate?: Rate | null;
+  limits?: QueryLimits | null;
   version: string;
   randomSeed?: number | null;
   artifacts: {
     [k: string]: Artifact;
   };
   namespaces?: number[] | null;
+  stringFormats?: {
+    [k: string]: string;
+  };
 }
 export interface Queries {
   dynamic: boolean;
@@ -462,6 +545,17 @@ export interface Rate {
   context_identifier?: string | null;
   local_excess: number;
 }
+export interface Deprecation {
+  reason?: string | null;
+  since?: string | null;
+  removal?: string | null;
+}
+export interface QueryLimits {
+  max_depth?: number | null;
+  max_aliases?: number | null;
+  max_cost?: number | null;
+  list_size: number;
+}
 export interface Artifact {
   path: string;
   hash: string;
@@ -477,6 +571,7 @@ export interface PrismaOperationMatData {
   table: string;
   operation: string;
   ordered_keys?: string[] | null;
+  soft_delete?: string | null;
 }
 export interface WasiMatData {
   func: string;
@@ -48,4 +48,10 @@ def prisma_migration(g: Graph):
         deploy=_get_operation_func(PrismaMigrationOperation.DEPLOY),
         # reset database -- dev
         reset=_get_operation_func(PrismaMigrationOperation.RESET),
+        # squash all migrations into one
+        squash=_get_operation_func(PrismaMigrationOperation.SQUASH),
+        # create and mark as applied the first migration of an existing database
+        baseline=_get_operation_func(PrismaMigrationOperation.BASELINE),
+        # read the models of an existing database
+        introspect=_get_operation_func(PrismaMigrationOperation.INTROSPECT),
     )
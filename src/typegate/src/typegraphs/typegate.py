@@ -345,6 +345,65 @@ def typegate(g: Graph):
         Materializer(query_prisma_model_mat_id.value, effect=fx.read()),
     )
 
+    prisma_metrics_mat_id = runtimes.register_typegate_materializer(
+        store, TypegateOperation.PRISMA_METRICS
+    )
+    if isinstance(prisma_metrics_mat_id, Err):
+        raise Exception(prisma_metrics_mat_id.value)
+    prisma_metrics = t.func(
+        t.struct({"typegraph": t.string()}),
+        t.list(
+            t.struct(
+                {
+                    "runtime": t.string(),
+                    # "primary" or "replica_<idx>"
+                    "engine": t.string(),
+                    "connected": t.boolean(),
+                    "queries": t.struct({"total": t.integer(), "errors": t.integer()}),
+                    "latency": t.struct(
+                        {
+                            "meanMs": t.float(),
+                            "maxMs": t.float(),
+                            "p50Ms": t.float(),
+                            "p95Ms": t.float(),
+                            "p99Ms": t.float(),
+                        }
+                    ),
+                    # queries awaiting the engine
+                    "inFlight": t.struct({"current": t.integer(), "max": t.integer()}),
+                    # connection pool gauges of the engine
+                    "pool": t.struct(
+                        {
+                            "open": t.integer(),
+                            "busy": t.integer(),
+                            "idle": t.integer(),
+                            "waiting": t.integer(),
+                        }
+                    ),
+                    "slowQueries": t.struct(
+                        {
+                            "thresholdMs": t.float(),
+                            "count": t.integer(),
+                            # most recent first
+                            "samples": t.list(
+                                t.struct(
+                                    {
+                                        "model": t.string().optional(),
+                                        "action": t.string(),
+                                        "durationMs": t.float(),
+                                        "timestamp": t.string(),
+                                    }
+                                )
+                            ),
+                        }
+                    ),
+                }
+            )
+        ),
+        Materializer(prisma_metrics_mat_id.value, effect=fx.read()),
+        rate_calls=True,
+    )
+
     g.expose(
         admin_only,
         typegraphs=t.func(
@@ -424,6 +483,7 @@ def typegate(g: Graph):
             raw_prisma_delete_mat,
         ),
         queryPrismaModel=query_prisma_model,
+        prismaMetrics=prisma_metrics,
         ping=t.func(
             t.struct({}),
             t.boolean(),  # always True
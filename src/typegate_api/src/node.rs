@@ -4,11 +4,11 @@
 use anyhow::{Context, Result};
 use indoc::indoc;
 use reqwest::{Client, IntoUrl, RequestBuilder, Url};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use crate::graphql::{self, Query};
-use tg_schema::Typegraph;
+use tg_schema::{runtimes::prisma::PrismaIntrospection, Typegraph};
 
 #[derive(Debug, Serialize, Clone)]
 pub struct BasicAuth {
@@ -36,6 +36,30 @@ impl Node {
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrismaSquashOutput {
+    pub squashed_migration_name: Option<String>,
+    pub squashed_count: usize,
+    pub migrations: Option<String>,
+    pub runtime_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrismaBaselineOutput {
+    pub baseline_migration_name: String,
+    pub migrations: String,
+    pub runtime_name: String,
+}
+
+#[derive(Debug)]
+pub struct PrismaIntrospectOutput {
+    pub datamodel: String,
+    pub introspection: PrismaIntrospection,
+    pub runtime_name: String,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
@@ -158,4 +182,120 @@ impl Node {
             .map(|tg| Some(Arc::new(tg)))
             .map_err(|err| Error::Other(anyhow::anyhow!(err)))
     }
+
+    fn prefixed(&self, tg_name: &str) -> String {
+        format!("{}{tg_name}", self.prefix.as_deref().unwrap_or_default())
+    }
+
+    pub async fn prisma_squash(
+        &self,
+        tg_name: &str,
+        runtime_name: Option<&str>,
+        migrations: Option<String>,
+        migration_name: &str,
+        mark_applied: bool,
+    ) -> Result<PrismaSquashOutput, Error> {
+        let res = self
+            .post("/typegate/prisma_migration")
+            .map_err(Error::Other)?
+            .timeout(Duration::from_secs(60))
+            .gql(
+                indoc! {"
+                mutation PrismaSquash($tg: String!, $runtime: String, $migrations: String, $name: String!, $markApplied: Boolean!) {
+                    squash(typegraph: $tg, runtime: $runtime, migrations: $migrations, name: $name, markApplied: $markApplied) {
+                        squashedMigrationName
+                        squashedCount
+                        migrations
+                        runtimeName
+                    }
+                }"}
+                .to_string(),
+                Some(serde_json::json!({
+                    "tg": self.prefixed(tg_name),
+                    "runtime": runtime_name,
+                    "migrations": migrations,
+                    "name": migration_name,
+                    "markApplied": mark_applied,
+                })),
+            )
+            .await
+            .map_err(Error::Graphql)?;
+        res.data("squash").map_err(Error::Other)
+    }
+
+    pub async fn prisma_baseline(
+        &self,
+        tg_name: &str,
+        runtime_name: Option<&str>,
+        migrations: Option<String>,
+        migration_name: &str,
+    ) -> Result<PrismaBaselineOutput, Error> {
+        let res = self
+            .post("/typegate/prisma_migration")
+            .map_err(Error::Other)?
+            .timeout(Duration::from_secs(60))
+            .gql(
+                indoc! {"
+                mutation PrismaBaseline($tg: String!, $runtime: String, $migrations: String, $name: String!) {
+                    baseline(typegraph: $tg, runtime: $runtime, migrations: $migrations, name: $name) {
+                        baselineMigrationName
+                        migrations
+                        runtimeName
+                    }
+                }"}
+                .to_string(),
+                Some(serde_json::json!({
+                    "tg": self.prefixed(tg_name),
+                    "runtime": runtime_name,
+                    "migrations": migrations,
+                    "name": migration_name,
+                })),
+            )
+            .await
+            .map_err(Error::Graphql)?;
+        res.data("baseline").map_err(Error::Other)
+    }
+
+    pub async fn prisma_introspect(
+        &self,
+        tg_name: &str,
+        runtime_name: Option<&str>,
+    ) -> Result<PrismaIntrospectOutput, Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Output {
+            datamodel: String,
+            introspection: String,
+            runtime_name: String,
+        }
+
+        let res = self
+            .post("/typegate/prisma_migration")
+            .map_err(Error::Other)?
+            .timeout(Duration::from_secs(60))
+            .gql(
+                indoc! {"
+                query PrismaIntrospect($tg: String!, $runtime: String) {
+                    introspect(typegraph: $tg, runtime: $runtime) {
+                        datamodel
+                        introspection
+                        runtimeName
+                    }
+                }"}
+                .to_string(),
+                Some(serde_json::json!({
+                    "tg": self.prefixed(tg_name),
+                    "runtime": runtime_name,
+                })),
+            )
+            .await
+            .map_err(Error::Graphql)?;
+        let out: Output = res.data("introspect").map_err(Error::Other)?;
+        Ok(PrismaIntrospectOutput {
+            datamodel: out.datamodel,
+            introspection: serde_json::from_str(&out.introspection)
+                .context("failed to parse introspection result")?,
+            runtime_name: out.runtime_name,
+        })
+    }
 }
@@ -3,7 +3,8 @@
 
 use crate::errors::Result;
 use indexmap::IndexMap;
-use tg_schema::{Auth, AuthProtocol, Cors, Rate};
+use tg_schema::naming::{CollisionPolicy, NameCase, NamingStrategy};
+use tg_schema::{Auth, AuthProtocol, Cors, QueryLimits, Rate};
 
 impl From<crate::sdk::core::Cors> for Cors {
     fn from(value: crate::sdk::core::Cors) -> Self {
@@ -30,6 +31,39 @@ impl From<crate::sdk::core::Rate> for Rate {
     }
 }
 
+impl From<crate::sdk::core::QueryLimits> for QueryLimits {
+    fn from(value: crate::sdk::core::QueryLimits) -> Self {
+        QueryLimits {
+            max_depth: value.max_depth,
+            max_aliases: value.max_aliases,
+            max_cost: value.max_cost,
+            list_size: value.list_size.unwrap_or(QueryLimits::DEFAULT_LIST_SIZE),
+        }
+    }
+}
+
+impl From<crate::sdk::core::NamingStrategy> for NamingStrategy {
+    fn from(value: crate::sdk::core::NamingStrategy) -> Self {
+        use crate::sdk::core::{CollisionPolicy as Collision, NameCase as Case};
+        NamingStrategy {
+            case: match value.case {
+                None | Some(Case::Preserve) => NameCase::Preserve,
+                Some(Case::Pascal) => NameCase::Pascal,
+                Some(Case::Snake) => NameCase::Snake,
+            },
+            prefix: value.prefix,
+            suffix: value.suffix,
+            rename_user_named: value.rename_user_named.unwrap_or(false),
+            collision: match value.collision {
+                None | Some(Collision::Hash) => CollisionPolicy::Hash,
+                Some(Collision::Counter) => CollisionPolicy::Counter,
+                Some(Collision::Error) => CollisionPolicy::Error,
+            },
+            stable_variants: value.stable_variants.unwrap_or(false),
+        }
+    }
+}
+
 impl From<crate::sdk::utils::AuthProtocol> for AuthProtocol {
     fn from(value: crate::sdk::utils::AuthProtocol) -> Self {
         match value {
@@ -3,10 +3,13 @@
 
 use crate::errors::Result;
 use crate::typegraph::TypegraphContext;
-use crate::types::{ExtendedTypeDef, TypeId};
+use crate::types::{ExtendedTypeDef, FindAttribute as _, RefAttrs, TypeDef, TypeId};
+use crate::validation::types::validate_value;
 use enum_dispatch::enum_dispatch;
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::rc::Rc;
-use tg_schema::{TypeNode, TypeNodeBase};
+use tg_schema::{Deprecation, TypeNode, TypeNodeBase};
 
 #[enum_dispatch]
 pub trait TypeConversion {
@@ -51,7 +54,10 @@ impl BaseBuilder {
         Ok(TypeNodeBase {
             description: None,
             enumeration: self.enumeration,
+            examples: None,
             title: self.name,
+            deprecated: None,
+            deprecated_values: None,
         })
     }
 
@@ -61,3 +67,110 @@ impl BaseBuilder {
         self
     }
 }
+
+/// Documentation metadata of a type, from the runtime-agnostic config
+/// (`with_config`): `description`, `examples`, `deprecated` and `deprecated_values`
+pub struct TypeMetadata {
+    description: Option<String>,
+    examples: Option<Vec<serde_json::Value>>,
+    deprecated: Option<Deprecation>,
+    deprecated_values: Option<BTreeMap<String, Deprecation>>,
+}
+
+/// `deprecated: true`, `deprecated: "reason"` or `deprecated: { reason, since, removal }`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DeprecatedConfig {
+    Flag(bool),
+    Reason(String),
+    Full(Deprecation),
+}
+
+impl TypeMetadata {
+    pub fn from_attributes(attributes: &RefAttrs) -> Result<Self> {
+        let configs = attributes.find_runtime_attrs("");
+        let deprecated = config_value(&configs, "deprecated")?.and_then(|config| match config {
+            DeprecatedConfig::Flag(flag) => flag.then(Deprecation::default),
+            DeprecatedConfig::Reason(reason) => Some(Deprecation {
+                reason: Some(reason),
+                ..Default::default()
+            }),
+            DeprecatedConfig::Full(deprecation) => Some(deprecation),
+        });
+        Ok(Self {
+            description: config_value(&configs, "description")?,
+            examples: config_value(&configs, "examples")?,
+            deprecated,
+            deprecated_values: config_value(&configs, "deprecated_values")?,
+        })
+    }
+
+    /// Examples must be valid values of the type; for functions, valid arguments
+    pub fn validate_examples(&self, xdef: &ExtendedTypeDef) -> Result<()> {
+        let Some(examples) = self.examples.as_ref() else {
+            return Ok(());
+        };
+        let type_id = match &xdef.type_def {
+            TypeDef::Func(func) => func.data.inp.into(),
+            _ => xdef.id,
+        };
+        for (i, example) in examples.iter().enumerate() {
+            if let Err(e) = validate_value(example, type_id, format!("<example {i}>")) {
+                return Err(format!("invalid example for {}: {e}", xdef.id.repr()?).into());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply(self, base: &mut TypeNodeBase) -> Result<()> {
+        if self.description.is_some() {
+            base.description = self.description;
+        }
+        base.examples = self.examples;
+        base.deprecated = self.deprecated;
+        let Some(values) = self.deprecated_values else {
+            return Ok(());
+        };
+        let Some(enumeration) = base.enumeration.as_ref() else {
+            return Err(format!(
+                "deprecated_values set on '{}', which is not an enum",
+                base.title
+            )
+            .into());
+        };
+        let mut res = BTreeMap::new();
+        for (value, deprecation) in values {
+            // the values of string enums can be given without the JSON quotes
+            let key = if enumeration.contains(&value) {
+                value
+            } else {
+                let key = serde_json::to_string(&value).unwrap();
+                if !enumeration.contains(&key) {
+                    return Err(format!(
+                        "deprecated value {value} is not in the enum of '{}'",
+                        base.title
+                    )
+                    .into());
+                }
+                key
+            };
+            res.insert(key, deprecation);
+        }
+        base.deprecated_values = Some(res);
+        Ok(())
+    }
+}
+
+fn config_value<T: serde::de::DeserializeOwned>(
+    configs: &[&serde_json::Value],
+    key: &str,
+) -> Result<Option<T>> {
+    configs
+        .iter()
+        .find_map(|config| config.get(key))
+        .map(|value| {
+            serde_json::from_value(value.clone())
+                .map_err(|e| format!("invalid config value for {key}: {e}").into())
+        })
+        .transpose()
+}
@@ -19,6 +19,7 @@ use indexmap::IndexMap;
 use std::rc::Rc;
 use std::{cell::RefCell, collections::HashMap};
 use tg_schema::runtimes::deno::PredefinedFunctionMatData;
+use tg_schema::StringFormat;
 
 const PLACEHOLDER_TYPE_SUFFIX: &str = "_____PLACEHOLDER_____";
 
@@ -65,6 +66,7 @@ pub struct Store {
     typegate_runtime: RuntimeId,
     typegraph_runtime: RuntimeId,
     graphql_endpoints: Vec<String>,
+    string_formats: IndexMap<String, String>,
     auths: Vec<tg_schema::Auth>,
 
     random_seed: Option<u32>,
@@ -406,6 +408,22 @@ impl Store {
         with_store(|s| s.graphql_endpoints.clone())
     }
 
+    /// The pattern is a JavaScript regular expression (unicode mode), compiled
+    /// by the typegate when the typegraph is deployed.
+    pub fn add_string_format(name: String, pattern: String) -> Result<()> {
+        let format: StringFormat = serde_json::from_value(name.clone().into())
+            .map_err(|e| format!("invalid string format name {name:?}: {e}"))?;
+        if !matches!(format, StringFormat::Custom(_)) {
+            return Err(format!("cannot override the built-in string format {name:?}").into());
+        }
+        with_store_mut(|s| s.string_formats.insert(name, pattern));
+        Ok(())
+    }
+
+    pub fn get_string_formats() -> IndexMap<String, String> {
+        with_store(|s| s.string_formats.clone())
+    }
+
     pub fn add_auth(auth: SdkAuth) -> Result<u32> {
         with_store_mut(|s| {
             let auth = auth.convert()?;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: MPL-2.0
 
 pub mod errors;
-use tg_schema::runtimes::deno::{ContextCheckX, PredefinedFunctionMatData};
+use tg_schema::runtimes::deno::{ContextCheckX, ContextRule, PredefinedFunctionMatData};
 use tg_schema::Injection;
 pub use types::sdk;
 
@@ -233,6 +233,24 @@ impl sdk::core::Handler for Lib {
         .map(|id| (id, name))
     }
 
+    fn register_context_rule_policy(name: String, rule: String) -> Result<(PolicyId, String)> {
+        // the patterns are JavaScript regular expressions, compiled by the
+        // typegate when the typegraph is deployed
+        serde_json::from_str::<ContextRule>(&rule)
+            .map_err(|e| format!("invalid context rule for policy {name:?}: {e}"))?;
+
+        let mat_id = Lib::get_predefined_deno_func(MaterializerDenoPredefined {
+            name: "context_rule".to_string(),
+            param: Some(rule),
+        })?;
+
+        Lib::register_policy(Policy {
+            name: name.clone(),
+            materializer: mat_id,
+        })
+        .map(|id| (id, name))
+    }
+
     fn rename_type(type_id: CoreTypeId, new_name: String) -> Result<CoreTypeId, sdk::core::Error> {
         TypeId(type_id).named(new_name).map(|t| t.id().0)
     }
@@ -411,6 +429,7 @@ mod tests {
                     max_age_sec: None,
                 },
                 rate: None,
+                limits: None,
             }
         }
     }
@@ -424,6 +443,7 @@ mod tests {
                 artifact_resolution: false,
                 codegen: false,
                 pretty: true,
+                naming_strategy: None,
                 prisma_migration: PrismaMigrationConfig {
                     migrations_dir: "".to_string(),
                     migration_actions: vec![],
@@ -21,7 +21,8 @@ use std::rc::Rc;
 use crate::conversion::runtimes::MaterializerConverter;
 use crate::global_store::Store;
 use crate::runtimes::prisma::migration::{
-    prisma_apply, prisma_create, prisma_deploy, prisma_diff, prisma_reset,
+    prisma_apply, prisma_baseline, prisma_create, prisma_deploy, prisma_diff, prisma_introspect,
+    prisma_reset, prisma_squash,
 };
 use crate::runtimes::typegraph::TypegraphOperation;
 use crate::sdk::aws::S3RuntimeData;
@@ -225,13 +226,16 @@ pub enum MaterializerData {
 
 macro_rules! prisma_op {
     ( $rt:expr, $model:expr, $op:ident, $name:expr, $effect:expr ) => {{
-        let types = {
+        let (types, soft_delete) = {
             let ctx = get_prisma_context($rt);
             let mut ctx = ctx.borrow_mut();
-            ctx.generate_types(
+            let types = ctx.generate_types(
                 $crate::runtimes::prisma::type_generation::$op,
                 $model.into(),
-            )?
+            )?;
+            let model = ctx.model($crate::types::TypeId($model))?;
+            let soft_delete = model.borrow().soft_delete_column();
+            (types, soft_delete)
         };
 
         let mat = PrismaMaterializer {
@@ -240,6 +244,7 @@ macro_rules! prisma_op {
                 .ok_or_else(|| "prisma model must be named".to_string())?,
             operation: $name.to_string(),
             ordered_keys: None,
+            soft_delete,
         };
 
         let mat_id = Store::register_materializer(Materializer::prisma($rt, mat, $effect));
@@ -523,6 +528,7 @@ impl crate::sdk::runtimes::Handler for crate::Lib {
             table: proc.query,
             operation: "executeRaw".to_string(),
             ordered_keys: Some(proc.ordered_keys),
+            soft_delete: None,
         };
         let mat_id = Store::register_materializer(Materializer::prisma(runtime, mat, effect));
         Ok(FuncParams {
@@ -548,6 +554,7 @@ impl crate::sdk::runtimes::Handler for crate::Lib {
             table: proc.query,
             operation: "queryRaw".to_string(),
             ordered_keys: Some(proc.ordered_keys),
+            soft_delete: None,
         };
         let mat_id = Store::register_materializer(Materializer::prisma(runtime, mat, Effect::Read));
         Ok(FuncParams {
@@ -583,6 +590,9 @@ impl crate::sdk::runtimes::Handler for crate::Lib {
             Op::Apply => (Effect::Update(false), prisma_apply()?),
             Op::Deploy => (Effect::Update(true), prisma_deploy()?),
             Op::Reset => (Effect::Delete(true), prisma_reset()?),
+            Op::Squash => (Effect::Update(false), prisma_squash()?),
+            Op::Baseline => (Effect::Create(false), prisma_baseline()?),
+            Op::Introspect => (Effect::Read, prisma_introspect()?),
         };
 
         let mat_id = Store::register_materializer(Materializer::prisma_migrate(
@@ -627,6 +637,7 @@ impl crate::sdk::runtimes::Handler for crate::Lib {
             SdkOP::RawPrismaUpdate => (Effect::Update(false), Op::RawPrismaQuery),
             SdkOP::RawPrismaDelete => (Effect::Delete(true), Op::RawPrismaQuery),
             SdkOP::QueryPrismaModel => (Effect::Read, Op::QueryPrismaModel),
+            SdkOP::PrismaMetrics => (Effect::Read, Op::PrismaMetrics),
             SdkOP::Ping => (Effect::Read, Op::Ping),
         };
 
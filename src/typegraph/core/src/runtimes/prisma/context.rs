@@ -325,6 +325,7 @@ impl PrismaContext {
                 .collect::<Result<Vec<_>>>()?,
             id_fields: model.id_fields.clone(),
             unique_constraints: model.unique_constraints.clone(),
+            soft_delete: model.soft_delete_column(),
         })
     }
 
@@ -336,6 +337,12 @@ impl PrismaContext {
         Ok(cm::PrismaRuntimeData {
             name: data.name.clone(),
             connection_string_secret: data.connection_string_secret.clone(),
+            pool: data.pool.as_ref().map(|pool| cm::PoolConfig {
+                size: pool.size,
+                connect_timeout_sec: pool.connect_timeout_sec,
+                socket_timeout_sec: pool.socket_timeout_sec,
+            }),
+            read_replica_secrets: data.read_replica_secrets.clone(),
             models: self
                 .models
                 .iter()
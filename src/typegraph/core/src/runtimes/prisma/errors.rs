@@ -55,6 +55,11 @@ pub fn id_field_not_found(model: &str) -> Error {
     format!("Id field not found: model {model}").into()
 }
 
+pub fn managed_column_conflict(model: &str, key: &str) -> Error {
+    format!(r#"Property "{model}::{key}" conflicts with the column managed by the model config"#)
+        .into()
+}
+
 pub fn unregistered_model(type_id: TypeId) -> Error {
     format!("Model not registered: {}", type_id.repr().unwrap()).into()
 }
@@ -28,6 +28,9 @@ impl MaterializerConverter for PrismaMigrationOperation {
                 Self::Apply => "apply",
                 Self::Deploy => "deploy",
                 Self::Reset => "reset",
+                Self::Squash => "squash",
+                Self::Baseline => "baseline",
+                Self::Introspect => "introspect",
             }
             .to_string(),
             runtime,
@@ -97,3 +100,45 @@ pub fn prisma_deploy() -> Result<(TypeId, TypeId)> {
 pub fn prisma_reset() -> Result<(TypeId, TypeId)> {
     Ok((inp_base()?.build()?, t::boolean().build()?))
 }
+
+pub fn prisma_squash() -> Result<(TypeId, TypeId)> {
+    Ok((
+        inp_base()?
+            .propx("name", t::string())?
+            .propx("markApplied", t::boolean())?
+            .propx("migrations", t::string().optional()?)?
+            .build()?,
+        t::struct_()
+            .propx("squashedMigrationName", t::string().optional()?)?
+            .propx("squashedCount", t::integer())?
+            .propx("migrations", t::string().optional()?)?
+            .propx("runtimeName", t::string())?
+            .build()?,
+    ))
+}
+
+pub fn prisma_baseline() -> Result<(TypeId, TypeId)> {
+    Ok((
+        inp_base()?
+            .propx("name", t::string())?
+            .propx("migrations", t::string().optional()?)?
+            .build()?,
+        t::struct_()
+            .propx("baselineMigrationName", t::string())?
+            .propx("migrations", t::string())?
+            .propx("runtimeName", t::string())?
+            .build()?,
+    ))
+}
+
+pub fn prisma_introspect() -> Result<(TypeId, TypeId)> {
+    Ok((
+        inp_base()?.build()?,
+        t::struct_()
+            .propx("datamodel", t::string())?
+            // serialized `tg_schema::runtimes::prisma::PrismaIntrospection`
+            .propx("introspection", t::string())?
+            .propx("runtimeName", t::string())?
+            .build()?,
+    ))
+}
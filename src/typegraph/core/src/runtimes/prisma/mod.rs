@@ -44,6 +44,7 @@ pub struct PrismaMaterializer {
     pub table: String,
     pub operation: String,
     pub ordered_keys: Option<Vec<String>>,
+    pub soft_delete: Option<String>,
 }
 
 impl MaterializerConverter for PrismaMaterializer {
@@ -67,6 +68,12 @@ impl MaterializerConverter for PrismaMaterializer {
             let value = serde_json::to_value(ordered_keys).map_err(|e| e.to_string())?;
             data.insert("ordered_keys".to_string(), value);
         }
+        if let Some(soft_delete) = self.soft_delete.clone() {
+            data.insert(
+                "soft_delete".to_string(),
+                serde_json::Value::String(soft_delete),
+            );
+        }
         Ok(Materializer {
             name: "prisma_operation".to_string(),
             runtime,
@@ -7,6 +7,7 @@ use crate::errors::{ErrorContext as _, Result, TgError};
 use crate::global_store::Store;
 use crate::runtimes::prisma::errors;
 use crate::runtimes::prisma::type_utils::RuntimeConfig;
+use crate::t::{self, TypeBuilder as _};
 use crate::types::{
     AsTypeDefEx as _, FindAttribute, NamedTypeRef, RefAttr, RefAttrs, Struct, Type, TypeDef,
     TypeRef,
@@ -121,6 +122,10 @@ impl std::cmp::PartialEq for ModelType {
 }
 impl std::cmp::Eq for ModelType {}
 
+pub const SOFT_DELETE_COLUMN: &str = "deletedAt";
+pub const CREATED_BY_COLUMN: &str = "createdBy";
+pub const UPDATED_BY_COLUMN: &str = "updatedBy";
+
 #[derive(Debug)]
 pub struct Model {
     pub model_type: ModelType,
@@ -129,6 +134,7 @@ pub struct Model {
     pub unique_constraints: Vec<Vec<String>>,
     // property -> relationship name
     pub relationships: IndexMap<String, String>,
+    pub soft_delete: bool,
 }
 
 impl TryFrom<TypeId> for Model {
@@ -164,13 +170,42 @@ impl TryFrom<TypeId> for Model {
 
         let unique_constraints = get_struct_level_unique_constraints(&model_type.name(), &config)?;
 
-        Ok(Self {
+        let soft_delete = config.get("soft_delete")?.unwrap_or(false);
+        let created_by: Option<String> = config.get("created_by")?;
+        let updated_by: Option<String> = config.get("updated_by")?;
+
+        let mut model = Self {
             model_type,
             props,
             id_fields,
             unique_constraints,
             relationships: Default::default(), // populated later
-        })
+            soft_delete,
+        };
+
+        if soft_delete {
+            model.add_managed_column(
+                SOFT_DELETE_COLUMN,
+                t::string().format("date-time").optional()?.build()?,
+                ManagedColumn::DeletedAt,
+            )?;
+        }
+        if let Some(context_path) = created_by {
+            model.add_managed_column(
+                CREATED_BY_COLUMN,
+                t::string().optional()?.build()?,
+                ManagedColumn::CreatedBy { context_path },
+            )?;
+        }
+        if let Some(context_path) = updated_by {
+            model.add_managed_column(
+                UPDATED_BY_COLUMN,
+                t::string().optional()?.build()?,
+                ManagedColumn::UpdatedBy { context_path },
+            )?;
+        }
+
+        Ok(model)
     }
 }
 
@@ -196,6 +231,34 @@ impl Model {
     pub fn get_prop(&self, key: &str) -> Option<&Property> {
         self.props.get(key)
     }
+
+    /// Name of the column marking the soft-deleted records
+    pub fn soft_delete_column(&self) -> Option<String> {
+        self.soft_delete.then(|| SOFT_DELETE_COLUMN.to_string())
+    }
+
+    fn add_managed_column(
+        &mut self,
+        key: &str,
+        type_id: TypeId,
+        managed: ManagedColumn,
+    ) -> Result<()> {
+        if self.props.contains_key(key) {
+            return Err(errors::managed_column_conflict(
+                &self.model_type.name(),
+                key,
+            ));
+        }
+        let prop = match Property::new(type_id)? {
+            Property::Scalar(prop) => Property::Scalar(ScalarProperty {
+                managed: Some(managed),
+                ..prop
+            }),
+            _ => unreachable!(),
+        };
+        self.props.insert(key.to_string(), prop);
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -279,6 +342,7 @@ impl Property {
                 unique,
                 auto,
                 default_value,
+                managed: None,
             })
         };
 
@@ -420,6 +484,17 @@ impl TryFrom<&tg_schema::Injection> for Injection {
     }
 }
 
+/// Column added and managed by the runtime according to the model config
+#[derive(Debug, Clone)]
+pub enum ManagedColumn {
+    /// set on soft delete
+    DeletedAt,
+    /// injected from the context on create
+    CreatedBy { context_path: String },
+    /// injected from the context on create and update
+    UpdatedBy { context_path: String },
+}
+
 #[derive(Debug, Clone)]
 pub struct ScalarProperty {
     pub wrapper_type_id: TypeId,
@@ -432,6 +507,7 @@ pub struct ScalarProperty {
     pub unique: bool,
     pub auto: bool,
     pub default_value: Option<serde_json::Value>,
+    pub managed: Option<ManagedColumn>,
 }
 
 #[cfg(test)]
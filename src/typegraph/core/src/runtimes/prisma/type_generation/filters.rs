@@ -145,6 +145,38 @@ impl TypeGen for StringFilter {
     }
 }
 
+/// Filter on the soft delete column of a model: `{ equals: null }`, the
+/// default, only matches the records that are not deleted, while an empty
+/// filter matches all the records.
+pub(super) struct SoftDeleteFilter;
+
+impl SoftDeleteFilter {
+    pub fn not_deleted() -> serde_json::Value {
+        serde_json::json!({ "equals": null })
+    }
+}
+
+impl TypeGen for SoftDeleteFilter {
+    fn generate(&self, context: &PrismaContext) -> Result<TypeId> {
+        let opt_type_id = t::optionalx(t::string().format("date-time"))?.build()?;
+
+        t::unionx![
+            t::struct_().prop("equals", opt_type_id),
+            t::struct_()
+                .prop("lt", opt_type_id)
+                .prop("gt", opt_type_id)
+                .prop("lte", opt_type_id)
+                .prop("gte", opt_type_id)
+                .min(1),
+        ]
+        .build_named(self.name(context)?)
+    }
+
+    fn name(&self, _context: &PrismaContext) -> Result<String> {
+        Ok("_prisma_soft_delete_filter".to_string())
+    }
+}
+
 pub(super) struct ScalarListFilter(pub TypeId);
 
 impl TypeGen for ScalarListFilter {
@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use tg_schema::runtimes::prisma::ScalarType;
+use tg_schema::{Injection, InjectionData, SingleValue};
 
 use crate::errors::Result;
 use crate::runtimes::prisma::context::PrismaContext;
-use crate::runtimes::prisma::model::{InjectionHandler, Property};
-use crate::runtimes::prisma::{relationship::Cardinality, type_generation::where_::Where};
+use crate::runtimes::prisma::model::{InjectionHandler, ManagedColumn, Property};
+use crate::runtimes::prisma::relationship::Cardinality;
+use crate::runtimes::prisma::type_generation::where_::{optional_where, Where};
 use crate::t::{self, TypeBuilder};
+use crate::types::type_ref::WithInjection as _;
 use crate::types::TypeId;
 
 use super::TypeGen;
@@ -105,10 +108,17 @@ impl TypeGen for InputType {
                         })?;
                         inner.addx(t::struct_().prop("update", update))?;
 
+                        // related records with soft delete are deleted with an
+                        // update setting `deletedAt`: no nested delete
+                        let hard_delete =
+                            !context.model(prop.model_type.type_id)?.borrow().soft_delete;
+
                         match prop.quantifier {
                             Cardinality::Optional => {
                                 inner.addx(t::struct_().propx("disconnect", t::boolean())?)?;
-                                inner.addx(t::struct_().propx("delete", t::boolean())?)?;
+                                if hard_delete {
+                                    inner.addx(t::struct_().propx("delete", t::boolean())?)?;
+                                }
 
                                 let upsert = t::struct_()
                                     .prop("create", create)
@@ -117,19 +127,19 @@ impl TypeGen for InputType {
                                 inner.addx(t::struct_().prop("upsert", upsert))?;
                             }
                             Cardinality::Many => {
-                                inner.addx(
-                                    t::struct_().propx(
-                                        "updateMany",
-                                        t::struct_()
-                                            .propx("where", t::optional(connect))?
-                                            .prop("data", update),
-                                    )?,
-                                )?;
-
+                                let where_ =
+                                    optional_where(context, prop.model_type.type_id, connect)?;
                                 inner.addx(t::struct_().propx(
-                                    "deleteMany",
-                                    t::struct_().propx("where", t::optional(connect))?,
+                                    "updateMany",
+                                    t::struct_().prop("where", where_).prop("data", update),
                                 )?)?;
+
+                                if hard_delete {
+                                    inner.addx(t::struct_().propx(
+                                        "deleteMany",
+                                        t::struct_().prop("where", where_),
+                                    )?)?;
+                                }
                             }
                             _ => (),
                         }
@@ -151,6 +161,21 @@ impl TypeGen for InputType {
                 }
 
                 Property::Scalar(prop) => {
+                    if let Some(managed) = &prop.managed {
+                        match (managed, self.operation) {
+                            (ManagedColumn::CreatedBy { context_path }, Operation::Create)
+                            | (ManagedColumn::UpdatedBy { context_path }, _) => {
+                                builder
+                                    .prop(k, context_injected(prop.wrapper_type_id, context_path)?);
+                                continue;
+                            }
+                            // deletedAt: soft delete (or restore) of the nested records
+                            (ManagedColumn::DeletedAt, Operation::Update) => (),
+                            // deletedAt: not deleted on creation
+                            // createdBy: immutable after creation
+                            _ => continue,
+                        }
+                    }
                     match self.operation {
                         Operation::Create => {
                             if let Some(inj) = &prop.injection {
@@ -235,3 +260,15 @@ impl TypeGen for InputType {
         Ok(format!("{model_name}_{op}_input{suffix}"))
     }
 }
+
+/// Audit columns are injected whatever the effect of the operation as the
+/// input type already tells whether the record is created or updated:
+/// a nested create within an update still sets `createdBy`.
+fn context_injected(type_id: TypeId, context_path: &str) -> Result<TypeId> {
+    let path = SingleValue {
+        value: serde_json::Value::String(context_path.to_string()),
+    };
+    Ok(type_id
+        .with_injection(Injection::Context(InjectionData::SingleValue(path)))?
+        .id())
+}
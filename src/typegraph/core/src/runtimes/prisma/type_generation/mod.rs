@@ -18,6 +18,7 @@ use self::input_type::InputType;
 use self::out_type::OutType;
 use self::query_input_type::QueryInputType;
 use self::query_where_expr::QueryWhereExpr;
+use self::where_::optional_where;
 use self::with_nested_count::WithNestedCount;
 
 use super::context::PrismaContext;
@@ -172,10 +173,14 @@ pub struct DeleteMany;
 impl PrismaOperation for FindUnique {
     fn generate_input_type(&self, context: &PrismaContext, model_id: TypeId) -> Result<TypeId> {
         t::struct_()
-            .propx(
+            .prop(
                 "where",
-                t::optional(context.generate(&QueryWhereExpr::new(model_id).unique())?),
-            )?
+                optional_where(
+                    context,
+                    model_id,
+                    context.generate(&QueryWhereExpr::new(model_id).unique())?,
+                )?,
+            )
             .build()
     }
 
@@ -297,7 +302,11 @@ impl PrismaOperation for UpdateMany {
             .prop("data", context.generate(&InputType::for_update(model_id))?)
             .prop(
                 "where",
-                t::optional(context.generate(&QueryWhereExpr::new(model_id))?).build()?,
+                optional_where(
+                    context,
+                    model_id,
+                    context.generate(&QueryWhereExpr::new(model_id))?,
+                )?,
             )
             .build()
     }
@@ -347,7 +356,11 @@ impl PrismaOperation for DeleteMany {
         t::struct_()
             .prop(
                 "where",
-                t::optional(context.generate(&QueryWhereExpr::new(model_id))?).build()?,
+                optional_where(
+                    context,
+                    model_id,
+                    context.generate(&QueryWhereExpr::new(model_id))?,
+                )?,
             )
             .build()
     }
@@ -378,6 +391,7 @@ impl PrismaContext {
 mod test {
     use super::*;
     use crate::test_utils::*;
+    use crate::types::{AsTypeDefEx as _, TypeDef};
     use paste::paste;
 
     /// generate a test for the operation:
@@ -480,4 +494,73 @@ mod test {
     // test_op!(UpsertOne);
     // test_op!(DeleteOne);
     // test_op!(DeleteMany);
+
+    fn prop(struct_id: TypeId, key: &str) -> Result<TypeId> {
+        let struct_ = struct_id.as_struct()?;
+        let found = struct_.data.props.iter().find(|(k, _)| k == key);
+        Ok(found
+            .ok_or_else(|| format!("prop {key} not found"))?
+            .1
+            .into())
+    }
+
+    fn optional_default(type_id: TypeId) -> Result<Option<serde_json::Value>> {
+        match type_id.as_xdef()?.type_def {
+            TypeDef::Optional(inner) => Ok(inner
+                .data
+                .default_item
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .unwrap()),
+            _ => Err("optional expected".into()),
+        }
+    }
+
+    #[test]
+    fn test_soft_delete() -> Result<()> {
+        setup(None)?;
+        let mut context = PrismaContext::default();
+
+        let (user, post) = models::soft_delete_relationship()?;
+        context.manage(user)?;
+
+        let inp = FindMany.generate_input_type(&context, post)?;
+        insta::assert_snapshot!(
+            "soft_delete Post find_many inp",
+            tree::PrintOptions::new().indent_size(3).print(inp)
+        );
+
+        // deleted posts filtered out by default, for the relation filters too
+        let not_deleted = serde_json::json!({ "equals": null });
+        assert_eq!(
+            optional_default(prop(inp, "where")?)?,
+            Some(serde_json::json!({ "deletedAt": not_deleted }))
+        );
+        let where_ = context.generate(&where_::Where::new(post))?;
+        assert_eq!(
+            optional_default(prop(where_, "deletedAt")?)?,
+            Some(not_deleted)
+        );
+
+        // nested soft delete: `updateMany` setting `deletedAt`
+        let update = context.generate(&InputType::for_update(user))?;
+        let TypeDef::Optional(posts) = prop(update, "posts")?.as_xdef()?.type_def else {
+            panic!("optional expected");
+        };
+        let TypeDef::Union(posts) = TypeId(posts.data.of).as_xdef()?.type_def else {
+            panic!("union expected");
+        };
+        let ops = posts
+            .data
+            .variants
+            .iter()
+            .map(|v| Ok(TypeId(*v).as_struct()?.data.props[0].0.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        assert!(ops.contains(&"updateMany".to_string()));
+        assert!(!ops.contains(&"deleteMany".to_string()));
+        prop(context.generate(&InputType::for_update(post))?, "deletedAt")?;
+
+        Ok(())
+    }
 }
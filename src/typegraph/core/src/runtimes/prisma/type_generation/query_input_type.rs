@@ -13,6 +13,7 @@ use super::{
     group_by::{GroupingFields, Having},
     order_by::OrderBy,
     query_where_expr::QueryWhereExpr,
+    where_::optional_where,
     TypeGen,
 };
 
@@ -43,10 +44,14 @@ impl TypeGen for QueryInputType {
         };
 
         builder
-            .propx(
+            .prop(
                 "where",
-                t::optional(context.generate(&QueryWhereExpr::new(self.model_id))?),
-            )?
+                optional_where(
+                    context,
+                    self.model_id,
+                    context.generate(&QueryWhereExpr::new(self.model_id))?,
+                )?,
+            )
             .propx("orderBy", t::optional(context.generate(&order_by)?))?
             .propx("take", t::optional(context.generate(&Take)?))?
             .propx("skip", t::optional(context.generate(&Skip)?))?
@@ -5,13 +5,14 @@ use tg_schema::runtimes::prisma::ScalarType;
 
 use crate::errors::Result;
 use crate::runtimes::prisma::context::PrismaContext;
-use crate::runtimes::prisma::model::Property;
+use crate::runtimes::prisma::model::{ManagedColumn, Property};
 use crate::runtimes::prisma::relationship::Cardinality;
 use crate::t::{self, TypeBuilder};
 use crate::types::TypeId;
 
 use super::filters::{
-    BooleanFilter, CompleteFilter, NumberFilter, NumberType, ScalarListFilter, StringFilter,
+    BooleanFilter, CompleteFilter, NumberFilter, NumberType, ScalarListFilter, SoftDeleteFilter,
+    StringFilter,
 };
 use super::TypeGen;
 
@@ -84,6 +85,21 @@ impl TypeGen for Where {
                 }
 
                 Property::Scalar(prop) => {
+                    // soft-deleted records are filtered out unless the filter
+                    // on the column is overridden, e.g. `deletedAt: {}`.
+                    // Note: `every` relation filters are false when a deleted
+                    // related record exists.
+                    if let (Some(ManagedColumn::DeletedAt), false) =
+                        (&prop.managed, self.aggregates)
+                    {
+                        let filter = context.generate(&SoftDeleteFilter)?;
+                        builder.propx(
+                            key,
+                            t::optional(filter).default_item(SoftDeleteFilter::not_deleted()),
+                        )?;
+                        continue;
+                    }
+
                     let generated = if let Cardinality::Many = prop.quantifier {
                         context.generate(&CompleteFilter(ScalarListFilter(prop.type_id)))?
                     } else {
@@ -148,6 +164,20 @@ impl TypeGen for Where {
     }
 }
 
+/// Type of an optional `where` argument: the soft-deleted records are
+/// still filtered out when it is omitted.
+pub(super) fn optional_where(
+    context: &PrismaContext,
+    model_id: TypeId,
+    where_type: TypeId,
+) -> Result<TypeId> {
+    let mut builder = t::optional(where_type);
+    if let Some(column) = context.model(model_id)?.borrow().soft_delete_column() {
+        builder.default_item(serde_json::json!({ column: SoftDeleteFilter::not_deleted() }));
+    }
+    builder.build()
+}
+
 #[cfg(test)]
 mod test {
     use super::super::*;
@@ -37,8 +37,11 @@ pub fn collect_random_runtime_config(out_type: TypeId) -> Result<Option<RandConf
     let xdef = out_type.as_xdef()?;
     let gen = {
         let xdef = &xdef;
-        let attr = xdef.attributes.find_runtime_attr("");
-        attr.and_then(|attr| serde_json::from_value::<Generator>(attr.clone()).ok())
+        // other configs (e.g. `examples`) may wrap the generator config
+        let attrs = xdef.attributes.find_runtime_attrs("");
+        attrs
+            .into_iter()
+            .find_map(|attr| serde_json::from_value::<Generator>(attr.clone()).ok())
     };
     // TODO fail if gen is some on parent?
     match xdef.type_def {
@@ -21,6 +21,7 @@ pub enum TypegateOperation {
     FindPrismaModels,
     RawPrismaQuery,
     QueryPrismaModel,
+    PrismaMetrics,
     Ping,
 }
 
@@ -45,6 +46,7 @@ impl MaterializerConverter for TypegateOperation {
                 Self::FindPrismaModels => "findPrismaModels",
                 Self::RawPrismaQuery => "execRawPrismaQuery",
                 Self::QueryPrismaModel => "queryPrismaModel",
+                Self::PrismaMetrics => "prismaMetrics",
                 Self::Ping => "ping",
             }
             .to_string(),
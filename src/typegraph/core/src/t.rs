@@ -218,7 +218,6 @@ pub fn string() -> StringBuilder {
 }
 
 impl StringBuilder {
-    #[allow(dead_code)]
     pub fn format(&mut self, format: impl Into<String>) -> &mut Self {
         self.data.format = Some(format.into());
         self
@@ -262,6 +261,13 @@ pub fn optionalx(item_builder: impl TypeBuilder) -> Result<OptionalBuilder> {
     Ok(optional(item_builder.build()?))
 }
 
+impl OptionalBuilder {
+    pub fn default_item(&mut self, value: serde_json::Value) -> &mut Self {
+        self.data.default_item = Some(value.to_string());
+        self
+    }
+}
+
 #[derive(Default)]
 pub struct ListBuilder {
     data: TypeList,
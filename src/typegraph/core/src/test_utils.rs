@@ -66,6 +66,24 @@ pub mod models {
 
         Ok((user, post))
     }
+
+    /// same as `simple_relationship`, with soft delete on the posts
+    pub fn soft_delete_relationship() -> Result<(TypeId, TypeId)> {
+        let user = t::struct_()
+            .propx("id", t::integer().as_id())?
+            .propx("name", t::string())?
+            .propx("posts", t::listx(t::ref_("Post", Default::default()))?)?
+            .build_named("User")?;
+
+        let post = t::struct_()
+            .propx("id", t::integer().config("auto", true).as_id())?
+            .propx("title", t::string())?
+            .propx("author", t::ref_("User", Default::default()))?
+            .config("soft_delete", true)?
+            .build_named("Post")?;
+
+        Ok((user, post))
+    }
 }
 
 pub fn setup(name: Option<&str>) -> crate::errors::Result<()> {
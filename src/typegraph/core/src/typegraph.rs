@@ -3,7 +3,7 @@
 
 use crate::conversion::hash::Hasher;
 use crate::conversion::runtimes::{convert_materializer, convert_runtime, ConvertedRuntime};
-use crate::conversion::types::TypeConversion as _;
+use crate::conversion::types::{TypeConversion as _, TypeMetadata};
 use crate::global_store::SavedState;
 use crate::typedef::struct_::extend_policy_chain;
 use crate::types::{
@@ -112,11 +112,13 @@ pub fn init(params: TypegraphInitParams) -> Result<()> {
             auths: vec![],
             prefix: params.prefix,
             rate: params.rate.map(|v| v.into()),
+            limits: params.limits.map(|v| v.into()),
             secrets: vec![],
             outjection_secrets: vec![],
             random_seed: Default::default(),
             artifacts: Default::default(),
             namespaces: Default::default(),
+            string_formats: Default::default(),
         },
         types: vec![],
         saved_store_state: Some(Store::save()),
@@ -130,7 +132,10 @@ pub fn init(params: TypegraphInitParams) -> Result<()> {
         base: TypeNodeBase {
             description: None,
             enumeration: None,
+            examples: None,
             title: params.name,
+            deprecated: None,
+            deprecated_values: None,
         },
         data: ObjectTypeData {
             properties: IndexMap::new(),
@@ -222,6 +227,7 @@ pub fn serialize(params: SerializeParams) -> Result<(String, Vec<SdkArtifact>)>
             // },
             artifacts: ctx.meta.artifacts,
             random_seed: Store::get_random_seed(),
+            string_formats: Store::get_string_formats(),
             auths,
             ..ctx.meta
         },
@@ -232,12 +238,18 @@ pub fn serialize(params: SerializeParams) -> Result<(String, Vec<SdkArtifact>)>
     tg.meta.prefix.clone_from(&params.prefix);
 
     let pretty = params.pretty;
+    let naming_strategy = params
+        .naming_strategy
+        .clone()
+        .map(Into::into)
+        .unwrap_or_default();
 
     // dedup_types(&mut tg);
 
     TypegraphPostProcessor::new(params).postprocess(&mut tg)?;
     NamingProcessor {
         user_named: ctx.user_named_types,
+        strategy: naming_strategy,
     }
     .postprocess(&mut tg)?;
 
@@ -388,7 +400,10 @@ impl TypegraphContext {
 
                 // let tpe = id.as_type()?;
 
-                let type_node = xdef.type_def.clone().convert(self, xdef)?;
+                let metadata = TypeMetadata::from_attributes(&xdef.attributes)?;
+                metadata.validate_examples(&xdef)?;
+                let mut type_node = xdef.type_def.clone().convert(self, xdef)?;
+                metadata.apply(type_node.base_mut())?;
 
                 self.types[idx] = Some(type_node);
                 if is_user_named {
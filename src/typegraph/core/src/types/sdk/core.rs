@@ -35,6 +35,14 @@ pub struct Rate {
     pub local_excess: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryLimits {
+    pub max_depth: Option<u32>,
+    pub max_aliases: Option<u32>,
+    pub max_cost: Option<u32>,
+    pub list_size: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypegraphInitParams {
     pub name: String,
@@ -43,6 +51,7 @@ pub struct TypegraphInitParams {
     pub prefix: Option<String>,
     pub cors: Cors,
     pub rate: Option<Rate>,
+    pub limits: Option<QueryLimits>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +75,32 @@ pub struct PrismaMigrationConfig {
     pub default_migration_action: MigrationAction,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameCase {
+    Preserve,
+    Pascal,
+    Snake,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionPolicy {
+    Hash,
+    Counter,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamingStrategy {
+    pub case: Option<NameCase>,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub rename_user_named: Option<bool>,
+    pub collision: Option<CollisionPolicy>,
+    pub stable_variants: Option<bool>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializeParams {
     pub typegraph_name: String,
@@ -75,6 +110,7 @@ pub struct SerializeParams {
     pub codegen: bool,
     pub prisma_migration: PrismaMigrationConfig,
     pub pretty: bool,
+    pub naming_strategy: Option<NamingStrategy>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -254,6 +290,10 @@ pub trait Handler {
         key: String,
         check: ContextCheck,
     ) -> Result<(PolicyId, String), super::Error>;
+    fn register_context_rule_policy(
+        name: String,
+        rule: String,
+    ) -> Result<(PolicyId, String), super::Error>;
     fn rename_type(tpe: TypeId, new_name: String) -> Result<TypeId, super::Error>;
     fn expose(
         fns: Vec<(String, TypeId)>,
@@ -138,10 +138,19 @@ pub struct MaterializerWasmWireHandler {
     pub func_name: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrismaPoolConfig {
+    pub size: Option<u32>,
+    pub connect_timeout_sec: Option<u32>,
+    pub socket_timeout_sec: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrismaRuntimeData {
     pub name: String,
     pub connection_string_secret: String,
+    pub pool: Option<PrismaPoolConfig>,
+    pub read_replica_secrets: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -161,6 +170,9 @@ pub enum PrismaMigrationOperation {
     Apply,
     Deploy,
     Reset,
+    Squash,
+    Baseline,
+    Introspect,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -203,6 +215,7 @@ pub enum TypegateOperation {
     RawPrismaUpdate,
     RawPrismaDelete,
     QueryPrismaModel,
+    PrismaMetrics,
     Ping,
 }
 
@@ -62,6 +62,7 @@ pub struct BaseOauth2Params {
 pub trait Handler {
     fn reduceb(super_type_id: TypeId, entries: Vec<ReduceEntry>) -> Result<TypeId, super::Error>;
     fn add_graphql_endpoint(graphql: String) -> Result<u32, super::Error>;
+    fn add_string_format(name: String, pattern: String) -> Result<(), super::Error>;
     fn add_auth(data: Auth) -> Result<u32, super::Error>;
     fn add_raw_auth(data: String) -> Result<u32, super::Error>;
     fn oauth2(params: BaseOauth2Params) -> Result<String, super::Error>;
@@ -38,7 +38,17 @@ impl ExtendedTypeDef {
         let attributes = self
             .attributes
             .iter()
-            .filter(|a| matches!(a.as_ref(), RefAttr::Policy(_) | RefAttr::Reduce(_)))
+            .filter(|a| match a.as_ref() {
+                RefAttr::Policy(_) | RefAttr::Reduce(_) => true,
+                // documentation metadata is set on the type node
+                RefAttr::RuntimeConfig { runtime, data } => {
+                    runtime.is_empty()
+                        && ["description", "examples", "deprecated", "deprecated_values"]
+                            .iter()
+                            .any(|key| data.get(key).is_some())
+                }
+                _ => false,
+            })
             .collect::<Vec<_>>();
         if !attributes.is_empty() {
             "attributes".hash(hasher);
@@ -29,6 +29,12 @@ impl InputResolverSync for RawTgResolver {
             } => Ok(GeneratorInputResolved::FdkTemplate {
                 template: self.load_fdk_template(default, override_path.as_deref())?,
             }),
+            GeneratorInputOrder::PrismaIntrospection { typegraph, .. } => {
+                color_eyre::eyre::bail!(
+                    "cannot introspect the database for typegraph {typegraph:?}: \
+                    introspection is only available through `meta gen`"
+                )
+            }
         }
     }
 }
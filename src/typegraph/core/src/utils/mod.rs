@@ -80,6 +80,10 @@ impl crate::sdk::utils::Handler for crate::Lib {
         Store::add_graphql_endpoint(graphql)
     }
 
+    fn add_string_format(name: String, pattern: String) -> Result<()> {
+        Store::add_string_format(name, pattern)
+    }
+
     fn add_auth(data: SdkAuth) -> Result<u32> {
         Store::add_auth(data)
     }
@@ -9,6 +9,7 @@ use std::{
 use indexmap::IndexSet;
 use sha2::{Digest, Sha256};
 use tg_schema::{
+    naming::{NameAllocator, NamingStrategy},
     visitor::{Edge, PathSegment},
     StringFormat, TypeNode, Typegraph,
 };
@@ -17,6 +18,7 @@ use crate::errors::TgError;
 
 pub struct NamingProcessor {
     pub user_named: HashSet<u32>,
+    pub strategy: NamingStrategy,
 }
 impl super::PostProcessor for NamingProcessor {
     fn postprocess(self, tg: &mut tg_schema::Typegraph) -> Result<(), crate::errors::TgError> {
@@ -56,9 +58,29 @@ impl super::PostProcessor for NamingProcessor {
             visit_type(&cx, &mut acc, ty_id).map_err(|err| TgError::from(format!("{err}")))?;
         }
         // crate::logger::error!("{:?} - {:#?}", cx.user_named, acc.named_types);
-        for (id, name) in acc.named_types {
-            let node = &mut tg.types[id as usize];
-            node.base_mut().title = name.to_string();
+
+        // explicit names first, so that the generated names are the ones
+        // disambiguated on collision
+        let user_named = cx.user_named;
+        let mut ids = acc.named_types.keys().copied().collect::<Vec<_>>();
+        ids.sort_by_key(|id| (!user_named.contains(id), *id));
+        let mut names = NameAllocator::default();
+        for (id, node) in tg.types.iter().enumerate() {
+            if !acc.named_types.contains_key(&(id as u32)) {
+                names.reserve(node.base().title.clone());
+            }
+        }
+        for id in ids {
+            let raw_name = &acc.named_types[&id];
+            let name = if user_named.contains(&id) {
+                self.strategy.apply_user_named(raw_name)
+            } else {
+                self.strategy.apply(raw_name)
+            };
+            let name = names
+                .allocate(&self.strategy, name, raw_name)
+                .map_err(|e| TgError::from(e.to_string()))?;
+            tg.types[id as usize].base_mut().title = name;
         }
         Ok(())
     }
@@ -239,6 +261,17 @@ fn visit_type(cx: &VisitContext, acc: &mut VisitCollector, id: u32) -> anyhow::R
                     StringFormat::Date => "string_date",
                     StringFormat::DateTime => "string_datetime",
                     StringFormat::Phone => "string_phone",
+                    StringFormat::Ipv4 => "string_ipv4",
+                    StringFormat::Ipv6 => "string_ipv6",
+                    StringFormat::Cidr => "string_cidr",
+                    StringFormat::Time => "string_time",
+                    StringFormat::Duration => "string_duration",
+                    StringFormat::Iban => "string_iban",
+                    StringFormat::Semver => "string_semver",
+                    StringFormat::Slug => "string_slug",
+                    StringFormat::CountryCode => "string_country_code",
+                    StringFormat::CurrencyCode => "string_currency_code",
+                    StringFormat::Custom(_) => "string_custom",
                 }
             } else if base.enumeration.as_ref().map(|vec| !vec.is_empty()) == Some(true) {
                 "string_enum"
@@ -391,3 +424,82 @@ impl metagen::shared::types::RenderType for NameRenderer {
     ) -> color_eyre::eyre::Result<String> {
     }
 } */
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::Result;
+    use crate::global_store::Store;
+    use crate::sdk::core::{Handler as _, NameCase, NamingStrategy, SerializeParams};
+    use crate::sdk::runtimes::{Effect, Handler as _, MaterializerDenoFunc};
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::setup;
+    use crate::Lib;
+
+    fn serialized_titles(strategy: NamingStrategy) -> Result<Vec<String>> {
+        Store::reset();
+        setup(None)?;
+        let payload = t::struct_()
+            .prop("value", t::integer().build()?)
+            .build_named("Payload")?;
+        let inp = t::struct_()
+            .prop("payload", payload)
+            .prop("tags", t::list(t::string().build()?).build()?)
+            .build()?;
+        let mat =
+            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => 12"), Effect::Read)?;
+        Lib::expose(
+            vec![(
+                "create_item".to_string(),
+                t::func(inp, payload, mat)?.into(),
+            )],
+            None,
+        )?;
+        let (tg, _) = Lib::serialize_typegraph(SerializeParams {
+            naming_strategy: Some(strategy),
+            ..Default::default()
+        })?;
+        let tg: serde_json::Value = serde_json::from_str(&tg).unwrap();
+        Ok(tg["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ty| ty["title"].as_str().unwrap().to_string())
+            .collect())
+    }
+
+    fn strategy() -> NamingStrategy {
+        NamingStrategy {
+            case: None,
+            prefix: None,
+            suffix: None,
+            rename_user_named: None,
+            collision: None,
+            stable_variants: None,
+        }
+    }
+
+    #[test]
+    fn test_naming_strategy() -> Result<()> {
+        let titles = serialized_titles(NamingStrategy {
+            case: Some(NameCase::Pascal),
+            prefix: Some("api".to_string()),
+            ..strategy()
+        })?;
+        assert_eq!(titles[0], "test");
+        assert_eq!(titles[1], "ApiRootCreateItemFn");
+        assert_eq!(titles[2], "ApiRootCreateItemFnInput");
+        assert_eq!(titles[4], "ApiPayloadValueInteger");
+        // explicit names are kept by default
+        assert_eq!(titles[3], "Payload");
+
+        let titles = serialized_titles(NamingStrategy {
+            case: Some(NameCase::Snake),
+            suffix: Some("t".to_string()),
+            rename_user_named: Some(true),
+            ..strategy()
+        })?;
+        assert_eq!(titles[1], "root_create_item_fn_t");
+        assert_eq!(titles[3], "payload_t");
+        Ok(())
+    }
+}
@@ -58,6 +58,7 @@ impl Materializer {
                     | P::Deny
                     | P::Pass
                     | P::ContextCheck { .. }
+                    | P::ContextRule(_)
                     | P::InternalPolicy { .. } => {
                         if let Ok(xdef) = TypeId(func.out).as_xdef() {
                             let TypeDef::String(_) = xdef.type_def else {
@@ -67,6 +68,7 @@ impl Materializer {
                                         P::Deny => "deny",
                                         P::Pass => "pass",
                                         P::ContextCheck { .. } => "context_check",
+                                        P::ContextRule(_) => "context_rule",
                                         P::InternalPolicy { .. } => "internal_policy",
                                         _ => unreachable!(),
                                     },
@@ -32,6 +32,13 @@ export type Rate = {
   localExcess: number
 }
 
+export type QueryLimits = {
+  maxDepth?: number
+  maxAliases?: number
+  maxCost?: number
+  listSize?: number
+}
+
 export type TypegraphInitParams = {
   name: string
   dynamic?: boolean
@@ -39,6 +46,7 @@ export type TypegraphInitParams = {
   prefix?: string
   cors: Cors
   rate?: Rate
+  limits?: QueryLimits
 }
 
 export type Artifact = {
@@ -59,6 +67,25 @@ export type PrismaMigrationConfig = {
   defaultMigrationAction: MigrationAction
 }
 
+export type NameCase =
+  | "preserve"
+  | "pascal"
+  | "snake"
+
+export type CollisionPolicy =
+  | "hash"
+  | "counter"
+  | "error"
+
+export type NamingStrategy = {
+  case?: NameCase
+  prefix?: string
+  suffix?: string
+  renameUserNamed?: boolean
+  collision?: CollisionPolicy
+  stableVariants?: boolean
+}
+
 export type SerializeParams = {
   typegraphName: string
   typegraphPath: string
@@ -67,6 +94,7 @@ export type SerializeParams = {
   codegen: boolean
   prismaMigration: PrismaMigrationConfig
   pretty: boolean
+  namingStrategy?: NamingStrategy
 }
 
 export type TypeProxy = {
@@ -287,6 +315,10 @@ export function registerContextPolicy(key: string, check: ContextCheck): [Policy
   return rpcRequest("register_context_policy", { key, check });
 }
 
+export function registerContextRulePolicy(name: string, rule: string): [PolicyId, string] {
+  return rpcRequest("register_context_rule_policy", { name, rule });
+}
+
 export function renameType(tpe: TypeId, new_name: string): TypeId {
   return rpcRequest("rename_type", { tpe, new_name });
 }
@@ -114,9 +114,17 @@ export type MaterializerWasmWireHandler = {
   funcName: string
 }
 
+export type PrismaPoolConfig = {
+  size?: number
+  connectTimeoutSec?: number
+  socketTimeoutSec?: number
+}
+
 export type PrismaRuntimeData = {
   name: string
   connectionStringSecret: string
+  pool?: PrismaPoolConfig
+  readReplicaSecrets?: string[]
 }
 
 export type PrismaLinkData = {
@@ -132,7 +140,10 @@ export type PrismaMigrationOperation =
   | "create"
   | "apply"
   | "deploy"
-  | "reset";
+  | "reset"
+  | "squash"
+  | "baseline"
+  | "introspect";
 
 export type TemporalRuntimeData = {
   name: string
@@ -167,6 +178,7 @@ export type TypegateOperation =
   | "raw_prisma_update"
   | "raw_prisma_delete"
   | "query_prisma_model"
+  | "prisma_metrics"
   | "ping";
 
 export type TypegraphOperation =
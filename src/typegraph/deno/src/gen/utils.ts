@@ -57,6 +57,10 @@ export function addGraphqlEndpoint(graphql: string): number {
   return rpcRequest("add_graphql_endpoint", { graphql });
 }
 
+export function addStringFormat(name: string, pattern: string): void {
+  return rpcRequest("add_string_format", { name, pattern });
+}
+
 export function addAuth(data: Auth): number {
   return rpcRequest("add_auth", { data });
 }
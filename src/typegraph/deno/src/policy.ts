@@ -49,6 +49,18 @@ export default class Policy {
     return new Policy(id, name);
   }
 
+  /**
+   * declarative policy on the context and the arguments, evaluated by the typegate,
+   * e.g. `Policy.rule("owner", ctx("user.id").eq(arg("owner_id")))`
+   */
+  static rule(name: string, rule: Rule): Policy {
+    const [id, policyName] = core.registerContextRulePolicy(
+      name,
+      JSON.stringify(rule.data),
+    );
+    return new Policy(id, policyName);
+  }
+
   static internal(): Policy {
     const [id, name] = core.getInternalPolicy();
     return new Policy(id, name);
@@ -66,3 +78,98 @@ export default class Policy {
     return new PolicyPerEffectObject(effects);
   }
 }
+
+// deno-lint-ignore no-explicit-any
+type RuleData = Record<string, any>;
+
+export class Rule {
+  constructor(public readonly data: RuleData) {}
+
+  static all(...rules: Rule[]): Rule {
+    return new Rule({ type: "all", rules: rules.map((r) => r.data) });
+  }
+
+  static any(...rules: Rule[]): Rule {
+    return new Rule({ type: "any", rules: rules.map((r) => r.data) });
+  }
+
+  static not(rule: Rule): Rule {
+    return new Rule({ type: "not", rule: rule.data });
+  }
+
+  and(other: Rule): Rule {
+    return Rule.all(this, other);
+  }
+
+  or(other: Rule): Rule {
+    return Rule.any(this, other);
+  }
+}
+
+export class Operand {
+  constructor(public readonly data: RuleData) {}
+
+  #compare(op: string, other: unknown): Rule {
+    const right = other instanceof Operand ? other : value(other);
+    return new Rule({
+      type: "compare",
+      left: this.data,
+      op,
+      right: right.data,
+    });
+  }
+
+  eq(other: unknown): Rule {
+    return this.#compare("eq", other);
+  }
+
+  ne(other: unknown): Rule {
+    return this.#compare("ne", other);
+  }
+
+  lt(other: unknown): Rule {
+    return this.#compare("lt", other);
+  }
+
+  lte(other: unknown): Rule {
+    return this.#compare("lte", other);
+  }
+
+  gt(other: unknown): Rule {
+    return this.#compare("gt", other);
+  }
+
+  gte(other: unknown): Rule {
+    return this.#compare("gte", other);
+  }
+
+  oneOf(values: unknown[] | Operand): Rule {
+    return this.#compare("in", values);
+  }
+
+  notNull(): Rule {
+    return new Rule({ type: "not_null", operand: this.data });
+  }
+
+  matches(pattern: string | RegExp): Rule {
+    return new Rule({
+      type: "pattern",
+      operand: this.data,
+      pattern: pattern instanceof RegExp ? pattern.source : pattern,
+    });
+  }
+}
+
+/** value in the request context, e.g. `user.id` */
+export function ctx(path: string): Operand {
+  return new Operand({ context: path });
+}
+
+/** argument of the function, e.g. `filter.owner_id` */
+export function arg(path: string): Operand {
+  return new Operand({ arg: path });
+}
+
+export function value(value: unknown): Operand {
+  return new Operand({ value });
+}
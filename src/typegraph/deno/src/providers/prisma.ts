@@ -5,7 +5,7 @@ import { Runtime } from "../runtimes/mod.ts";
 import { runtimes } from "../sdk.ts";
 import { Typedef } from "../types.ts";
 import { t } from "../index.ts";
-import type { Effect } from "../gen/runtimes.ts";
+import type { Effect, PrismaPoolConfig } from "../gen/runtimes.ts";
 import { genRef } from "./../typegraph.ts";
 
 type PrismaLinkArg = {
@@ -13,18 +13,33 @@ type PrismaLinkArg = {
   field?: string;
   unique?: boolean;
 };
+type PrismaRuntimeOptions = {
+  /** connection pool of the primary database and the read replicas */
+  pool?: PrismaPoolConfig;
+  /** read-only queries are routed to these databases */
+  readReplicaSecrets?: string[];
+};
+
 export class PrismaRuntime extends Runtime {
   name: string;
   connectionStringSecret: string;
+  readReplicaSecrets?: string[];
 
-  constructor(name: string, connectionStringSecret: string) {
+  constructor(
+    name: string,
+    connectionStringSecret: string,
+    options: PrismaRuntimeOptions = {},
+  ) {
     const id = runtimes.registerPrismaRuntime({
       name,
       connectionStringSecret,
+      pool: options.pool,
+      readReplicaSecrets: options.readReplicaSecrets,
     });
     super(id);
     this.name = name;
     this.connectionStringSecret = connectionStringSecret;
+    this.readReplicaSecrets = options.readReplicaSecrets;
   }
 
   /** create a function for a prisma `findUnique` query */
@@ -8,5 +8,5 @@ import * as sdkUtils from "./gen/utils.ts";
 
 export { aws, core, runtimes, sdkUtils };
 
-export type { Cors, Rate } from "./gen/core.ts";
+export type { Cors, QueryLimits, Rate } from "./gen/core.ts";
 export type { Auth, AuthProtocol } from "./gen/utils.ts";
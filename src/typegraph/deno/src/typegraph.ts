@@ -13,6 +13,7 @@ import {
 import {
   type Auth,
   type Cors as CoreCors,
+  type QueryLimits,
   type Rate,
   sdkUtils,
 } from "./sdk.ts";
@@ -35,6 +36,8 @@ interface TypegraphArgs {
   secrets?: Array<string>;
   cors?: Cors;
   rate?: Rate;
+  /** bounds on the depth, aliases and cost of the queries */
+  limits?: QueryLimits;
   disableAutoSerialization?: boolean;
 }
 
@@ -86,6 +89,11 @@ export interface TypegraphBuilderArgs extends InjectionSourceType {
   ) => void;
   inherit: () => InheritDef;
   rest: (graphql: string) => number;
+  /**
+   * register a custom string format, usable with `t.string({}, { format: name })`;
+   * the pattern is a JavaScript regular expression
+   */
+  stringFormat: (name: string, pattern: string) => void;
   auth: (value: Auth | RawAuth) => number;
   ref: (name: string) => t.Typedef;
   configureRandomInjection: (params: { seed: number }) => void;
@@ -164,7 +172,7 @@ export async function typegraph(
     ? { name: nameOrArgs }
     : nameOrArgs;
 
-  const { name, dynamic, cors, prefix, rate, secrets } = args;
+  const { name, dynamic, cors, prefix, rate, limits, secrets } = args;
   const builder = "builder" in args
     ? (args.builder as TypegraphBuilder)
     : maybeBuilder!;
@@ -195,6 +203,7 @@ export async function typegraph(
     secrets: secrets ?? [],
     cors: cors ? { ...defaultCorsFields, ...cors } : defaultCorsFields,
     rate: rate ? { ...defaultRateFields, ...rate } : undefined,
+    limits,
   };
 
   core.initTypegraph({ name, dynamic, path, ...tgParams });
@@ -212,6 +221,9 @@ export async function typegraph(
     rest: (graphql: string) => {
       return sdkUtils.addGraphqlEndpoint(graphql);
     },
+    stringFormat: (name: string, pattern: string) => {
+      return sdkUtils.addStringFormat(name, pattern);
+    },
     auth: (value: Auth | RawAuth) => {
       if (value instanceof RawAuth) {
         return sdkUtils.addRawAuth(value.jsonStr);
@@ -149,6 +149,30 @@ export class Typedef {
     }) as this;
   }
 
+  /** set runtime-agnostic config on the type */
+  private withTypeConfig(config: Record<string, unknown>): this {
+    const id = core.withConfig(this._id, JSON.stringify(config));
+    return new Proxy(this, {
+      get(target, prop, receiver) {
+        if (prop === "_id") {
+          return id;
+        } else {
+          return Reflect.get(target, prop, receiver);
+        }
+      },
+    }) as this;
+  }
+
+  /** set the description of the type */
+  describe(description: string): this {
+    return this.withTypeConfig({ description });
+  }
+
+  /** set sample values for the type, or sample arguments for a function */
+  examples(...examples: unknown[]): this {
+    return this.withTypeConfig({ examples });
+  }
+
   /** inject static value */
   set(value: InjectionValue<unknown>): this {
     return this.withInjection(serializeStaticInjection(value));
@@ -614,6 +638,24 @@ export class Func<
     });
   }
 
+  /** mark the function as deprecated */
+  deprecated(
+    deprecation: { reason?: string; since?: string; removal?: string } = {},
+  ): Func<I, O, M> {
+    const id = core.withConfig(
+      this._id,
+      JSON.stringify({ deprecated: deprecation }),
+    );
+    return new Func(
+      id,
+      this.inp,
+      this.out,
+      this.mat,
+      this.parameterTransform,
+      this.config,
+    );
+  }
+
   /** */
   static fromTypeFunc(data: FuncParams): Func {
     return func(
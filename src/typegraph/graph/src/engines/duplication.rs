@@ -13,6 +13,8 @@ pub trait DuplicationEngineFactory: Clone {
 
 pub trait DupKey: std::hash::Hash + std::fmt::Debug + Eq + Clone {
     fn is_default(&self) -> bool;
+    /// Stable representation of the key, for the naming of the duplicates
+    fn canonical_repr(&self) -> String;
 }
 
 pub trait DuplicationEngine: Clone {
@@ -87,6 +89,13 @@ impl DupKey for DefaultDuplicationKey {
             .map(|inj| inj.is_empty())
             .unwrap_or(true)
     }
+
+    fn canonical_repr(&self) -> String {
+        match &self.injection {
+            Some(inj) => inj.to_canonical_json().to_string(),
+            None => "null".to_string(),
+        }
+    }
 }
 
 pub enum Deduplication {
@@ -39,12 +39,42 @@ pub trait NamingEngine {
 }
 
 mod default {
+    use tg_schema::naming::{NameAllocator, NamingStrategy};
+
     use crate::{TypeNodeExt as _, Wrap as _};
 
     use super::*;
 
     #[derive(Default, Clone)]
-    pub struct DefaultNamingEngineFactory;
+    pub struct DefaultNamingEngineFactory {
+        strategy: NamingStrategy,
+    }
+
+    impl DefaultNamingEngineFactory {
+        /// The case and the affixes of the strategy are applied by the typegraph
+        /// serialization (`typegraphs.naming_strategy`): only the naming of the
+        /// duplicates and the collision policy can be configured here.
+        pub fn new(strategy: NamingStrategy) -> Result<Self> {
+            let defaults = NamingStrategy::default();
+            let unsupported = [
+                ("case", strategy.case != defaults.case),
+                ("prefix", strategy.prefix.is_some()),
+                ("suffix", strategy.suffix.is_some()),
+                ("rename_user_named", strategy.rename_user_named),
+            ]
+            .into_iter()
+            .filter_map(|(field, set)| set.then_some(field))
+            .collect::<Vec<_>>();
+            if !unsupported.is_empty() {
+                bail!(
+                    "naming strategy of the code generator: unsupported {}; \
+                    configure them for the typegraph serialization",
+                    unsupported.join(", ")
+                );
+            }
+            Ok(Self { strategy })
+        }
+    }
 
     impl NamingEngineFactory for DefaultNamingEngineFactory {
         type Engine = DefaultNamingEngine;
@@ -52,12 +82,23 @@ mod default {
         fn create(&self) -> Self::Engine {
             Self::Engine {
                 reg: Default::default(),
+                names: Default::default(),
+                strategy: self.strategy.clone(),
             }
         }
     }
 
     pub struct DefaultNamingEngine {
         reg: NameRegistry,
+        names: NameAllocator,
+        strategy: NamingStrategy,
+    }
+
+    impl DefaultNamingEngine {
+        fn register_reserved(&mut self, name: String, ty: Type) -> Result<()> {
+            self.names.reserve(name.clone());
+            self.reg.register(name, ty)
+        }
     }
 
     impl NamingEngine for DefaultNamingEngine {
@@ -67,24 +108,26 @@ mod default {
                 unreachable!("no registered type");
             }
             if let Some(item) = value_type.default.as_ref() {
-                self.registry()
-                    .register(item.title().to_owned(), item.clone())?;
+                self.register_reserved(item.title().to_owned(), item.clone())?;
             }
-            for (idx, item) in value_type.variants.values().enumerate() {
-                self.registry()
-                    .register(format!("{}_{}", item.title(), idx + 1), item.clone())?;
+            for (idx, (key, item)) in value_type.variants.iter().enumerate() {
+                let key = key.canonical_repr();
+                let name = self.strategy.variant_name(item.title(), idx + 1, &key);
+                let name = self
+                    .names
+                    .allocate(&self.strategy, name, &format!("{}#{key}", item.title()))
+                    .map_err(|e| eyre!("{e}"))?;
+                self.reg.register(name, item.clone())?;
             }
             Ok(())
         }
 
         fn name_function(&mut self, function: &Arc<FunctionType>) -> Result<()> {
-            self.registry()
-                .register(function.base().title.clone(), function.wrap())
+            self.register_reserved(function.base().title.clone(), function.wrap())
         }
 
         fn name_ns_object(&mut self, ns_object: &Arc<ObjectType>) -> Result<()> {
-            self.registry()
-                .register(ns_object.base().title.clone(), ns_object.wrap())
+            self.register_reserved(ns_object.base().title.clone(), ns_object.wrap())
         }
 
         fn registry(&mut self) -> &mut NameRegistry {
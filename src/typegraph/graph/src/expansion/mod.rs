@@ -14,6 +14,7 @@ use crate::{Type, Wrap as _};
 use indexmap::IndexMap;
 pub use map::{ConversionMap, MapItem, ValueType};
 use step::{ConversionStep, StepPlan};
+use tg_schema::naming::NamingStrategy;
 use tg_schema::runtimes::TGRuntime;
 
 pub mod map;
@@ -96,7 +97,16 @@ impl ExpansionConfig {
     ) -> ExpansionConfig<DefaultDuplicationEngineFactory, DefaultNamingEngineFactory> {
         Self::new()
             .with_duplication(DefaultDuplicationEngineFactory)
-            .with_naming_engine(DefaultNamingEngineFactory)
+            .with_naming_engine(DefaultNamingEngineFactory::default())
+    }
+
+    /// Default engines, with a custom naming strategy for the duplicated types
+    pub fn with_naming_strategy(
+        strategy: NamingStrategy,
+    ) -> Result<ExpansionConfig<DefaultDuplicationEngineFactory, DefaultNamingEngineFactory>> {
+        Ok(Self::new()
+            .with_duplication(DefaultDuplicationEngineFactory)
+            .with_naming_engine(DefaultNamingEngineFactory::new(strategy)?))
     }
 }
 
@@ -165,6 +165,7 @@ impl<G: DuplicationEngine> ConversionStep<G> {
                 min_length: data.min_length,
                 max_length: data.max_length,
                 enumeration: node_base.enumeration.clone(),
+                deprecated_values: node_base.deprecated_values.clone(),
             }
             .into(),
         );
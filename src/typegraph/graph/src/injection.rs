@@ -148,6 +148,58 @@ impl Injection {
     }
 }
 
+impl InjectionNode {
+    /// JSON representation with sorted object keys, independent of the
+    /// `Debug` output and of the insertion order of the values
+    pub fn to_canonical_json(&self) -> serde_json::Value {
+        use serde_json::json;
+        match self {
+            Self::Parent { children } => json!({
+                "children": children
+                    .iter()
+                    .map(|(key, node)| (key.clone(), node.to_canonical_json()))
+                    .collect::<serde_json::Map<_, _>>(),
+            }),
+            Self::Leaf { injection } => {
+                let (source, data) = match injection.as_ref() {
+                    Injection::Static { value } => ("static", canonical_value(value)),
+                    Injection::Context { path } => ("context", json!(path)),
+                    Injection::Secret { name } => ("secret", json!(name)),
+                    Injection::Parent { prop_name } => ("parent", json!(prop_name)),
+                    Injection::Dynamic { generator } => ("dynamic", json!(generator)),
+                    Injection::Random { generator, args } => (
+                        "random",
+                        json!({
+                            "args": args
+                                .iter()
+                                .map(|(key, value)| (key.clone(), canonical_value(value)))
+                                .collect::<serde_json::Map<_, _>>(),
+                            "gen": generator,
+                        }),
+                    ),
+                };
+                json!({ "data": data, "source": source })
+            }
+        }
+    }
+}
+
+fn canonical_value(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.clone(), canonical_value(value)))
+                .collect::<serde_json::Map<_, _>>()
+                .into()
+        }
+        serde_json::Value::Array(items) => items.iter().map(canonical_value).collect(),
+        _ => value.clone(),
+    }
+}
+
 fn select(inj_data: &tg_schema::InjectionData, fx: EffectType) -> Option<&serde_json::Value> {
     use tg_schema::InjectionData as D;
     match inj_data {
@@ -99,6 +99,18 @@ impl<K: DupKey> Typegraph<K> {
     pub fn name(&self) -> &str {
         self.root.title()
     }
+
+    /// Number of duplicates created by the duplication engine, by type index
+    pub fn duplicate_counts(&self) -> IndexMap<u32, usize> {
+        self.conversion_map
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| {
+                let count = item.as_value()?.variants.len();
+                (count > 0).then_some((idx as u32, count))
+            })
+            .collect()
+    }
 }
 
 struct StackItem {
@@ -85,6 +85,8 @@ pub struct TypeBase {
     pub title: String,
     pub name: OnceLock<Arc<str>>,
     pub description: Option<String>,
+    pub examples: Option<Vec<serde_json::Value>>,
+    pub deprecated: Option<tg_schema::Deprecation>,
     pub injection: Option<Arc<InjectionNode>>, // why here??
 }
 
@@ -101,6 +103,8 @@ impl TypeBase {
             title: schema.title.clone(),
             name: Default::default(),
             description: schema.description.clone(),
+            examples: schema.examples.clone(),
+            deprecated: schema.deprecated.clone(),
             injection,
         }
     }
@@ -141,6 +145,7 @@ pub trait TypeNodeExt: TypeNode {
     fn title(&self) -> &str;
     fn key(&self) -> TypeKey;
     fn injection(&self) -> Option<Arc<InjectionNode>>;
+    fn deprecated(&self) -> Option<&tg_schema::Deprecation>;
     fn is_descendant_of(&self, other: &Type) -> bool;
 }
 
@@ -176,6 +181,10 @@ where
         self.base().injection.clone()
     }
 
+    fn deprecated(&self) -> Option<&tg_schema::Deprecation> {
+        self.base().deprecated.as_ref()
+    }
+
     fn is_descendant_of(&self, other: &Type) -> bool {
         let key = self.key();
         crate::visitor::traverse_types(other.clone(), false, |node, acc| -> Result<_, Infallible> {
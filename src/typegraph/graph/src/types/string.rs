@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use super::interlude::*;
+use std::collections::BTreeMap;
 
 #[derive(Debug)]
 pub struct StringType {
@@ -11,6 +12,8 @@ pub struct StringType {
     pub pattern: Option<String>,
     pub format: Option<tg_schema::StringFormat>,
     pub enumeration: Option<Vec<String>>,
+    /// by JSON-serialized value
+    pub deprecated_values: Option<BTreeMap<String, tg_schema::Deprecation>>,
 }
 
 impl TypeNode for Arc<StringType> {
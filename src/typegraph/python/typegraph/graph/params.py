@@ -78,6 +78,26 @@ class Rate:
         self.local_excess = local_excess
 
 
+class QueryLimits:
+    max_depth: Optional[int]
+    max_aliases: Optional[int]
+    max_cost: Optional[int]
+    list_size: Optional[int]
+
+    def __init__(
+        self,
+        *,
+        max_depth: Optional[int] = None,
+        max_aliases: Optional[int] = None,
+        max_cost: Optional[int] = None,
+        list_size: Optional[int] = None,
+    ):
+        self.max_depth = max_depth
+        self.max_aliases = max_aliases
+        self.max_cost = max_cost
+        self.list_size = list_size
+
+
 class Cors:
     allow_origin: List[str]
     allow_headers: List[str]
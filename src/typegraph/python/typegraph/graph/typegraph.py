@@ -11,9 +11,13 @@ from typegraph.gen.core import (
     Rate,
     TypegraphInitParams,
 )
-from typegraph.gen.core import Cors as CoreCors, Rate as CoreRate
+from typegraph.gen.core import (
+    Cors as CoreCors,
+    QueryLimits as CoreQueryLimits,
+    Rate as CoreRate,
+)
 from typegraph.gen.utils import Auth
-from typegraph.graph.params import Cors, RawAuth
+from typegraph.graph.params import Cors, QueryLimits, RawAuth
 from typegraph.graph.shared_types import FinalizationResult, TypegraphOutput
 from typegraph.policy import Policy, PolicyPerEffect, PolicySpec, get_policy_chain
 from typegraph.envs.cli import CLI_ENV
@@ -34,6 +38,7 @@ class Typegraph:
     path: str
     _context: List["Typegraph"] = []
     rate: Optional[CoreRate]
+    limits: Optional[CoreQueryLimits]
     cors: Optional[CoreCors]
     prefix: Optional[str]
 
@@ -43,6 +48,7 @@ class Typegraph:
         dynamic: Optional[bool] = None,
         *,
         rate: Optional[Rate] = None,
+        limits: Optional[QueryLimits] = None,
         cors: Optional[Cors] = None,
         prefix: Optional[str] = None,
     ):
@@ -51,6 +57,7 @@ class Typegraph:
         self.path = str(Path(inspect.stack()[2].filename).resolve())
 
         self.rate = Rate(**rate.__dict__) if rate else None
+        self.limits = CoreQueryLimits(**limits.__dict__) if limits else None
 
         cors = cors or Cors()
         self.cors = CoreCors(**cors.__dict__)
@@ -120,6 +127,13 @@ class Graph:
     def rest(self, graphql: str) -> int:
         return sdk_utils.add_graphql_endpoint(graphql)
 
+    def string_format(self, name: str, pattern: str):
+        """
+        register a custom string format, usable with `t.string(format=name)`;
+        the pattern is a JavaScript regular expression
+        """
+        sdk_utils.add_string_format(name, pattern)
+
     def auth(self, value: Union[Auth, RawAuth]):
         if isinstance(value, RawAuth):
             return sdk_utils.add_raw_auth(value.json_str)
@@ -153,6 +167,7 @@ def typegraph(
     *,
     dynamic: Optional[bool] = None,
     rate: Optional[Rate] = None,
+    limits: Optional[QueryLimits] = None,
     cors: Optional[Cors] = None,
     prefix: Optional[str] = None,
 ) -> Callable[[Callable[[Graph], None]], TypegraphOutput]:
@@ -179,6 +194,7 @@ def typegraph(
             name=actual_name,
             dynamic=dynamic,
             rate=rate,
+            limits=limits,
             cors=cors,
             prefix=prefix,
         )
@@ -200,6 +216,7 @@ def typegraph(
                 dynamic=tg.dynamic,
                 path=tg.path,
                 rate=tg.rate,
+                limits=tg.limits,
                 cors=tg.cors or default_cors,
                 prefix=tg.prefix,
             ),
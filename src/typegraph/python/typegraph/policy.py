@@ -1,9 +1,10 @@
 # Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 # SPDX-License-Identifier: MPL-2.0
 
+import json
 from dataclasses import dataclass
 from re import Pattern
-from typing import List, Optional, Union
+from typing import Any, Dict, List, Optional, Union
 
 from typegraph.gen.core import (
     MaterializerId,
@@ -42,6 +43,16 @@ class Policy:
         (policy_id, name) = res
         return cls(id=policy_id, name=name)
 
+    @classmethod
+    def rule(cls, name: str, rule: "Rule") -> "Policy":
+        """
+        Declarative policy on the context and the arguments, evaluated by the typegate,
+        e.g. `Policy.rule("owner", ctx("user.id").eq(arg("owner_id")))`
+        """
+        res = core.register_context_rule_policy(name, json.dumps(rule.data))
+        (policy_id, name) = res
+        return cls(id=policy_id, name=name)
+
     @classmethod
     def internal(cls) -> "Policy":
         res = core.get_internal_policy()
@@ -64,6 +75,90 @@ class Policy:
         return PolicyPerEffect(create=create, update=update, delete=delete, read=read)
 
 
+class Rule:
+    data: Dict[str, Any]
+
+    def __init__(self, data: Dict[str, Any]):
+        self.data = data
+
+    @staticmethod
+    def all(*rules: "Rule") -> "Rule":
+        return Rule({"type": "all", "rules": [r.data for r in rules]})
+
+    @staticmethod
+    def any(*rules: "Rule") -> "Rule":
+        return Rule({"type": "any", "rules": [r.data for r in rules]})
+
+    @staticmethod
+    def not_(rule: "Rule") -> "Rule":
+        return Rule({"type": "not", "rule": rule.data})
+
+    def __and__(self, other: "Rule") -> "Rule":
+        return Rule.all(self, other)
+
+    def __or__(self, other: "Rule") -> "Rule":
+        return Rule.any(self, other)
+
+    def __invert__(self) -> "Rule":
+        return Rule.not_(self)
+
+
+class Operand:
+    data: Dict[str, Any]
+
+    def __init__(self, data: Dict[str, Any]):
+        self.data = data
+
+    def _compare(self, op: str, other: Any) -> Rule:
+        right = other if isinstance(other, Operand) else value(other)
+        return Rule(
+            {"type": "compare", "left": self.data, "op": op, "right": right.data}
+        )
+
+    def eq(self, other: Any) -> Rule:
+        return self._compare("eq", other)
+
+    def ne(self, other: Any) -> Rule:
+        return self._compare("ne", other)
+
+    def lt(self, other: Any) -> Rule:
+        return self._compare("lt", other)
+
+    def lte(self, other: Any) -> Rule:
+        return self._compare("lte", other)
+
+    def gt(self, other: Any) -> Rule:
+        return self._compare("gt", other)
+
+    def gte(self, other: Any) -> Rule:
+        return self._compare("gte", other)
+
+    def one_of(self, values: Any) -> Rule:
+        return self._compare("in", values)
+
+    def not_null(self) -> Rule:
+        return Rule({"type": "not_null", "operand": self.data})
+
+    def matches(self, pattern: Union[str, Pattern]) -> Rule:
+        if not isinstance(pattern, str):
+            pattern = pattern.pattern
+        return Rule({"type": "pattern", "operand": self.data, "pattern": pattern})
+
+
+def ctx(path: str) -> Operand:
+    """value in the request context, e.g. `user.id`"""
+    return Operand({"context": path})
+
+
+def arg(path: str) -> Operand:
+    """argument of the function, e.g. `filter.owner_id`"""
+    return Operand({"arg": path})
+
+
+def value(value: Any) -> Operand:
+    return Operand({"value": value})
+
+
 @dataclass
 class PolicyPerEffect:
     create: Optional[Policy] = None
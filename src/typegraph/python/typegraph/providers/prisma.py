@@ -1,7 +1,7 @@
 # Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 # SPDX-License-Identifier: MPL-2.0
 
-from typing import Union, Optional
+from typing import List, Union, Optional
 
 from typegraph.runtimes.base import Runtime
 from typegraph.sdk import runtimes
@@ -9,6 +9,7 @@ from typegraph.gen.runtimes import (
     Effect,
     PrismaRuntimeData,
     PrismaLinkData,
+    PrismaPoolConfig,
 )
 from typegraph import t
 from typegraph.graph.typegraph import gen_ref
@@ -17,15 +18,29 @@ from typegraph.graph.typegraph import gen_ref
 class PrismaRuntime(Runtime):
     name: str
     connection_string_secret: str
+    read_replica_secrets: Optional[List[str]]
 
-    def __init__(self, name: str, connection_string_secret: str):
+    def __init__(
+        self,
+        name: str,
+        connection_string_secret: str,
+        *,
+        pool: Optional[PrismaPoolConfig] = None,
+        read_replica_secrets: Optional[List[str]] = None,
+    ):
         runtime_id = runtimes.register_prisma_runtime(
-            PrismaRuntimeData(name, connection_string_secret)
+            PrismaRuntimeData(
+                name=name,
+                connection_string_secret=connection_string_secret,
+                pool=pool,
+                read_replica_secrets=read_replica_secrets,
+            )
         )
 
         super().__init__(runtime_id)
         self.name = name
         self.connection_string_secret = connection_string_secret
+        self.read_replica_secrets = read_replica_secrets
 
     def find_unique(self, model: Union[str, t.typedef]) -> t.func:
         if isinstance(model, str):
@@ -88,6 +88,18 @@ class typedef:
         ret.injection = injection
         return ret
 
+    def _with_config(self, config: Dict[str, Any]) -> Self:
+        ret = copy.copy(self)
+        ret._id = core.with_config(self._id, JsonLib.dumps(config))
+        return ret
+
+    def describe(self, description: str) -> Self:
+        return self._with_config({"description": description})
+
+    def examples(self, *examples: Any) -> Self:
+        """sample values for the type, or sample arguments for a function"""
+        return self._with_config({"examples": og_list(examples)})
+
     def optional(
         self,
         default_value: Optional[Any] = None,
@@ -632,6 +644,27 @@ class func(typedef):
             rate_weight=weight,
         )
 
+    def deprecated(
+        self,
+        reason: Optional[str] = None,
+        *,
+        since: Optional[str] = None,
+        removal: Optional[str] = None,
+    ) -> "func":
+        deprecation = {"reason": reason, "since": since, "removal": removal}
+        config = JsonLib.dumps(
+            {"deprecated": {k: v for k, v in deprecation.items() if v is not None}}
+        )
+        return func(
+            self.inp,
+            self.out,
+            self.mat,
+            parameter_transform=self.parameter_transform,
+            rate_calls=self.rate_calls,
+            rate_weight=self.rate_weight,
+            type_id=core.with_config(self._id, config),
+        )
+
     def extend(self, props: Dict[str, typedef]):
         res = core.extend_struct(
             self.out._id, og_list((k, v._id) for k, v in props.items())
@@ -0,0 +1,933 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Classification of the changes between two versions of a typegraph, as seen
+//! by the API consumers.
+//!
+//! Constraint changes depend on the position of the type: a tighter constraint
+//! is breaking for the inputs, but safe for the outputs.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{Debug, Display};
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use crate::{
+    EffectType, Injection, InjectionNode, ObjectTypeData, PolicyIndices, TypeId, TypeNode,
+    Typegraph,
+};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeSeverity {
+    Safe,
+    /// might break some clients, e.g. new values in an output enum
+    Dangerous,
+    Breaking,
+}
+
+impl Display for ChangeSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeSeverity::Safe => write!(f, "safe"),
+            ChangeSeverity::Dangerous => write!(f, "dangerous"),
+            ChangeSeverity::Breaking => write!(f, "breaking"),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Change {
+    pub severity: ChangeSeverity,
+    /// e.g. `createUser.input.email`
+    pub path: String,
+    pub message: String,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}: {}", self.severity, self.path, self.message)
+    }
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct TypegraphDiff {
+    pub changes: Vec<Change>,
+}
+
+impl TypegraphDiff {
+    pub fn new(old: &Typegraph, new: &Typegraph) -> Result<Self> {
+        let mut differ = Differ {
+            old,
+            new,
+            visited: Default::default(),
+            changes: vec![],
+        };
+        differ.diff_exposed_functions()?;
+        Ok(Self {
+            changes: differ.changes,
+        })
+    }
+
+    pub fn max_severity(&self) -> Option<ChangeSeverity> {
+        self.changes.iter().map(|c| c.severity).max()
+    }
+
+    pub fn has_breaking_changes(&self) -> bool {
+        self.max_severity() == Some(ChangeSeverity::Breaking)
+    }
+
+    pub fn filter(&self, min_severity: ChangeSeverity) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(move |c| c.severity >= min_severity)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Position {
+    Input,
+    Output,
+}
+
+/// Direction of a constraint change
+enum Constraint {
+    Tighter,
+    Looser,
+    Changed,
+}
+
+impl Constraint {
+    fn severity(&self, pos: Position) -> ChangeSeverity {
+        use ChangeSeverity as S;
+        match (self, pos) {
+            (Constraint::Tighter, Position::Input) => S::Breaking,
+            (Constraint::Tighter, Position::Output) => S::Safe,
+            (Constraint::Looser, Position::Input) => S::Safe,
+            (Constraint::Looser, Position::Output) => S::Dangerous,
+            (Constraint::Changed, Position::Input) => S::Breaking,
+            (Constraint::Changed, Position::Output) => S::Dangerous,
+        }
+    }
+}
+
+struct Differ<'a> {
+    old: &'a Typegraph,
+    new: &'a Typegraph,
+    visited: HashSet<(TypeId, TypeId, Position)>,
+    changes: Vec<Change>,
+}
+
+impl Differ<'_> {
+    fn push(&mut self, severity: ChangeSeverity, path: &str, message: impl Into<String>) {
+        self.changes.push(Change {
+            severity,
+            path: path.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn diff_exposed_functions(&mut self) -> Result<()> {
+        let old_fns = self.old.exposed_functions()?;
+        let new_fns = self
+            .new
+            .exposed_functions()?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        let mut seen = HashSet::new();
+        for (path, old_idx) in old_fns.into_iter() {
+            let name = path.join(".");
+            match new_fns.get(&path) {
+                None => self.push(ChangeSeverity::Breaking, &name, "function removed"),
+                Some(&new_idx) => {
+                    let old_policies = field_policies(self.old, &path)?;
+                    let new_policies = field_policies(self.new, &path)?;
+                    if old_policies != new_policies {
+                        self.push(
+                            ChangeSeverity::Dangerous,
+                            &name,
+                            format!("policies changed from {old_policies:?} to {new_policies:?}"),
+                        );
+                    }
+                    self.diff_deprecation(&name, old_idx, new_idx);
+                    self.diff_function(&name, old_idx, new_idx)?;
+                }
+            }
+            seen.insert(path);
+        }
+
+        let mut added = new_fns
+            .keys()
+            .filter(|path| !seen.contains(*path))
+            .map(|path| path.join("."))
+            .collect::<Vec<_>>();
+        added.sort();
+        for name in added {
+            self.push(ChangeSeverity::Safe, &name, "function added");
+        }
+        Ok(())
+    }
+
+    fn diff_deprecation(&mut self, path: &str, old_idx: TypeId, new_idx: TypeId) {
+        let (old_tg, new_tg) = (self.old, self.new);
+        let old = &old_tg.types[old_idx as usize].base().deprecated;
+        let new = &new_tg.types[new_idx as usize].base().deprecated;
+        match (old, new) {
+            (None, Some(new)) => {
+                self.push(
+                    ChangeSeverity::Safe,
+                    path,
+                    format!("deprecated: {}", new.message()),
+                );
+            }
+            (Some(_), None) => self.push(ChangeSeverity::Safe, path, "no longer deprecated"),
+            _ => {}
+        }
+    }
+
+    fn diff_function(&mut self, path: &str, old_idx: TypeId, new_idx: TypeId) -> Result<()> {
+        let (TypeNode::Function { data: old, .. }, TypeNode::Function { data: new, .. }) = (
+            &self.old.types[old_idx as usize],
+            &self.new.types[new_idx as usize],
+        ) else {
+            bail!("expected functions at {path}");
+        };
+
+        let old_effect = effect(self.old, old.materializer);
+        let new_effect = effect(self.new, new.materializer);
+        if old_effect != new_effect {
+            self.push(
+                ChangeSeverity::Breaking,
+                path,
+                format!("effect changed from {old_effect:?} to {new_effect:?}"),
+            );
+        }
+
+        // injected parameters are not part of the public interface
+        let old_injections = flatten_injections(&old.injections);
+        let new_injections = flatten_injections(&new.injections);
+        for (key, injection) in old_injections.iter() {
+            let param = format!("{path}.input.{key}");
+            match new_injections.get(key) {
+                None => self.push(
+                    ChangeSeverity::Dangerous,
+                    &param,
+                    "parameter is no longer injected",
+                ),
+                Some(new_injection) if new_injection != injection => {
+                    self.push(ChangeSeverity::Dangerous, &param, "injection changed")
+                }
+                _ => {}
+            }
+        }
+        for key in new_injections.keys() {
+            if !old_injections.contains_key(key) {
+                self.push(
+                    ChangeSeverity::Breaking,
+                    &format!("{path}.input.{key}"),
+                    "parameter is now injected",
+                );
+            }
+        }
+        let injected = old
+            .injections
+            .keys()
+            .chain(new.injections.keys())
+            .filter(|k| {
+                matches!(old.injections.get(*k), Some(InjectionNode::Leaf { .. }))
+                    || matches!(new.injections.get(*k), Some(InjectionNode::Leaf { .. }))
+            })
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        self.diff_object_props(
+            &format!("{path}.input"),
+            old.input,
+            new.input,
+            Position::Input,
+            &injected,
+        )?;
+        self.diff_type(
+            &format!("{path}.output"),
+            old.output,
+            new.output,
+            Position::Output,
+        )
+    }
+
+    fn diff_type(
+        &mut self,
+        path: &str,
+        old_idx: TypeId,
+        new_idx: TypeId,
+        pos: Position,
+    ) -> Result<()> {
+        if !self.visited.insert((old_idx, new_idx, pos)) {
+            return Ok(());
+        }
+        use ChangeSeverity as S;
+        use TypeNode as N;
+        let old_node = &self.old.types[old_idx as usize];
+        let new_node = &self.new.types[new_idx as usize];
+
+        match (old_node, new_node) {
+            (N::Optional { data: old, .. }, N::Optional { data: new, .. }) => {
+                if pos == Position::Input && old.default_value != new.default_value {
+                    self.push(S::Dangerous, path, "default value changed");
+                }
+                return self.diff_type(path, old.item, new.item, pos);
+            }
+            (N::Optional { data: old, .. }, _) => {
+                match pos {
+                    Position::Input => self.push(S::Breaking, path, "became required"),
+                    Position::Output => self.push(S::Safe, path, "no longer nullable"),
+                }
+                return self.diff_type(path, old.item, new_idx, pos);
+            }
+            (_, N::Optional { data: new, .. }) => {
+                match pos {
+                    Position::Input => self.push(S::Safe, path, "became optional"),
+                    Position::Output => self.push(S::Breaking, path, "became nullable"),
+                }
+                return self.diff_type(path, old_idx, new.item, pos);
+            }
+            (N::List { data: old, .. }, N::List { data: new, .. }) => {
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "minItems",
+                    min_bound(old.min_items, new.min_items),
+                );
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "maxItems",
+                    max_bound(old.max_items, new.max_items),
+                );
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "uniqueItems",
+                    flag(
+                        old.unique_items.unwrap_or(false),
+                        new.unique_items.unwrap_or(false),
+                    ),
+                );
+                self.diff_type(&format!("{path}[]"), old.items, new.items, pos)?;
+            }
+            (N::Object { .. }, N::Object { .. }) => {
+                self.diff_object_props(path, old_idx, new_idx, pos, &Default::default())?;
+            }
+            (N::Function { data: old, .. }, N::Function { data: new, .. }) => {
+                // output field with arguments
+                self.diff_object_props(
+                    &format!("{path}.args"),
+                    old.input,
+                    new.input,
+                    Position::Input,
+                    &Default::default(),
+                )?;
+                self.diff_type(path, old.output, new.output, pos)?;
+            }
+            (N::Union { data: old, .. }, N::Union { data: new, .. }) => {
+                self.diff_variants(path, &old.any_of, &new.any_of, pos)?;
+            }
+            (N::Either { data: old, .. }, N::Either { data: new, .. }) => {
+                self.diff_variants(path, &old.one_of, &new.one_of, pos)?;
+            }
+            (N::Integer { data: old, .. }, N::Integer { data: new, .. }) => {
+                self.diff_constraint(path, pos, "minimum", min_bound(old.minimum, new.minimum));
+                self.diff_constraint(path, pos, "maximum", max_bound(old.maximum, new.maximum));
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "exclusiveMinimum",
+                    min_bound(old.exclusive_minimum, new.exclusive_minimum),
+                );
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "exclusiveMaximum",
+                    max_bound(old.exclusive_maximum, new.exclusive_maximum),
+                );
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "multipleOf",
+                    exact(&old.multiple_of, &new.multiple_of),
+                );
+            }
+            (N::Float { data: old, .. }, N::Float { data: new, .. }) => {
+                self.diff_constraint(path, pos, "minimum", min_bound(old.minimum, new.minimum));
+                self.diff_constraint(path, pos, "maximum", max_bound(old.maximum, new.maximum));
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "exclusiveMinimum",
+                    min_bound(old.exclusive_minimum, new.exclusive_minimum),
+                );
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "exclusiveMaximum",
+                    max_bound(old.exclusive_maximum, new.exclusive_maximum),
+                );
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "multipleOf",
+                    exact(&old.multiple_of, &new.multiple_of),
+                );
+            }
+            (N::String { data: old, .. }, N::String { data: new, .. }) => {
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "minLength",
+                    min_bound(old.min_length, new.min_length),
+                );
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "maxLength",
+                    max_bound(old.max_length, new.max_length),
+                );
+                self.diff_constraint(path, pos, "pattern", exact(&old.pattern, &new.pattern));
+                self.diff_constraint(path, pos, "format", exact(&old.format, &new.format));
+            }
+            (N::File { data: old, .. }, N::File { data: new, .. }) => {
+                self.diff_constraint(path, pos, "minSize", min_bound(old.min_size, new.min_size));
+                self.diff_constraint(path, pos, "maxSize", max_bound(old.max_size, new.max_size));
+                self.diff_constraint(
+                    path,
+                    pos,
+                    "mimeTypes",
+                    exact(&old.mime_types, &new.mime_types),
+                );
+            }
+            (N::Boolean { .. }, N::Boolean { .. }) | (N::Any { .. }, N::Any { .. }) => {}
+            _ => {
+                self.push(
+                    S::Breaking,
+                    path,
+                    format!(
+                        "type changed from {} to {}",
+                        old_node.type_name(),
+                        new_node.type_name()
+                    ),
+                );
+                return Ok(());
+            }
+        }
+
+        self.diff_enum(
+            path,
+            pos,
+            old_node.base().enumeration.as_ref(),
+            new_node.base().enumeration.as_ref(),
+        );
+        Ok(())
+    }
+
+    fn diff_object_props(
+        &mut self,
+        path: &str,
+        old_idx: TypeId,
+        new_idx: TypeId,
+        pos: Position,
+        ignored: &HashSet<String>,
+    ) -> Result<()> {
+        let (Some(old), Some(new)) = (
+            object_data(self.old, old_idx),
+            object_data(self.new, new_idx),
+        ) else {
+            return self.diff_type(path, old_idx, new_idx, pos);
+        };
+
+        for (key, &old_prop) in old.properties.iter() {
+            if ignored.contains(key) {
+                continue;
+            }
+            let prop_path = format!("{path}.{key}");
+            match new.properties.get(key) {
+                None => self.push(ChangeSeverity::Breaking, &prop_path, "field removed"),
+                Some(&new_prop) => {
+                    let old_policies = policy_names(self.old, old.policies.get(key));
+                    let new_policies = policy_names(self.new, new.policies.get(key));
+                    if old_policies != new_policies {
+                        self.push(
+                            ChangeSeverity::Dangerous,
+                            &prop_path,
+                            format!("policies changed from {old_policies:?} to {new_policies:?}"),
+                        );
+                    }
+                    self.diff_deprecation(&prop_path, old_prop, new_prop);
+                    self.diff_type(&prop_path, old_prop, new_prop, pos)?;
+                }
+            }
+        }
+        for (key, &new_prop) in new.properties.iter() {
+            if ignored.contains(key) || old.properties.contains_key(key) {
+                continue;
+            }
+            let required = !matches!(self.new.types[new_prop as usize], TypeNode::Optional { .. });
+            let severity = match (pos, required) {
+                (Position::Input, true) => ChangeSeverity::Breaking,
+                _ => ChangeSeverity::Safe,
+            };
+            let message = if severity == ChangeSeverity::Breaking {
+                "required field added"
+            } else {
+                "field added"
+            };
+            self.push(severity, &format!("{path}.{key}"), message);
+        }
+        Ok(())
+    }
+
+    /// Variants are matched by title, then by position
+    fn diff_variants(
+        &mut self,
+        path: &str,
+        old: &[TypeId],
+        new: &[TypeId],
+        pos: Position,
+    ) -> Result<()> {
+        let mut unmatched_new = new.to_vec();
+        let mut pairs = vec![];
+        let mut unmatched_old = vec![];
+        for &old_variant in old {
+            let title = &self.old.types[old_variant as usize].base().title;
+            match unmatched_new
+                .iter()
+                .position(|&v| &self.new.types[v as usize].base().title == title)
+            {
+                Some(i) => pairs.push((old_variant, unmatched_new.remove(i))),
+                None => unmatched_old.push(old_variant),
+            }
+        }
+        let matched_by_position = unmatched_old.len().min(unmatched_new.len());
+        for (o, n) in unmatched_old
+            .drain(..matched_by_position)
+            .zip(unmatched_new.drain(..matched_by_position))
+        {
+            pairs.push((o, n));
+        }
+
+        for (i, (o, n)) in pairs.into_iter().enumerate() {
+            self.diff_type(&format!("{path}#{i}"), o, n, pos)?;
+        }
+        if !unmatched_old.is_empty() {
+            let severity = match pos {
+                Position::Input => ChangeSeverity::Breaking,
+                Position::Output => ChangeSeverity::Safe,
+            };
+            self.push(
+                severity,
+                path,
+                format!("{} variant(s) removed", unmatched_old.len()),
+            );
+        }
+        if !unmatched_new.is_empty() {
+            let severity = match pos {
+                Position::Input => ChangeSeverity::Safe,
+                Position::Output => ChangeSeverity::Dangerous,
+            };
+            self.push(
+                severity,
+                path,
+                format!("{} variant(s) added", unmatched_new.len()),
+            );
+        }
+        Ok(())
+    }
+
+    fn diff_enum(
+        &mut self,
+        path: &str,
+        pos: Position,
+        old: Option<&Vec<String>>,
+        new: Option<&Vec<String>>,
+    ) {
+        match (old, new) {
+            (None, None) => {}
+            (None, Some(_)) => self.diff_constraint(path, pos, "enum", Some(Constraint::Tighter)),
+            (Some(_), None) => self.diff_constraint(path, pos, "enum", Some(Constraint::Looser)),
+            (Some(old), Some(new)) => {
+                let old = old.iter().collect::<BTreeSet<_>>();
+                let new = new.iter().collect::<BTreeSet<_>>();
+                let removed = old.difference(&new).collect::<Vec<_>>();
+                let added = new.difference(&old).collect::<Vec<_>>();
+                if !removed.is_empty() {
+                    let severity = Constraint::Tighter.severity(pos);
+                    self.push(severity, path, format!("enum values removed: {removed:?}"));
+                }
+                if !added.is_empty() {
+                    let severity = Constraint::Looser.severity(pos);
+                    self.push(severity, path, format!("enum values added: {added:?}"));
+                }
+            }
+        }
+    }
+
+    fn diff_constraint(
+        &mut self,
+        path: &str,
+        pos: Position,
+        name: &str,
+        change: Option<Constraint>,
+    ) {
+        if let Some(change) = change {
+            let message = match change {
+                Constraint::Tighter => format!("constraint {name} tightened"),
+                Constraint::Looser => format!("constraint {name} relaxed"),
+                Constraint::Changed => format!("constraint {name} changed"),
+            };
+            self.push(change.severity(pos), path, message);
+        }
+    }
+}
+
+fn min_bound<T: PartialOrd>(old: Option<T>, new: Option<T>) -> Option<Constraint> {
+    match (old, new) {
+        (None, Some(_)) => Some(Constraint::Tighter),
+        (Some(_), None) => Some(Constraint::Looser),
+        (Some(old), Some(new)) if new > old => Some(Constraint::Tighter),
+        (Some(old), Some(new)) if new < old => Some(Constraint::Looser),
+        _ => None,
+    }
+}
+
+fn max_bound<T: PartialOrd>(old: Option<T>, new: Option<T>) -> Option<Constraint> {
+    match (old, new) {
+        (None, Some(_)) => Some(Constraint::Tighter),
+        (Some(_), None) => Some(Constraint::Looser),
+        (Some(old), Some(new)) if new < old => Some(Constraint::Tighter),
+        (Some(old), Some(new)) if new > old => Some(Constraint::Looser),
+        _ => None,
+    }
+}
+
+fn exact<T: PartialEq + Debug>(old: &Option<T>, new: &Option<T>) -> Option<Constraint> {
+    match (old, new) {
+        (None, Some(_)) => Some(Constraint::Tighter),
+        (Some(_), None) => Some(Constraint::Looser),
+        (Some(old), Some(new)) if old != new => Some(Constraint::Changed),
+        _ => None,
+    }
+}
+
+fn flag(old: bool, new: bool) -> Option<Constraint> {
+    match (old, new) {
+        (false, true) => Some(Constraint::Tighter),
+        (true, false) => Some(Constraint::Looser),
+        _ => None,
+    }
+}
+
+fn effect(tg: &Typegraph, materializer: u32) -> EffectType {
+    tg.materializers[materializer as usize]
+        .effect
+        .effect
+        .unwrap_or(EffectType::Read)
+}
+
+fn object_data(tg: &Typegraph, type_idx: TypeId) -> Option<&ObjectTypeData> {
+    match &tg.types[type_idx as usize] {
+        TypeNode::Object { data, .. } => Some(data),
+        _ => None,
+    }
+}
+
+fn policy_names(tg: &Typegraph, policies: Option<&Vec<PolicyIndices>>) -> Vec<String> {
+    let name = |idx: u32| tg.policies[idx as usize].name.clone();
+    policies
+        .into_iter()
+        .flatten()
+        .map(|p| match p {
+            PolicyIndices::Policy(idx) => name(*idx),
+            PolicyIndices::EffectPolicies(by_effect) => [
+                ("read", by_effect.read),
+                ("create", by_effect.create),
+                ("update", by_effect.update),
+                ("delete", by_effect.delete),
+            ]
+            .into_iter()
+            .filter_map(|(effect, idx)| idx.map(|idx| format!("{effect}:{}", name(idx))))
+            .collect::<Vec<_>>()
+            .join(","),
+        })
+        .collect()
+}
+
+/// Policies of an exposed function, from its namespace
+fn field_policies(tg: &Typegraph, path: &[String]) -> Result<Vec<String>> {
+    let Some((key, namespaces)) = path.split_last() else {
+        bail!("empty path");
+    };
+    let mut parent = 0;
+    for ns in namespaces {
+        let Some(&idx) = object_data(tg, parent).and_then(|data| data.properties.get(ns)) else {
+            bail!("namespace {ns:?} not found");
+        };
+        parent = idx;
+    }
+    let Some(data) = object_data(tg, parent) else {
+        bail!("expected an object for the namespace of {}", path.join("."));
+    };
+    Ok(policy_names(tg, data.policies.get(key)))
+}
+
+fn flatten_injections(tree: &BTreeMap<String, InjectionNode>) -> BTreeMap<String, &Injection> {
+    fn collect<'a>(
+        prefix: &str,
+        tree: &'a BTreeMap<String, InjectionNode>,
+        res: &mut BTreeMap<String, &'a Injection>,
+    ) {
+        for (key, node) in tree {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            match node {
+                InjectionNode::Leaf { injection } => {
+                    res.insert(path, injection);
+                }
+                InjectionNode::Parent { children } => collect(&path, children, res),
+            }
+        }
+    }
+    let mut res = BTreeMap::new();
+    collect("", tree, &mut res);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::testing::sample_typegraph;
+
+    type Changes = Vec<(ChangeSeverity, String, String)>;
+
+    /// The sample typegraph, with its type list updated
+    fn typegraph(update: impl FnOnce(&mut Vec<Value>)) -> Typegraph {
+        let mut value = serde_json::to_value(sample_typegraph()).unwrap();
+        update(value["types"].as_array_mut().unwrap());
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn changes(old: &Typegraph, new: &Typegraph) -> Changes {
+        TypegraphDiff::new(old, new)
+            .unwrap()
+            .changes
+            .into_iter()
+            .map(|c| (c.severity, c.path, c.message))
+            .collect()
+    }
+
+    fn change(
+        severity: ChangeSeverity,
+        path: &str,
+        message: &str,
+    ) -> (ChangeSeverity, String, String) {
+        (severity, path.to_string(), message.to_string())
+    }
+
+    /// Adds a type at the end of the list, and returns its index
+    fn push(types: &mut Vec<Value>, node: Value) -> usize {
+        types.push(node);
+        types.len() - 1
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let tg = sample_typegraph();
+        assert_eq!(changes(&tg, &tg), vec![]);
+    }
+
+    #[test]
+    fn test_removed_field() {
+        let old = sample_typegraph();
+        let new = typegraph(|types| {
+            types[7]["properties"]
+                .as_object_mut()
+                .unwrap()
+                .remove("title");
+        });
+        // `Post` is reported once, at the first place it is reachable from
+        assert_eq!(
+            changes(&old, &new),
+            vec![change(
+                ChangeSeverity::Breaking,
+                "findPost.output.title",
+                "field removed"
+            )]
+        );
+        assert_eq!(
+            changes(&new, &old),
+            vec![change(
+                ChangeSeverity::Safe,
+                "findPost.output.title",
+                "field added"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_narrowed_type() {
+        let old = sample_typegraph();
+        let new = typegraph(|types| {
+            let title = push(
+                types,
+                json!({"type": "string", "title": "title", "maxLength": 64}),
+            );
+            types[15]["properties"]["title"] = json!(title);
+        });
+        assert_eq!(
+            changes(&old, &new),
+            vec![change(
+                ChangeSeverity::Breaking,
+                "blog.createPost.input.title",
+                "constraint maxLength tightened"
+            )]
+        );
+        assert_eq!(
+            changes(&new, &old),
+            vec![change(
+                ChangeSeverity::Safe,
+                "blog.createPost.input.title",
+                "constraint maxLength relaxed"
+            )]
+        );
+
+        let new = typegraph(|types| {
+            let id = push(
+                types,
+                json!({"type": "integer", "title": "id", "minimum": 1}),
+            );
+            types[2]["properties"]["id"] = json!(id);
+            types[7]["properties"]["id"] = json!(id);
+        });
+        assert_eq!(
+            changes(&old, &new),
+            vec![
+                change(
+                    ChangeSeverity::Breaking,
+                    "findPost.input.id",
+                    "constraint minimum tightened"
+                ),
+                change(
+                    ChangeSeverity::Safe,
+                    "findPost.output.id",
+                    "constraint minimum tightened"
+                ),
+            ]
+        );
+
+        let new = typegraph(|types| {
+            types[16]["properties"]["query"] = json!(3);
+        });
+        assert_eq!(
+            changes(&old, &new),
+            vec![change(
+                ChangeSeverity::Breaking,
+                "search.input.query",
+                "type changed from string to integer"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_optional_to_required() {
+        let optional = typegraph(|types| {
+            let query = push(
+                types,
+                json!({"type": "optional", "title": "query_optional", "item": 6, "default_value": null}),
+            );
+            types[16]["properties"]["query"] = json!(query);
+        });
+        let required = sample_typegraph();
+        assert_eq!(
+            changes(&optional, &required),
+            vec![change(
+                ChangeSeverity::Breaking,
+                "search.input.query",
+                "became required"
+            )]
+        );
+        assert_eq!(
+            changes(&required, &optional),
+            vec![change(
+                ChangeSeverity::Safe,
+                "search.input.query",
+                "became optional"
+            )]
+        );
+
+        // outputs are the other way around
+        let non_nullable = typegraph(|types| {
+            types[1]["output"] = json!(7);
+        });
+        let nullable = sample_typegraph();
+        assert_eq!(
+            changes(&nullable, &non_nullable),
+            vec![change(
+                ChangeSeverity::Safe,
+                "findPost.output",
+                "no longer nullable"
+            )]
+        );
+        assert_eq!(
+            changes(&non_nullable, &nullable),
+            vec![change(
+                ChangeSeverity::Breaking,
+                "findPost.output",
+                "became nullable"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_enum_value_removed() {
+        let removed = r#"enum values removed: ["\"published\""]"#;
+
+        let old = sample_typegraph();
+        let new = typegraph(|types| {
+            types[11]["enum"] = json!(["\"draft\""]);
+        });
+        // an output with fewer possible values is safe
+        assert_eq!(
+            changes(&old, &new),
+            vec![change(
+                ChangeSeverity::Safe,
+                "findPost.output.status",
+                removed
+            )]
+        );
+
+        let with_status_input = |values: Value| {
+            typegraph(|types| {
+                let status = push(
+                    types,
+                    json!({"type": "string", "title": "status_input", "enum": values}),
+                );
+                types[15]["properties"]["status"] = json!(status);
+            })
+        };
+        let old = with_status_input(json!(["\"draft\"", "\"published\""]));
+        let new = with_status_input(json!(["\"draft\""]));
+        assert_eq!(
+            changes(&old, &new),
+            vec![change(
+                ChangeSeverity::Breaking,
+                "blog.createPost.input.status",
+                removed
+            )]
+        );
+    }
+}
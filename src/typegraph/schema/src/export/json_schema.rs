@@ -0,0 +1,337 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+
+use crate::{InjectionNode, StringFormat, TypeId, TypeNode, TypeNodeBase, Typegraph};
+
+type Injections = BTreeMap<String, InjectionNode>;
+
+/// Generate JSON Schemas for the type nodes of a typegraph.
+///
+/// Objects are emitted as definitions and referenced with `$ref`, so that
+/// recursive types are supported.
+pub struct JsonSchemaGenerator<'a> {
+    tg: &'a Typegraph,
+    ref_prefix: &'static str,
+    names: HashMap<TypeId, String>,
+    taken_names: HashMap<String, TypeId>,
+    pub definitions: IndexMap<String, Value>,
+}
+
+impl<'a> JsonSchemaGenerator<'a> {
+    /// `ref_prefix` is the location of the definitions in the final document,
+    /// e.g. `#/$defs/` or `#/components/schemas/`
+    pub fn new(tg: &'a Typegraph, ref_prefix: &'static str) -> Self {
+        Self {
+            tg,
+            ref_prefix,
+            names: Default::default(),
+            taken_names: Default::default(),
+            definitions: Default::default(),
+        }
+    }
+
+    pub fn generate(&mut self, type_idx: TypeId) -> Result<Value> {
+        let node = &self.tg.types[type_idx as usize];
+        if let TypeNode::Object { .. } = node {
+            let name = self.definition_name(type_idx, &node.base().title);
+            if !self.definitions.contains_key(&name) {
+                // placeholder for recursive references
+                self.definitions.insert(name.clone(), Value::Null);
+                let schema = self.generate_object(type_idx, None)?;
+                self.definitions.insert(name.clone(), schema);
+            }
+            return Ok(json!({ "$ref": format!("{}{name}", self.ref_prefix) }));
+        }
+        self.generate_inline(type_idx, None)
+    }
+
+    /// Input schema of a function, without the injected parameters
+    pub fn generate_input(&mut self, fn_idx: TypeId) -> Result<Value> {
+        let TypeNode::Function { data, .. } = &self.tg.types[fn_idx as usize] else {
+            bail!("expected a function at #{fn_idx}");
+        };
+        self.generate_with(data.input, &data.injections)
+    }
+
+    /// Schema of a type without the injected properties, at any depth.
+    /// Types with injections are inlined as they differ from their definition.
+    fn generate_with(&mut self, type_idx: TypeId, injections: &Injections) -> Result<Value> {
+        if injections.values().all(InjectionNode::is_empty) {
+            return self.generate(type_idx);
+        }
+        match &self.tg.types[type_idx as usize] {
+            TypeNode::Object { .. } => self.generate_object(type_idx, Some(injections)),
+            _ => self.generate_inline(type_idx, Some(injections)),
+        }
+    }
+
+    /// Item of an optional or a list, or variant of a union, with the injections of the parent
+    fn generate_child(
+        &mut self,
+        type_idx: TypeId,
+        injections: Option<&Injections>,
+    ) -> Result<Value> {
+        match injections {
+            Some(injections) => self.generate_with(type_idx, injections),
+            None => self.generate(type_idx),
+        }
+    }
+
+    pub fn generate_output(&mut self, fn_idx: TypeId) -> Result<Value> {
+        let TypeNode::Function { data, .. } = &self.tg.types[fn_idx as usize] else {
+            bail!("expected a function at #{fn_idx}");
+        };
+        self.generate(data.output)
+    }
+
+    fn definition_name(&mut self, type_idx: TypeId, title: &str) -> String {
+        if let Some(name) = self.names.get(&type_idx) {
+            return name.clone();
+        }
+        let name = match self.taken_names.get(title) {
+            Some(&idx) if idx != type_idx => format!("{title}_{type_idx}"),
+            _ => title.to_string(),
+        };
+        self.taken_names.insert(name.clone(), type_idx);
+        self.names.insert(type_idx, name.clone());
+        name
+    }
+
+    fn generate_object(
+        &mut self,
+        type_idx: TypeId,
+        injections: Option<&Injections>,
+    ) -> Result<Value> {
+        let TypeNode::Object { base, data } = &self.tg.types[type_idx as usize] else {
+            bail!("expected an object at #{type_idx}");
+        };
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for (key, &prop_idx) in data.properties.iter() {
+            let schema = match injections.and_then(|inj| inj.get(key)) {
+                Some(InjectionNode::Leaf { .. }) => continue,
+                Some(InjectionNode::Parent { children }) => {
+                    self.generate_with(prop_idx, children)?
+                }
+                None => self.generate(prop_idx)?,
+            };
+            properties.insert(key.clone(), schema);
+            if !matches!(self.tg.types[prop_idx as usize], TypeNode::Optional { .. }) {
+                required.push(Value::String(key.clone()));
+            }
+        }
+        let mut schema = Map::new();
+        schema.insert("type".into(), json!("object"));
+        schema.insert("properties".into(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".into(), Value::Array(required));
+        }
+        if !data.additional_props {
+            schema.insert("additionalProperties".into(), json!(false));
+        }
+        with_base(schema, base)
+    }
+
+    fn generate_inline(
+        &mut self,
+        type_idx: TypeId,
+        injections: Option<&Injections>,
+    ) -> Result<Value> {
+        let node = &self.tg.types[type_idx as usize];
+        let mut schema = Map::new();
+        let mut insert = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                schema.insert(key.to_string(), value);
+            }
+        };
+        match node {
+            TypeNode::Any { .. } => {}
+            TypeNode::Boolean { .. } => insert("type", Some(json!("boolean"))),
+            TypeNode::Integer { data, .. } => {
+                insert("type", Some(json!("integer")));
+                insert("minimum", data.minimum.map(Into::into));
+                insert("maximum", data.maximum.map(Into::into));
+                insert("exclusiveMinimum", data.exclusive_minimum.map(Into::into));
+                insert("exclusiveMaximum", data.exclusive_maximum.map(Into::into));
+                insert("multipleOf", data.multiple_of.map(Into::into));
+            }
+            TypeNode::Float { data, .. } => {
+                insert("type", Some(json!("number")));
+                insert("minimum", data.minimum.map(Into::into));
+                insert("maximum", data.maximum.map(Into::into));
+                insert("exclusiveMinimum", data.exclusive_minimum.map(Into::into));
+                insert("exclusiveMaximum", data.exclusive_maximum.map(Into::into));
+                insert("multipleOf", data.multiple_of.map(Into::into));
+            }
+            TypeNode::String { data, .. } => {
+                insert("type", Some(json!("string")));
+                insert("minLength", data.min_length.map(Into::into));
+                insert("maxLength", data.max_length.map(Into::into));
+                insert("pattern", data.pattern.clone().map(Into::into));
+                insert("format", data.format.as_ref().and_then(json_schema_format));
+            }
+            TypeNode::File { data, .. } => {
+                insert("type", Some(json!("string")));
+                insert("format", Some(json!("binary")));
+                insert(
+                    "contentMediaType",
+                    data.mime_types
+                        .as_ref()
+                        .filter(|types| types.len() == 1)
+                        .map(|types| types[0].clone().into()),
+                );
+            }
+            TypeNode::Optional { data, .. } => {
+                let item = self.generate_child(data.item, injections)?;
+                schema.insert("anyOf".into(), json!([item, { "type": "null" }]));
+                if let Some(default_value) = data.default_value.clone() {
+                    schema.insert("default".into(), default_value);
+                }
+            }
+            TypeNode::List { data, .. } => {
+                let items = self.generate_child(data.items, injections)?;
+                schema.insert("type".into(), json!("array"));
+                schema.insert("items".into(), items);
+                if let Some(min_items) = data.min_items {
+                    schema.insert("minItems".into(), min_items.into());
+                }
+                if let Some(max_items) = data.max_items {
+                    schema.insert("maxItems".into(), max_items.into());
+                }
+                if let Some(unique_items) = data.unique_items {
+                    schema.insert("uniqueItems".into(), unique_items.into());
+                }
+            }
+            TypeNode::Union { data, .. } => {
+                let variants = data
+                    .any_of
+                    .iter()
+                    .map(|&idx| self.generate_child(idx, injections))
+                    .collect::<Result<Vec<_>>>()?;
+                schema.insert("anyOf".into(), variants.into());
+            }
+            TypeNode::Either { data, .. } => {
+                let variants = data
+                    .one_of
+                    .iter()
+                    .map(|&idx| self.generate_child(idx, injections))
+                    .collect::<Result<Vec<_>>>()?;
+                schema.insert("oneOf".into(), variants.into());
+            }
+            TypeNode::Object { .. } => unreachable!(),
+            // field with arguments on an output type
+            TypeNode::Function { data, .. } => return self.generate(data.output),
+        }
+        with_base(schema, node.base())
+    }
+}
+
+pub(super) fn with_base(mut schema: Map<String, Value>, base: &TypeNodeBase) -> Result<Value> {
+    let mut res = Map::new();
+    res.insert("title".into(), base.title.clone().into());
+    if let Some(description) = base.description.clone() {
+        res.insert("description".into(), description.into());
+    }
+    if base.deprecated.is_some() {
+        res.insert("deprecated".into(), true.into());
+    }
+    if let Some(examples) = base.examples.clone() {
+        res.insert("examples".into(), examples.into());
+    }
+    if let Some(enumeration) = base.enumeration.as_ref() {
+        let values = enumeration
+            .iter()
+            .map(|v| serde_json::from_str(v))
+            .collect::<serde_json::Result<Vec<Value>>>()?;
+        schema.insert("enum".into(), values.into());
+    }
+    res.extend(schema);
+    Ok(Value::Object(res))
+}
+
+impl Typegraph {
+    /// Exposed functions, with their path from the root (namespaces)
+    pub fn exposed_functions(&self) -> Result<Vec<(Vec<String>, TypeId)>> {
+        let mut res = vec![];
+        self.collect_functions(0, &mut vec![], &mut res)?;
+        Ok(res)
+    }
+
+    fn collect_functions(
+        &self,
+        type_idx: TypeId,
+        path: &mut Vec<String>,
+        res: &mut Vec<(Vec<String>, TypeId)>,
+    ) -> Result<()> {
+        let TypeNode::Object { data, .. } = &self.types[type_idx as usize] else {
+            bail!("expected an object at #{type_idx}");
+        };
+        for (key, &prop_idx) in data.properties.iter() {
+            path.push(key.clone());
+            match &self.types[prop_idx as usize] {
+                TypeNode::Function { .. } => res.push((path.clone(), prop_idx)),
+                TypeNode::Object { .. } => self.collect_functions(prop_idx, path, res)?,
+                _ => bail!("unexpected type at {}", path.join(".")),
+            }
+            path.pop();
+        }
+        Ok(())
+    }
+
+    /// JSON Schema (draft 2020-12) of the input and output of each exposed function
+    pub fn to_json_schema(&self) -> Result<Value> {
+        let mut generator = JsonSchemaGenerator::new(self, "#/$defs/");
+        let mut functions = Map::new();
+        for (path, fn_idx) in self.exposed_functions()? {
+            functions.insert(
+                path.join("."),
+                json!({
+                    "input": generator.generate_input(fn_idx)?,
+                    "output": generator.generate_output(fn_idx)?,
+                }),
+            );
+        }
+        Ok(json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": self.name()?,
+            "functions": functions,
+            "$defs": generator.definitions,
+        }))
+    }
+}
+
+/// Name of the format in the JSON Schema vocabulary; the other formats are
+/// omitted as JSON Schema does not define them.
+fn json_schema_format(format: &StringFormat) -> Option<Value> {
+    let name = match format {
+        StringFormat::Uuid => "uuid",
+        StringFormat::Email => "email",
+        StringFormat::Uri => "uri",
+        StringFormat::Hostname => "hostname",
+        StringFormat::Date => "date",
+        StringFormat::DateTime => "date-time",
+        StringFormat::Time => "time",
+        StringFormat::Duration => "duration",
+        StringFormat::Ipv4 => "ipv4",
+        StringFormat::Ipv6 => "ipv6",
+        _ => return None,
+    };
+    Some(name.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::sample_typegraph;
+
+    #[test]
+    fn test_json_schema() {
+        let schema = sample_typegraph().to_json_schema().unwrap();
+        insta::assert_snapshot!(serde_json::to_string_pretty(&schema).unwrap());
+    }
+}
@@ -0,0 +1,9 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Export the typegraph to standard API description formats.
+
+mod json_schema;
+mod openapi;
+
+pub use json_schema::JsonSchemaGenerator;
@@ -0,0 +1,469 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use graphql_parser::query::{
+    self as gql, Definition, FragmentDefinition, OperationDefinition, Selection, SelectionSet,
+    Type as GqlType, TypeCondition,
+};
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+
+use super::json_schema::JsonSchemaGenerator;
+use crate::{Auth, AuthProtocol, EffectType, TypeId, TypeNode, Typegraph};
+
+type Fragments<'q> = HashMap<String, FragmentDefinition<'q, String>>;
+
+/// REST endpoint defined from a named GraphQL query
+struct Endpoint<'q> {
+    name: String,
+    variables: Vec<gql::VariableDefinition<'q, String>>,
+    selection_set: SelectionSet<'q, String>,
+    fragments: Fragments<'q>,
+    fields: Vec<EndpointField<'q>>,
+}
+
+/// Function selected in an endpoint query
+struct EndpointField<'q> {
+    fn_idx: TypeId,
+    arguments: Vec<(String, gql::Value<'q, String>)>,
+}
+
+impl Typegraph {
+    /// OpenAPI 3.1 document for the REST endpoints (`meta.queries.endpoints`).
+    ///
+    /// This follows the document served by the typegate on `/rest/__schema`
+    /// (see `buildOpenAPISpecFrom`): same paths, operation ids and error
+    /// responses. Responses only document the fields selected by the query.
+    pub fn to_openapi(&self, base_url: Option<&str>) -> Result<Value> {
+        let tg_name = self.name()?;
+        let mut generator = JsonSchemaGenerator::new(self, "#/components/schemas/");
+        let mut paths = Map::new();
+
+        for query in self.meta.queries.endpoints.iter() {
+            let document = gql::parse_query::<String>(query)
+                .map_err(|e| anyhow!("invalid endpoint query {query:?}: {e}"))?;
+            let endpoint = self
+                .parse_endpoint(document)
+                .with_context(|| format!("invalid endpoint query {query:?}"))?;
+            let method = self.endpoint_method(&endpoint)?;
+            let operation = self.endpoint_operation(&tg_name, method, &endpoint, &mut generator)?;
+            paths.insert(
+                format!("/{tg_name}/rest/{}", endpoint.name),
+                json!({ method: operation }),
+            );
+        }
+
+        let mut components = Map::new();
+        let mut schemas = Map::new();
+        schemas.insert("Error".into(), error_schema());
+        schemas.extend(generator.definitions);
+        components.insert("schemas".into(), schemas.into());
+
+        let mut security = vec![];
+        if !self.meta.auths.is_empty() {
+            let mut schemes = Map::new();
+            for auth in self.meta.auths.iter() {
+                schemes.insert(auth.name.clone(), security_scheme(&tg_name, auth));
+                security.push(json!({ &auth.name: [] }));
+            }
+            // authentication is optional; access is controlled by the policies
+            security.push(json!({}));
+            components.insert("securitySchemes".into(), schemes.into());
+        }
+
+        let mut doc = Map::new();
+        doc.insert("openapi".into(), json!("3.1.0"));
+        doc.insert(
+            "info".into(),
+            json!({
+                "title": tg_name,
+                "description": format!("REST endpoints of the typegraph {tg_name:?}"),
+                "version": self.meta.version,
+            }),
+        );
+        if let Some(base_url) = base_url {
+            doc.insert("servers".into(), json!([{ "url": base_url }]));
+        }
+        doc.insert("paths".into(), paths.into());
+        doc.insert("components".into(), components.into());
+        if !security.is_empty() {
+            doc.insert("security".into(), security.into());
+        }
+        Ok(Value::Object(doc))
+    }
+
+    fn parse_endpoint<'q>(&self, document: gql::Document<'q, String>) -> Result<Endpoint<'q>> {
+        let mut operation = None;
+        let mut fragments = Fragments::new();
+        for definition in document.definitions {
+            match definition {
+                Definition::Operation(op) => {
+                    if operation.replace(op).is_some() {
+                        bail!("only one operation is allowed");
+                    }
+                }
+                Definition::Fragment(fragment) => {
+                    fragments.insert(fragment.name.clone(), fragment);
+                }
+            }
+        }
+        let operation = operation.ok_or_else(|| anyhow!("operation not found"))?;
+        let (name, variables, selection_set) = match operation {
+            OperationDefinition::Query(q) => (q.name, q.variable_definitions, q.selection_set),
+            OperationDefinition::Mutation(m) => (m.name, m.variable_definitions, m.selection_set),
+            _ => bail!("only named queries and mutations are supported"),
+        };
+        let name = name.ok_or_else(|| anyhow!("query name is required"))?;
+        let mut fields = vec![];
+        self.collect_endpoint_fields(0, &selection_set, &mut fields)?;
+        Ok(Endpoint {
+            name,
+            variables,
+            selection_set,
+            fragments,
+            fields,
+        })
+    }
+
+    fn collect_endpoint_fields<'q>(
+        &self,
+        parent_idx: TypeId,
+        selection_set: &SelectionSet<'q, String>,
+        fields: &mut Vec<EndpointField<'q>>,
+    ) -> Result<()> {
+        let TypeNode::Object { data, .. } = &self.types[parent_idx as usize] else {
+            bail!("expected an object at #{parent_idx}");
+        };
+        for selection in selection_set.items.iter() {
+            let Selection::Field(field) = selection else {
+                bail!("fragments are not supported on endpoint root fields");
+            };
+            let &idx = data
+                .properties
+                .get(&field.name)
+                .ok_or_else(|| anyhow!("function {:?} not found", field.name))?;
+            match &self.types[idx as usize] {
+                TypeNode::Function { .. } => fields.push(EndpointField {
+                    fn_idx: idx,
+                    arguments: field.arguments.clone(),
+                }),
+                // namespace
+                TypeNode::Object { .. } => {
+                    self.collect_endpoint_fields(idx, &field.selection_set, fields)?
+                }
+                _ => bail!("unexpected type for {:?}", field.name),
+            }
+        }
+        Ok(())
+    }
+
+    fn endpoint_method(&self, endpoint: &Endpoint) -> Result<&'static str> {
+        let field = endpoint
+            .fields
+            .first()
+            .ok_or_else(|| anyhow!("no field selected"))?;
+        let TypeNode::Function { data, .. } = &self.types[field.fn_idx as usize] else {
+            unreachable!();
+        };
+        let effect = self.materializers[data.materializer as usize].effect.effect;
+        // see `effectToMethod` in the typegate
+        Ok(match effect {
+            None | Some(EffectType::Read) => "get",
+            Some(EffectType::Create) => "post",
+            Some(EffectType::Update) => "put",
+            Some(EffectType::Delete) => "delete",
+        })
+    }
+
+    fn endpoint_operation(
+        &self,
+        tg_name: &str,
+        method: &str,
+        endpoint: &Endpoint,
+        generator: &mut JsonSchemaGenerator,
+    ) -> Result<Value> {
+        let mut variables = IndexMap::new();
+        for var in endpoint.variables.iter() {
+            let schema = match self.find_variable_type(endpoint, &var.name) {
+                Some(type_idx) => generator.generate(type_idx)?,
+                None => graphql_type_schema(&var.var_type),
+            };
+            let required = matches!(var.var_type, GqlType::NonNullType(_));
+            variables.insert(var.name.clone(), (schema, required));
+        }
+
+        let output =
+            self.selection_schema(0, &endpoint.selection_set, &endpoint.fragments, generator)?;
+
+        let name = &endpoint.name;
+        let error = |description: String| {
+            json!({
+                "description": description,
+                "content": {
+                    "application/json": {
+                        "schema": { "$ref": "#/components/schemas/Error" }
+                    }
+                }
+            })
+        };
+        let mut operation = Map::new();
+        operation.insert("summary".into(), json!(format!("Perform {name}")));
+        operation.insert(
+            "operationId".into(),
+            json!(format!("{method}_{tg_name}_{name}").to_lowercase()),
+        );
+        if method == "get" {
+            let parameters = variables
+                .into_iter()
+                .map(|(name, (schema, required))| {
+                    json!({
+                        "name": name,
+                        "in": "query",
+                        "required": required,
+                        "schema": schema,
+                    })
+                })
+                .collect::<Vec<_>>();
+            operation.insert("parameters".into(), parameters.into());
+        } else {
+            let required = variables
+                .iter()
+                .filter(|(_, (_, required))| *required)
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+            let properties = variables
+                .into_iter()
+                .map(|(name, (schema, _))| (name, schema))
+                .collect::<Map<_, _>>();
+            operation.insert(
+                "requestBody".into(),
+                json!({
+                    "required": true,
+                    "content": {
+                        "application/json": {
+                            "schema": {
+                                "type": "object",
+                                "properties": properties,
+                                "required": required,
+                            }
+                        }
+                    }
+                }),
+            );
+        }
+        operation.insert(
+            "responses".into(),
+            json!({
+                "200": {
+                    "description": format!("Perform {name}: OK"),
+                    "content": {
+                        "application/json": {
+                            "schema": output,
+                        }
+                    }
+                },
+                "400": error(format!("Perform {name}: Bad Request")),
+                "403": error(format!("Perform {name}: Forbidden")),
+                "500": error(format!("Perform {name}: Service unavailable")),
+            }),
+        );
+        Ok(Value::Object(operation))
+    }
+
+    /// Schema of the fields selected on a value of the given type
+    fn selection_schema<'q>(
+        &self,
+        type_idx: TypeId,
+        selection_set: &SelectionSet<'q, String>,
+        fragments: &Fragments<'q>,
+        generator: &mut JsonSchemaGenerator,
+    ) -> Result<Value> {
+        if selection_set.items.is_empty() {
+            return generator.generate(type_idx);
+        }
+        let mut variants = |ids: &[TypeId]| {
+            ids.iter()
+                .map(|&idx| self.selection_schema(idx, selection_set, fragments, generator))
+                .collect::<Result<Vec<_>>>()
+        };
+        Ok(match &self.types[type_idx as usize] {
+            TypeNode::Optional { data, .. } => {
+                let item = self.selection_schema(data.item, selection_set, fragments, generator)?;
+                json!({ "anyOf": [item, { "type": "null" }] })
+            }
+            TypeNode::List { data, .. } => {
+                let items =
+                    self.selection_schema(data.items, selection_set, fragments, generator)?;
+                json!({ "type": "array", "items": items })
+            }
+            TypeNode::Union { data, .. } => json!({ "anyOf": variants(&data.any_of)? }),
+            TypeNode::Either { data, .. } => json!({ "oneOf": variants(&data.one_of)? }),
+            TypeNode::Function { data, .. } => {
+                self.selection_schema(data.output, selection_set, fragments, generator)?
+            }
+            TypeNode::Object { base, data } => {
+                let mut fields = vec![];
+                collect_fields(selection_set, &base.title, fragments, &mut fields)?;
+                let mut properties = Map::new();
+                let mut required = vec![];
+                for field in fields {
+                    let key = field.alias.as_ref().unwrap_or(&field.name);
+                    if properties.contains_key(key) {
+                        continue;
+                    }
+                    let (schema, optional) = if field.name == "__typename" {
+                        (json!({ "type": "string" }), false)
+                    } else {
+                        let &prop_idx = data.properties.get(&field.name).ok_or_else(|| {
+                            anyhow!("field {:?} not found on {:?}", field.name, base.title)
+                        })?;
+                        let schema = self.selection_schema(
+                            prop_idx,
+                            &field.selection_set,
+                            fragments,
+                            generator,
+                        )?;
+                        let optional =
+                            matches!(self.types[prop_idx as usize], TypeNode::Optional { .. });
+                        (schema, optional)
+                    };
+                    properties.insert(key.clone(), schema);
+                    if !optional {
+                        required.push(key.clone());
+                    }
+                }
+                json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                })
+            }
+            // no selection on scalars
+            _ => generator.generate(type_idx)?,
+        })
+    }
+
+    /// Type of the function parameter the variable is directly bound to
+    fn find_variable_type(&self, endpoint: &Endpoint, var_name: &str) -> Option<TypeId> {
+        endpoint.fields.iter().find_map(|field| {
+            let TypeNode::Function { data, .. } = &self.types[field.fn_idx as usize] else {
+                return None;
+            };
+            let TypeNode::Object { data: input, .. } = &self.types[data.input as usize] else {
+                return None;
+            };
+            field.arguments.iter().find_map(|(arg, value)| match value {
+                gql::Value::Variable(v) if v == var_name => input.properties.get(arg).copied(),
+                _ => None,
+            })
+        })
+    }
+}
+
+/// Fields selected on an object, including the matching fragments
+fn collect_fields<'a, 'q>(
+    selection_set: &'a SelectionSet<'q, String>,
+    type_name: &str,
+    fragments: &'a Fragments<'q>,
+    fields: &mut Vec<&'a gql::Field<'q, String>>,
+) -> Result<()> {
+    for selection in selection_set.items.iter() {
+        match selection {
+            Selection::Field(field) => fields.push(field),
+            Selection::InlineFragment(fragment) => {
+                let matches = match &fragment.type_condition {
+                    Some(TypeCondition::On(name)) => name == type_name,
+                    None => true,
+                };
+                if matches {
+                    collect_fields(&fragment.selection_set, type_name, fragments, fields)?;
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                let fragment = fragments
+                    .get(&spread.fragment_name)
+                    .ok_or_else(|| anyhow!("fragment {:?} not found", spread.fragment_name))?;
+                let TypeCondition::On(name) = &fragment.type_condition;
+                if name == type_name {
+                    collect_fields(&fragment.selection_set, type_name, fragments, fields)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn graphql_type_schema(ty: &GqlType<String>) -> Value {
+    match ty {
+        GqlType::NonNullType(inner) => graphql_type_schema(inner),
+        GqlType::ListType(inner) => json!({ "type": "array", "items": graphql_type_schema(inner) }),
+        GqlType::NamedType(name) => match name.as_str() {
+            "Int" | "Integer" => json!({ "type": "integer" }),
+            "Float" => json!({ "type": "number" }),
+            "Boolean" => json!({ "type": "boolean" }),
+            "String" | "ID" => json!({ "type": "string" }),
+            _ => json!({}),
+        },
+    }
+}
+
+fn error_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["message", "extensions"],
+        "properties": {
+            "message": { "type": "string" },
+            "locations": { "type": "array", "items": { "type": "string" } },
+            "path": { "type": "array", "items": { "type": "string" } },
+            "extensions": {
+                "type": "object",
+                "properties": {
+                    "timestamp": { "type": "string", "format": "date-time" }
+                }
+            }
+        }
+    })
+}
+
+fn security_scheme(tg_name: &str, auth: &Auth) -> Value {
+    match auth.protocol {
+        AuthProtocol::Basic => json!({ "type": "http", "scheme": "basic" }),
+        AuthProtocol::Jwt => json!({ "type": "http", "scheme": "bearer", "bearerFormat": "JWT" }),
+        AuthProtocol::OAuth2 => {
+            let get = |key: &str| auth.auth_data.get(key).cloned().unwrap_or(Value::Null);
+            let scopes = get("scopes")
+                .as_str()
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(|scope| (scope.to_string(), json!("")))
+                .collect::<Map<_, _>>();
+            json!({
+                "type": "oauth2",
+                "description": format!("Login flow at /{tg_name}/auth/{}", auth.name),
+                "flows": {
+                    "authorizationCode": {
+                        "authorizationUrl": get("authorize_url"),
+                        "tokenUrl": get("access_url"),
+                        "scopes": scopes,
+                    }
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::sample_typegraph;
+
+    #[test]
+    fn test_openapi() {
+        let doc = sample_typegraph()
+            .to_openapi(Some("http://localhost:7890"))
+            .unwrap();
+        insta::assert_snapshot!(serde_json::to_string_pretty(&doc).unwrap());
+    }
+}
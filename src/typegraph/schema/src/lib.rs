@@ -1,8 +1,14 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
+pub mod diff;
+pub mod export;
+pub mod naming;
 pub mod parameter_transform;
 pub mod runtimes;
+pub mod stats;
+#[cfg(test)]
+mod testing;
 pub mod types;
 pub mod utils;
 pub mod validator;
@@ -75,6 +81,26 @@ pub struct Rate {
     pub local_excess: u32,
 }
 
+/// Bounds on the shape of the queries, checked by the typegate before the
+/// execution.
+///
+/// The cost of a query is the sum of the costs of the selected fields: the
+/// `rate_weight` of the functions (default 1), 1 for the other fields with a
+/// selection set, and 0 for scalars, each multiplied by the cardinality of the
+/// enclosing lists.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct QueryLimits {
+    pub max_depth: Option<u32>,
+    pub max_aliases: Option<u32>,
+    pub max_cost: Option<u32>,
+    /// cardinality hint for the lists without `max_items`
+    pub list_size: u32,
+}
+
+impl QueryLimits {
+    pub const DEFAULT_LIST_SIZE: u32 = 10;
+}
+
 // TODO: remove default, as they should all be explicity set in the core SDK
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Queries {
@@ -94,12 +120,19 @@ pub struct TypeMeta {
     pub cors: Cors,
     pub auths: Vec<Auth>,
     pub rate: Option<Rate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub limits: Option<QueryLimits>,
     pub version: String,
     pub random_seed: Option<u32>,
     pub artifacts: BTreeMap<PathBuf, Artifact>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[serde(default)]
     pub namespaces: Vec<u32>,
+    /// custom string formats: name -> regex
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default)]
+    pub string_formats: IndexMap<String, String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -0,0 +1,235 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Naming strategies for the generated type names, shared by the typegraph
+//! serialization and the typegraph expansion of the code generators.
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NameCase {
+    #[default]
+    Preserve,
+    Pascal,
+    Snake,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionPolicy {
+    /// suffix with a hash of the origin of the type
+    #[default]
+    Hash,
+    /// suffix with the first available number, from 2
+    Counter,
+    Error,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct NamingStrategy {
+    pub case: NameCase,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    /// also apply the case and the affixes to the explicit names (`rename`,
+    /// `name=`); by default explicit names are kept as is
+    pub rename_user_named: bool,
+    pub collision: CollisionPolicy,
+    /// name the duplicates of a type from a hash of their duplication key
+    /// instead of their index, so that they do not change when other
+    /// duplicates are added or removed
+    pub stable_variants: bool,
+}
+
+impl NamingStrategy {
+    /// Apply the case and the affixes to a generated name
+    pub fn apply(&self, name: &str) -> String {
+        let name = [self.prefix.as_deref(), Some(name), self.suffix.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("_");
+        match self.case {
+            NameCase::Preserve => name,
+            NameCase::Pascal => to_pascal_case(&name),
+            NameCase::Snake => to_snake_case(&name),
+        }
+    }
+
+    pub fn apply_user_named(&self, name: &str) -> String {
+        if self.rename_user_named {
+            self.apply(name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Join a name and a disambiguation suffix, following the case
+    pub fn join(&self, name: &str, suffix: &str) -> String {
+        match self.case {
+            NameCase::Pascal => format!("{name}{}", to_pascal_case(suffix)),
+            NameCase::Preserve | NameCase::Snake => format!("{name}_{suffix}"),
+        }
+    }
+
+    /// Name of the n-th (from 1) duplicate of a type
+    pub fn variant_name(&self, name: &str, index: usize, key: &str) -> String {
+        if self.stable_variants {
+            self.join(name, &short_hash(key))
+        } else {
+            self.join(name, &index.to_string())
+        }
+    }
+}
+
+/// Tracks the names in use, and resolves the collisions with the configured
+/// policy
+#[derive(Debug, Default)]
+pub struct NameAllocator {
+    taken: HashSet<String>,
+}
+
+impl NameAllocator {
+    /// Mark a name as taken, without checking for collisions
+    pub fn reserve(&mut self, name: String) {
+        self.taken.insert(name);
+    }
+
+    /// `origin` identifies the type, for the `hash` policy
+    pub fn allocate(
+        &mut self,
+        strategy: &NamingStrategy,
+        name: String,
+        origin: &str,
+    ) -> Result<String> {
+        if !self.taken.contains(&name) {
+            self.taken.insert(name.clone());
+            return Ok(name);
+        }
+        let candidate = match strategy.collision {
+            CollisionPolicy::Error => bail!("duplicate type name: '{name}'"),
+            CollisionPolicy::Hash => Some(strategy.join(&name, &short_hash(origin)))
+                .filter(|candidate| !self.taken.contains(candidate)),
+            CollisionPolicy::Counter => None,
+        };
+        let candidate = candidate.unwrap_or_else(|| {
+            (2..)
+                .map(|n| strategy.join(&name, &n.to_string()))
+                .find(|candidate| !self.taken.contains(candidate))
+                .unwrap()
+        });
+        self.taken.insert(candidate.clone());
+        Ok(candidate)
+    }
+}
+
+/// first 5 hex digits of the FNV-1a hash; stable across platforms and versions
+pub fn short_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")[..5].to_string()
+}
+
+fn words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = vec![];
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            // `userId` -> user, Id; `HTTPServer` -> HTTP, Server
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+pub fn to_pascal_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+pub fn to_snake_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy() {
+        let strategy = NamingStrategy {
+            case: NameCase::Pascal,
+            prefix: Some("api".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            strategy.apply("root_createUser_fn_input"),
+            "ApiRootCreateUserFnInput"
+        );
+        assert_eq!(strategy.apply_user_named("user_profile"), "user_profile");
+        assert_eq!(to_snake_case("HTTPServerConfig2"), "http_server_config2");
+        assert_eq!(to_pascal_case("string_date_time"), "StringDateTime");
+
+        let mut names = NameAllocator::default();
+        let counter = NamingStrategy {
+            collision: CollisionPolicy::Counter,
+            ..Default::default()
+        };
+        assert_eq!(
+            names.allocate(&counter, "user".into(), "#1").unwrap(),
+            "user"
+        );
+        assert_eq!(
+            names.allocate(&counter, "user".into(), "#2").unwrap(),
+            "user_2"
+        );
+        let hashed = names
+            .allocate(&Default::default(), "user".into(), "#3")
+            .unwrap();
+        assert_eq!(hashed, format!("user_{}", short_hash("#3")));
+        let error = NamingStrategy {
+            collision: CollisionPolicy::Error,
+            ..Default::default()
+        };
+        assert!(names.allocate(&error, "user".into(), "#4").is_err());
+    }
+}
@@ -21,6 +21,58 @@ pub enum ContextCheckX {
     Pattern(String),
 }
 
+/// Operand of a context rule; paths are dot-separated, e.g. `user.id`
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleOperand {
+    /// path in the request context
+    Context(String),
+    /// path in the arguments of the function
+    Arg(String),
+    Value(Value),
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    /// the left operand is an item of the right operand (list)
+    In,
+}
+
+/// Declarative policy, evaluated by the typegate without a policy function
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContextRule {
+    NotNull {
+        operand: RuleOperand,
+    },
+    Pattern {
+        operand: RuleOperand,
+        /// JavaScript regular expression
+        pattern: String,
+    },
+    Compare {
+        left: RuleOperand,
+        op: CompareOp,
+        right: RuleOperand,
+    },
+    All {
+        rules: Vec<ContextRule>,
+    },
+    Any {
+        rules: Vec<ContextRule>,
+    },
+    Not {
+        rule: Box<ContextRule>,
+    },
+}
+
 #[derive(PartialEq, Eq, Hash, Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "name", content = "param")]
@@ -33,6 +85,7 @@ pub enum PredefinedFunctionMatData {
     Pass,
     InternalPolicy,
     ContextCheck { key: String, value: ContextCheckX },
+    ContextRule(ContextRule),
 }
 
 #[derive(Serialize)]
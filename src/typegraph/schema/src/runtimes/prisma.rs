@@ -116,6 +116,19 @@ pub struct Model {
     pub props: Vec<Property>,
     pub id_fields: Vec<String>,
     pub unique_constraints: Vec<Vec<String>>,
+    /// column marking the soft-deleted records
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_delete: Option<String>,
+}
+
+/// Connection pool options, passed to the query engine as connection string parameters
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PoolConfig {
+    /// maximum number of connections in the pool (`connection_limit`)
+    pub size: Option<u32>,
+    pub connect_timeout_sec: Option<u32>,
+    pub socket_timeout_sec: Option<u32>,
 }
 
 #[skip_serializing_none]
@@ -124,6 +137,11 @@ pub struct Model {
 pub struct PrismaRuntimeData {
     pub name: String,
     pub connection_string_secret: String,
+    #[serde(default)]
+    pub pool: Option<PoolConfig>,
+    // read-only queries are routed to the replicas when set
+    #[serde(default)]
+    pub read_replica_secrets: Option<Vec<String>>,
     pub models: Vec<Model>,
     pub relationships: Vec<Relationship>,
     // if migration_options is not None: migrations will be applied on push
@@ -136,4 +154,76 @@ pub struct PrismaOperationMatData {
     pub table: String,
     pub operation: String,
     pub ordered_keys: Option<Vec<String>>,
+    #[serde(default)]
+    pub soft_delete: Option<String>,
+}
+
+/// Database schema read by the prisma introspection engine
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PrismaIntrospection {
+    pub models: Vec<IntrospectedModel>,
+    pub enums: Vec<IntrospectedEnum>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IntrospectedEnum {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IntrospectedModel {
+    pub name: String,
+    pub fields: Vec<IntrospectedField>,
+    /// fields of a compound primary key (`@@id`)
+    pub id_fields: Vec<String>,
+    /// compound unique constraints (`@@unique`)
+    pub unique_constraints: Vec<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IntrospectedFieldKind {
+    Scalar,
+    Enum,
+    Relation,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IntrospectedDefault {
+    AutoIncrement,
+    Uuid,
+    Cuid,
+    Now,
+    DbGenerated,
+    Value { value: serde_json::Value },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IntrospectedRelation {
+    pub name: String,
+    /// foreign key fields on this side of the relation
+    pub fields: Vec<String>,
+    pub references: Vec<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IntrospectedField {
+    pub name: String,
+    pub kind: IntrospectedFieldKind,
+    /// prisma scalar type, enum name or model name
+    pub field_type: String,
+    /// native database type (`@db.*` attribute)
+    pub native_type: Option<String>,
+    pub cardinality: Cardinality,
+    pub id: bool,
+    pub unique: bool,
+    /// `@updatedAt` attribute
+    #[serde(default)]
+    pub updated_at: bool,
+    pub default: Option<IntrospectedDefault>,
+    pub relation: Option<IntrospectedRelation>,
 }
@@ -0,0 +1,433 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Size and complexity statistics of a typegraph.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::visitor::{
+    CurrentNode, DefaultLayer, Edge, ParentFn, Path, PathSegment, TypeVisitor, TypeVisitorContext,
+    VisitResult, VisitorResult,
+};
+use crate::{TypeId, TypeNode, Typegraph};
+
+/// number of entries in the lists of the report
+const TOP: usize = 10;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Depth {
+    pub function: String,
+    pub depth: usize,
+    pub path: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TreeSize {
+    pub function: String,
+    pub types: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Duplication {
+    pub type_idx: TypeId,
+    pub title: String,
+    pub duplicates: usize,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct TypegraphStats {
+    pub types: usize,
+    pub types_by_kind: BTreeMap<&'static str, usize>,
+    pub functions: usize,
+    pub materializers: usize,
+    pub max_input_depth: Option<Depth>,
+    pub max_output_depth: Option<Depth>,
+    /// strongly connected components of the type graph, as type titles
+    pub cycles: Vec<Vec<String>>,
+    pub unused_types: Vec<String>,
+    pub unused_materializers: Vec<String>,
+    /// largest input trees of the functions of the Prisma runtimes
+    pub largest_prisma_inputs: Vec<TreeSize>,
+    /// duplicated types, most duplicated first; see [`TypegraphStats::add_duplications`]
+    pub duplications: Vec<Duplication>,
+}
+
+impl TypegraphStats {
+    pub fn new(tg: &Typegraph) -> Self {
+        let mut types_by_kind = BTreeMap::new();
+        for node in tg.types.iter() {
+            *types_by_kind.entry(node.type_name()).or_default() += 1;
+        }
+
+        let context = StatsContext { tg };
+        let collector = tg
+            .traverse_types(Collector::default(), &context, DefaultLayer, 0)
+            .unwrap_or_default();
+
+        let unused_types = (0..tg.types.len() as u32)
+            .filter(|idx| !collector.reached.contains(idx))
+            .map(|idx| format!("#{idx} {}", tg.types[idx as usize].base().title))
+            .collect();
+
+        let used_materializers = collector
+            .functions
+            .values()
+            .map(|f| f.materializer)
+            .chain(tg.policies.iter().map(|p| p.materializer))
+            .collect::<HashSet<_>>();
+        let unused_materializers = tg
+            .materializers
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !used_materializers.contains(&(*idx as u32)))
+            .map(|(idx, mat)| {
+                let runtime = tg.runtimes[mat.runtime as usize].name();
+                format!("#{idx} {runtime}/{}", mat.name)
+            })
+            .collect();
+
+        let mut largest_prisma_inputs = collector
+            .input_sizes
+            .iter()
+            .filter_map(|(parent_fn, size)| {
+                let function = collector.functions.get(parent_fn)?;
+                let mat = &tg.materializers[function.materializer as usize];
+                (tg.runtimes[mat.runtime as usize].name() == "prisma").then(|| TreeSize {
+                    function: function.path.clone(),
+                    types: *size,
+                })
+            })
+            .collect::<Vec<_>>();
+        largest_prisma_inputs.sort_by(|a, b| b.types.cmp(&a.types));
+        largest_prisma_inputs.truncate(TOP);
+
+        Self {
+            types: tg.types.len(),
+            types_by_kind,
+            functions: collector.functions.len(),
+            materializers: tg.materializers.len(),
+            max_input_depth: collector.max_input_depth,
+            max_output_depth: collector.max_output_depth,
+            cycles: find_cycles(tg),
+            unused_types,
+            unused_materializers,
+            largest_prisma_inputs,
+            duplications: vec![],
+        }
+    }
+
+    /// Add the number of duplicates for each type, as created by the
+    /// duplication engine of the typegraph expansion
+    pub fn add_duplications(
+        &mut self,
+        tg: &Typegraph,
+        duplicates: impl IntoIterator<Item = (TypeId, usize)>,
+    ) {
+        self.duplications
+            .extend(duplicates.into_iter().filter(|(_, count)| *count > 0).map(
+                |(type_idx, duplicates)| Duplication {
+                    type_idx,
+                    title: tg.types[type_idx as usize].base().title.clone(),
+                    duplicates,
+                },
+            ));
+        self.duplications
+            .sort_by(|a, b| b.duplicates.cmp(&a.duplicates));
+    }
+}
+
+impl Display for TypegraphStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "types: {}", self.types)?;
+        for (kind, count) in self.types_by_kind.iter() {
+            writeln!(f, "  {kind}: {count}")?;
+        }
+        writeln!(f, "functions: {}", self.functions)?;
+        writeln!(f, "materializers: {}", self.materializers)?;
+        if let Some(depth) = &self.max_input_depth {
+            writeln!(
+                f,
+                "deepest input: {} in {} at {}",
+                depth.depth, depth.function, depth.path
+            )?;
+        }
+        if let Some(depth) = &self.max_output_depth {
+            writeln!(
+                f,
+                "deepest output: {} in {} at {}",
+                depth.depth, depth.function, depth.path
+            )?;
+        }
+
+        writeln!(f, "recursive cycles: {}", self.cycles.len())?;
+        for cycle in self.cycles.iter().take(TOP) {
+            writeln!(f, "  {}", cycle.join(" -> "))?;
+        }
+        writeln!(f, "unused types: {}", self.unused_types.len())?;
+        for ty in self.unused_types.iter().take(TOP) {
+            writeln!(f, "  {ty}")?;
+        }
+        writeln!(
+            f,
+            "unused materializers: {}",
+            self.unused_materializers.len()
+        )?;
+        for mat in self.unused_materializers.iter().take(TOP) {
+            writeln!(f, "  {mat}")?;
+        }
+        if !self.largest_prisma_inputs.is_empty() {
+            writeln!(f, "largest prisma inputs:")?;
+            for tree in self.largest_prisma_inputs.iter() {
+                writeln!(f, "  {}: {} types", tree.function, tree.types)?;
+            }
+        }
+        let total: usize = self.duplications.iter().map(|d| d.duplicates).sum();
+        writeln!(f, "duplicated types: {total}")?;
+        for dup in self.duplications.iter().take(TOP) {
+            writeln!(f, "  #{} {}: {}", dup.type_idx, dup.title, dup.duplicates)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct StatsContext<'a> {
+    tg: &'a Typegraph,
+}
+
+impl TypeVisitorContext for StatsContext<'_> {
+    fn get_typegraph(&self) -> &Typegraph {
+        self.tg
+    }
+}
+
+struct FunctionInfo {
+    path: String,
+    materializer: u32,
+}
+
+#[derive(Default)]
+struct Collector {
+    reached: HashSet<TypeId>,
+    functions: HashMap<ParentFn, FunctionInfo>,
+    input_sizes: HashMap<ParentFn, usize>,
+    max_input_depth: Option<Depth>,
+    max_output_depth: Option<Depth>,
+}
+
+impl VisitorResult for Collector {
+    fn from_error(_path: String, _message: String) -> Self {
+        Default::default()
+    }
+}
+
+/// dot-separated path of the function from the root
+fn function_path(path: &[PathSegment]) -> String {
+    path.iter()
+        .filter_map(|seg| match &seg.edge {
+            Edge::ObjectProp(name) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+impl Collector {
+    fn update_depth(&mut self, path: &[PathSegment], input: bool) {
+        let Some(fn_edge) = path
+            .iter()
+            .rposition(|seg| matches!(seg.edge, Edge::FunctionInput | Edge::FunctionOutput))
+        else {
+            return;
+        };
+        let nested = &path[fn_edge + 1..];
+        let depth = nested
+            .iter()
+            .filter(|seg| matches!(seg.edge, Edge::ObjectProp(_) | Edge::ArrayItem))
+            .count();
+        let max = if input {
+            &mut self.max_input_depth
+        } else {
+            &mut self.max_output_depth
+        };
+        if max.as_ref().is_none_or(|m| depth > m.depth) {
+            *max = Some(Depth {
+                function: function_path(&path[..fn_edge]),
+                depth,
+                path: Path(nested).to_string(),
+            });
+        }
+    }
+}
+
+impl<'a> TypeVisitor<'a> for Collector {
+    type Return = Self;
+    type Context = StatsContext<'a>;
+
+    fn visit(
+        &mut self,
+        current_node: CurrentNode<'_>,
+        _context: &Self::Context,
+    ) -> VisitResult<Self::Return> {
+        self.reached.insert(current_node.type_idx);
+        if let TypeNode::Function { data, .. } = current_node.type_node {
+            if let Some(PathSegment {
+                from,
+                edge: Edge::ObjectProp(key),
+            }) = current_node.path.last()
+            {
+                self.functions.insert(
+                    ParentFn {
+                        struct_idx: *from,
+                        fn_key: key.clone(),
+                    },
+                    FunctionInfo {
+                        path: function_path(current_node.path),
+                        materializer: data.materializer,
+                    },
+                );
+            }
+        }
+        self.update_depth(current_node.path, false);
+        VisitResult::Continue(true)
+    }
+
+    fn visit_input_type(
+        &mut self,
+        current_node: CurrentNode<'_>,
+        _context: &Self::Context,
+        parent_fn: ParentFn,
+    ) -> VisitResult<Self::Return> {
+        self.reached.insert(current_node.type_idx);
+        *self.input_sizes.entry(parent_fn).or_default() += 1;
+        self.update_depth(current_node.path, true);
+        VisitResult::Continue(true)
+    }
+
+    fn take_result(&mut self) -> Option<Self::Return> {
+        Some(std::mem::take(self))
+    }
+}
+
+/// Tarjan's strongly connected components, keeping the recursive ones
+fn find_cycles(tg: &Typegraph) -> Vec<Vec<String>> {
+    struct State<'a> {
+        tg: &'a Typegraph,
+        index: Vec<Option<usize>>,
+        lowlink: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<TypeId>,
+        next_index: usize,
+        cycles: Vec<Vec<String>>,
+    }
+
+    fn children(node: &TypeNode) -> Vec<TypeId> {
+        match node {
+            // functions are not part of the recursion of the data types
+            TypeNode::Function { .. } => vec![],
+            _ => node.children().unwrap_or_default(),
+        }
+    }
+
+    fn connect(state: &mut State, v: TypeId) {
+        let vi = v as usize;
+        state.index[vi] = Some(state.next_index);
+        state.lowlink[vi] = state.next_index;
+        state.next_index += 1;
+        state.stack.push(v);
+        state.on_stack[vi] = true;
+
+        let children = children(&state.tg.types[vi]);
+        for &w in children.iter() {
+            let wi = w as usize;
+            match state.index[wi] {
+                None => {
+                    connect(state, w);
+                    state.lowlink[vi] = state.lowlink[vi].min(state.lowlink[wi]);
+                }
+                Some(w_index) if state.on_stack[wi] => {
+                    state.lowlink[vi] = state.lowlink[vi].min(w_index);
+                }
+                _ => {}
+            }
+        }
+
+        if Some(state.lowlink[vi]) == state.index[vi] {
+            let mut component = vec![];
+            loop {
+                let w = state.stack.pop().unwrap();
+                state.on_stack[w as usize] = false;
+                component.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            if component.len() > 1 || children.contains(&v) {
+                component.reverse();
+                state.cycles.push(
+                    component
+                        .into_iter()
+                        .map(|idx| state.tg.types[idx as usize].base().title.clone())
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    let len = tg.types.len();
+    let mut state = State {
+        tg,
+        index: vec![None; len],
+        lowlink: vec![0; len],
+        on_stack: vec![false; len],
+        stack: vec![],
+        next_index: 0,
+        cycles: vec![],
+    };
+    for v in 0..len {
+        if state.index[v].is_none() {
+            connect(&mut state, v as TypeId);
+        }
+    }
+    state.cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::sample_typegraph;
+
+    #[test]
+    fn test_stats() {
+        let tg = sample_typegraph();
+        let mut stats = TypegraphStats::new(&tg);
+        stats.add_duplications(&tg, [(7, 2), (10, 0), (4, 1)]);
+        insta::assert_snapshot!(stats.to_string());
+    }
+
+    #[test]
+    fn test_cycles_and_unused() {
+        let mut value = serde_json::to_value(sample_typegraph()).unwrap();
+        let types = value["types"].as_array_mut().unwrap();
+        // Author.posts -> Post.author -> Author
+        types.push(json!({"type": "list", "title": "Post_list", "items": 7}));
+        let posts = types.len() - 1;
+        types[10]["properties"]["posts"] = json!(posts);
+        types.push(json!({"type": "boolean", "title": "orphan"}));
+        let materializers = value["materializers"].as_array_mut().unwrap();
+        let mut orphan = materializers[0].clone();
+        orphan["name"] = json!("orphan");
+        materializers.push(orphan);
+        let tg: Typegraph = serde_json::from_value(value).unwrap();
+
+        let stats = TypegraphStats::new(&tg);
+        assert_eq!(stats.cycles, vec![vec!["Post", "Author", "Post_list"]]);
+        assert_eq!(stats.unused_types, vec!["#23 orphan"]);
+        assert_eq!(stats.unused_materializers, vec!["#3 deno/orphan"]);
+    }
+}
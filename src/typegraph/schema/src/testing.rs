@@ -0,0 +1,9 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::Typegraph;
+
+/// Small typegraph with namespaces, injections, unions and REST endpoints
+pub fn sample_typegraph() -> Typegraph {
+    serde_json::from_str(include_str!("fixtures/sample.json")).unwrap()
+}
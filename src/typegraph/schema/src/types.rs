@@ -67,6 +67,43 @@ pub struct TypeNodeBase {
     pub description: Option<String>,
     #[serde(default, rename = "enum")]
     pub enumeration: Option<Vec<String>>, // JSON-serialized values
+    /// sample values; for functions, sample arguments
+    #[serde(default)]
+    pub examples: Option<Vec<serde_json::Value>>,
+    #[serde(default)]
+    pub deprecated: Option<Deprecation>,
+    /// deprecated enum values, by JSON-serialized value
+    #[serde(default)]
+    pub deprecated_values: Option<BTreeMap<String, Deprecation>>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Deprecation {
+    pub reason: Option<String>,
+    /// version in which the type was deprecated
+    pub since: Option<String>,
+    /// version in which the type is planned to be removed
+    pub removal: Option<String>,
+}
+
+impl Deprecation {
+    /// Human readable deprecation notice, for the generated code
+    pub fn message(&self) -> String {
+        let mut message = self
+            .reason
+            .clone()
+            .unwrap_or_else(|| "deprecated".to_string());
+        match (&self.since, &self.removal) {
+            (Some(since), Some(removal)) => {
+                message.push_str(&format!(" (since {since}, to be removed in {removal})"))
+            }
+            (Some(since), None) => message.push_str(&format!(" (since {since})")),
+            (None, Some(removal)) => message.push_str(&format!(" (to be removed in {removal})")),
+            (None, None) => {}
+        }
+        message
+    }
 }
 
 #[skip_serializing_none]
@@ -112,6 +149,23 @@ pub enum StringFormat {
     Date,
     DateTime,
     Phone,
+    Ipv4,
+    Ipv6,
+    /// IPv4 or IPv6 address range
+    Cidr,
+    Time,
+    /// ISO 8601 duration
+    Duration,
+    Iban,
+    Semver,
+    Slug,
+    /// ISO 3166-1 alpha-2
+    CountryCode,
+    /// ISO 4217
+    CurrencyCode,
+    /// custom format registered in the typegraph meta (`TypeMeta::string_formats`)
+    #[serde(untagged)]
+    Custom(String),
 }
 
 impl core::fmt::Display for StringFormat {
@@ -127,6 +181,17 @@ impl core::fmt::Display for StringFormat {
             Date => write!(f, "date"),
             DateTime => write!(f, "date_time"),
             Phone => write!(f, "phone"),
+            Ipv4 => write!(f, "ipv4"),
+            Ipv6 => write!(f, "ipv6"),
+            Cidr => write!(f, "cidr"),
+            Time => write!(f, "time"),
+            Duration => write!(f, "duration"),
+            Iban => write!(f, "iban"),
+            Semver => write!(f, "semver"),
+            Slug => write!(f, "slug"),
+            CountryCode => write!(f, "country_code"),
+            CurrencyCode => write!(f, "currency_code"),
+            Custom(name) => write!(f, "{name}"),
         }
     }
 }
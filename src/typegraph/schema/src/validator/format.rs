@@ -0,0 +1,340 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! String format validators matching the format validators of the typegate
+//! (see `src/typegate/src/engine/typecheck/common.ts`).
+//!
+//! The typegate relies on validator.js for most formats. The formats whose
+//! validator.js implementation has no exact equivalent here (`email`, `uri`,
+//! `hostname` and `phone`) are checked against their basic grammar, leaving
+//! the stricter rules to the typegate; `date-time` must be RFC 3339.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use chrono::{DateTime, NaiveDate};
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::value::matches_pattern;
+use crate::StringFormat;
+
+/// any version, as `validate` from `@std/uuid`
+static UUID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$")
+        .unwrap()
+});
+
+static TIME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([01][0-9]|2[0-3]):[0-5][0-9](:([0-5][0-9]|60)(\.[0-9]+)?)?(Z|[+-]([01][0-9]|2[0-3]):[0-5][0-9])?$")
+        .unwrap()
+});
+
+static DURATION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^P([0-9]+W|([0-9]+Y)?([0-9]+M)?([0-9]+D)?(T([0-9]+H)?([0-9]+M)?([0-9]+(\.[0-9]+)?S)?)?)$",
+    )
+    .unwrap()
+});
+
+static IBAN: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Z]{2}[0-9]{2}[A-Z0-9]{11,30}$").unwrap());
+
+/// from https://semver.org
+static SEMVER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(?:-((?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?(?:\+([0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?$",
+    )
+    .unwrap()
+});
+
+/// dot-atom local part (RFC 5322), without quoted strings and comments
+static EMAIL_LOCAL_PART: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]+(\.[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]+)*$").unwrap()
+});
+
+/// scheme and percent-encoded characters (RFC 3986)
+static URI: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*:([A-Za-z0-9\-._~:/?#\[\]@!$&'()*+,;=]|%[0-9A-Fa-f]{2})*$")
+        .unwrap()
+});
+
+static HOSTNAME_LABEL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?$").unwrap());
+
+/// digits with an optional international prefix and separators
+static PHONE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\+?[0-9(]([0-9 ().-]*[0-9])?$").unwrap());
+
+static SLUG: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9]+(?:-[a-z0-9]+)*$").unwrap());
+
+/// ISO 3166-1 alpha-2 officially assigned codes
+const COUNTRY_CODES: &str = "\
+AD AE AF AG AI AL AM AO AQ AR AS AT AU AW AX AZ BA BB BD BE BF BG BH BI BJ BL BM BN BO BQ BR BS \
+BT BV BW BY BZ CA CC CD CF CG CH CI CK CL CM CN CO CR CU CV CW CX CY CZ DE DJ DK DM DO DZ EC EE \
+EG EH ER ES ET FI FJ FK FM FO FR GA GB GD GE GF GG GH GI GL GM GN GP GQ GR GS GT GU GW GY HK HM \
+HN HR HT HU ID IE IL IM IN IO IQ IR IS IT JE JM JO JP KE KG KH KI KM KN KP KR KW KY KZ LA LB LC \
+LI LK LR LS LT LU LV LY MA MC MD ME MF MG MH MK ML MM MN MO MP MQ MR MS MT MU MV MW MX MY MZ NA \
+NC NE NF NG NI NL NO NP NR NU NZ OM PA PE PF PG PH PK PL PM PN PR PS PT PW PY QA RE RO RS RU RW \
+SA SB SC SD SE SG SH SI SJ SK SL SM SN SO SR SS ST SV SX SY SZ TC TD TF TG TH TJ TK TL TM TN TO \
+TR TT TV TW TZ UA UG UM US UY UZ VA VC VE VG VI VN VU WF WS YE YT ZA ZM ZW";
+
+/// ISO 4217 active codes
+const CURRENCY_CODES: &str = "\
+AED AFN ALL AMD ANG AOA ARS AUD AWG AZN BAM BBD BDT BGN BHD BIF BMD BND BOB BOV BRL BSD BTN BWP \
+BYN BZD CAD CDF CHE CHF CHW CLF CLP CNY COP COU CRC CUC CUP CVE CZK DJF DKK DOP DZD EGP ERN ETB \
+EUR FJD FKP GBP GEL GHS GIP GMD GNF GTQ GYD HKD HNL HTG HUF IDR ILS INR IQD IRR ISK JMD JOD JPY \
+KES KGS KHR KMF KPW KRW KWD KYD KZT LAK LBP LKR LRD LSL LYD MAD MDL MGA MKD MMK MNT MOP MRU MUR \
+MVR MWK MXN MXV MYR MZN NAD NGN NIO NOK NPR NZD OMR PAB PEN PGK PHP PKR PLN PYG QAR RON RSD RUB \
+RWF SAR SBD SCR SDG SEK SGD SHP SLE SLL SOS SRD SSP STN SVC SYP SZL THB TJS TMT TND TOP TRY TTD \
+TWD TZS UAH UGX USD USN UYI UYU UYW UZS VED VES VND VUV WST XAF XAG XAU XBA XBB XBC XBD XCD XCG \
+XDR XOF XPD XPF XPT XSU XTS XUA XXX YER ZAR ZMW ZWG ZWL";
+
+/// Custom formats are resolved from `custom_formats` (see `TypeMeta::string_formats`);
+/// like the `pattern` constraint, their JavaScript patterns are only checked
+/// here when supported by the `regex` crate.
+pub fn is_valid(
+    format: &StringFormat,
+    value: &str,
+    custom_formats: &IndexMap<String, String>,
+) -> bool {
+    match format {
+        StringFormat::Email => is_email(value),
+        StringFormat::Uri => URI.is_match(value),
+        StringFormat::Hostname => is_hostname(value),
+        StringFormat::Phone => is_phone(value),
+        StringFormat::DateTime => DateTime::parse_from_rfc3339(value).is_ok(),
+        StringFormat::Uuid => UUID.is_match(value),
+        StringFormat::Json => serde_json::from_str::<serde_json::Value>(value).is_ok(),
+        StringFormat::Ean => is_ean(value),
+        StringFormat::Date => is_date(value),
+        StringFormat::Ipv4 => value.parse::<Ipv4Addr>().is_ok(),
+        StringFormat::Ipv6 => is_ipv6(value),
+        StringFormat::Cidr => is_cidr(value),
+        StringFormat::Time => TIME.is_match(value),
+        StringFormat::Duration => is_duration(value),
+        StringFormat::Iban => is_iban(value),
+        StringFormat::Semver => SEMVER.is_match(value),
+        StringFormat::Slug => SLUG.is_match(value),
+        StringFormat::CountryCode => is_code(COUNTRY_CODES, 2, value),
+        StringFormat::CurrencyCode => is_code(CURRENCY_CODES, 3, value),
+        StringFormat::Custom(name) => custom_formats
+            .get(name)
+            .is_some_and(|pattern| matches_pattern(pattern, value) != Some(false)),
+    }
+}
+
+/// `local@domain`, the domain having a top-level domain
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return false;
+    };
+    local.len() <= 64
+        && EMAIL_LOCAL_PART.is_match(local)
+        && domain.contains('.')
+        && is_hostname(domain)
+}
+
+/// RFC 1123
+fn is_hostname(value: &str) -> bool {
+    value.len() <= 253 && value.split('.').all(|label| HOSTNAME_LABEL.is_match(label))
+}
+
+/// E.164: at most 15 digits
+fn is_phone(value: &str) -> bool {
+    let digits = value.bytes().filter(|b| b.is_ascii_digit()).count();
+    PHONE.is_match(value) && (7..=15).contains(&digits)
+}
+
+/// with an optional zone index, e.g. `fe80::1%eth0`
+fn is_ipv6(value: &str) -> bool {
+    let addr = match value.split_once('%') {
+        Some((addr, zone)) if !zone.is_empty() => addr,
+        Some(_) => return false,
+        None => value,
+    };
+    addr.parse::<Ipv6Addr>().is_ok()
+}
+
+/// IP address with a prefix length, e.g. `10.0.0.0/8`
+fn is_cidr(value: &str) -> bool {
+    let Some((addr, prefix)) = value.split_once('/') else {
+        return false;
+    };
+    if prefix.is_empty()
+        || prefix.len() > 3
+        || (prefix.len() > 1 && prefix.starts_with('0'))
+        || !prefix.bytes().all(|b| b.is_ascii_digit())
+    {
+        return false;
+    }
+    let Ok(prefix) = prefix.parse::<u8>() else {
+        return false;
+    };
+    match addr.parse::<IpAddr>() {
+        Ok(IpAddr::V4(_)) => prefix <= 32,
+        Ok(IpAddr::V6(_)) => prefix <= 128,
+        Err(_) => false,
+    }
+}
+
+/// at least one component is required, and the time designator must be followed by one
+fn is_duration(value: &str) -> bool {
+    DURATION.is_match(value) && value != "P" && !value.ends_with('T')
+}
+
+/// with the ISO 7064 mod 97-10 checksum; spaces and hyphens are allowed between the groups
+fn is_iban(value: &str) -> bool {
+    let value = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_ascii_uppercase();
+    if !IBAN.is_match(&value) {
+        return false;
+    }
+    let (head, tail) = value.split_at(4);
+    let remainder = tail.chars().chain(head.chars()).fold(0u32, |acc, c| {
+        let digit = c.to_digit(36).unwrap();
+        if digit < 10 {
+            (acc * 10 + digit) % 97
+        } else {
+            (acc * 100 + digit) % 97
+        }
+    });
+    remainder == 1
+}
+
+/// case insensitive
+fn is_code(codes: &str, len: usize, value: &str) -> bool {
+    value.len() == len
+        && codes
+            .split_ascii_whitespace()
+            .any(|code| code.eq_ignore_ascii_case(value))
+}
+
+/// EAN-8, EAN-13 and EAN-14 with check digit
+fn is_ean(value: &str) -> bool {
+    if !matches!(value.len(), 8 | 13 | 14) || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let digits = value.bytes().map(|b| (b - b'0') as u32).collect::<Vec<_>>();
+    let (check, payload) = digits.split_last().unwrap();
+    let sum: u32 = payload
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { d * 3 } else { *d })
+        .sum();
+    (10 - sum % 10) % 10 == *check
+}
+
+fn is_date(value: &str) -> bool {
+    ["%Y-%m-%d", "%Y/%m/%d"]
+        .iter()
+        .any(|fmt| NaiveDate::parse_from_str(value, fmt).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats() {
+        let cases = [
+            (
+                StringFormat::Uuid,
+                "0c8c3dfc-4ac6-4a29-b6a4-5b6e1c6b1f3e",
+                true,
+            ),
+            (StringFormat::Uuid, "0c8c3dfc-4ac6", false),
+            (
+                StringFormat::Uuid,
+                "0c8c3dfc4ac64a29b6a45b6e1c6b1f3e",
+                false,
+            ),
+            (StringFormat::Json, r#"{"a": [1, 2]}"#, true),
+            (StringFormat::Json, "{a: 1}", false),
+            (StringFormat::Email, "john.doe@metatype.dev", true),
+            (StringFormat::Email, "john.doe@", false),
+            (StringFormat::Email, "john..doe@metatype.dev", false),
+            (StringFormat::Email, "john.doe@localhost", false),
+            (StringFormat::Uri, "https://metatype.dev/docs?q=1#top", true),
+            (StringFormat::Uri, "urn:isbn:0451450523", true),
+            (StringFormat::Uri, "metatype.dev", false),
+            (StringFormat::Uri, "https://metatype.dev/a b", false),
+            (StringFormat::Uri, "https://metatype.dev/%zz", false),
+            (StringFormat::Hostname, "localhost", true),
+            (StringFormat::Hostname, "api.metatype.dev", true),
+            (StringFormat::Hostname, "-api.metatype.dev", false),
+            (StringFormat::Hostname, "api..metatype.dev", false),
+            (StringFormat::Phone, "+33 6 12 34 56 78", true),
+            (StringFormat::Phone, "(555) 123-4567", true),
+            (StringFormat::Phone, "12-34", false),
+            (StringFormat::Phone, "+33 6 12 34 56 7a", false),
+            (StringFormat::DateTime, "2024-01-15T10:30:00Z", true),
+            (
+                StringFormat::DateTime,
+                "2024-01-15T10:30:00.123+02:00",
+                true,
+            ),
+            (StringFormat::DateTime, "2024-01", false),
+            (StringFormat::DateTime, "2024-01-15 10:30", false),
+            (StringFormat::DateTime, "2024-02-30T10:30:00Z", false),
+            (StringFormat::Ean, "4006381333931", true),
+            (StringFormat::Ean, "4006381333932", false),
+            (StringFormat::Ean, "96385074", true),
+            (StringFormat::Date, "2024-02-29", true),
+            (StringFormat::Date, "2023-02-29", false),
+            (StringFormat::Ipv4, "192.168.1.1", true),
+            (StringFormat::Ipv4, "256.1.1.1", false),
+            (StringFormat::Ipv6, "2001:db8::ff00:42:8329", true),
+            (StringFormat::Ipv6, "fe80::1%eth0", true),
+            (StringFormat::Ipv6, "192.168.1.1", false),
+            (StringFormat::Cidr, "10.0.0.0/8", true),
+            (StringFormat::Cidr, "2001:db8::/32", true),
+            (StringFormat::Cidr, "10.0.0.0/33", false),
+            (StringFormat::Cidr, "10.0.0.0/08", false),
+            (StringFormat::Cidr, "10.0.0.0", false),
+            (StringFormat::Time, "12:30", true),
+            (StringFormat::Time, "23:59:59.999+02:00", true),
+            (StringFormat::Time, "24:00", false),
+            (StringFormat::Duration, "P1Y2M10DT2H30M", true),
+            (StringFormat::Duration, "PT0.5S", true),
+            (StringFormat::Duration, "P3W", true),
+            (StringFormat::Duration, "P", false),
+            (StringFormat::Duration, "P1DT", false),
+            (StringFormat::Iban, "GB82 WEST 1234 5698 7654 32", true),
+            (StringFormat::Iban, "gb82-west-1234-5698-7654-32", true),
+            (StringFormat::Iban, "GB82WEST12345698765433", false),
+            (StringFormat::Semver, "1.2.3-rc.1+build.5", true),
+            (StringFormat::Semver, "1.02.3", false),
+            (StringFormat::Slug, "hello-world-2", true),
+            (StringFormat::Slug, "Hello--world", false),
+            (StringFormat::CountryCode, "FR", true),
+            (StringFormat::CountryCode, "ZZ", false),
+            (StringFormat::CurrencyCode, "EUR", true),
+            (StringFormat::CountryCode, "fr", true),
+            (StringFormat::CurrencyCode, "eur", true),
+            (StringFormat::CurrencyCode, "EURO", false),
+            (StringFormat::Custom("zip".to_string()), "75001", true),
+            (StringFormat::Custom("zip".to_string()), "7500A", false),
+            (StringFormat::Custom("unknown".to_string()), "75001", false),
+            // lookaheads are left to the typegate
+            (
+                StringFormat::Custom("password".to_string()),
+                "password",
+                true,
+            ),
+        ];
+        let custom_formats = IndexMap::from([
+            ("zip".to_string(), r"^[0-9]{5}$".to_string()),
+            ("password".to_string(), r"^(?=.*[0-9]).{8,}$".to_string()),
+        ]);
+        for (format, value, expected) in cases {
+            assert_eq!(
+                is_valid(&format, value, &custom_formats),
+                expected,
+                "format {format}: {value:?}"
+            );
+        }
+    }
+}
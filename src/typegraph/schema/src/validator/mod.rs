@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MPL-2.0
 
 mod common;
+mod format;
 mod injection;
 mod input;
 mod types;
@@ -13,7 +14,7 @@ use super::visitor::{
     visit_child, ChildNode, CurrentNode, ParentFn, Path, PathSegment, TypeVisitor,
     TypeVisitorContext, VisitLayer, VisitResult, VisitorResult,
 };
-use crate::{TypeNode, Typegraph};
+use crate::{StringFormat, TypeNode, Typegraph};
 use std::collections::{hash_map, HashMap};
 
 use self::types::{EnsureSubtypeOf, ErrorCollector, ExtendedTypeNode};
@@ -226,6 +227,19 @@ impl<'a> TypeVisitor<'a> for Validator {
                     }
                 }
             }
+        } else if let TypeNode::String { data, .. } = type_node {
+            // any unknown format name deserializes as a custom format:
+            // misspelled built-in formats are reported here
+            if let Some(StringFormat::Custom(name)) = &data.format {
+                if !tg.meta.string_formats.contains_key(name) {
+                    self.push_error(
+                        current_node.path,
+                        format!(
+                            "Unknown string format {name:?}: not a built-in format, nor registered on the typegraph"
+                        ),
+                    );
+                }
+            }
         } else if let TypeNode::Union { data, .. } = type_node {
             let variants = data.any_of.clone();
 
@@ -275,3 +289,33 @@ impl<'a> TypeVisitor<'a> for Validator {
         Some(std::mem::take(&mut self.errors))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::sample_typegraph;
+
+    fn with_format(format: &str) -> Typegraph {
+        let mut tg = sample_typegraph();
+        tg.meta
+            .string_formats
+            .insert("postal-code".to_string(), r"^[0-9]{5}$".to_string());
+        if let TypeNode::String { data, .. } = &mut tg.types[14] {
+            data.format = Some(serde_json::from_value(format.into()).unwrap());
+        }
+        tg
+    }
+
+    #[test]
+    fn test_string_formats() {
+        assert!(validate_typegraph(&with_format("email")).is_empty());
+        assert!(validate_typegraph(&with_format("postal-code")).is_empty());
+
+        let errors = validate_typegraph(&with_format("emial"));
+        // reported wherever the type is reachable from
+        assert!(!errors.is_empty());
+        assert!(errors
+            .iter()
+            .all(|e| e.message.starts_with(r#"Unknown string format "emial""#)));
+    }
+}
@@ -1,9 +1,12 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
 
 use anyhow::{anyhow, bail, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde_json::Value;
 
 use crate::{
@@ -15,6 +18,22 @@ fn to_string(value: &Value) -> String {
     serde_json::to_string(value).unwrap()
 }
 
+static PATTERNS: Lazy<Mutex<HashMap<String, Option<Regex>>>> = Lazy::new(Default::default);
+
+/// Whether `value` matches the pattern, or `None` if the pattern is not
+/// supported by the `regex` crate.
+///
+/// Patterns are JavaScript regular expressions evaluated by the typegate; the
+/// ones relying on features like lookarounds or backreferences are left to
+/// the typegate.
+pub(super) fn matches_pattern(pattern: &str, value: &str) -> Option<bool> {
+    let mut patterns = PATTERNS.lock().unwrap();
+    let re = patterns
+        .entry(pattern.to_string())
+        .or_insert_with(|| Regex::new(pattern).ok());
+    re.as_ref().map(|re| re.is_match(value))
+}
+
 // TODO validation path
 impl Typegraph {
     pub fn validate_value(&self, type_idx: u32, value: &Value) -> Result<()> {
@@ -119,7 +138,16 @@ impl Typegraph {
                 );
             }
         }
-        // TODO pattern, format
+        if let Some(pattern) = data.pattern.as_ref() {
+            if matches_pattern(pattern, s) == Some(false) {
+                bail!("Expected to match pattern {pattern:?}, got {s:?}");
+            }
+        }
+        if let Some(format) = data.format.as_ref() {
+            if !super::format::is_valid(format, s, &self.meta.string_formats) {
+                bail!("Expected to match format {format}, got {s:?}");
+            }
+        }
         Ok(())
     }
 
@@ -266,3 +294,16 @@ mod number_validator {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(matches_pattern(r"^[0-9]{5}$", "75001"), Some(true));
+        assert_eq!(matches_pattern(r"^[0-9]{5}$", "7500A"), Some(false));
+        // lookaheads are only supported by the typegate
+        assert_eq!(matches_pattern(r"^(?=.*[0-9]).{8,}$", "password"), None);
+    }
+}
@@ -29,6 +29,13 @@ type Rate = {
   local_excess: UInt;
 };
 
+type QueryLimits = {
+  max_depth?: UInt;
+  max_aliases?: UInt;
+  max_cost?: UInt;
+  list_size?: UInt;
+};
+
 type TypegraphInitParams = {
   name: string;
   dynamic?: boolean;
@@ -36,6 +43,7 @@ type TypegraphInitParams = {
   prefix?: string;
   cors: Cors;
   rate?: Rate;
+  limits?: QueryLimits;
 };
 
 type Artifact = {
@@ -56,6 +64,19 @@ type PrismaMigrationConfig = {
   default_migration_action: MigrationAction;
 };
 
+type NameCase = "preserve" | "pascal" | "snake";
+
+type CollisionPolicy = "hash" | "counter" | "error";
+
+type NamingStrategy = {
+  case?: NameCase;
+  prefix?: string;
+  suffix?: string;
+  rename_user_named?: boolean;
+  collision?: CollisionPolicy;
+  stable_variants?: boolean;
+};
+
 type SerializeParams = {
   typegraph_name: string;
   typegraph_path: string;
@@ -64,6 +85,7 @@ type SerializeParams = {
   codegen: boolean;
   prisma_migration: PrismaMigrationConfig;
   pretty: boolean;
+  naming_strategy?: NamingStrategy;
 };
 
 type TypeProxy = {
@@ -235,6 +257,11 @@ type register_context_policy = (
   check: ContextCheck,
 ) => [PolicyId, string];
 
+type register_context_rule_policy = (
+  name: string,
+  rule: string,
+) => [PolicyId, string];
+
 type rename_type = (tpe: TypeId, new_name: string) => TypeId;
 
 type expose = (fns: [string, TypeId][], default_policy?: PolicySpec[]) => void;
@@ -245,6 +272,7 @@ export type {
   Artifact,
   as_id,
   booleanb,
+  CollisionPolicy,
   ContextCheck,
   Cors,
   eitherb,
@@ -264,6 +292,8 @@ export type {
   listb,
   MaterializerId,
   MigrationAction,
+  NameCase,
+  NamingStrategy,
   optionalb,
   ParameterTransform,
   Policy,
@@ -271,9 +301,11 @@ export type {
   PolicyPerEffect,
   PolicySpec,
   PrismaMigrationConfig,
+  QueryLimits,
   Rate,
   refb,
   register_context_policy,
+  register_context_rule_policy,
   register_policy,
   rename_type,
   RuntimeId,
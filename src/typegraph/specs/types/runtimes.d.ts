@@ -114,9 +114,17 @@ type MaterializerWasmWireHandler = {
   func_name: string;
 };
 
+type PrismaPoolConfig = {
+  size?: UInt;
+  connect_timeout_sec?: UInt;
+  socket_timeout_sec?: UInt;
+};
+
 type PrismaRuntimeData = {
   name: string;
   connection_string_secret: string;
+  pool?: PrismaPoolConfig;
+  read_replica_secrets?: string[];
 };
 
 type PrismaLinkData = {
@@ -132,7 +140,10 @@ type PrismaMigrationOperation =
   | "create"
   | "apply"
   | "deploy"
-  | "reset";
+  | "reset"
+  | "squash"
+  | "baseline"
+  | "introspect";
 
 type TemporalRuntimeData = {
   name: string;
@@ -167,6 +178,7 @@ type TypegateOperation =
   | "raw_prisma_update"
   | "raw_prisma_delete"
   | "query_prisma_model"
+  | "prisma_metrics"
   | "ping";
 
 type TypegraphOperation = "resolver" | "get_type" | "get_schema";
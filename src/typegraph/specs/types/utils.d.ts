@@ -50,6 +50,8 @@ type reduceb = (super_type_id: TypeId, entries: ReduceEntry[]) => TypeId;
 
 type add_graphql_endpoint = (graphql: string) => UInt;
 
+type add_string_format = (name: string, pattern: string) => void;
+
 type add_auth = (data: Auth) => UInt;
 
 type add_raw_auth = (data: string) => UInt;
@@ -82,6 +84,7 @@ export type {
   add_auth,
   add_graphql_endpoint,
   add_raw_auth,
+  add_string_format,
   Auth,
   AuthProtocol,
   FdkConfig,
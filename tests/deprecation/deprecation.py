@@ -0,0 +1,41 @@
+# Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+# SPDX-License-Identifier: MPL-2.0
+
+from typegraph import typegraph, Policy, t, Graph
+from typegraph.runtimes.deno import DenoRuntime
+
+
+@typegraph()
+def deprecation(g: Graph):
+    deno = DenoRuntime()
+    public = Policy.public()
+
+    user = t.struct(
+        {
+            "name": t.string(),
+            "nickname": t.string(
+                config={
+                    "deprecated": {"reason": "use name", "since": "1.2", "removal": "2.0"}
+                }
+            ).optional(),
+            "role": t.string(
+                enum=["admin", "user", "guest"],
+                config={"deprecated_values": {"guest": {"reason": "use user"}}},
+            ),
+        },
+        name="User",
+    )
+
+    g.expose(
+        public,
+        user=deno.func(
+            t.struct({}),
+            user,
+            code="() => ({ name: 'Alice', nickname: 'al', role: 'guest' })",
+        ),
+        getUser=deno.func(
+            t.struct({}),
+            user,
+            code="() => ({ name: 'Alice', nickname: 'al', role: 'guest' })",
+        ).deprecated("use user", since="1.1"),
+    )
@@ -0,0 +1,75 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+import { gql, Meta } from "../utils/mod.ts";
+
+Meta.test("Deprecation", async (t) => {
+  const e = await t.engine("deprecation/deprecation.py");
+
+  await t.should("report the deprecated functions", async () => {
+    await gql`
+      query {
+        __type(name: "Query") {
+          fields(includeDeprecated: true) {
+            name
+            isDeprecated
+            deprecationReason
+          }
+        }
+      }
+    `
+      .expectData({
+        __type: {
+          fields: [
+            { name: "user", isDeprecated: false, deprecationReason: null },
+            {
+              name: "getUser",
+              isDeprecated: true,
+              deprecationReason: "use user (since 1.1)",
+            },
+          ],
+        },
+      })
+      .on(e);
+  });
+
+  await t.should("report the deprecated fields", async () => {
+    await gql`
+      query {
+        __type(name: "User") {
+          fields(includeDeprecated: true) {
+            name
+            isDeprecated
+            deprecationReason
+          }
+        }
+      }
+    `
+      .expectData({
+        __type: {
+          fields: [
+            { name: "name", isDeprecated: false, deprecationReason: null },
+            {
+              name: "nickname",
+              isDeprecated: true,
+              deprecationReason: "use name (since 1.2, to be removed in 2.0)",
+            },
+            { name: "role", isDeprecated: false, deprecationReason: null },
+          ],
+        },
+      })
+      .on(e);
+  });
+
+  await t.should("still resolve the deprecated fields", async () => {
+    await gql`
+      query {
+        getUser { nickname role }
+      }
+    `
+      .expectData({
+        getUser: { nickname: "al", role: "guest" },
+      })
+      .on(e);
+  });
+});
@@ -0,0 +1,24 @@
+# Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+# SPDX-License-Identifier: MPL-2.0
+
+from typegraph import typegraph, Policy, t, Graph
+from typegraph.graph.params import Rate
+from typegraph.runtimes.graphql import GraphQLRuntime
+
+
+@typegraph(
+    rate=Rate(window_limit=1000, window_sec=60, query_limit=2, local_excess=0),
+)
+def graphql_rate_limited(g: Graph):
+    gql = GraphQLRuntime("https://example.com/api/graphql")
+
+    next_int = (
+        gql.query(
+            t.struct({}),
+            t.integer(),
+        )
+        .rate(calls=True, weight=1)
+        .with_policy(Policy.public())
+    )
+
+    g.expose(nextInt=next_int)
@@ -1,13 +1,14 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
-import { gql, Meta } from "../utils/mod.ts";
+import { execute, gql, Meta } from "../utils/mod.ts";
 import * as mf from "test/mock_fetch";
 import { buildSchema, graphql } from "graphql";
 import { withInlinedVars } from "@metatype/typegate/runtimes/utils/graphql_inline_vars.ts";
-import { assertEquals } from "@std/assert";
+import { assert, assertEquals } from "@std/assert";
 import outdent from "outdent";
 import { assertNotEquals } from "@std/assert/not-equals";
+import { encodeHex } from "@std/encoding/hex";
 import { clearSyncData, setupSync } from "test-utils/hooks.ts";
 
 const syncConfig = {
@@ -249,6 +250,250 @@ Meta.test("GraphQL request idempotency", async (t) => {
   );
 });
 
+Meta.test("GraphQL persisted queries", async (t) => {
+  const e = await t.engine("graphql/graphql.py");
+  const query = "query { user(id: 1) { id name } }";
+  const sha256Hash = encodeHex(
+    await crypto.subtle.digest("SHA-256", new TextEncoder().encode(query)),
+  );
+  const extensions = { persistedQuery: { version: 1, sha256Hash } };
+  const send = async (operations: Record<string, unknown>) => {
+    const res = await execute(
+      e,
+      new Request(`http://typegate.local/${e.name}`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ variables: {}, ...operations }),
+      }),
+    );
+    return { status: res.status, body: await res.json() };
+  };
+
+  await t.should("ask for the document of unknown hashes", async () => {
+    const { status, body } = await send({ extensions });
+    assertEquals(status, 400);
+    assertEquals(body.errors[0].message, "PersistedQueryNotFound");
+  });
+
+  await t.should("register the document with its hash", async () => {
+    const { status, body } = await send({ query, extensions });
+    assertEquals(status, 200);
+    assertEquals(body, { data: { user: { id: 1, name: "User 1" } } });
+  });
+
+  await t.should("run the document of known hashes", async () => {
+    const { status, body } = await send({ extensions });
+    assertEquals(status, 200);
+    assertEquals(body, { data: { user: { id: 1, name: "User 1" } } });
+  });
+
+  await t.should("reject a hash not matching the document", async () => {
+    const { status, body } = await send({
+      query: "query { nextInt }",
+      extensions,
+    });
+    assertEquals(status, 400);
+    assertEquals(
+      body.errors[0].message,
+      "provided sha256Hash does not match query",
+    );
+  });
+});
+
+async function* readEvents(
+  body: ReadableStream<Uint8Array>,
+): AsyncGenerator<{ event: string; data: string }> {
+  const reader = body.pipeThrough(new TextDecoderStream()).getReader();
+  let buffer = "";
+  try {
+    while (true) {
+      const { value, done } = await reader.read();
+      if (done) {
+        return;
+      }
+      buffer += value;
+      let end;
+      while ((end = buffer.indexOf("\n\n")) >= 0) {
+        const fields = Object.fromEntries(
+          buffer.slice(0, end).split("\n").map((line) => {
+            const sep = line.indexOf(": ");
+            return sep < 0 ? [line.replace(/:$/, ""), ""] : [
+              line.slice(0, sep),
+              line.slice(sep + 2),
+            ];
+          }),
+        );
+        buffer = buffer.slice(end + 2);
+        yield { event: fields.event, data: fields.data };
+      }
+    }
+  } finally {
+    await reader.cancel();
+  }
+}
+
+Meta.test("GraphQL subscriptions", async (t) => {
+  const e = await t.engine("graphql/graphql.py");
+  const stream = async (query: ReturnType<typeof gql>) => {
+    const request = await query
+      .withHeaders({ "Accept": "text/event-stream" })
+      .getRequest(`http://typegate.local/${e.name}`, e.tg.typegate.cryptoKeys);
+    return await execute(e, request);
+  };
+
+  await t.should("push the results as they change", async () => {
+    const res = await stream(gql`subscription Counter { nextInt }`);
+    assertEquals(res.status, 200);
+    assertEquals(res.headers.get("content-type"), "text/event-stream");
+
+    const values: number[] = [];
+    for await (const { event, data } of readEvents(res.body!)) {
+      assertEquals(event, "next");
+      values.push(JSON.parse(data).data.nextInt);
+      if (values.length == 3) {
+        break;
+      }
+    }
+    // the counter is incremented on each poll
+    assertEquals(values, [values[0], values[0] + 1, values[0] + 2]);
+  });
+
+  await t.should("push a single result for queries", async () => {
+    const res = await stream(gql`query { user(id: 1) { id } }`);
+    const events = [];
+    for await (const event of readEvents(res.body!)) {
+      events.push(event);
+    }
+    assertEquals(events, [
+      { event: "next", data: JSON.stringify({ data: { user: { id: 1 } } }) },
+      { event: "complete", data: "" },
+    ]);
+  });
+
+  await t.should("require an event stream", async () => {
+    await gql`subscription { nextInt }`
+      .expectErrorContains("text/event-stream")
+      .on(e);
+  });
+
+  await t.should("only select query fields", async () => {
+    const res = await stream(
+      gql`subscription { updateUser(id: 1, patch: { name: "John" }) { id } }`,
+    );
+    assert(!res.ok);
+    assertEquals(res.headers.get("content-type"), "application/json");
+    await res.body?.cancel();
+  });
+
+});
+
+Meta.test({
+  name: "GraphQL subscriptions introspection",
+  introspection: true,
+}, async (t) => {
+  const e = await t.engine("graphql/graphql.py");
+  const config = e.tg.typegate.config.base;
+  const query = gql`
+    query {
+      __schema {
+        subscriptionType {
+          name
+          fields { name }
+        }
+      }
+    }
+  `;
+
+  await t.should("not list subscriptions by default", async () => {
+    await query
+      .expectData({ __schema: { subscriptionType: null } })
+      .on(e);
+  });
+
+  await t.should("list the query fields when enabled", async () => {
+    config.subscription_introspection = true;
+    try {
+      await query
+        .expectBody((body) => {
+          const { name, fields } = body.data.__schema.subscriptionType;
+          assertEquals(name, "Subscription");
+          assertEquals(
+            fields.map((field: { name: string }) => field.name).sort(),
+            ["nextInt", "user"],
+          );
+        })
+        .on(e);
+    } finally {
+      config.subscription_introspection = false;
+    }
+  });
+});
+
+Meta.test("GraphQL subscriptions on a rate-limited typegraph", async (t) => {
+  const e = await t.engine("graphql/graphql_rate_limited.py");
+  const config = e.tg.typegate.config.base;
+  const stream = async (query: ReturnType<typeof gql>) => {
+    const request = await query
+      .withHeaders({ "Accept": "text/event-stream" })
+      .getRequest(`http://typegate.local/${e.name}`, e.tg.typegate.cryptoKeys);
+    return await execute(e, request);
+  };
+
+  await t.should("charge the subscription once", async () => {
+    const res = await stream(gql`subscription { nextInt }`);
+    assertEquals(res.status, 200);
+
+    const values: number[] = [];
+    for await (const { event, data } of readEvents(res.body!)) {
+      assertEquals(event, "next");
+      const { data: result, errors } = JSON.parse(data);
+      assertEquals(errors, undefined);
+      values.push(result.nextInt);
+      if (values.length == 3) {
+        break;
+      }
+    }
+    assertEquals(values, [values[0], values[0] + 1, values[0] + 2]);
+  });
+
+  await t.should("bound the open subscriptions per client", async () => {
+    const maxPerClient = config.subscription_max_per_client;
+    config.subscription_max_per_client = 1;
+    try {
+      const first = await stream(gql`subscription { nextInt }`);
+      assertEquals(first.status, 200);
+
+      const second = await stream(gql`subscription { nextInt }`);
+      assertEquals(second.status, 429);
+      const body = await second.json();
+      assert(body.errors[0].message.includes("too many open subscriptions"));
+
+      // closing the first subscription releases its slot
+      await first.body!.cancel();
+      const third = await stream(gql`subscription { nextInt }`);
+      assertEquals(third.status, 200);
+      await third.body!.cancel();
+    } finally {
+      config.subscription_max_per_client = maxPerClient;
+    }
+  });
+
+  await t.should("complete the subscriptions after their lifetime", async () => {
+    const maxLifetime = config.subscription_max_lifetime_sec;
+    config.subscription_max_lifetime_sec = 1.5;
+    try {
+      const res = await stream(gql`subscription { nextInt }`);
+      const events = [];
+      for await (const { event } of readEvents(res.body!)) {
+        events.push(event);
+      }
+      assertEquals(events.at(-1), "complete");
+    } finally {
+      config.subscription_max_lifetime_sec = maxLifetime;
+    }
+  });
+});
+
 Meta.test(
   {
     name: "Idempotency Key logic in sync mode",
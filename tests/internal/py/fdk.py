@@ -12,6 +12,7 @@ import urllib
 import urllib.error
 import urllib.request as request
 import uuid
+import warnings
 from abc import ABC, abstractmethod
 
 
@@ -996,6 +997,9 @@ class QueryGraphBase:
     def __init__(self, ty_to_gql_ty_map: typing.Dict[str, str]):
         self.ty_to_gql_ty_map = ty_to_gql_ty_map
 
+    def _warn_deprecated(self, message: str):
+        warnings.warn(message, DeprecationWarning, stacklevel=3)
+
 
 class Transports:
     @staticmethod
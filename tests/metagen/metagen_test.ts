@@ -4,7 +4,11 @@
 import { Meta } from "test-utils/mod.ts";
 import { join } from "@std/path/join";
 import { resolve } from "@std/path/resolve";
-import { assertEquals } from "@std/assert";
+import {
+  assertEquals,
+  assertNotEquals,
+  assertStringIncludes,
+} from "@std/assert";
 import { GraphQLQuery } from "test-utils/query/graphql_query.ts";
 import type { JSONValue } from "@metatype/typegate/utils.ts";
 import { testDir } from "test-utils/dir.ts";
@@ -111,6 +115,65 @@ members = ["fdk/"]
   );
 });
 
+Meta.test("metagen check", async (t) => {
+  const tmpDir = t.tempDir;
+  const typegraphPath = join(import.meta.dirname!, "./typegraphs/metagen.ts");
+  const schemaPath = join(tmpDir, "schema/schema.graphql");
+
+  await Deno.writeTextFile(
+    join(tmpDir, "metatype.yml"),
+    `
+typegates:
+  dev:
+    url: "http://localhost:7890"
+    username: admin
+    password: password
+
+metagen:
+  targets:
+    main:
+      - generator: graphql_sdl
+        path: ./schema/
+        typegraph_path: ${typegraphPath}
+`,
+  );
+  const gen = (...args: string[]) =>
+    Meta.cli(
+      { env: { MCLI_LOADER_CMD: `deno run -A --config ${denoJson}` } },
+      "-C",
+      tmpDir,
+      "gen",
+      ...args,
+    );
+
+  assertEquals((await gen()).code, 0);
+
+  await t.should("pass on an up-to-date tree", async () => {
+    const res = await gen("--check");
+    assertEquals(res.code, 0);
+    assertEquals(res.stdout.trim(), "");
+  });
+
+  await t.should("fail with a diff on a modified tree", async () => {
+    const generated = await Deno.readTextFile(schemaPath);
+    const modified = generated.replace("type Query {", "type Query {\n  stale: Int");
+    await Deno.writeTextFile(schemaPath, modified);
+    await Deno.remove(join(tmpDir, "schema/introspection.json"));
+
+    const res = await gen("--check");
+    assertNotEquals(res.code, 0);
+    assertStringIncludes(res.stdout, "--- a/schema/schema.graphql");
+    assertStringIncludes(res.stdout, "+++ b/schema/schema.graphql");
+    assertStringIncludes(res.stdout, "-  stale: Int");
+    assertStringIncludes(res.stdout, "--- /dev/null");
+    assertStringIncludes(res.stdout, "+++ b/schema/introspection.json");
+    assertStringIncludes(res.stderr, "2 generated file(s) are not up to date");
+
+    // nothing is written
+    assertEquals(await Deno.readTextFile(schemaPath), modified);
+  });
+});
+
 // Meta.test("metagen python runs on cyclic types", async (t) => {
 //   const typegraphPath = join(import.meta.dirname!, "typegraphs/python.py");
 //   const basePath = join(t.tempDir, "fdk");
@@ -702,6 +765,13 @@ Meta.test(
         command: $`python3 main.py`.cwd(join(scriptsPath, "py")),
         expected: expectedSchema,
       },
+      {
+        name: "client_go",
+        // go is not part of the dev environment, but is installed on CI
+        skip: !Deno.env.get("CI") && !(await $.commandExists("go")),
+        command: $`go run .`.cwd(join(scriptsPath, "go")),
+        expected: expectedSchema,
+      },
     ];
 
     await using _engine = await metaTest.engine("metagen/typegraphs/sample.ts");
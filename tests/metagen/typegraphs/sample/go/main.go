@@ -0,0 +1,193 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+
+	"sample/client"
+)
+
+const postId = "94be5420-8c4a-4e67-b4f4-e1b2b54832a2"
+
+func main() {
+	if err := run(context.Background()); err != nil {
+		fmt.Fprintln(os.Stderr, err)
+		os.Exit(1)
+	}
+}
+
+func run(ctx context.Context) error {
+	qg := client.NewQueryGraph()
+	addr := fmt.Sprintf("http://localhost:%s/sample", os.Getenv("TG_PORT"))
+	gql := client.NewGraphQlTransport(qg, addr)
+
+	queryNodes := func() map[string]client.AnyQueryNode {
+		return map[string]client.AnyQueryNode{
+			"user": qg.GetUser(client.UserSelections{
+				SelectAll: true,
+				Posts: client.SelectAliased(map[string]client.PostSelections{
+					"post1": {Id: client.Get(), Slug: client.Get(), Title: client.Get()},
+					"post2": {SelectAll: true, Id: client.Skip()},
+				}),
+			}),
+			"posts":        qg.GetPosts(client.PostSelections{SelectAll: true}),
+			"scalarNoArgs": qg.ScalarNoArgs(),
+		}
+	}
+
+	preparedQ, err := gql.PrepareQueryMany(queryNodes())
+	if err != nil {
+		return err
+	}
+	res1, err := preparedQ.Perform(ctx, nil)
+	if err != nil {
+		return err
+	}
+	res1a, err := preparedQ.Perform(ctx, nil)
+	if err != nil {
+		return err
+	}
+
+	preparedM, err := gql.PrepareMutationMany(map[string]client.AnyMutationNode{
+		"scalarArgs": qg.ScalarArgs(client.Placeholders[client.Post](map[string]string{
+			"id":    "id",
+			"slug":  "slug",
+			"title": "title",
+		})),
+		"compositeNoArgs": qg.CompositeNoArgs(client.PostSelections{SelectAll: true}),
+		"compositeArgs": qg.CompositeArgs(
+			client.Placeholders[client.RootCompositeArgsFnInput](map[string]string{"id": "id"}),
+			client.PostSelections{SelectAll: true},
+		),
+	})
+	if err != nil {
+		return err
+	}
+	res2, err := preparedM.Perform(ctx, map[string]any{
+		"id":    postId,
+		"slug":  "s",
+		"title": "t",
+	})
+	if err != nil {
+		return err
+	}
+
+	res3, err := gql.QueryMany(ctx, queryNodes())
+	if err != nil {
+		return err
+	}
+
+	res4, err := gql.MutationMany(ctx, map[string]client.AnyMutationNode{
+		"scalarArgs":      qg.ScalarArgs(client.Args(client.Post{Id: postId, Slug: "", Title: ""})),
+		"compositeNoArgs": qg.CompositeNoArgs(client.PostSelections{SelectAll: true}),
+		"compositeArgs": qg.CompositeArgs(
+			client.Args(client.RootCompositeArgsFnInput{Id: postId}),
+			client.PostSelections{SelectAll: true},
+		),
+	})
+	if err != nil {
+		return err
+	}
+
+	unionArgs := client.Args(client.RootCompositeArgsFnInput{Id: postId})
+	res5, err := gql.QueryMany(ctx, map[string]client.AnyQueryNode{
+		"scalarUnion": qg.ScalarUnion(unionArgs),
+		// allows ignoring some members
+		"compositeUnion1": qg.CompositeUnion(unionArgs, client.RootCompositeUnionFnOutputSelections{
+			Post: client.Select(client.PostSelections{SelectAll: true}),
+		}),
+		// returns empty if returned type wasn't selected
+		// in union member
+		"compositeUnion2": qg.CompositeUnion(unionArgs, client.RootCompositeUnionFnOutputSelections{
+			User: client.Select(client.UserSelections{SelectAll: true}),
+		}),
+		"mixedUnion": qg.MixedUnion(unionArgs, client.RootMixedUnionFnOutputSelections{
+			Post: client.Select(client.PostSelections{SelectAll: true}),
+			User: client.Select(client.UserSelections{SelectAll: true}),
+		}),
+	})
+	if err != nil {
+		return err
+	}
+
+	res6, err := gql.QueryMany(ctx, map[string]client.AnyQueryNode{
+		"scalarOnly": qg.NestedComposite(client.RootNestedCompositeFnOutputSelections{SelectAll: true}),
+		"withStruct": qg.NestedComposite(client.RootNestedCompositeFnOutputSelections{
+			SelectAll: true,
+			Composite: client.Select(client.RootNestedCompositeFnOutputCompositeStructSelections{
+				SelectAll: true,
+			}),
+		}),
+		"withStructNested": qg.NestedComposite(client.RootNestedCompositeFnOutputSelections{
+			SelectAll: true,
+			Composite: client.Select(client.RootNestedCompositeFnOutputCompositeStructSelections{
+				SelectAll: true,
+				Nested: client.Select(client.RootNestedCompositeFnOutputCompositeStructNestedStructSelections{
+					SelectAll: true,
+				}),
+			}),
+		}),
+		"withList": qg.NestedComposite(client.RootNestedCompositeFnOutputSelections{
+			SelectAll: true,
+			List: client.Select(client.RootNestedCompositeFnOutputListStructSelections{
+				SelectAll: true,
+			}),
+		}),
+	})
+	if err != nil {
+		return err
+	}
+
+	res7a, err := client.Query(ctx, gql, qg.GetPosts(client.PostSelections{SelectAll: true}))
+	if err != nil {
+		return err
+	}
+	res7b, err := client.Mutation(ctx, gql, qg.ScalarArgs(client.Args(client.Post{Id: postId, Slug: "", Title: ""})))
+	if err != nil {
+		return err
+	}
+	numArgs := client.Placeholders[client.RootIdentityFnInput](map[string]string{"input": "num"})
+	identitySel := client.RootIdentityFnInputSelections{SelectAll: true}
+	preparedIdentity, err := client.PrepareQuery(gql, qg.Identity(numArgs, identitySel))
+	if err != nil {
+		return err
+	}
+	res7c, err := preparedIdentity.Perform(ctx, map[string]any{"num": 0})
+	if err != nil {
+		return err
+	}
+	preparedIdentityUpdate, err := client.PrepareMutation(gql, qg.IdentityUpdate(numArgs, identitySel))
+	if err != nil {
+		return err
+	}
+	res7d, err := preparedIdentityUpdate.Perform(ctx, map[string]any{"num": 0})
+	if err != nil {
+		return err
+	}
+
+	out, err := json.MarshalIndent([]any{
+		res1,
+		res1a,
+		res2,
+		res3,
+		res4,
+		res5,
+		res6,
+		map[string]any{
+			"singleQuery":            res7a,
+			"singleMutation":         res7b,
+			"singlePreparedQuery":    res7c,
+			"singlePreparedMutation": res7d,
+		},
+	}, "", "  ")
+	if err != nil {
+		return err
+	}
+	fmt.Println(string(out))
+	return nil
+}
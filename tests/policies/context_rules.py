@@ -0,0 +1,25 @@
+# Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+# SPDX-License-Identifier: MPL-2.0
+
+from typegraph import typegraph, t, Graph
+from typegraph.policy import Policy, arg, ctx
+from typegraph.runtimes import DenoRuntime
+
+
+@typegraph()
+def context_rules(g: Graph):
+    deno = DenoRuntime()
+
+    # reads the arguments: evaluated for each stage
+    owner = Policy.rule("owner", ctx("user.id").eq(arg("owner_id")))
+    # only reads the context: evaluated once per request
+    authenticated = Policy.rule("authenticated", ctx("user.id").not_null())
+    # JavaScript regular expression: lookbehinds are not supported by Rust
+    staff = Policy.rule("staff", ctx("user.email").matches(r"(?<=@)metatype\.dev$"))
+
+    doc = t.struct({"owner_id": t.string()})
+
+    g.expose(
+        getDoc=deno.identity(doc).with_policy(authenticated, owner),
+        getStaffDoc=deno.identity(doc).with_policy(staff),
+    )
@@ -0,0 +1,158 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+import { assertEquals } from "@std/assert";
+import { DenoRuntime } from "@metatype/typegate/runtimes/deno/deno.ts";
+import type { QueryEngine } from "@metatype/typegate/engine/query_engine.ts";
+import { gql, Meta } from "../utils/mod.ts";
+
+/** number of evaluations of each policy, by name */
+function spyOnPolicies(e: QueryEngine): Map<string, number> {
+  const { tg } = e;
+  const deno = tg.runtimeReferences.find((rt) => rt instanceof DenoRuntime)!;
+  const policyByMat = new Map(
+    tg.tg.policies.map((p) => [tg.materializer(p.materializer), p.name]),
+  );
+
+  const evaluations = new Map<string, number>();
+  const delegate = deno.delegate.bind(deno);
+  deno.delegate = (mat, ...rest) => {
+    const resolver = delegate(mat, ...rest);
+    const policy = policyByMat.get(mat);
+    if (policy == null) {
+      return resolver;
+    }
+    return (args) => {
+      evaluations.set(policy, (evaluations.get(policy) ?? 0) + 1);
+      return resolver(args);
+    };
+  };
+  return evaluations;
+}
+
+Meta.test("Context rule policies", async (t) => {
+  const e = await t.engine("policies/context_rules.py");
+  // policy resolvers are created with the plans: spy before the first query
+  const evaluations = spyOnPolicies(e);
+
+  await t.should("evaluate the policy with the args of the stage", async () => {
+    await gql`
+      query {
+        getDoc(owner_id: "alice") {
+          owner_id
+        }
+      }
+    `
+      .withContext({ user: { id: "alice" } })
+      .expectData({ getDoc: { owner_id: "alice" } })
+      .on(e);
+
+    await gql`
+      query {
+        getDoc(owner_id: "bob") {
+          owner_id
+        }
+      }
+    `
+      .withContext({ user: { id: "alice" } })
+      .expectErrorContains("Authorization failed")
+      .on(e);
+  });
+
+  await t.should("use the resolved variables", async () => {
+    const query = gql`
+      query($owner: String!) {
+        getDoc(owner_id: $owner) {
+          owner_id
+        }
+      }
+    `;
+    await query
+      .withVars({ owner: "alice" })
+      .withContext({ user: { id: "alice" } })
+      .expectData({ getDoc: { owner_id: "alice" } })
+      .on(e);
+    // same plan, other variables
+    await query
+      .withVars({ owner: "bob" })
+      .withContext({ user: { id: "alice" } })
+      .expectErrorContains("Authorization failed")
+      .on(e);
+  });
+
+  await t.should(
+    "not reuse the decision for a stage with other args",
+    async () => {
+      await gql`
+        query {
+          mine: getDoc(owner_id: "alice") {
+            owner_id
+          }
+          theirs: getDoc(owner_id: "bob") {
+            owner_id
+          }
+        }
+      `
+        .withContext({ user: { id: "alice" } })
+        .expectErrorContains("Authorization failed")
+        .on(e);
+
+      await gql`
+        query {
+          theirs: getDoc(owner_id: "bob") {
+            owner_id
+          }
+          mine: getDoc(owner_id: "alice") {
+            owner_id
+          }
+        }
+      `
+        .withContext({ user: { id: "alice" } })
+        .expectErrorContains("Authorization failed")
+        .on(e);
+    },
+  );
+
+  await t.should("cache the decision of the other policies", async () => {
+    evaluations.clear();
+    await gql`
+      query {
+        first: getDoc(owner_id: "alice") {
+          owner_id
+        }
+        second: getDoc(owner_id: "alice") {
+          owner_id
+        }
+      }
+    `
+      .withContext({ user: { id: "alice" } })
+      .expectData({
+        first: { owner_id: "alice" },
+        second: { owner_id: "alice" },
+      })
+      .on(e);
+
+    assertEquals(Object.fromEntries(evaluations), {
+      authenticated: 1,
+      owner: 2,
+    });
+  });
+
+  await t.should("match JavaScript regular expressions", async () => {
+    const query = gql`
+      query {
+        getStaffDoc(owner_id: "alice") {
+          owner_id
+        }
+      }
+    `;
+    await query
+      .withContext({ user: { email: "alice@metatype.dev" } })
+      .expectData({ getStaffDoc: { owner_id: "alice" } })
+      .on(e);
+    await query
+      .withContext({ user: { email: "alice@example.com" } })
+      .expectErrorContains("Authorization failed")
+      .on(e);
+  });
+});
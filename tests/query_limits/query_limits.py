@@ -0,0 +1,36 @@
+# Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+# SPDX-License-Identifier: MPL-2.0
+
+from typegraph import typegraph, Policy, t, Graph
+from typegraph.graph.params import QueryLimits
+from typegraph.runtimes.deno import DenoRuntime
+
+
+@typegraph(
+    limits=QueryLimits(max_depth=4, max_aliases=2, max_cost=20, list_size=3),
+)
+def query_limits(g: Graph):
+    deno = DenoRuntime()
+    public = Policy.public()
+
+    node = t.struct(
+        {
+            "value": t.integer(),
+            "children": t.list(g.ref("Node")),
+        },
+        name="Node",
+    )
+
+    g.expose(
+        public,
+        tree=deno.func(
+            t.struct({}),
+            node,
+            code="() => ({ value: 1, children: [{ value: 2, children: [] }] })",
+        ).rate(weight=2),
+        nodes=deno.func(
+            t.struct({"take": t.integer()}),
+            t.list(node),
+            code="({ take }) => Array.from({ length: take }, (_, value) => ({ value, children: [] }))",
+        ),
+    )
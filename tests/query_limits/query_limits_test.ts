@@ -0,0 +1,117 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+import { gql, Meta } from "../utils/mod.ts";
+
+Meta.test("Query limits", async (t) => {
+  const e = await t.engine("query_limits/query_limits.py");
+
+  await t.should("accept queries within the limits", async () => {
+    await gql`
+      query {
+        tree {
+          value
+          children {
+            value
+            children { value }
+          }
+        }
+      }
+    `
+      .expectData({
+        tree: {
+          value: 1,
+          children: [{ value: 2, children: [] }],
+        },
+      })
+      .on(e);
+  });
+
+  await t.should("reject queries that are too deep", async () => {
+    await gql`
+      query {
+        tree {
+          children {
+            children {
+              children { value }
+            }
+          }
+        }
+      }
+    `
+      .expectErrorContains("depth 5 is greater than the maximum of 4")
+      .on(e);
+  });
+
+  await t.should("reject queries with too many aliases", async () => {
+    await gql`
+      query {
+        a: tree { value }
+        b: tree { value }
+        c: tree { value }
+      }
+    `
+      .expectErrorContains("3 aliases, the maximum is 2")
+      .on(e);
+  });
+
+  await t.should("compute the cost from the pagination arguments", async () => {
+    await gql`
+      query {
+        nodes(take: 2) {
+          children {
+            children { value }
+          }
+        }
+      }
+    `
+      .expectData({
+        nodes: [{ children: [] }, { children: [] }],
+      })
+      .on(e);
+
+    // 1 + 10 * (1 + 3 * 1)
+    await gql`
+      query {
+        nodes(take: 10) {
+          children {
+            children { value }
+          }
+        }
+      }
+    `
+      .expectErrorContains("cost 41 is greater than the maximum of 20")
+      .on(e);
+  });
+
+  await t.should("check the cost with the variables of each request", async () => {
+    const query = gql`
+      query($take: Int!) {
+        nodes(take: $take) {
+          children {
+            children { value }
+          }
+        }
+      }
+    `;
+    await query
+      .withVars({ take: 2 })
+      .expectData({
+        nodes: [{ children: [] }, { children: [] }],
+      })
+      .on(e);
+
+    // the plan is cached, the limits are not
+    await query
+      .withVars({ take: 10 })
+      .expectErrorContains("cost 41 is greater than the maximum of 20")
+      .on(e);
+
+    await query
+      .withVars({ take: 1 })
+      .expectData({
+        nodes: [{ children: [] }],
+      })
+      .on(e);
+  });
+});
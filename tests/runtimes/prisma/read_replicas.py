@@ -0,0 +1,33 @@
+# Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+# SPDX-License-Identifier: MPL-2.0
+
+from typegraph import Graph, Policy, t, typegraph
+from typegraph.gen.runtimes import PrismaPoolConfig
+from typegraph.providers.prisma import PrismaRuntime
+
+
+@typegraph()
+def read_replicas(g: Graph):
+    db = PrismaRuntime(
+        "prisma",
+        "POSTGRES",
+        pool=PrismaPoolConfig(size=2, connect_timeout_sec=5, socket_timeout_sec=10),
+        read_replica_secrets=["POSTGRES_REPLICA"],
+    )
+
+    record = t.struct(
+        {
+            "id": t.integer(as_id=True, config=["auto"]),
+            "name": t.string(),
+        },
+        name="Record",
+    )
+
+    public = Policy.public()
+
+    g.expose(
+        public,
+        createRecord=db.create(record),
+        findRecords=db.find_many(record),
+        findFirstRecord=db.find_first(record),
+    )
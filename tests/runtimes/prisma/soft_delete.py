@@ -0,0 +1,41 @@
+# Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+# SPDX-License-Identifier: MPL-2.0
+
+from typegraph import Graph, Policy, t, typegraph
+from typegraph.providers.prisma import PrismaRuntime
+
+
+@typegraph()
+def soft_delete(g: Graph):
+    db = PrismaRuntime("prisma", "POSTGRES")
+
+    public = Policy.public()
+
+    users = t.struct(
+        {
+            "id": t.integer(as_id=True, config={"auto": True}),
+            "name": t.string(),
+            "posts": db.link(t.list(g.ref("Post")), "postAuthor"),
+        },
+        name="User",
+    )
+
+    posts = t.struct(
+        {
+            "id": t.integer(as_id=True),
+            "title": t.string(),
+            "author": db.link(g.ref("User"), "postAuthor"),
+        },
+        config={"soft_delete": True, "created_by": "username"},
+        name="Post",
+    )
+
+    g.expose(
+        public,
+        createUser=db.create(users),
+        updateUser=db.update(users),
+        findUser=db.find_unique(users),
+        findUsers=db.find_many(users),
+        findPosts=db.find_many(posts),
+        deletePost=db.delete(posts),
+    )
@@ -0,0 +1,195 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+import { assertEquals } from "@std/assert";
+import { gql, Meta } from "test-utils/mod.ts";
+import { dropSchema, randomPGConnStr } from "test-utils/database.ts";
+
+Meta.test("prisma soft delete", async (t) => {
+  const { connStr, schema } = randomPGConnStr();
+  await dropSchema(schema);
+  const e = await t.engine("runtimes/prisma/soft_delete.py", {
+    secrets: {
+      POSTGRES: connStr,
+    },
+  });
+
+  await t.should("set the audit columns of nested creates", async () => {
+    await gql`
+      mutation {
+        createUser(
+          data: {
+            name: "Alice"
+            posts: { create: [{ id: 1, title: "first" }, { id: 2, title: "second" }] }
+          }
+        ) {
+          id
+        }
+      }
+    `
+      .withContext({ username: "alice" })
+      .expectData({ createUser: { id: 1 } })
+      .on(e);
+
+    await gql`
+      mutation {
+        updateUser(
+          where: { id: 1 }
+          data: { posts: { create: { id: 3, title: "third" } } }
+        ) {
+          id
+        }
+      }
+    `
+      .withContext({ username: "bob" })
+      .expectData({ updateUser: { id: 1 } })
+      .on(e);
+
+    await gql`
+      query {
+        findPosts(orderBy: { id: asc }) {
+          id
+          createdBy
+          deletedAt
+        }
+      }
+    `
+      .expectData({
+        findPosts: [
+          { id: 1, createdBy: "alice", deletedAt: null },
+          { id: 2, createdBy: "alice", deletedAt: null },
+          { id: 3, createdBy: "bob", deletedAt: null },
+        ],
+      })
+      .on(e);
+  });
+
+  await t.should("hide the deleted records", async () => {
+    await gql`
+      mutation {
+        deletePost(where: { id: 2 }) {
+          id
+        }
+      }
+    `
+      .expectData({ deletePost: { id: 2 } })
+      .on(e);
+
+    await gql`
+      query {
+        findPosts(orderBy: { id: asc }) {
+          id
+        }
+      }
+    `
+      .expectData({ findPosts: [{ id: 1 }, { id: 3 }] })
+      .on(e);
+  });
+
+  await t.should("hide the deleted records of nested relations", async () => {
+    await gql`
+      query {
+        findUser(where: { id: 1 }) {
+          name
+          posts {
+            id
+            title
+            author {
+              posts {
+                id
+              }
+            }
+          }
+        }
+      }
+    `
+      .expectData({
+        findUser: {
+          name: "Alice",
+          posts: [
+            {
+              id: 1,
+              title: "first",
+              author: { posts: [{ id: 1 }, { id: 3 }] },
+            },
+            {
+              id: 3,
+              title: "third",
+              author: { posts: [{ id: 1 }, { id: 3 }] },
+            },
+          ],
+        },
+      })
+      .on(e);
+  });
+
+  await t.should("filter the deleted records in relations", async () => {
+    await gql`
+      query {
+        withDeleted: findUsers(where: { posts: { some: { id: 2 } } }) {
+          id
+        }
+        withExisting: findUsers(where: { posts: { some: { id: 3 } } }) {
+          id
+        }
+      }
+    `
+      .expectData({ withDeleted: [], withExisting: [{ id: 1 }] })
+      .on(e);
+  });
+
+  await t.should("soft delete the nested records", async () => {
+    await gql`
+      mutation {
+        updateUser(
+          where: { id: 1 }
+          data: { posts: { deleteMany: { where: { id: 3 } } } }
+        ) {
+          id
+        }
+      }
+    `
+      .expectErrorContains("Unexpected property 'deleteMany'")
+      .on(e);
+
+    await gql`
+      mutation {
+        updateUser(
+          where: { id: 1 }
+          data: {
+            posts: {
+              updateMany: {
+                where: { id: 3 }
+                data: { deletedAt: "2024-01-01T00:00:00.000Z" }
+              }
+            }
+          }
+        ) {
+          posts {
+            id
+          }
+        }
+      }
+    `
+      .expectData({ updateUser: { posts: [{ id: 1 }] } })
+      .on(e);
+  });
+
+  await t.should("include the deleted records on request", async () => {
+    await gql`
+      query {
+        findPosts(where: { deletedAt: {} }, orderBy: { id: asc }) {
+          id
+          deletedAt
+        }
+      }
+    `
+      .expectBody((body) => {
+        const posts = body.data.findPosts;
+        assertEquals(posts.map((p: { id: number }) => p.id), [1, 2, 3]);
+        assertEquals(posts[0].deletedAt, null);
+        assertEquals(posts[2].deletedAt, "2024-01-01T00:00:00.000Z");
+      })
+      .on(e);
+  });
+});
@@ -0,0 +1,31 @@
+# Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+# SPDX-License-Identifier: MPL-2.0
+
+from typegraph import t, typegraph
+from typegraph.graph.typegraph import Graph
+from typegraph.policy import Policy
+from typegraph.runtimes.random import RandomRuntime
+
+profile = t.struct(
+    {
+        "handle": t.string()
+        .describe("Public handle of the user")
+        .examples("alice", "bob"),
+        "score": t.integer().examples(42),
+        "tags": t.list(t.string()).examples(["admin"]),
+    },
+    name="Profile",
+).describe("A user profile")
+
+
+@typegraph()
+def random_examples(g: Graph):
+    random = RandomRuntime(seed=1)
+    pub = Policy.public()
+
+    g.expose(
+        profile=random.gen(profile)
+        .describe("Get a sample profile")
+        .examples({})
+        .with_policy(pub),
+    )
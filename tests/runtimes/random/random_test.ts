@@ -191,3 +191,69 @@ Meta.test("Deno: Random", async (t) => {
     }).on(e);
   });
 });
+
+Meta.test("Python: Random with examples", async (t) => {
+  const e = await t.engine("runtimes/random/random_examples.py");
+
+  await t.should("prefer sample values from the examples", async () => {
+    await gql`
+      query {
+        profile {
+          score
+          tags
+        }
+      }
+    `
+      .expectData({
+        profile: {
+          score: 42,
+          tags: ["admin"],
+        },
+      })
+      .on(e);
+  });
+
+  await t.should("pick one of the examples", async () => {
+    await gql`
+      query {
+        profile {
+          handle
+        }
+      }
+    `
+      .expectBody((body) => {
+        const handle = body.data.profile.handle;
+        if (!["alice", "bob"].includes(handle)) {
+          throw new Error(`unexpected handle: ${handle}`);
+        }
+      })
+      .on(e);
+  });
+
+  await t.should("expose the descriptions and examples", async () => {
+    await gql`
+      query {
+        __type(name: "Profile") {
+          fields {
+            name
+            description
+          }
+        }
+      }
+    `
+      .expectData({
+        __type: {
+          fields: [
+            {
+              name: "handle",
+              description:
+                'Public handle of the user\n\nExamples:\n- `"alice"`\n- `"bob"`',
+            },
+            { name: "score", description: "Examples:\n- `42`" },
+            { name: "tags", description: 'Examples:\n- `["admin"]`' },
+          ],
+        },
+      })
+      .on(e);
+  });
+});
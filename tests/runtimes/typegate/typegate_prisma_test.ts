@@ -1,6 +1,7 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
+import { assert, assertEquals } from "@std/assert";
 import { gql, Meta } from "test-utils/mod.ts";
 import { dropSchema } from "test-utils/database.ts";
 
@@ -240,4 +241,38 @@ Meta.test({
       })
       .on(e);
   });
+
+  await t.should("report the prisma engine metrics", async () => {
+    await gql`
+      query PrismaMetrics($typegraph: String!) {
+        prismaMetrics(typegraph: $typegraph) {
+          runtime
+          connected
+          queries { total errors }
+          latency { maxMs p50Ms }
+          inFlight { current max }
+          pool { open busy idle waiting }
+          slowQueries { thresholdMs count }
+        }
+      }
+    `
+      .withVars({ typegraph: "prisma" })
+      .withHeaders(adminHeaders)
+      .expectBody(({ data }) => {
+        assertEquals(data.prismaMetrics.length, 1);
+        const [metrics] = data.prismaMetrics;
+        assertEquals(metrics.runtime, "prisma");
+        assert(metrics.connected);
+        // the queries of the previous steps went through the engine
+        assert(metrics.queries.total >= 3, JSON.stringify(metrics));
+        assert(metrics.latency.maxMs >= metrics.latency.p50Ms);
+        assertEquals(metrics.inFlight.current, 0);
+        assert(metrics.inFlight.max >= 1);
+        // the connection opened by the engine is reported by the pool
+        assert(metrics.pool.open >= 1, JSON.stringify(metrics.pool));
+        assertEquals(metrics.pool.waiting, 0);
+        assert(metrics.slowQueries.thresholdMs > 0);
+      })
+      .on(e);
+  });
 });
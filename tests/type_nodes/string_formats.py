@@ -0,0 +1,27 @@
+# Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+# SPDX-License-Identifier: MPL-2.0
+
+from typegraph import Policy, t, typegraph, Graph
+from typegraph.runtimes.deno import DenoRuntime
+
+
+@typegraph()
+def string_formats(g: Graph):
+    # lookaheads are not supported by the `regex` crate
+    g.string_format("postal-code", r"^(?=[0-9]{5}$)(?!00)")
+
+    formats = t.struct(
+        {
+            "ip": t.string(format="ipv4").optional(),
+            "slug": t.string(format="slug").optional(),
+            "country": t.string(format="country-code").optional(),
+            "postalCode": t.string(format="postal-code").optional(),
+        }
+    )
+
+    deno = DenoRuntime()
+    public = Policy.public()
+
+    g.expose(
+        test=deno.identity(formats).with_policy(public),
+    )
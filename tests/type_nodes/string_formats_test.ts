@@ -0,0 +1,51 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+import { gql, Meta } from "../utils/mod.ts";
+
+Meta.test("string formats", async (t) => {
+  const e = await t.engine("type_nodes/string_formats.py");
+
+  await t.should("accept valid values", async () => {
+    await gql`
+      query {
+        test(ip: "10.0.0.1", slug: "hello-world", country: "FR", postalCode: "75001") {
+          ip
+          slug
+          country
+          postalCode
+        }
+      }
+    `.expectData({
+      test: {
+        ip: "10.0.0.1",
+        slug: "hello-world",
+        country: "FR",
+        postalCode: "75001",
+      },
+    })
+      .on(e);
+  });
+
+  await t.should("reject invalid values of built-in formats", async () => {
+    await gql`
+      query {
+        test(slug: "Hello World") {
+          slug
+        }
+      }
+    `.expectErrorContains("string does not statisfy the required format 'slug'")
+      .on(e);
+  });
+
+  await t.should("reject invalid values of custom formats", async () => {
+    await gql`
+      query {
+        test(postalCode: "00123") {
+          postalCode
+        }
+      }
+    `.expectErrorContains("format 'postal-code'")
+      .on(e);
+  });
+});
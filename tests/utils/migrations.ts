@@ -52,6 +52,27 @@ export async function dropSchemas(engine: QueryEngine) {
   }
 }
 
+export async function executeRaw(engine: QueryEngine, query: string) {
+  const runtimes = engine.tg.runtimeReferences.filter((r) =>
+    r instanceof PrismaRuntime
+  ) as PrismaRuntime[];
+
+  for (const runtime of runtimes) {
+    const res = await runtime.query({
+      action: "executeRaw",
+      query: {
+        arguments: { query, parameters: "[]" },
+        selection: {},
+      },
+    });
+
+    if ("errors" in res) {
+      console.error(JSON.stringify(res.errors));
+      throw new Error(`cannot execute ${query}`);
+    }
+  }
+}
+
 export async function recreateMigrations(engine: QueryEngine) {
   const runtimes = engine.tg.tg.runtimes.filter(
     (rt) => rt.name === "prisma",